@@ -0,0 +1,123 @@
+//! Grade queued code submissions.
+//!
+//! ```text
+//! cargo run -p grader --bin grading-worker
+//! ```
+//!
+//! Reads `DATABASE_URL` and the `GRADER_*` settings (see `GraderConfig`).
+//! Any number of workers can run against the same database; each run is
+//! claimed by exactly one. Runs a worker was killed in the middle of are
+//! requeued after `GRADER_STALE_RUN_SECONDS`.
+
+use grader::config::GraderConfig;
+use grader::sandbox::seccomp::SyscallPolicy;
+use grader::sandbox::{self, Limits, SandboxCommand};
+use grader::worker::Worker;
+use sqlx::PgPool;
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const REQUEUE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fail fast when the host does not allow the sandbox, e.g. with
+/// unprivileged user namespaces disabled
+fn check_sandbox(config: &GraderConfig) -> Result<(), String> {
+    let dir = config.work_dir.join("sandbox-check");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let result = sandbox::run(&SandboxCommand {
+        program: "true".to_string(),
+        args: Vec::new(),
+        env: vec![("PATH".to_string(), "/usr/bin:/bin".to_string())],
+        mounts: config.toolchains.dirs.clone(),
+        work_dir: dir.clone(),
+        stdin: None,
+        stdout: dir.join("stdout"),
+        stderr: dir.join("stderr"),
+        limits: Limits {
+            cpu_time_ms: 1000,
+            wall_time_ms: 5000,
+            address_space_bytes: None,
+            stack_bytes: None,
+            output_bytes: 1024,
+            open_files: 16,
+            processes: 1,
+        },
+        policy: SyscallPolicy::Run,
+    });
+    fs::remove_dir_all(&dir).ok();
+
+    match result {
+        Ok(execution) if execution.succeeded() => Ok(()),
+        Ok(execution) => Err(format!(
+            "test program ended with {:?}",
+            execution.termination
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let config = GraderConfig::from_env();
+    if let Err(e) = check_sandbox(&config) {
+        eprintln!("Sandbox unavailable: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to the database");
+
+    let concurrency = config.concurrency.max(1);
+    let poll_interval = config.poll_interval;
+    let worker = Worker::new(pool, config);
+
+    let requeuer = worker.clone();
+    tokio::spawn(async move {
+        loop {
+            match requeuer.requeue_stale().await {
+                Ok(0) => {}
+                Ok(count) => println!("Requeued {} stale grading runs", count),
+                Err(e) => eprintln!("Failed to requeue stale runs: {}", e),
+            }
+            tokio::time::sleep(REQUEUE_INTERVAL).await;
+        }
+    });
+
+    println!("Grading with {} workers", concurrency);
+    let mut workers = Vec::with_capacity(concurrency);
+    for _ in 0..concurrency {
+        let worker = worker.clone();
+        workers.push(tokio::spawn(async move {
+            loop {
+                match worker.process_next().await {
+                    Ok(Some(run)) => println!(
+                        "Graded run {} of submission {}: {:?} {:?} ({}/{})",
+                        run.id,
+                        run.submission_id,
+                        run.status,
+                        run.verdict,
+                        run.passed_tests,
+                        run.total_tests
+                    ),
+                    Ok(None) => tokio::time::sleep(poll_interval).await,
+                    Err(e) => {
+                        eprintln!("Failed to process grading run: {}", e);
+                        tokio::time::sleep(poll_interval).await;
+                    }
+                }
+            }
+        }));
+    }
+
+    for worker in workers {
+        worker.await.ok();
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,96 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Settings of the grading worker, read from the environment
+#[derive(Debug, Clone)]
+pub struct GraderConfig {
+    /// Parent of the per-run work directories
+    pub work_dir: PathBuf,
+    /// Runs graded in parallel
+    pub concurrency: usize,
+    pub poll_interval: Duration,
+    /// Runs left `running` this long are assumed abandoned and requeued
+    pub stale_after: Duration,
+    pub toolchains: Toolchains,
+}
+
+/// Compiler and interpreter commands, looked up in `PATH` unless absolute
+///
+/// Point these at the real binaries rather than version manager shims
+/// (pyenv, asdf): shims start helper processes, which submitted programs
+/// may not.
+#[derive(Debug, Clone)]
+pub struct Toolchains {
+    pub cc: String,
+    pub cxx: String,
+    pub rustc: String,
+    pub python: String,
+    /// The only host paths sandboxed processes see besides their work
+    /// directory; every toolchain must live under one of them
+    pub dirs: Vec<PathBuf>,
+}
+
+/// Where distributions install compilers, interpreters and their libraries
+const SYSTEM_TOOLCHAIN_DIRS: [&str; 9] = [
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/libx32",
+    "/etc/alternatives",
+    "/etc/ld.so.cache",
+];
+
+/// The system directories plus rustup's toolchains. Only the binaries of
+/// `CARGO_HOME` are included, the rest of it may hold registry tokens
+fn default_toolchain_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = SYSTEM_TOOLCHAIN_DIRS.iter().map(PathBuf::from).collect();
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let rustup_home = env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".rustup")));
+    let cargo_home = env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".cargo")));
+    dirs.extend(rustup_home);
+    dirs.extend(cargo_home.map(|cargo_home| cargo_home.join("bin")));
+    dirs
+}
+
+impl GraderConfig {
+    pub fn from_env() -> Self {
+        Self {
+            work_dir: env::var("GRADER_WORK_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| env::temp_dir().join("confuse-grader")),
+            concurrency: env::var("GRADER_CONCURRENCY")
+                .map(|v| v.parse().expect("GRADER_CONCURRENCY must be a number"))
+                .unwrap_or(2),
+            poll_interval: Duration::from_millis(
+                env::var("GRADER_POLL_INTERVAL_MS")
+                    .map(|v| v.parse().expect("GRADER_POLL_INTERVAL_MS must be a number"))
+                    .unwrap_or(1000),
+            ),
+            stale_after: Duration::from_secs(
+                env::var("GRADER_STALE_RUN_SECONDS")
+                    .map(|v| {
+                        v.parse()
+                            .expect("GRADER_STALE_RUN_SECONDS must be a number")
+                    })
+                    .unwrap_or(600),
+            ),
+            toolchains: Toolchains {
+                cc: env::var("GRADER_CC").unwrap_or_else(|_| "gcc".to_string()),
+                cxx: env::var("GRADER_CXX").unwrap_or_else(|_| "g++".to_string()),
+                rustc: env::var("GRADER_RUSTC").unwrap_or_else(|_| "rustc".to_string()),
+                python: env::var("GRADER_PYTHON").unwrap_or_else(|_| "python3".to_string()),
+                dirs: env::var_os("GRADER_TOOLCHAIN_DIRS")
+                    .map(|dirs| env::split_paths(&dirs).collect())
+                    .unwrap_or_else(default_toolchain_dirs),
+            },
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::sandbox::SandboxError;
+use std::fmt;
+use std::io;
+
+/// Why a run could not be graded; never the submission's fault
+#[derive(Debug)]
+pub enum GraderError {
+    Database(sqlx::Error),
+    Sandbox(SandboxError),
+    Io(io::Error),
+    /// The run points at something that no longer exists or can not be
+    /// graded, e.g. a task without test cases
+    Invalid(String),
+}
+
+impl fmt::Display for GraderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraderError::Database(e) => write!(f, "Database error: {}", e),
+            GraderError::Sandbox(e) => write!(f, "{}", e),
+            GraderError::Io(e) => write!(f, "I/O error: {}", e),
+            GraderError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for GraderError {}
+
+impl From<sqlx::Error> for GraderError {
+    fn from(err: sqlx::Error) -> Self {
+        GraderError::Database(err)
+    }
+}
+
+impl From<SandboxError> for GraderError {
+    fn from(err: SandboxError) -> Self {
+        GraderError::Sandbox(err)
+    }
+}
+
+impl From<io::Error> for GraderError {
+    fn from(err: io::Error) -> Self {
+        GraderError::Io(err)
+    }
+}
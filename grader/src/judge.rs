@@ -0,0 +1,300 @@
+//! Compile a submission and run it against each test case.
+
+use crate::config::Toolchains;
+use crate::error::GraderError;
+use crate::languages::{LanguageSpec, language_spec};
+use crate::sandbox::seccomp::SyscallPolicy;
+use crate::sandbox::{self, Execution, Limits, SandboxCommand, Termination};
+use models::{GradingVerdict, ProgrammingLanguage, TaskTestCase};
+use repositories::traits::NewGradingResult;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+const COMPILE_CPU_TIME_MS: u64 = 20_000;
+const COMPILE_WALL_TIME_MS: u64 = 40_000;
+const COMPILE_FILE_BYTES: u64 = 256 * 1024 * 1024;
+/// Compiler drivers start a handful of helpers (`cc1`, `as`, `collect2`,
+/// `ld`), some of them threaded
+const COMPILE_PROCESSES: u64 = 64;
+/// Threads a submitted program may run, its main thread included
+const RUN_PROCESSES: u64 = 16;
+/// Compiler diagnostics kept on the run
+const COMPILE_OUTPUT_BYTES: usize = 64 * 1024;
+const OUTPUT_LIMIT_BYTES: u64 = 64 * 1024 * 1024;
+/// Most address space a program gets beyond its memory limit
+const ADDRESS_SPACE_SLACK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Outcome of grading one submission
+#[derive(Debug, Clone)]
+pub struct Grade {
+    pub verdict: GradingVerdict,
+    pub compile_output: Option<String>,
+    pub results: Vec<NewGradingResult>,
+}
+
+/// Compile `source` and run it against `test_cases` in `work_dir`
+///
+/// Blocks until every test has run. On a compilation error every test is
+/// `CE`; otherwise all tests run and the overall verdict is the first
+/// failing one in test order.
+pub fn grade(
+    work_dir: &Path,
+    language: ProgrammingLanguage,
+    source: &str,
+    test_cases: &[TaskTestCase],
+    toolchains: &Toolchains,
+) -> Result<Grade, GraderError> {
+    let spec = language_spec(language, toolchains);
+    fs::write(work_dir.join(spec.source_file), source)?;
+
+    // 1. Compile
+    if let Some(compile) = &spec.compile {
+        let (execution, output) = compile_source(work_dir, compile, toolchains)?;
+        if !execution.succeeded() {
+            let output = match execution.termination {
+                Termination::WallTimeout => "Compilation timed out".to_string(),
+                _ if execution.cpu_time_ms > COMPILE_CPU_TIME_MS => {
+                    "Compilation timed out".to_string()
+                }
+                _ => output,
+            };
+            let results = test_cases
+                .iter()
+                .map(|case| NewGradingResult {
+                    test_case_id: case.id,
+                    position: case.position,
+                    verdict: GradingVerdict::CE,
+                    points: 0,
+                    max_points: case.points,
+                    time_ms: 0,
+                    memory_kb: 0,
+                    exit_code: None,
+                })
+                .collect();
+
+            return Ok(Grade {
+                verdict: GradingVerdict::CE,
+                compile_output: Some(output),
+                results,
+            });
+        }
+    }
+
+    // 2. Run every test
+    let mut results = Vec::with_capacity(test_cases.len());
+    for case in test_cases {
+        results.push(run_test(work_dir, &spec, case, toolchains)?);
+    }
+
+    let verdict = results
+        .iter()
+        .map(|result| result.verdict)
+        .find(|verdict| *verdict != GradingVerdict::AC)
+        .unwrap_or(GradingVerdict::AC);
+
+    Ok(Grade {
+        verdict,
+        compile_output: None,
+        results,
+    })
+}
+
+/// Environment of sandboxed processes: nothing of the worker's but the
+/// toolchain lookup
+fn sandbox_env(work_dir: &Path) -> Vec<(String, String)> {
+    let work_dir = work_dir.to_string_lossy().to_string();
+    let mut env = vec![
+        ("HOME".to_string(), work_dir.clone()),
+        ("TMPDIR".to_string(), work_dir),
+        ("LANG".to_string(), "C.UTF-8".to_string()),
+    ];
+    for key in ["PATH", "RUSTUP_HOME", "CARGO_HOME", "RUSTUP_TOOLCHAIN"] {
+        if let Ok(value) = std::env::var(key) {
+            env.push((key.to_string(), value));
+        }
+    }
+    // rustup finds its toolchains through HOME, which is the work dir here
+    if std::env::var("RUSTUP_HOME").is_err()
+        && let Ok(home) = std::env::var("HOME")
+    {
+        env.push(("RUSTUP_HOME".to_string(), format!("{}/.rustup", home)));
+    }
+    env
+}
+
+fn compile_source(
+    work_dir: &Path,
+    compile: &[String],
+    toolchains: &Toolchains,
+) -> Result<(Execution, String), GraderError> {
+    let stdout = work_dir.join("compile.stdout");
+    let stderr = work_dir.join("compile.stderr");
+
+    let execution = sandbox::run(&SandboxCommand {
+        program: compile[0].clone(),
+        args: compile[1..].to_vec(),
+        env: sandbox_env(work_dir),
+        mounts: toolchains.dirs.clone(),
+        work_dir: work_dir.to_path_buf(),
+        stdin: None,
+        stdout: stdout.clone(),
+        stderr: stderr.clone(),
+        limits: Limits {
+            cpu_time_ms: COMPILE_CPU_TIME_MS,
+            wall_time_ms: COMPILE_WALL_TIME_MS,
+            address_space_bytes: None,
+            stack_bytes: None,
+            output_bytes: COMPILE_FILE_BYTES,
+            open_files: 512,
+            processes: COMPILE_PROCESSES,
+        },
+        policy: SyscallPolicy::Compile,
+    })?;
+
+    let mut output = read_prefix(&stderr, COMPILE_OUTPUT_BYTES)?;
+    output.extend(read_prefix(
+        &stdout,
+        COMPILE_OUTPUT_BYTES - output.len().min(COMPILE_OUTPUT_BYTES),
+    )?);
+
+    Ok((execution, String::from_utf8_lossy(&output).into_owned()))
+}
+
+fn run_test(
+    work_dir: &Path,
+    spec: &LanguageSpec,
+    case: &TaskTestCase,
+    toolchains: &Toolchains,
+) -> Result<NewGradingResult, GraderError> {
+    let input = work_dir.join("input.txt");
+    let output = work_dir.join("output.txt");
+    fs::write(&input, &case.input)?;
+
+    let memory_bytes = case.memory_limit_kb as u64 * 1024;
+    let time_limit_ms = case.time_limit_ms as u64;
+
+    // Exceeding the memory limit is judged on peak resident memory. The
+    // address space cap leaves room above the limit so a growing program
+    // shows up as MLE, while still bounding what it can take from the host;
+    // an allocation beyond the cap fails and usually ends in RE
+    let address_space_bytes = (memory_bytes * 4).min(memory_bytes + ADDRESS_SPACE_SLACK_BYTES)
+        + spec.runtime_overhead_bytes;
+    let execution = sandbox::run(&SandboxCommand {
+        program: spec.run[0].clone(),
+        args: spec.run[1..].to_vec(),
+        env: sandbox_env(work_dir),
+        mounts: toolchains.dirs.clone(),
+        work_dir: work_dir.to_path_buf(),
+        stdin: Some(input),
+        stdout: output.clone(),
+        stderr: work_dir.join("stderr.txt"),
+        limits: Limits {
+            cpu_time_ms: time_limit_ms,
+            wall_time_ms: time_limit_ms * 2 + 1000,
+            address_space_bytes: Some(address_space_bytes),
+            stack_bytes: Some(memory_bytes),
+            output_bytes: OUTPUT_LIMIT_BYTES,
+            open_files: 64,
+            processes: RUN_PROCESSES,
+        },
+        policy: SyscallPolicy::Run,
+    })?;
+
+    let verdict = if execution.termination == Termination::WallTimeout
+        || execution.cpu_time_ms > time_limit_ms
+    {
+        GradingVerdict::TLE
+    } else if execution.peak_memory_kb > case.memory_limit_kb as u64 {
+        GradingVerdict::MLE
+    } else if !execution.succeeded() {
+        GradingVerdict::RE
+    } else if outputs_match(&fs::read(&output)?, &case.expected_output) {
+        GradingVerdict::AC
+    } else {
+        GradingVerdict::WA
+    };
+
+    let exit_code = match execution.termination {
+        Termination::Exited(code) => Some(code),
+        Termination::Signaled(signal) => Some(128 + signal),
+        Termination::WallTimeout => None,
+    };
+
+    Ok(NewGradingResult {
+        test_case_id: case.id,
+        position: case.position,
+        verdict,
+        points: if verdict == GradingVerdict::AC {
+            case.points
+        } else {
+            0
+        },
+        max_points: case.points,
+        time_ms: execution.cpu_time_ms.min(i32::MAX as u64) as i32,
+        memory_kb: execution.peak_memory_kb.min(i32::MAX as u64) as i32,
+        exit_code,
+    })
+}
+
+fn read_prefix(path: &Path, limit: usize) -> Result<Vec<u8>, GraderError> {
+    let mut buf = Vec::new();
+    fs::File::open(path)?
+        .take(limit as u64)
+        .read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Compare program output with the expected output, ignoring trailing
+/// whitespace on each line, trailing blank lines and `\r\n` line endings
+pub fn outputs_match(actual: &[u8], expected: &str) -> bool {
+    let normalize = |text| {
+        let mut lines: Vec<&str> = str::lines(text).map(str::trim_end).collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    };
+
+    match std::str::from_utf8(actual) {
+        Ok(actual) => normalize(actual) == normalize(expected),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::outputs_match;
+
+    #[test]
+    fn matches_identical_output() {
+        assert!(outputs_match(b"1 2 3\n4\n", "1 2 3\n4\n"));
+    }
+
+    #[test]
+    fn ignores_trailing_whitespace_and_blank_lines() {
+        assert!(outputs_match(b"1 2 3  \n4\t\n\n\n", "1 2 3\n4"));
+        assert!(outputs_match(b"42", "42\n \n"));
+        assert!(outputs_match(b"", "\n\n"));
+    }
+
+    #[test]
+    fn normalizes_crlf_line_endings() {
+        assert!(outputs_match(b"1 2\r\n3\r\n", "1 2\n3\n"));
+        assert!(outputs_match(b"1 2\n3\n", "1 2\r\n3\r\n"));
+    }
+
+    #[test]
+    fn keeps_leading_and_inner_whitespace() {
+        assert!(!outputs_match(b" 1 2\n", "1 2\n"));
+        assert!(!outputs_match(b"1  2\n", "1 2\n"));
+        assert!(!outputs_match(b"1\n\n2\n", "1\n2\n"));
+    }
+
+    #[test]
+    fn rejects_different_or_invalid_output() {
+        assert!(!outputs_match(b"1 2 4\n", "1 2 3\n"));
+        assert!(!outputs_match(b"1\n", "1\n2\n"));
+        assert!(!outputs_match(&[0xff, 0xfe], ""));
+    }
+}
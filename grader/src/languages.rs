@@ -0,0 +1,80 @@
+use crate::config::Toolchains;
+use models::ProgrammingLanguage;
+
+/// How to build and run a program in one language
+///
+/// Commands run in the work directory, which holds the source as
+/// `source_file`.
+#[derive(Debug, Clone)]
+pub struct LanguageSpec {
+    pub source_file: &'static str,
+    /// `None` for interpreted languages without a syntax check
+    pub compile: Option<Vec<String>>,
+    pub run: Vec<String>,
+    /// Address space the runtime needs on top of the program's own memory
+    pub runtime_overhead_bytes: u64,
+}
+
+const MIB: u64 = 1024 * 1024;
+
+fn command(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|part| part.to_string()).collect()
+}
+
+pub fn language_spec(language: ProgrammingLanguage, toolchains: &Toolchains) -> LanguageSpec {
+    match language {
+        ProgrammingLanguage::C => LanguageSpec {
+            source_file: "main.c",
+            compile: Some(command(&[
+                &toolchains.cc,
+                "-O2",
+                "-std=c17",
+                "-o",
+                "main",
+                "main.c",
+                "-lm",
+            ])),
+            run: command(&["./main"]),
+            runtime_overhead_bytes: 16 * MIB,
+        },
+        ProgrammingLanguage::Cpp => LanguageSpec {
+            source_file: "main.cpp",
+            compile: Some(command(&[
+                &toolchains.cxx,
+                "-O2",
+                "-std=c++17",
+                "-o",
+                "main",
+                "main.cpp",
+            ])),
+            run: command(&["./main"]),
+            runtime_overhead_bytes: 16 * MIB,
+        },
+        ProgrammingLanguage::Rust => LanguageSpec {
+            source_file: "main.rs",
+            compile: Some(command(&[
+                &toolchains.rustc,
+                "-O",
+                "--edition",
+                "2021",
+                "-o",
+                "main",
+                "main.rs",
+            ])),
+            run: command(&["./main"]),
+            runtime_overhead_bytes: 16 * MIB,
+        },
+        // py_compile turns syntax errors into compilation errors
+        ProgrammingLanguage::Python => LanguageSpec {
+            source_file: "main.py",
+            compile: Some(command(&[
+                &toolchains.python,
+                "-m",
+                "py_compile",
+                "main.py",
+            ])),
+            run: command(&[&toolchains.python, "main.py"]),
+            runtime_overhead_bytes: 64 * MIB,
+        },
+    }
+}
@@ -0,0 +1,13 @@
+//! Automated grading of code submissions.
+//!
+//! The database queues a `grading_runs` row whenever a submission with a
+//! programming language is submitted. The `grading-worker` binary claims
+//! queued runs, compiles and runs the submitted code in a [`sandbox`] against
+//! the task's hidden test cases and stores a verdict per test.
+
+pub mod config;
+pub mod error;
+pub mod judge;
+pub mod languages;
+pub mod sandbox;
+pub mod worker;
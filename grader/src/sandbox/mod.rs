@@ -0,0 +1,772 @@
+//! Run untrusted programs in a Linux sandbox.
+//!
+//! Every process is started in fresh user, PID, mount, network, IPC and UTS
+//! namespaces:
+//!
+//! - it runs as `nobody` without capabilities and can not signal or inspect
+//!   processes outside the sandbox, its own `/proc` only shows the sandbox
+//! - its root is an empty filesystem holding only the `mounts` of the
+//!   command, read-only, a few device files and the writable work
+//!   directory; nothing else of the host, such as the worker's
+//!   configuration or other runs' work directories, is reachable
+//! - there is no network, only a loopback interface that is down
+//!
+//! On top of that, rlimits bound CPU time, address space, stack, output
+//! size, open files and processes, and a seccomp filter (see [`seccomp`])
+//! denies syscalls that reach outside the sandbox. The process is killed
+//! once it exceeds its wall-clock budget; ending PID 1 of the namespace ends
+//! all its children.
+//!
+//! Unprivileged user namespaces must be enabled on the host
+//! (`kernel.unprivileged_userns_clone`, or the AppArmor restriction lifted
+//! on Ubuntu). Linux 5.14 or newer is required: `mount_setattr` came in
+//! 5.12, and only since 5.14 is `RLIMIT_NPROC` counted per user namespace
+//! rather than across every process of the worker's user.
+
+pub mod seccomp;
+
+use libc::{c_char, c_int, c_long, c_void};
+use seccomp::{SyscallPolicy, build_filter};
+use std::collections::HashSet;
+use std::ffi::{CString, OsStr};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Resource limits of one sandboxed process
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// CPU time after which the process is killed
+    pub cpu_time_ms: u64,
+    /// Real time after which the process is killed, covers sleeping and
+    /// blocking
+    pub wall_time_ms: u64,
+    /// `RLIMIT_AS`; allocations beyond it fail
+    pub address_space_bytes: Option<u64>,
+    pub stack_bytes: Option<u64>,
+    /// Largest file the process may write, including its stdout
+    pub output_bytes: u64,
+    pub open_files: u64,
+    /// `RLIMIT_NPROC`; threads count as processes
+    pub processes: u64,
+}
+
+/// A program to run in the sandbox
+#[derive(Debug, Clone)]
+pub struct SandboxCommand {
+    /// Looked up in `PATH` unless it contains a `/`
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    /// Host paths made visible read-only at the same place, e.g. the
+    /// toolchain; missing ones are skipped and symlinks are recreated as
+    /// they are, so their targets must be mounted too
+    pub mounts: Vec<PathBuf>,
+    /// The only writable directory, also the working directory
+    pub work_dir: PathBuf,
+    /// Read from `/dev/null` when `None`
+    pub stdin: Option<PathBuf>,
+    pub stdout: PathBuf,
+    pub stderr: PathBuf,
+    pub limits: Limits,
+    pub policy: SyscallPolicy,
+}
+
+/// How a sandboxed process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    Exited(i32),
+    Signaled(i32),
+    /// Killed after running past `Limits::wall_time_ms`
+    WallTimeout,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Execution {
+    pub termination: Termination,
+    /// User plus system time of the process and its children
+    pub cpu_time_ms: u64,
+    pub wall_time_ms: u64,
+    /// Peak resident set size
+    pub peak_memory_kb: u64,
+}
+
+impl Execution {
+    pub fn succeeded(&self) -> bool {
+        self.termination == Termination::Exited(0)
+    }
+}
+
+#[derive(Debug)]
+pub enum SandboxError {
+    Io(io::Error),
+    /// The sandbox could not be set up; nothing of the program ran
+    Setup {
+        step: &'static str,
+        errno: i32,
+    },
+    ProgramNotFound(String),
+}
+
+impl fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxError::Io(e) => write!(f, "I/O error: {}", e),
+            SandboxError::Setup { step, errno } => write!(
+                f,
+                "Sandbox setup failed ({}): {}",
+                step,
+                io::Error::from_raw_os_error(*errno)
+            ),
+            SandboxError::ProgramNotFound(program) => {
+                write!(f, "Program '{}' not found in PATH", program)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SandboxError {}
+
+impl From<io::Error> for SandboxError {
+    fn from(err: io::Error) -> Self {
+        SandboxError::Io(err)
+    }
+}
+
+/// Setup steps of the child, reported back through the error pipe
+const STEPS: [&str; 11] = [
+    "uid/gid map",
+    "private mounts",
+    "new root",
+    "populate root",
+    "mount /proc",
+    "detach host root",
+    "chdir",
+    "redirect stdio",
+    "rlimits",
+    "seccomp",
+    "execve",
+];
+
+const NAMESPACES: c_int = libc::CLONE_NEWUSER
+    | libc::CLONE_NEWPID
+    | libc::CLONE_NEWNS
+    | libc::CLONE_NEWNET
+    | libc::CLONE_NEWIPC
+    | libc::CLONE_NEWUTS;
+
+/// `nobody`, the user and group the program runs as
+const SANDBOX_ID: u32 = 65534;
+
+/// Device files every program may open
+const DEVICES: [&str; 4] = ["/dev/null", "/dev/zero", "/dev/random", "/dev/urandom"];
+
+/// Where the host's root stays reachable while the new root is populated
+const HOST_ROOT: &str = "/host";
+
+const MOUNT_ATTR_RDONLY: u64 = 0x1;
+const MOUNT_ATTR_NOSUID: u64 = 0x2;
+const AT_RECURSIVE: u32 = 0x8000;
+const CLOSE_RANGE_CLOEXEC: u32 = 1 << 2;
+
+/// `struct mount_attr` of `mount_setattr(2)`
+#[repr(C)]
+struct MountAttr {
+    attr_set: u64,
+    attr_clr: u64,
+    propagation: u64,
+    userns_fd: u64,
+}
+
+/// One step of populating the sandbox's root
+#[derive(Debug)]
+enum RootEntry {
+    Dir(CString),
+    /// An empty file to bind a file onto
+    File(CString),
+    Symlink {
+        target: CString,
+        path: CString,
+    },
+    /// Bind the host's `source`, reached through [`HOST_ROOT`], onto
+    /// `target`
+    Bind {
+        source: CString,
+        target: CString,
+        writable: bool,
+    },
+}
+
+/// Collects the entries of the sandbox's root, creating each directory once
+#[derive(Default)]
+struct RootBuilder {
+    entries: Vec<RootEntry>,
+    created: HashSet<PathBuf>,
+}
+
+impl RootBuilder {
+    /// Create `path` and its missing parents
+    fn dirs(&mut self, path: &Path) -> Result<(), SandboxError> {
+        if path == Path::new("/") || self.created.contains(path) {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            self.dirs(parent)?;
+        }
+        self.entries.push(RootEntry::Dir(c_string(path)?));
+        self.created.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Make the host's `path` visible at the same place; skipped when it
+    /// does not exist
+    fn bind(&mut self, path: &Path, writable: bool) -> Result<(), SandboxError> {
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Can not mount '{}' in the sandbox", path.display()),
+            )
+            .into());
+        };
+        // Resolve symlinked parents, the mount must land on a real path
+        let parent = match parent.canonicalize() {
+            Ok(parent) => parent,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let path = parent.join(name);
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if self.created.contains(&path) {
+            return Ok(());
+        }
+
+        self.dirs(&parent)?;
+        let target = c_string(&path)?;
+        if metadata.file_type().is_symlink() {
+            self.entries.push(RootEntry::Symlink {
+                target: c_string(fs::read_link(&path)?)?,
+                path: target,
+            });
+        } else {
+            self.entries.push(if metadata.is_dir() {
+                RootEntry::Dir(target.clone())
+            } else {
+                RootEntry::File(target.clone())
+            });
+            self.entries.push(RootEntry::Bind {
+                source: c_string(Path::new(HOST_ROOT).join(path.strip_prefix("/").unwrap()))?,
+                target,
+                writable,
+            });
+        }
+        self.created.insert(path);
+        Ok(())
+    }
+}
+
+/// Everything the child needs, prepared before forking: after the fork the
+/// child may not allocate
+struct ChildSetup {
+    program: CString,
+    argv: Vec<CString>,
+    envp: Vec<CString>,
+    uid_map: CString,
+    gid_map: CString,
+    root: Vec<RootEntry>,
+    work_dir: CString,
+    stdin: File,
+    stdout: File,
+    stderr: File,
+    rlimits: Vec<(libc::__rlimit_resource_t, u64)>,
+    filter: Vec<libc::sock_filter>,
+}
+
+/// Run `command` in the sandbox and wait for it to end
+///
+/// Blocks the calling thread; run it on a blocking thread pool.
+pub fn run(command: &SandboxCommand) -> Result<Execution, SandboxError> {
+    let setup = prepare(command)?;
+
+    let argv: Vec<*const c_char> = setup
+        .argv
+        .iter()
+        .map(|arg| arg.as_ptr())
+        .chain(std::iter::once(std::ptr::null()))
+        .collect();
+    let envp: Vec<*const c_char> = setup
+        .envp
+        .iter()
+        .map(|var| var.as_ptr())
+        .chain(std::iter::once(std::ptr::null()))
+        .collect();
+
+    let (error_read, error_write) = pipe()?;
+    let started = Instant::now();
+
+    // SAFETY: the child only makes raw syscalls on memory prepared above
+    // and ends in execve or _exit
+    let pid = unsafe {
+        libc::syscall(
+            libc::SYS_clone,
+            (NAMESPACES | libc::SIGCHLD) as c_long,
+            0 as c_long,
+            0 as c_long,
+            0 as c_long,
+            0 as c_long,
+        )
+    };
+    if pid < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    if pid == 0 {
+        unsafe { child(&setup, &argv, &envp, error_write.as_raw_fd()) }
+    }
+    let pid = pid as libc::pid_t;
+
+    drop(error_write);
+    drop(setup);
+
+    // The pipe closes on a successful execve; otherwise the child reports
+    // the failed step and errno
+    let mut report = [0u8; 8];
+    let mut error_read = File::from(error_read);
+    let reported = read_full(&mut error_read, &mut report)?;
+    if reported == report.len() {
+        wait(pid, None)?;
+        let step = u32::from_ne_bytes(report[..4].try_into().unwrap()) as usize;
+        let errno = i32::from_ne_bytes(report[4..].try_into().unwrap());
+        return Err(SandboxError::Setup {
+            step: STEPS.get(step).copied().unwrap_or("unknown"),
+            errno,
+        });
+    }
+
+    let deadline = started + Duration::from_millis(command.limits.wall_time_ms);
+    let (status, usage, timed_out) = wait(pid, Some(deadline))?;
+
+    let cpu_time = |t: libc::timeval| t.tv_sec as u64 * 1000 + t.tv_usec as u64 / 1000;
+    let termination = if timed_out {
+        Termination::WallTimeout
+    } else if libc::WIFEXITED(status) {
+        Termination::Exited(libc::WEXITSTATUS(status))
+    } else {
+        Termination::Signaled(libc::WTERMSIG(status))
+    };
+
+    Ok(Execution {
+        termination,
+        cpu_time_ms: cpu_time(usage.ru_utime) + cpu_time(usage.ru_stime),
+        wall_time_ms: started.elapsed().as_millis() as u64,
+        peak_memory_kb: usage.ru_maxrss.max(0) as u64,
+    })
+}
+
+/// Find `program` in `PATH`, like a shell would
+pub fn resolve_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        return Some(PathBuf::from(program));
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(program))
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    // SAFETY: `path` is a valid C string
+    unsafe { libc::access(path.as_ptr(), libc::X_OK) == 0 }
+}
+
+fn c_string(value: impl AsRef<OsStr>) -> Result<CString, SandboxError> {
+    CString::new(value.as_ref().as_bytes()).map_err(|e| SandboxError::Io(io::Error::other(e)))
+}
+
+fn prepare(command: &SandboxCommand) -> Result<ChildSetup, SandboxError> {
+    let program = resolve_program(&command.program)
+        .ok_or_else(|| SandboxError::ProgramNotFound(command.program.clone()))?;
+
+    let argv = std::iter::once(command.program.as_str())
+        .chain(command.args.iter().map(String::as_str))
+        .map(c_string)
+        .collect::<Result<_, _>>()?;
+    let envp = command
+        .env
+        .iter()
+        .map(|(key, value)| c_string(format!("{}={}", key, value)))
+        .collect::<Result<_, _>>()?;
+
+    // SAFETY: getuid and getgid can not fail
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+
+    let stdin = match &command.stdin {
+        Some(path) => File::open(path)?,
+        None => File::open("/dev/null")?,
+    };
+
+    let limits = &command.limits;
+    let mut rlimits = vec![
+        // SIGXCPU at the soft limit, SIGKILL a second later
+        (libc::RLIMIT_CPU, limits.cpu_time_ms.div_ceil(1000)),
+        (libc::RLIMIT_FSIZE, limits.output_bytes),
+        (libc::RLIMIT_NOFILE, limits.open_files),
+        (libc::RLIMIT_NPROC, limits.processes),
+        (libc::RLIMIT_CORE, 0),
+    ];
+    if let Some(bytes) = limits.address_space_bytes {
+        rlimits.push((libc::RLIMIT_AS, bytes));
+    }
+    if let Some(bytes) = limits.stack_bytes {
+        rlimits.push((libc::RLIMIT_STACK, bytes));
+    }
+
+    // The program is looked up on the host, so it must be under one of the
+    // mounts to be found in the sandbox
+    let work_dir = command.work_dir.canonicalize()?;
+    let mut root = RootBuilder::default();
+    for path in &command.mounts {
+        root.bind(path, false)?;
+    }
+    for device in DEVICES {
+        root.bind(Path::new(device), false)?;
+    }
+    root.bind(&work_dir, true)?;
+    root.dirs(Path::new("/proc"))?;
+
+    Ok(ChildSetup {
+        program: c_string(program)?,
+        argv,
+        envp,
+        uid_map: c_string(format!("{} {} 1", SANDBOX_ID, uid))?,
+        gid_map: c_string(format!("{} {} 1", SANDBOX_ID, gid))?,
+        root: root.entries,
+        work_dir: c_string(&work_dir)?,
+        stdin,
+        stdout: File::create(&command.stdout)?,
+        stderr: File::create(&command.stderr)?,
+        rlimits,
+        filter: build_filter(command.policy),
+    })
+}
+
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0 as c_int; 2];
+    // SAFETY: `fds` has room for both ends
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: pipe2 returned two fresh descriptors
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+fn read_full(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match file.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read)
+}
+
+/// Wait for `pid` to end, killing it at `deadline`
+///
+/// Returns the wait status, resource usage and whether it was killed for
+/// running past the deadline.
+fn wait(pid: libc::pid_t, deadline: Option<Instant>) -> io::Result<(c_int, libc::rusage, bool)> {
+    let mut timed_out = false;
+
+    if let Some(deadline) = deadline {
+        // SAFETY: plain syscall on our own child
+        let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+        if pidfd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: pidfd_open returned a fresh descriptor
+        let pidfd = unsafe { OwnedFd::from_raw_fd(pidfd as c_int) };
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let mut poll = libc::pollfd {
+                fd: pidfd.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let timeout = remaining.as_millis().min(c_int::MAX as u128) as c_int;
+            // SAFETY: `poll` points at one valid pollfd
+            let ready = unsafe { libc::poll(&mut poll, 1, timeout) };
+            if ready > 0 {
+                break;
+            }
+            if ready < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(err);
+            }
+            if Instant::now() >= deadline {
+                // Killing PID 1 of the namespace kills everything in it
+                // SAFETY: the child has not been reaped, so `pid` is ours
+                unsafe { libc::kill(pid, libc::SIGKILL) };
+                timed_out = true;
+                break;
+            }
+        }
+    }
+
+    let mut status = 0;
+    // SAFETY: rusage is plain data
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: `status` and `usage` are valid for writes
+        let reaped = unsafe { libc::wait4(pid, &mut status, 0, &mut usage) };
+        if reaped == pid {
+            return Ok((status, usage, timed_out));
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// Report the failed `step` to the parent and exit
+unsafe fn fail(error_fd: c_int, step: u32) -> ! {
+    unsafe {
+        let errno = *libc::__errno_location();
+        let mut report = [0u8; 8];
+        report[..4].copy_from_slice(&step.to_ne_bytes());
+        report[4..].copy_from_slice(&errno.to_ne_bytes());
+        libc::write(error_fd, report.as_ptr() as *const c_void, report.len());
+        libc::_exit(127)
+    }
+}
+
+unsafe fn write_file(path: &[u8], contents: &[u8]) -> bool {
+    unsafe {
+        let fd = libc::open(
+            path.as_ptr() as *const c_char,
+            libc::O_WRONLY | libc::O_CLOEXEC,
+        );
+        if fd < 0 {
+            return false;
+        }
+        let written = libc::write(fd, contents.as_ptr() as *const c_void, contents.len());
+        libc::close(fd);
+        written == contents.len() as isize
+    }
+}
+
+unsafe fn mount_setattr(path: *const c_char, flags: u32, attr: &MountAttr) -> bool {
+    unsafe {
+        libc::syscall(
+            libc::SYS_mount_setattr,
+            libc::AT_FDCWD,
+            path,
+            flags,
+            attr as *const MountAttr,
+            std::mem::size_of::<MountAttr>(),
+        ) == 0
+    }
+}
+
+/// Runs in the cloned child, PID 1 of the new namespaces
+unsafe fn child(
+    setup: &ChildSetup,
+    argv: &[*const c_char],
+    envp: &[*const c_char],
+    error_fd: c_int,
+) -> ! {
+    unsafe {
+        let null = std::ptr::null::<c_char>();
+        let root = c"/".as_ptr();
+        let proc = c"/proc".as_ptr();
+        let work_dir = setup.work_dir.as_ptr();
+
+        // 0. Run as nobody; setgroups must be denied before mapping groups
+        if !write_file(b"/proc/self/setgroups\0", b"deny")
+            || !write_file(b"/proc/self/uid_map\0", setup.uid_map.as_bytes())
+            || !write_file(b"/proc/self/gid_map\0", setup.gid_map.as_bytes())
+        {
+            fail(error_fd, 0);
+        }
+
+        // 1. Keep mount changes inside the namespace
+        if libc::mount(
+            null,
+            root,
+            null,
+            libc::MS_REC | libc::MS_PRIVATE,
+            std::ptr::null(),
+        ) != 0
+        {
+            fail(error_fd, 1);
+        }
+
+        // 2. Switch to an empty root, keeping the host's under HOST_ROOT
+        // until the new one is populated. Any directory can hold the
+        // tmpfs, it is only seen in this namespace
+        let new_root = c"/tmp".as_ptr();
+        let host_root = c"/tmp/host".as_ptr();
+        if libc::mount(
+            c"tmpfs".as_ptr(),
+            new_root,
+            c"tmpfs".as_ptr(),
+            libc::MS_NOSUID | libc::MS_NODEV,
+            c"mode=0755".as_ptr() as *const c_void,
+        ) != 0
+            || libc::mkdir(host_root, 0o700) != 0
+            || libc::syscall(libc::SYS_pivot_root, new_root, host_root) != 0
+            || libc::chdir(root) != 0
+        {
+            fail(error_fd, 2);
+        }
+
+        // 3. Bind the mounts read-only and the work directory writable
+        let read_only = MountAttr {
+            attr_set: MOUNT_ATTR_RDONLY | MOUNT_ATTR_NOSUID,
+            attr_clr: 0,
+            propagation: 0,
+            userns_fd: 0,
+        };
+        let writable = MountAttr {
+            attr_set: MOUNT_ATTR_NOSUID,
+            attr_clr: MOUNT_ATTR_RDONLY,
+            propagation: 0,
+            userns_fd: 0,
+        };
+        for entry in &setup.root {
+            let created = match entry {
+                RootEntry::Dir(path) => {
+                    libc::mkdir(path.as_ptr(), 0o755) == 0
+                        || *libc::__errno_location() == libc::EEXIST
+                }
+                RootEntry::File(path) => {
+                    let fd = libc::open(
+                        path.as_ptr(),
+                        libc::O_WRONLY | libc::O_CREAT | libc::O_CLOEXEC,
+                        0o644,
+                    );
+                    fd >= 0 && libc::close(fd) == 0
+                }
+                RootEntry::Symlink { target, path } => {
+                    libc::symlink(target.as_ptr(), path.as_ptr()) == 0
+                }
+                RootEntry::Bind {
+                    source,
+                    target,
+                    writable: is_writable,
+                } => {
+                    let attr = if *is_writable { &writable } else { &read_only };
+                    libc::mount(
+                        source.as_ptr(),
+                        target.as_ptr(),
+                        null,
+                        libc::MS_BIND | libc::MS_REC,
+                        std::ptr::null(),
+                    ) == 0
+                        && mount_setattr(target.as_ptr(), AT_RECURSIVE, attr)
+                }
+            };
+            if !created {
+                fail(error_fd, 3);
+            }
+        }
+
+        // 4. Only show the sandbox's own processes; hide /proc entirely
+        // where a new proc can not be mounted (e.g. inside containers).
+        // The kernel only allows it while the host's proc is visible, so
+        // this comes before the host root is detached
+        let hidden = libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC;
+        if libc::mount(
+            c"proc".as_ptr(),
+            proc,
+            c"proc".as_ptr(),
+            hidden,
+            std::ptr::null(),
+        ) != 0
+            && libc::mount(
+                c"tmpfs".as_ptr(),
+                proc,
+                c"tmpfs".as_ptr(),
+                hidden,
+                std::ptr::null(),
+            ) != 0
+        {
+            fail(error_fd, 4);
+        }
+
+        // 5. Drop the host root and make the new one read-only
+        let host_root = c"/host".as_ptr();
+        if libc::umount2(host_root, libc::MNT_DETACH) != 0
+            || libc::rmdir(host_root) != 0
+            || !mount_setattr(root, 0, &read_only)
+        {
+            fail(error_fd, 5);
+        }
+
+        if libc::chdir(work_dir) != 0 {
+            fail(error_fd, 6);
+        }
+
+        // 7. Standard streams; nothing else survives execve
+        if libc::dup2(setup.stdin.as_raw_fd(), 0) < 0
+            || libc::dup2(setup.stdout.as_raw_fd(), 1) < 0
+            || libc::dup2(setup.stderr.as_raw_fd(), 2) < 0
+        {
+            fail(error_fd, 7);
+        }
+        libc::syscall(libc::SYS_close_range, 3u32, u32::MAX, CLOSE_RANGE_CLOEXEC);
+
+        // 8. Resource limits; RLIMIT_CPU gets a second of grace before the
+        // hard limit kills
+        for &(resource, value) in &setup.rlimits {
+            let hard = if resource == libc::RLIMIT_CPU {
+                value + 1
+            } else {
+                value
+            };
+            let limit = libc::rlimit {
+                rlim_cur: value,
+                rlim_max: hard,
+            };
+            if libc::setrlimit(resource, &limit) != 0 {
+                fail(error_fd, 8);
+            }
+        }
+
+        // 9. Seccomp, which requires no_new_privs without CAP_SYS_ADMIN
+        let program = libc::sock_fprog {
+            len: setup.filter.len() as u16,
+            filter: setup.filter.as_ptr() as *mut libc::sock_filter,
+        };
+        if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+            || libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &program as *const libc::sock_fprog,
+            ) != 0
+        {
+            fail(error_fd, 9);
+        }
+
+        libc::execve(setup.program.as_ptr(), argv.as_ptr(), envp.as_ptr());
+        fail(error_fd, 10)
+    }
+}
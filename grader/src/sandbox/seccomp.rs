@@ -0,0 +1,172 @@
+//! Seccomp BPF filters for sandboxed processes.
+//!
+//! The filters are deny lists: everything a compiler or an ordinary program
+//! needs stays available, while syscalls that reach outside the sandbox
+//! (debugging other processes, mounting, loading kernel code, opening
+//! sockets, ...) fail with `EPERM`. Syscalls of a foreign architecture kill
+//! the process.
+
+use libc::{c_long, sock_filter};
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH_CURRENT: u32 = 0xC000_003E;
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH_CURRENT: u32 = 0xC000_00B7;
+
+/// x32 syscalls share the x86_64 audit arch but set this bit in the number
+#[cfg(target_arch = "x86_64")]
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+// Offsets into `struct seccomp_data`
+const OFFSET_NR: u32 = 0;
+const OFFSET_ARCH: u32 = 4;
+const OFFSET_ARG0_LOW: u32 = 16;
+
+const RET_KILL_PROCESS: u32 = 0x8000_0000;
+const RET_ERRNO: u32 = 0x0005_0000;
+const RET_ALLOW: u32 = 0x7fff_0000;
+
+/// Which syscalls a sandboxed process may make
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyscallPolicy {
+    /// Compilers spawn helper processes (`cc1`, `as`, `ld`)
+    Compile,
+    /// Submitted programs may start threads but not processes
+    Run,
+}
+
+/// Syscalls no sandboxed process may make
+const DENIED: &[c_long] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_kcmp,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_chroot,
+    libc::SYS_mount_setattr,
+    libc::SYS_open_tree,
+    libc::SYS_move_mount,
+    libc::SYS_fsopen,
+    libc::SYS_fsconfig,
+    libc::SYS_fsmount,
+    libc::SYS_fspick,
+    libc::SYS_setns,
+    libc::SYS_unshare,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_reboot,
+    libc::SYS_kexec_load,
+    libc::SYS_kexec_file_load,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_userfaultfd,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_acct,
+    libc::SYS_quotactl,
+    libc::SYS_settimeofday,
+    libc::SYS_clock_settime,
+    libc::SYS_clock_adjtime,
+    libc::SYS_adjtimex,
+    libc::SYS_sethostname,
+    libc::SYS_setdomainname,
+    libc::SYS_name_to_handle_at,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_io_uring_setup,
+    libc::SYS_io_uring_enter,
+    libc::SYS_io_uring_register,
+    libc::SYS_personality,
+    // Unix sockets on the host filesystem are reachable from any network
+    // namespace
+    libc::SYS_socket,
+    libc::SYS_connect,
+];
+
+/// Process creation, denied by `SyscallPolicy::Run`
+#[cfg(target_arch = "x86_64")]
+const PROCESS_CREATION: &[c_long] = &[libc::SYS_fork, libc::SYS_vfork];
+#[cfg(target_arch = "aarch64")]
+const PROCESS_CREATION: &[c_long] = &[];
+
+fn stmt(code: u32, k: u32) -> sock_filter {
+    sock_filter {
+        code: code as u16,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+fn jump(code: u32, k: u32, jt: u8, jf: u8) -> sock_filter {
+    sock_filter {
+        code: code as u16,
+        jt,
+        jf,
+        k,
+    }
+}
+
+const LOAD: u32 = libc::BPF_LD | libc::BPF_W | libc::BPF_ABS;
+const JEQ: u32 = libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K;
+const JSET: u32 = libc::BPF_JMP | libc::BPF_JSET | libc::BPF_K;
+const RET: u32 = libc::BPF_RET | libc::BPF_K;
+
+/// Build the filter program for `policy`
+///
+/// Built before the sandboxed process is forked; installing it is the last
+/// step before `execve`.
+pub fn build_filter(policy: SyscallPolicy) -> Vec<sock_filter> {
+    let mut filter = vec![
+        stmt(LOAD, OFFSET_ARCH),
+        jump(JEQ, AUDIT_ARCH_CURRENT, 1, 0),
+        stmt(RET, RET_KILL_PROCESS),
+        stmt(LOAD, OFFSET_NR),
+    ];
+
+    #[cfg(target_arch = "x86_64")]
+    filter.extend([
+        jump(
+            libc::BPF_JMP | libc::BPF_JGE | libc::BPF_K,
+            X32_SYSCALL_BIT,
+            0,
+            1,
+        ),
+        stmt(RET, RET_KILL_PROCESS),
+    ]);
+
+    let deny = |filter: &mut Vec<sock_filter>, nr: c_long, errno: i32| {
+        filter.push(jump(JEQ, nr as u32, 0, 1));
+        filter.push(stmt(RET, RET_ERRNO | errno as u32));
+    };
+
+    for &nr in DENIED {
+        deny(&mut filter, nr, libc::EPERM);
+    }
+
+    if policy == SyscallPolicy::Run {
+        for &nr in PROCESS_CREATION {
+            deny(&mut filter, nr, libc::EPERM);
+        }
+
+        // clone3 passes its flags in memory the filter can not read; libc
+        // falls back to clone on ENOSYS
+        deny(&mut filter, libc::SYS_clone3, libc::ENOSYS);
+
+        // clone is allowed for threads only
+        filter.extend([
+            jump(JEQ, libc::SYS_clone as u32, 0, 3),
+            stmt(LOAD, OFFSET_ARG0_LOW),
+            jump(JSET, libc::CLONE_THREAD as u32, 1, 0),
+            stmt(RET, RET_ERRNO | libc::EPERM as u32),
+        ]);
+    }
+
+    filter.push(stmt(RET, RET_ALLOW));
+    filter
+}
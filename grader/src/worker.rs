@@ -0,0 +1,123 @@
+use crate::config::GraderConfig;
+use crate::error::GraderError;
+use crate::judge::{Grade, grade};
+use chrono::Utc;
+use models::GradingRun;
+use repositories::repositories::{
+    GradingRepository, SubmissionRepository, SubmissionRevisionRepository, TaskTestCaseRepository,
+};
+use repositories::traits::{
+    GradingRepositoryTrait, SubmissionRepositoryTrait, SubmissionRevisionRepositoryTrait,
+    TaskTestCaseRepositoryTrait,
+};
+use sqlx::PgPool;
+use std::fs::{self, DirBuilder};
+use std::os::unix::fs::DirBuilderExt;
+use std::sync::Arc;
+
+/// Claims queued grading runs and grades them
+#[derive(Clone)]
+pub struct Worker {
+    config: Arc<GraderConfig>,
+    grading: Arc<dyn GradingRepositoryTrait>,
+    submission: Arc<dyn SubmissionRepositoryTrait>,
+    submission_revision: Arc<dyn SubmissionRevisionRepositoryTrait>,
+    task_test_case: Arc<dyn TaskTestCaseRepositoryTrait>,
+}
+
+impl Worker {
+    pub fn new(pool: PgPool, config: GraderConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            grading: Arc::new(GradingRepository::new(pool.clone())),
+            submission: Arc::new(SubmissionRepository::new(pool.clone())),
+            submission_revision: Arc::new(SubmissionRevisionRepository::new(pool.clone())),
+            task_test_case: Arc::new(TaskTestCaseRepository::new(pool)),
+        }
+    }
+
+    /// Grade the oldest queued run
+    ///
+    /// Returns: The finished run, `None` when the queue is empty
+    ///
+    /// Side effects:
+    /// - Stores the verdicts, or marks the run failed when it could not be
+    ///   graded
+    pub async fn process_next(&self) -> Result<Option<GradingRun>, GraderError> {
+        let Some(run) = self.grading.claim_next().await? else {
+            return Ok(None);
+        };
+
+        let run = match self.grade_run(&run).await {
+            Ok(grade) => {
+                self.grading
+                    .complete(run.id, grade.verdict, grade.compile_output, grade.results)
+                    .await?
+            }
+            Err(e) => self.grading.fail(run.id, &e.to_string()).await?,
+        };
+
+        Ok(Some(run))
+    }
+
+    /// Requeue runs abandoned by a crashed or stopped worker
+    pub async fn requeue_stale(&self) -> Result<u64, GraderError> {
+        let stale_after = chrono::Duration::from_std(self.config.stale_after)
+            .map_err(|e| GraderError::Invalid(e.to_string()))?;
+        Ok(self.grading.requeue_stale(Utc::now() - stale_after).await?)
+    }
+
+    async fn grade_run(&self, run: &GradingRun) -> Result<Grade, GraderError> {
+        // 1. Load the graded version and the task's tests
+        let submission = self
+            .submission
+            .find_by_id(run.submission_id)
+            .await?
+            .ok_or_else(|| GraderError::Invalid("Submission not found".to_string()))?;
+
+        let revision = self
+            .submission_revision
+            .find_by_version(run.submission_id, run.submission_version)
+            .await?
+            .ok_or_else(|| {
+                GraderError::Invalid(format!(
+                    "Version {} of the submission not found",
+                    run.submission_version
+                ))
+            })?;
+
+        let test_cases = self.task_test_case.find_by_task(submission.task_id).await?;
+        if test_cases.is_empty() {
+            return Err(GraderError::Invalid("Task has no test cases".to_string()));
+        }
+
+        // 2. Compile and run in a fresh work directory only the worker's
+        // user can enter. A run requeued after a crash may have left its
+        // directory behind
+        DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(&self.config.work_dir)?;
+        let work_dir = self.config.work_dir.join(run.id.to_string());
+        if work_dir.exists() {
+            fs::remove_dir_all(&work_dir)?;
+        }
+        DirBuilder::new().mode(0o700).create(&work_dir)?;
+
+        let language = run.language;
+        let toolchains = self.config.toolchains.clone();
+        let dir = work_dir.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            grade(&dir, language, &revision.content, &test_cases, &toolchains)
+        })
+        .await
+        .map_err(|e| GraderError::Invalid(e.to_string()))?;
+
+        // 3. Clean up; a leftover directory only costs disk space
+        if let Err(e) = fs::remove_dir_all(&work_dir) {
+            eprintln!("Failed to remove {}: {}", work_dir.display(), e);
+        }
+
+        result
+    }
+}
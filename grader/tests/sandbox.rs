@@ -0,0 +1,206 @@
+//! Grade small C programs end to end in the sandbox.
+//!
+//! Needs what the worker needs: unprivileged user namespaces and a C
+//! compiler (`GRADER_CC`, `gcc` by default).
+
+use chrono::Utc;
+use grader::config::GraderConfig;
+use grader::judge::{Grade, grade};
+use models::{GradingVerdict, ProgrammingLanguage, TaskTestCase};
+use std::fs::{self, DirBuilder};
+use std::os::unix::fs::DirBuilderExt;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const TIME_LIMIT_MS: i32 = 1000;
+const MEMORY_LIMIT_KB: i32 = 64 * 1024;
+
+/// A private directory removed when dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("grader-test-{}", Uuid::new_v4()));
+        DirBuilder::new().mode(0o700).create(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+fn test_case(input: &str, expected_output: &str, memory_limit_kb: i32) -> TaskTestCase {
+    TaskTestCase {
+        id: Uuid::new_v4(),
+        task_id: Uuid::new_v4(),
+        position: 1,
+        input: input.to_string(),
+        expected_output: expected_output.to_string(),
+        time_limit_ms: TIME_LIMIT_MS,
+        memory_limit_kb,
+        is_sample: false,
+        points: 1,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn grade_c(source: &str, case: TaskTestCase) -> Grade {
+    let work_dir = TempDir::new();
+    let toolchains = GraderConfig::from_env().toolchains;
+    grade(
+        &work_dir.0,
+        ProgrammingLanguage::C,
+        source,
+        &[case],
+        &toolchains,
+    )
+    .unwrap()
+}
+
+#[test]
+fn accepts_a_correct_program() {
+    let source = r#"
+        #include <stdio.h>
+        int main(void) {
+            int a, b;
+            scanf("%d %d", &a, &b);
+            printf("%d\n", a + b);
+            return 0;
+        }
+    "#;
+    let grade = grade_c(source, test_case("2 3\n", "5\n", MEMORY_LIMIT_KB));
+    assert_eq!(grade.verdict, GradingVerdict::AC);
+}
+
+#[test]
+fn denies_sockets() {
+    let source = r#"
+        #include <errno.h>
+        #include <stdio.h>
+        #include <sys/socket.h>
+        int main(void) {
+            int inet = socket(AF_INET, SOCK_STREAM, 0);
+            int unix_socket = socket(AF_UNIX, SOCK_STREAM, 0);
+            puts(inet < 0 && unix_socket < 0 && errno == EPERM ? "denied" : "allowed");
+            return 0;
+        }
+    "#;
+    let grade = grade_c(source, test_case("", "denied", MEMORY_LIMIT_KB));
+    assert_eq!(grade.verdict, GradingVerdict::AC);
+}
+
+#[test]
+fn denies_fork() {
+    let source = r#"
+        #define _POSIX_C_SOURCE 200809L
+        #include <stdio.h>
+        #include <unistd.h>
+        int main(void) {
+            pid_t pid = fork();
+            if (pid == 0) {
+                _exit(0);
+            }
+            puts(pid < 0 ? "denied" : "allowed");
+            return 0;
+        }
+    "#;
+    let grade = grade_c(source, test_case("", "denied", MEMORY_LIMIT_KB));
+    assert_eq!(grade.verdict, GradingVerdict::AC);
+}
+
+#[test]
+fn hides_files_outside_the_work_dir() {
+    let outside = TempDir::new();
+    let secret = outside.0.join("secret.txt");
+    fs::write(&secret, "DATABASE_URL=postgres://secret").unwrap();
+
+    // The path comes in on stdin; the program reports whether it exists
+    let source = r#"
+        #include <stdio.h>
+        #include <string.h>
+        int main(void) {
+            char path[4096];
+            if (!fgets(path, sizeof path, stdin)) {
+                return 1;
+            }
+            path[strcspn(path, "\n")] = 0;
+            puts(fopen(path, "r") ? "visible" : "hidden");
+            puts(fopen("/proc/1/root/etc/hostname", "r") ? "visible" : "hidden");
+            return 0;
+        }
+    "#;
+    let input = format!("{}\n", secret.display());
+    let grade = grade_c(
+        source,
+        test_case(&input, "hidden\nhidden\n", MEMORY_LIMIT_KB),
+    );
+    assert_eq!(grade.verdict, GradingVerdict::AC);
+}
+
+#[test]
+fn reports_time_limit_exceeded() {
+    let source = r#"
+        int main(void) {
+            volatile unsigned long i = 0;
+            for (;;) {
+                i++;
+            }
+        }
+    "#;
+    let grade = grade_c(source, test_case("", "", MEMORY_LIMIT_KB));
+    assert_eq!(grade.verdict, GradingVerdict::TLE);
+}
+
+#[test]
+fn reports_memory_limit_exceeded() {
+    let source = r#"
+        #include <stdlib.h>
+        int main(void) {
+            size_t size = 48 * 1024 * 1024;
+            volatile char *buffer = malloc(size);
+            if (!buffer) {
+                return 1;
+            }
+            for (size_t i = 0; i < size; i += 4096) {
+                buffer[i] = 1;
+            }
+            return 0;
+        }
+    "#;
+    let grade = grade_c(source, test_case("", "", 16 * 1024));
+    assert_eq!(grade.verdict, GradingVerdict::MLE);
+}
+
+#[test]
+fn reports_runtime_error() {
+    // Not abort(): the program is PID 1 of its namespace, which ignores
+    // signals it sends itself
+    let source = r#"
+        int main(void) {
+            volatile int *null = 0;
+            *null = 1;
+            return 0;
+        }
+    "#;
+    let grade = grade_c(source, test_case("", "", MEMORY_LIMIT_KB));
+    assert_eq!(grade.verdict, GradingVerdict::RE);
+    assert_eq!(grade.results[0].exit_code, Some(128 + libc::SIGSEGV));
+}
+
+#[test]
+fn reports_compilation_errors() {
+    let grade = grade_c(
+        "int main(void) { return }",
+        test_case("", "", MEMORY_LIMIT_KB),
+    );
+    assert_eq!(grade.verdict, GradingVerdict::CE);
+    assert!(
+        grade
+            .compile_output
+            .is_some_and(|output| output.contains("error"))
+    );
+}
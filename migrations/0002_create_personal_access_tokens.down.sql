@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS update_personal_access_tokens_updated_at ON personal_access_tokens;
+
+DROP TABLE IF EXISTS personal_access_tokens;
@@ -0,0 +1,21 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS enforce_submissions_status_transition ON submissions;
+DROP FUNCTION IF EXISTS enforce_submission_status_transition();
+
+-- Statuses introduced with the enum have no VARCHAR equivalent
+UPDATE submissions SET status = 'submitted' WHERE status IN ('under_review', 'accepted', 'rejected');
+UPDATE submissions SET status = 'draft' WHERE status = 'withdrawn';
+
+ALTER TABLE submissions ALTER COLUMN status DROP NOT NULL;
+ALTER TABLE submissions ALTER COLUMN status DROP DEFAULT;
+ALTER TABLE submissions ALTER COLUMN status TYPE VARCHAR(50) USING status::text;
+ALTER TABLE submissions ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE submissions
+    ADD CONSTRAINT submissions_status_check CHECK (status IN ('draft', 'submitted'));
+
+ALTER TABLE problems_or_tasks ALTER COLUMN difficulty TYPE VARCHAR(20) USING difficulty::text;
+ALTER TABLE problems_or_tasks
+    ADD CONSTRAINT problems_or_tasks_difficulty_check CHECK (difficulty IN ('easy', 'medium', 'hard'));
+
+DROP TYPE IF EXISTS submission_status;
+DROP TYPE IF EXISTS task_difficulty;
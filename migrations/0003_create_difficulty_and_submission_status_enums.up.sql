@@ -0,0 +1,42 @@
+-- Replace the CHECK-constrained VARCHAR columns with Postgres enum types
+CREATE TYPE task_difficulty AS ENUM ('easy', 'medium', 'hard');
+
+CREATE TYPE submission_status AS ENUM (
+    'draft',
+    'submitted',
+    'under_review',
+    'accepted',
+    'rejected',
+    'withdrawn'
+);
+
+ALTER TABLE problems_or_tasks DROP CONSTRAINT IF EXISTS problems_or_tasks_difficulty_check;
+ALTER TABLE problems_or_tasks
+    ALTER COLUMN difficulty TYPE task_difficulty USING difficulty::task_difficulty;
+
+ALTER TABLE submissions DROP CONSTRAINT IF EXISTS submissions_status_check;
+ALTER TABLE submissions ALTER COLUMN status DROP DEFAULT;
+UPDATE submissions SET status = 'draft' WHERE status IS NULL;
+ALTER TABLE submissions
+    ALTER COLUMN status TYPE submission_status USING status::submission_status;
+ALTER TABLE submissions ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE submissions ALTER COLUMN status SET NOT NULL;
+
+-- Reject status changes that skip or reverse the review workflow
+CREATE OR REPLACE FUNCTION enforce_submission_status_transition()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status AND NOT (
+        (OLD.status = 'draft' AND NEW.status IN ('submitted', 'withdrawn'))
+        OR (OLD.status = 'submitted' AND NEW.status IN ('under_review', 'accepted', 'rejected', 'withdrawn'))
+        OR (OLD.status = 'under_review' AND NEW.status IN ('accepted', 'rejected', 'withdrawn'))
+    ) THEN
+        RAISE EXCEPTION 'invalid submission status transition from % to %', OLD.status, NEW.status
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER enforce_submissions_status_transition BEFORE UPDATE OF status ON submissions
+    FOR EACH ROW EXECUTE FUNCTION enforce_submission_status_transition();
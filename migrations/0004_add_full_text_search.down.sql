@@ -0,0 +1,20 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS update_problems_or_tasks_search_vector ON problems_or_tasks;
+DROP TRIGGER IF EXISTS update_submissions_search_vector ON submissions;
+DROP TRIGGER IF EXISTS update_task_comments_search_vector ON task_comments;
+DROP TRIGGER IF EXISTS update_submission_comments_search_vector ON submission_comments;
+DROP TRIGGER IF EXISTS update_task_comment_replies_search_vector ON task_comment_replies;
+DROP TRIGGER IF EXISTS update_submission_comment_replies_search_vector ON submission_comment_replies;
+
+DROP FUNCTION IF EXISTS update_problems_or_tasks_search_vector();
+DROP FUNCTION IF EXISTS update_submissions_search_vector();
+DROP FUNCTION IF EXISTS update_comments_search_vector();
+DROP FUNCTION IF EXISTS update_replies_search_vector();
+
+-- Dropping the columns also drops their GIN indexes
+ALTER TABLE problems_or_tasks DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE submissions DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE task_comments DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE submission_comments DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE task_comment_replies DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE submission_comment_replies DROP COLUMN IF EXISTS search_vector;
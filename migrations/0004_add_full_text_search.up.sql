@@ -0,0 +1,84 @@
+-- Full-text search: a weighted tsvector per searchable table, kept up to
+-- date by triggers and served by GIN indexes
+ALTER TABLE problems_or_tasks ADD COLUMN search_vector tsvector;
+ALTER TABLE submissions ADD COLUMN search_vector tsvector;
+ALTER TABLE task_comments ADD COLUMN search_vector tsvector;
+ALTER TABLE submission_comments ADD COLUMN search_vector tsvector;
+ALTER TABLE task_comment_replies ADD COLUMN search_vector tsvector;
+ALTER TABLE submission_comment_replies ADD COLUMN search_vector tsvector;
+
+-- Task titles outrank task content
+CREATE OR REPLACE FUNCTION update_problems_or_tasks_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector =
+        setweight(to_tsvector('english', COALESCE(NEW.title, '')), 'A') ||
+        setweight(to_tsvector('english', COALESCE(NEW.content, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_submissions_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = setweight(to_tsvector('english', COALESCE(NEW.content, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_comments_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = setweight(to_tsvector('english', COALESCE(NEW.comment, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_replies_search_vector()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_vector = setweight(to_tsvector('english', COALESCE(NEW.reply, '')), 'B');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER update_problems_or_tasks_search_vector
+    BEFORE INSERT OR UPDATE OF title, content ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION update_problems_or_tasks_search_vector();
+
+CREATE TRIGGER update_submissions_search_vector
+    BEFORE INSERT OR UPDATE OF content ON submissions
+    FOR EACH ROW EXECUTE FUNCTION update_submissions_search_vector();
+
+CREATE TRIGGER update_task_comments_search_vector
+    BEFORE INSERT OR UPDATE OF comment ON task_comments
+    FOR EACH ROW EXECUTE FUNCTION update_comments_search_vector();
+
+CREATE TRIGGER update_submission_comments_search_vector
+    BEFORE INSERT OR UPDATE OF comment ON submission_comments
+    FOR EACH ROW EXECUTE FUNCTION update_comments_search_vector();
+
+CREATE TRIGGER update_task_comment_replies_search_vector
+    BEFORE INSERT OR UPDATE OF reply ON task_comment_replies
+    FOR EACH ROW EXECUTE FUNCTION update_replies_search_vector();
+
+CREATE TRIGGER update_submission_comment_replies_search_vector
+    BEFORE INSERT OR UPDATE OF reply ON submission_comment_replies
+    FOR EACH ROW EXECUTE FUNCTION update_replies_search_vector();
+
+-- Backfill existing rows
+UPDATE problems_or_tasks SET search_vector =
+    setweight(to_tsvector('english', COALESCE(title, '')), 'A') ||
+    setweight(to_tsvector('english', COALESCE(content, '')), 'B');
+UPDATE submissions SET search_vector = setweight(to_tsvector('english', COALESCE(content, '')), 'B');
+UPDATE task_comments SET search_vector = setweight(to_tsvector('english', COALESCE(comment, '')), 'B');
+UPDATE submission_comments SET search_vector = setweight(to_tsvector('english', COALESCE(comment, '')), 'B');
+UPDATE task_comment_replies SET search_vector = setweight(to_tsvector('english', COALESCE(reply, '')), 'B');
+UPDATE submission_comment_replies SET search_vector = setweight(to_tsvector('english', COALESCE(reply, '')), 'B');
+
+CREATE INDEX idx_problems_or_tasks_search_vector ON problems_or_tasks USING gin(search_vector);
+CREATE INDEX idx_submissions_search_vector ON submissions USING gin(search_vector);
+CREATE INDEX idx_task_comments_search_vector ON task_comments USING gin(search_vector);
+CREATE INDEX idx_submission_comments_search_vector ON submission_comments USING gin(search_vector);
+CREATE INDEX idx_task_comment_replies_search_vector ON task_comment_replies USING gin(search_vector);
+CREATE INDEX idx_submission_comment_replies_search_vector ON submission_comment_replies USING gin(search_vector);
@@ -0,0 +1,16 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS update_tag_usage_count_on_task_delete ON problems_or_tasks;
+DROP TRIGGER IF EXISTS sync_problems_or_tasks_tags ON problems_or_tasks;
+DROP TRIGGER IF EXISTS normalize_problems_or_tasks_tags ON problems_or_tasks;
+
+-- problems_or_tasks.tags keeps the canonical slugs it was rewritten to
+DROP TABLE IF EXISTS task_tags;
+DROP TABLE IF EXISTS tag_aliases;
+DROP TABLE IF EXISTS tags;
+
+DROP FUNCTION IF EXISTS update_tag_usage_on_task_delete();
+DROP FUNCTION IF EXISTS update_tag_usage_on_task_tags();
+DROP FUNCTION IF EXISTS refresh_tag_usage_count(UUID);
+DROP FUNCTION IF EXISTS sync_task_tags();
+DROP FUNCTION IF EXISTS normalize_task_tags();
+DROP FUNCTION IF EXISTS slugify_tag(TEXT);
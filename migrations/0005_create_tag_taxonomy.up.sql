@@ -0,0 +1,159 @@
+-- Normalized tag taxonomy
+--
+-- `problems_or_tasks.tags` stays the denormalized list read by the API and
+-- the tag filters, but is rewritten to canonical tag slugs on every write,
+-- and `task_tags` mirrors it for joins and usage counts.
+
+-- "Rust Lang " -> "rust-lang", "C++" -> "c++", "C#" -> "c#"
+CREATE OR REPLACE FUNCTION slugify_tag(raw TEXT)
+RETURNS TEXT AS $$
+    SELECT rtrim(
+        left(btrim(regexp_replace(lower(btrim(raw)), '[^a-z0-9+#.]+', '-', 'g'), '-'), 64),
+        '-'
+    );
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE TABLE tags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    slug VARCHAR(64) UNIQUE NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    description TEXT,
+    usage_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (slug <> '' AND slug = slugify_tag(slug))
+);
+
+-- Alternative slugs that resolve to a canonical tag
+CREATE TABLE tag_aliases (
+    alias VARCHAR(64) PRIMARY KEY,
+    tag_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE,
+    CHECK (alias <> '' AND alias = slugify_tag(alias))
+);
+
+CREATE TABLE task_tags (
+    task_id UUID NOT NULL,
+    tag_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (task_id, tag_id),
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_tags_slug_prefix ON tags(slug text_pattern_ops);
+CREATE INDEX idx_tags_usage_count ON tags(usage_count DESC);
+CREATE INDEX idx_tag_aliases_prefix ON tag_aliases(alias text_pattern_ops);
+CREATE INDEX idx_tag_aliases_tag_id ON tag_aliases(tag_id);
+CREATE INDEX idx_task_tags_tag_id ON task_tags(tag_id);
+
+CREATE TRIGGER update_tags_updated_at BEFORE UPDATE ON tags
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Rewrite a task's tags to unique canonical slugs, creating unknown tags
+CREATE OR REPLACE FUNCTION normalize_task_tags()
+RETURNS TRIGGER AS $$
+DECLARE
+    canonical TEXT[] := '{}';
+    raw TEXT;
+    tag_slug TEXT;
+    resolved TEXT;
+BEGIN
+    IF NEW.tags IS NULL OR jsonb_typeof(NEW.tags) <> 'array' THEN
+        NEW.tags = '[]'::jsonb;
+        RETURN NEW;
+    END IF;
+
+    FOR raw IN SELECT jsonb_array_elements_text(NEW.tags) LOOP
+        tag_slug := slugify_tag(raw);
+        CONTINUE WHEN tag_slug = '';
+
+        SELECT t.slug INTO resolved
+        FROM tag_aliases a JOIN tags t ON t.id = a.tag_id
+        WHERE a.alias = tag_slug;
+
+        IF resolved IS NULL THEN
+            INSERT INTO tags (slug, name) VALUES (tag_slug, left(btrim(raw), 64))
+            ON CONFLICT DO NOTHING;
+            resolved := tag_slug;
+        END IF;
+
+        IF NOT resolved = ANY(canonical) THEN
+            canonical := canonical || resolved;
+        END IF;
+    END LOOP;
+
+    NEW.tags = to_jsonb(canonical);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Mirror a task's tags into task_tags
+CREATE OR REPLACE FUNCTION sync_task_tags()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM task_tags tt
+    USING tags t
+    WHERE tt.tag_id = t.id AND tt.task_id = NEW.id AND NOT NEW.tags ? t.slug;
+
+    INSERT INTO task_tags (task_id, tag_id)
+    SELECT NEW.id, t.id FROM tags t
+    WHERE t.slug IN (SELECT jsonb_array_elements_text(NEW.tags))
+    ON CONFLICT DO NOTHING;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- usage_count is the number of live tasks carrying the tag
+CREATE OR REPLACE FUNCTION refresh_tag_usage_count(target UUID)
+RETURNS VOID AS $$
+    UPDATE tags SET usage_count = (
+        SELECT COUNT(*) FROM task_tags tt
+        JOIN problems_or_tasks p ON p.id = tt.task_id
+        WHERE tt.tag_id = target AND p.deleted_at IS NULL
+    )
+    WHERE id = target;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION update_tag_usage_on_task_tags()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM refresh_tag_usage_count(OLD.tag_id);
+        RETURN OLD;
+    END IF;
+
+    PERFORM refresh_tag_usage_count(NEW.tag_id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION update_tag_usage_on_task_delete()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_tag_usage_count(tag_id) FROM task_tags WHERE task_id = NEW.id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER normalize_problems_or_tasks_tags
+    BEFORE INSERT OR UPDATE OF tags ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION normalize_task_tags();
+
+CREATE TRIGGER sync_problems_or_tasks_tags
+    AFTER INSERT OR UPDATE OF tags ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION sync_task_tags();
+
+CREATE TRIGGER update_tag_usage_count
+    AFTER INSERT OR DELETE ON task_tags
+    FOR EACH ROW EXECUTE FUNCTION update_tag_usage_on_task_tags();
+
+CREATE TRIGGER update_tag_usage_count_on_task_delete
+    AFTER UPDATE OF deleted_at ON problems_or_tasks
+    FOR EACH ROW WHEN (OLD.deleted_at IS DISTINCT FROM NEW.deleted_at)
+    EXECUTE FUNCTION update_tag_usage_on_task_delete();
+
+-- Backfill: re-writing every task's tags runs them through the triggers
+UPDATE problems_or_tasks SET tags = tags;
@@ -0,0 +1,14 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS stamp_submissions_task_revision ON submissions;
+DROP TRIGGER IF EXISTS record_problems_or_tasks_revision ON problems_or_tasks;
+DROP TRIGGER IF EXISTS version_problems_or_tasks ON problems_or_tasks;
+
+DROP FUNCTION IF EXISTS stamp_submission_task_revision();
+DROP FUNCTION IF EXISTS record_task_revision();
+DROP FUNCTION IF EXISTS version_task();
+
+DROP TABLE IF EXISTS task_revisions;
+DROP FUNCTION IF EXISTS prevent_task_revision_update();
+
+ALTER TABLE submissions DROP COLUMN IF EXISTS task_revision;
+ALTER TABLE problems_or_tasks DROP COLUMN IF EXISTS current_revision;
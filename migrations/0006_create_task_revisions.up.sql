@@ -0,0 +1,106 @@
+-- Immutable task revision history
+--
+-- Every change to a task's title, content, file, tags or difficulty bumps
+-- `problems_or_tasks.current_revision` and stores a snapshot of the new
+-- state. Submissions record the revision they were written against.
+ALTER TABLE problems_or_tasks ADD COLUMN current_revision INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE submissions ADD COLUMN task_revision INTEGER;
+
+CREATE TABLE task_revisions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL,
+    revision INTEGER NOT NULL,
+    title VARCHAR(500) NOT NULL,
+    content TEXT NOT NULL,
+    file_url TEXT,
+    tags JSONB NOT NULL DEFAULT '[]'::jsonb,
+    difficulty task_difficulty NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (task_id, revision),
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_revisions_task_id ON task_revisions(task_id, created_at DESC, id DESC);
+
+CREATE OR REPLACE FUNCTION prevent_task_revision_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'task revisions are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER prevent_task_revisions_update BEFORE UPDATE ON task_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_task_revision_update();
+
+-- Runs after normalize_problems_or_tasks_tags (triggers fire in name order),
+-- so tags are compared in their canonical form
+CREATE OR REPLACE FUNCTION version_task()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        NEW.current_revision = 1;
+    ELSIF NEW.title IS DISTINCT FROM OLD.title
+        OR NEW.content IS DISTINCT FROM OLD.content
+        OR NEW.file_url IS DISTINCT FROM OLD.file_url
+        OR NEW.tags IS DISTINCT FROM OLD.tags
+        OR NEW.difficulty IS DISTINCT FROM OLD.difficulty
+    THEN
+        NEW.current_revision = OLD.current_revision + 1;
+    ELSE
+        NEW.current_revision = OLD.current_revision;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION record_task_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR NEW.current_revision <> OLD.current_revision THEN
+        INSERT INTO task_revisions (task_id, revision, title, content, file_url, tags, difficulty)
+        VALUES (
+            NEW.id, NEW.current_revision, NEW.title, NEW.content, NEW.file_url,
+            COALESCE(NEW.tags, '[]'::jsonb), NEW.difficulty
+        );
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER version_problems_or_tasks
+    BEFORE INSERT OR UPDATE ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION version_task();
+
+-- Not `UPDATE OF current_revision`: that only fires when the column is in
+-- the SET list, and here it is set by the BEFORE trigger
+CREATE TRIGGER record_problems_or_tasks_revision
+    AFTER INSERT OR UPDATE ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION record_task_revision();
+
+-- A submission is written against the task as it is when its content is saved
+CREATE OR REPLACE FUNCTION stamp_submission_task_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR NEW.content IS DISTINCT FROM OLD.content THEN
+        NEW.task_revision = (
+            SELECT current_revision FROM problems_or_tasks WHERE id = NEW.task_id
+        );
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER stamp_submissions_task_revision
+    BEFORE INSERT OR UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION stamp_submission_task_revision();
+
+-- Backfill: existing tasks start at revision 1, and existing submissions are
+-- assumed to have been written against it
+INSERT INTO task_revisions (task_id, revision, title, content, file_url, tags, difficulty, created_at)
+SELECT id, 1, title, content, file_url, COALESCE(tags, '[]'::jsonb), difficulty, updated_at
+FROM problems_or_tasks;
+
+UPDATE submissions SET task_revision = 1;
@@ -0,0 +1,27 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS record_submissions_revision ON submissions;
+DROP TRIGGER IF EXISTS version_submissions ON submissions;
+
+DROP FUNCTION IF EXISTS record_submission_revision();
+DROP FUNCTION IF EXISTS version_submission();
+
+DROP TABLE IF EXISTS submission_revisions;
+DROP FUNCTION IF EXISTS prevent_submission_revision_update();
+
+-- Restore the transitions from 0003
+CREATE OR REPLACE FUNCTION enforce_submission_status_transition()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status AND NOT (
+        (OLD.status = 'draft' AND NEW.status IN ('submitted', 'withdrawn'))
+        OR (OLD.status = 'submitted' AND NEW.status IN ('under_review', 'accepted', 'rejected', 'withdrawn'))
+        OR (OLD.status = 'under_review' AND NEW.status IN ('accepted', 'rejected', 'withdrawn'))
+    ) THEN
+        RAISE EXCEPTION 'invalid submission status transition from % to %', OLD.status, NEW.status
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE submissions DROP COLUMN IF EXISTS current_version;
@@ -0,0 +1,117 @@
+-- Submission version history
+--
+-- Every saved draft and every submit / resubmit is stored as an immutable
+-- snapshot. Once submitted, content can only change by resubmitting, which
+-- records a new version instead of mutating the submitted one.
+ALTER TABLE submissions ADD COLUMN current_version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE submission_revisions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    submission_id UUID NOT NULL,
+    version INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    file_url TEXT,
+    -- 'draft' for a saved draft, 'submitted' for a submitted snapshot
+    status submission_status NOT NULL,
+    task_revision INTEGER,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (submission_id, version),
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_submission_revisions_submission_id
+    ON submission_revisions(submission_id, created_at DESC, id DESC);
+
+CREATE OR REPLACE FUNCTION prevent_submission_revision_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'submission revisions are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER prevent_submission_revisions_update BEFORE UPDATE ON submission_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_submission_revision_update();
+
+-- A rejected submission may be resubmitted, and a submitted one may be
+-- resubmitted before review
+CREATE OR REPLACE FUNCTION enforce_submission_status_transition()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status AND NOT (
+        (OLD.status = 'draft' AND NEW.status IN ('submitted', 'withdrawn'))
+        OR (OLD.status = 'submitted' AND NEW.status IN ('under_review', 'accepted', 'rejected', 'withdrawn'))
+        OR (OLD.status = 'under_review' AND NEW.status IN ('accepted', 'rejected', 'withdrawn'))
+        OR (OLD.status = 'rejected' AND NEW.status = 'submitted')
+    ) THEN
+        RAISE EXCEPTION 'invalid submission status transition from % to %', OLD.status, NEW.status
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Runs after the other BEFORE triggers (they fire in name order), so the
+-- task revision stamp is already set
+CREATE OR REPLACE FUNCTION version_submission()
+RETURNS TRIGGER AS $$
+DECLARE
+    content_changed BOOLEAN;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        NEW.current_version = 1;
+        RETURN NEW;
+    END IF;
+
+    content_changed = NEW.content IS DISTINCT FROM OLD.content
+        OR NEW.file_url IS DISTINCT FROM OLD.file_url;
+
+    IF content_changed AND OLD.status <> 'draft' AND NEW.status <> 'submitted' THEN
+        RAISE EXCEPTION 'submitted content can not be edited, resubmit instead'
+            USING ERRCODE = 'check_violation';
+    END IF;
+
+    IF content_changed OR (NEW.status = 'submitted' AND OLD.status <> 'submitted') THEN
+        NEW.current_version = OLD.current_version + 1;
+    ELSE
+        NEW.current_version = OLD.current_version;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION record_submission_revision()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR NEW.current_version <> OLD.current_version THEN
+        INSERT INTO submission_revisions (
+            submission_id, version, content, file_url, status, task_revision
+        )
+        VALUES (
+            NEW.id, NEW.current_version, NEW.content, NEW.file_url,
+            CASE WHEN NEW.status = 'draft' THEN 'draft' ELSE 'submitted' END::submission_status,
+            NEW.task_revision
+        );
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER version_submissions
+    BEFORE INSERT OR UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION version_submission();
+
+CREATE TRIGGER record_submissions_revision
+    AFTER INSERT OR UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION record_submission_revision();
+
+-- Backfill: the current state of every submission becomes version 1
+INSERT INTO submission_revisions (
+    submission_id, version, content, file_url, status, task_revision, created_at
+)
+SELECT
+    id, 1, content, file_url,
+    CASE WHEN status = 'draft' THEN 'draft' ELSE 'submitted' END::submission_status,
+    task_revision, COALESCE(submitted_at, updated_at)
+FROM submissions;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP FUNCTION IF EXISTS blob_is_referenced(TEXT);
+DROP TABLE IF EXISTS blobs;
@@ -0,0 +1,28 @@
+-- Uploaded file attachments
+--
+-- Blobs are content addressed: the SHA-256 of the bytes is both the unique
+-- key and the object name in the blob store, so uploading the same file
+-- twice stores it once. `file_url` columns reference a blob by ending in
+-- its hash.
+CREATE TABLE blobs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    sha256 CHAR(64) UNIQUE NOT NULL CHECK (sha256 ~ '^[0-9a-f]{64}$'),
+    content_type VARCHAR(255) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    uploaded_by UUID,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (uploaded_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_blobs_created_at ON blobs(created_at, id);
+
+-- Whether any task, submission or revision of either points at the blob.
+-- Soft deleted rows and history count, so restoring or rolling back never
+-- leaves a dangling file_url.
+CREATE OR REPLACE FUNCTION blob_is_referenced(blob_sha256 TEXT)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (SELECT 1 FROM problems_or_tasks WHERE right(file_url, 65) = '/' || blob_sha256)
+        OR EXISTS (SELECT 1 FROM task_revisions WHERE right(file_url, 65) = '/' || blob_sha256)
+        OR EXISTS (SELECT 1 FROM submissions WHERE right(file_url, 65) = '/' || blob_sha256)
+        OR EXISTS (SELECT 1 FROM submission_revisions WHERE right(file_url, 65) = '/' || blob_sha256);
+$$ LANGUAGE sql STABLE;
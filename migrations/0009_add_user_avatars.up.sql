@@ -0,0 +1,7 @@
+-- Uploaded avatars
+--
+-- Processed avatars are stored in the blob store as
+-- `avatars/{user_id}/{avatar_hash}/{size}.png`. `avatar_hash` is NULL when
+-- the user has no uploaded avatar, in which case `avatar_url` is either an
+-- external URL or NULL (an identicon is served).
+ALTER TABLE users ADD COLUMN avatar_hash TEXT CHECK (avatar_hash ~ '^[0-9a-f]{16}$');
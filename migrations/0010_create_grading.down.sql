@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS submissions_queue_grading ON submissions;
+DROP FUNCTION IF EXISTS queue_submission_grading();
+DROP TABLE IF EXISTS grading_results;
+DROP TABLE IF EXISTS grading_runs;
+DROP TABLE IF EXISTS task_test_cases;
+ALTER TABLE submissions DROP COLUMN IF EXISTS language;
+DROP TYPE IF EXISTS grading_verdict;
+DROP TYPE IF EXISTS grading_run_status;
+DROP TYPE IF EXISTS programming_language;
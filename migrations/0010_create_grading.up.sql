@@ -0,0 +1,92 @@
+-- Automated grading of code submissions
+--
+-- Task authors attach hidden test cases. When a submission with a
+-- programming language enters (or is resubmitted as) 'submitted', a grading
+-- run is queued; the `grading-worker` claims queued runs, executes the code
+-- in a sandbox and stores one verdict per test case.
+CREATE TYPE programming_language AS ENUM ('c', 'cpp', 'python', 'rust');
+
+CREATE TYPE grading_run_status AS ENUM ('queued', 'running', 'completed', 'failed');
+
+-- Accepted, wrong answer, time / memory limit exceeded, runtime error,
+-- compilation error
+CREATE TYPE grading_verdict AS ENUM ('AC', 'WA', 'TLE', 'MLE', 'RE', 'CE');
+
+ALTER TABLE submissions ADD COLUMN language programming_language;
+
+CREATE TABLE task_test_cases (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL,
+    position INTEGER NOT NULL CHECK (position > 0),
+    input TEXT NOT NULL DEFAULT '',
+    expected_output TEXT NOT NULL,
+    time_limit_ms INTEGER NOT NULL DEFAULT 2000 CHECK (time_limit_ms BETWEEN 100 AND 30000),
+    memory_limit_kb INTEGER NOT NULL DEFAULT 262144 CHECK (memory_limit_kb BETWEEN 8192 AND 2097152),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (task_id, position) DEFERRABLE INITIALLY IMMEDIATE,
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_task_test_cases_updated_at BEFORE UPDATE ON task_test_cases
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- One attempt at grading one version of a submission
+CREATE TABLE grading_runs (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    submission_id UUID NOT NULL,
+    submission_version INTEGER NOT NULL,
+    language programming_language NOT NULL,
+    status grading_run_status NOT NULL DEFAULT 'queued',
+    -- First failing verdict in test order, 'AC' when every test passed
+    verdict grading_verdict,
+    passed_tests INTEGER NOT NULL DEFAULT 0,
+    total_tests INTEGER NOT NULL DEFAULT 0,
+    compile_output TEXT,
+    -- Internal failure of the grader, never the submission's fault
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    started_at TIMESTAMP WITH TIME ZONE,
+    finished_at TIMESTAMP WITH TIME ZONE,
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_grading_runs_submission_id ON grading_runs(submission_id, created_at DESC, id DESC);
+CREATE INDEX idx_grading_runs_queued ON grading_runs(created_at) WHERE status = 'queued';
+
+CREATE TABLE grading_results (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    run_id UUID NOT NULL,
+    test_case_id UUID,
+    position INTEGER NOT NULL,
+    verdict grading_verdict NOT NULL,
+    time_ms INTEGER NOT NULL,
+    memory_kb INTEGER NOT NULL,
+    exit_code INTEGER,
+    UNIQUE (run_id, position),
+    FOREIGN KEY (run_id) REFERENCES grading_runs(id) ON DELETE CASCADE,
+    FOREIGN KEY (test_case_id) REFERENCES task_test_cases(id) ON DELETE SET NULL
+);
+
+-- Queue a run whenever a new version of a code submission is submitted.
+-- Runs after record_submission_revision, so the version being graded is
+-- already in submission_revisions.
+CREATE OR REPLACE FUNCTION queue_submission_grading()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status = 'submitted'
+        AND NEW.language IS NOT NULL
+        AND (TG_OP = 'INSERT'
+            OR OLD.status IS DISTINCT FROM NEW.status
+            OR OLD.current_version IS DISTINCT FROM NEW.current_version)
+        AND EXISTS (SELECT 1 FROM task_test_cases WHERE task_id = NEW.task_id)
+    THEN
+        INSERT INTO grading_runs (submission_id, submission_version, language)
+        VALUES (NEW.id, NEW.current_version, NEW.language);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_queue_grading AFTER INSERT OR UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION queue_submission_grading();
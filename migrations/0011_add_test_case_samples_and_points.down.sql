@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE grading_runs DROP COLUMN IF EXISTS max_score, DROP COLUMN IF EXISTS score;
+ALTER TABLE grading_results DROP COLUMN IF EXISTS max_points, DROP COLUMN IF EXISTS points;
+DROP INDEX IF EXISTS idx_task_test_cases_samples;
+ALTER TABLE task_test_cases DROP COLUMN IF EXISTS points, DROP COLUMN IF EXISTS is_sample;
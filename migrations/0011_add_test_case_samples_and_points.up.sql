@@ -0,0 +1,19 @@
+-- Sample test cases and points
+--
+-- Sample cases are shown to everyone as worked examples; the rest stay
+-- hidden from everyone but the task author. Every case, sample or hidden,
+-- is graded and is worth `points`; a run scores the points of the cases it
+-- passed.
+ALTER TABLE task_test_cases
+    ADD COLUMN is_sample BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN points INTEGER NOT NULL DEFAULT 1 CHECK (points BETWEEN 0 AND 1000);
+
+CREATE INDEX idx_task_test_cases_samples ON task_test_cases(task_id, position) WHERE is_sample;
+
+ALTER TABLE grading_results
+    ADD COLUMN points INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN max_points INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE grading_runs
+    ADD COLUMN score INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN max_score INTEGER NOT NULL DEFAULT 0;
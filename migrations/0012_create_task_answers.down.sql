@@ -0,0 +1,11 @@
+DROP TRIGGER IF EXISTS submissions_verify_answer ON submissions;
+DROP FUNCTION IF EXISTS verify_submission_answer();
+DROP FUNCTION IF EXISTS answer_matches(task_answers, TEXT);
+DROP FUNCTION IF EXISTS normalize_answer(TEXT);
+DROP TABLE IF EXISTS task_answer_progress;
+DROP TABLE IF EXISTS answer_attempts;
+DROP TRIGGER IF EXISTS task_answers_check_pattern ON task_answers;
+DROP FUNCTION IF EXISTS check_task_answer_pattern();
+DROP TABLE IF EXISTS task_answers;
+DROP TYPE IF EXISTS answer_verdict;
+DROP TYPE IF EXISTS answer_match_mode;
@@ -0,0 +1,167 @@
+-- Expected answers for non-code tasks
+--
+-- A task author may store a hidden expected answer. When a submission to
+-- the task enters (or is resubmitted as) 'submitted', its content is
+-- compared with the answer: every comparison is an attempt with a verdict,
+-- and each user's attempts at a task are counted.
+CREATE TYPE answer_match_mode AS ENUM ('exact', 'normalized', 'regex', 'numeric', 'answer_set');
+
+CREATE TYPE answer_verdict AS ENUM ('correct', 'incorrect');
+
+CREATE TABLE task_answers (
+    task_id UUID PRIMARY KEY,
+    mode answer_match_mode NOT NULL,
+    -- The expected answer; every acceptable answer in 'answer_set' mode
+    answers TEXT[] NOT NULL CHECK (
+        cardinality(answers) >= 1
+        AND array_position(answers, NULL) IS NULL
+        AND (mode = 'answer_set' OR cardinality(answers) = 1)
+    ),
+    -- 'regex' and 'answer_set' only; 'normalized' always ignores case
+    case_insensitive BOOLEAN NOT NULL DEFAULT FALSE,
+    -- 'numeric' only: largest accepted absolute difference
+    tolerance DOUBLE PRECISION CHECK (tolerance >= 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER update_task_answers_updated_at BEFORE UPDATE ON task_answers
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Reject patterns the regex engine can not compile when they are stored,
+-- not when a submission is checked against them
+CREATE OR REPLACE FUNCTION check_task_answer_pattern()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.mode = 'regex' THEN
+        PERFORM '' ~ ('^(?:' || NEW.answers[1] || ')$');
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_answers_check_pattern BEFORE INSERT OR UPDATE ON task_answers
+    FOR EACH ROW EXECUTE FUNCTION check_task_answer_pattern();
+
+-- Every check of a submitted version against the expected answer
+CREATE TABLE answer_attempts (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    task_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    submission_id UUID NOT NULL,
+    submission_version INTEGER NOT NULL,
+    -- 1 for the user's first attempt at the task
+    attempt_number INTEGER NOT NULL,
+    verdict answer_verdict NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_answer_attempts_submission_id ON answer_attempts(submission_id, created_at DESC, id DESC);
+CREATE INDEX idx_answer_attempts_task_user ON answer_attempts(task_id, user_id, created_at DESC, id DESC);
+
+-- Attempt count and outcome per user and task
+CREATE TABLE task_answer_progress (
+    task_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- First correct attempt
+    solved_at TIMESTAMP WITH TIME ZONE,
+    last_verdict answer_verdict,
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (task_id, user_id),
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Lowercase, trim and collapse runs of whitespace
+CREATE OR REPLACE FUNCTION normalize_answer(value TEXT)
+RETURNS TEXT AS $$
+    SELECT lower(regexp_replace(btrim(value, E' \t\r\n'), '\s+', ' ', 'g'));
+$$ LANGUAGE SQL IMMUTABLE;
+
+-- Whether a submitted answer matches the expected one. Surrounding
+-- whitespace of the submission is ignored in every mode; a submission that
+-- can not be compared (e.g. an out of range number) does not match.
+CREATE OR REPLACE FUNCTION answer_matches(expected task_answers, submitted TEXT)
+RETURNS BOOLEAN AS $$
+DECLARE
+    given TEXT := btrim(submitted, E' \t\r\n');
+BEGIN
+    CASE expected.mode
+        WHEN 'exact' THEN
+            RETURN given = expected.answers[1];
+        WHEN 'normalized' THEN
+            RETURN normalize_answer(given) = normalize_answer(expected.answers[1]);
+        WHEN 'regex' THEN
+            IF expected.case_insensitive THEN
+                RETURN given ~* ('^(?:' || expected.answers[1] || ')$');
+            END IF;
+            RETURN given ~ ('^(?:' || expected.answers[1] || ')$');
+        WHEN 'numeric' THEN
+            IF given !~ '^[-+]?([0-9]+(\.[0-9]*)?|\.[0-9]+)([eE][-+]?[0-9]+)?$' THEN
+                RETURN FALSE;
+            END IF;
+            RETURN abs(given::NUMERIC - expected.answers[1]::NUMERIC)
+                <= COALESCE(expected.tolerance, 0)::NUMERIC;
+        WHEN 'answer_set' THEN
+            IF expected.case_insensitive THEN
+                RETURN lower(given) = ANY (SELECT lower(answer) FROM unnest(expected.answers) answer);
+            END IF;
+            RETURN given = ANY (expected.answers);
+    END CASE;
+EXCEPTION
+    WHEN data_exception OR program_limit_exceeded THEN
+        RETURN FALSE;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+-- Check every newly submitted version of a submission to a task with an
+-- expected answer
+CREATE OR REPLACE FUNCTION verify_submission_answer()
+RETURNS TRIGGER AS $$
+DECLARE
+    expected task_answers;
+    verdict answer_verdict;
+    attempt INTEGER;
+BEGIN
+    IF NEW.status = 'submitted'
+        AND (TG_OP = 'INSERT'
+            OR OLD.status IS DISTINCT FROM NEW.status
+            OR OLD.current_version IS DISTINCT FROM NEW.current_version)
+    THEN
+        SELECT * INTO expected FROM task_answers WHERE task_id = NEW.task_id;
+        IF FOUND THEN
+            verdict := CASE WHEN answer_matches(expected, NEW.content)
+                THEN 'correct' ELSE 'incorrect' END;
+
+            INSERT INTO task_answer_progress AS progress (
+                task_id, user_id, attempts, solved_at, last_verdict, last_attempt_at
+            )
+            VALUES (
+                NEW.task_id, NEW.user_id, 1,
+                CASE WHEN verdict = 'correct' THEN NOW() END,
+                verdict, NOW()
+            )
+            ON CONFLICT (task_id, user_id) DO UPDATE SET
+                attempts = progress.attempts + 1,
+                solved_at = COALESCE(progress.solved_at, EXCLUDED.solved_at),
+                last_verdict = EXCLUDED.last_verdict,
+                last_attempt_at = EXCLUDED.last_attempt_at
+            RETURNING attempts INTO attempt;
+
+            INSERT INTO answer_attempts (
+                task_id, user_id, submission_id, submission_version, attempt_number, verdict
+            )
+            VALUES (NEW.task_id, NEW.user_id, NEW.id, NEW.current_version, attempt, verdict);
+        END IF;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_verify_answer AFTER INSERT OR UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION verify_submission_answer();
@@ -0,0 +1,18 @@
+DROP TRIGGER IF EXISTS submission_comments_apply_removal_reputation ON submission_comments;
+DROP TRIGGER IF EXISTS task_comments_apply_removal_reputation ON task_comments;
+DROP FUNCTION IF EXISTS apply_comment_removal_reputation();
+DROP TRIGGER IF EXISTS submissions_apply_featured_reputation ON submissions;
+DROP FUNCTION IF EXISTS apply_featured_reputation();
+DROP TRIGGER IF EXISTS submission_ratings_apply_reputation ON submission_ratings;
+DROP TRIGGER IF EXISTS task_ratings_apply_reputation ON task_ratings;
+DROP FUNCTION IF EXISTS apply_rating_reputation();
+DROP FUNCTION IF EXISTS reputation_weight(reputation_event_type, INTEGER);
+DROP FUNCTION IF EXISTS reverse_reputation_event(reputation_event_type, UUID);
+DROP FUNCTION IF EXISTS record_reputation_event(UUID, reputation_event_type, UUID, UUID, DECIMAL);
+DROP TABLE IF EXISTS reputation_events;
+DROP FUNCTION IF EXISTS prevent_reputation_event_changes();
+ALTER TABLE submission_comments DROP COLUMN IF EXISTS removed_by;
+ALTER TABLE task_comments DROP COLUMN IF EXISTS removed_by;
+DROP TABLE IF EXISTS reputation_settings;
+DROP TABLE IF EXISTS reputation_weights;
+DROP TYPE IF EXISTS reputation_event_type;
@@ -0,0 +1,300 @@
+-- Reputation
+--
+-- Users gain or lose points when their work is rated or featured and when
+-- a moderator removes one of their comments. Every change is appended to
+-- the reputation_events ledger, and users.reputation_score is the sum of a
+-- user's ledger. Points gained per day are capped; the cap and the points
+-- per event are configurable.
+CREATE TYPE reputation_event_type AS ENUM (
+    'task_rated',
+    'submission_rated',
+    'submission_featured',
+    'comment_removed'
+);
+
+-- Points awarded per event; rated events have one row per rating value,
+-- every other event a single row with rating_value 0
+CREATE TABLE reputation_weights (
+    event_type reputation_event_type NOT NULL,
+    rating_value SMALLINT NOT NULL DEFAULT 0 CHECK (rating_value BETWEEN 0 AND 4),
+    points DECIMAL(10,2) NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (event_type, rating_value),
+    CHECK (
+        (event_type IN ('task_rated', 'submission_rated')) = (rating_value BETWEEN 1 AND 4)
+    )
+);
+
+CREATE TRIGGER update_reputation_weights_updated_at BEFORE UPDATE ON reputation_weights
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+INSERT INTO reputation_weights (event_type, rating_value, points) VALUES
+    ('task_rated', 1, -1),
+    ('task_rated', 2, 0),
+    ('task_rated', 3, 2),
+    ('task_rated', 4, 5),
+    ('submission_rated', 1, -1),
+    ('submission_rated', 2, 0),
+    ('submission_rated', 3, 1),
+    ('submission_rated', 4, 3),
+    ('submission_featured', 0, 15),
+    ('comment_removed', 0, -10);
+
+-- Single row of global settings
+CREATE TABLE reputation_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    -- Most points a user can gain per UTC day; losses are never capped
+    daily_cap DECIMAL(10,2) NOT NULL DEFAULT 200 CHECK (daily_cap >= 0),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TRIGGER update_reputation_settings_updated_at BEFORE UPDATE ON reputation_settings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+INSERT INTO reputation_settings DEFAULT VALUES;
+
+-- Moderator who removed a comment; NULL when the author deleted it
+ALTER TABLE task_comments ADD COLUMN removed_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE submission_comments ADD COLUMN removed_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Append-only ledger of reputation changes. An event is undone (a rating
+-- changed or withdrawn, a submission unfeatured, a comment restored) by
+-- appending its reversal rather than by deleting it.
+CREATE TABLE reputation_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL,
+    event_type reputation_event_type NOT NULL,
+    -- The rating, submission or comment the event is about
+    source_id UUID NOT NULL,
+    -- Who caused it: the rater or the moderator; NULL when unknown
+    actor_id UUID,
+    -- Points applied to the score, after the daily cap
+    points DECIMAL(10,2) NOT NULL,
+    -- Points the event was worth before the daily cap
+    base_points DECIMAL(10,2) NOT NULL,
+    -- The event this one reverses
+    reverses_id UUID UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (reverses_id) REFERENCES reputation_events(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_reputation_events_user_id ON reputation_events(user_id, created_at DESC, id DESC);
+CREATE INDEX idx_reputation_events_source ON reputation_events(event_type, source_id);
+
+-- Ledger rows can only go away with their user
+CREATE OR REPLACE FUNCTION prevent_reputation_event_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF pg_trigger_depth() > 1 THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'reputation_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reputation_events_append_only BEFORE UPDATE OR DELETE ON reputation_events
+    FOR EACH ROW EXECUTE FUNCTION prevent_reputation_event_changes();
+
+-- Append an event worth base_points to a user's ledger and apply it,
+-- capping gains at what is left of the user's daily cap
+CREATE OR REPLACE FUNCTION record_reputation_event(
+    target_user UUID,
+    kind reputation_event_type,
+    source UUID,
+    actor UUID,
+    base_points DECIMAL(10,2)
+)
+RETURNS VOID AS $$
+DECLARE
+    applied DECIMAL(10,2) := base_points;
+    cap DECIMAL(10,2);
+    gained DECIMAL(10,2);
+BEGIN
+    -- Serializes the cap check per user; skips users being deleted
+    PERFORM 1 FROM users WHERE id = target_user FOR UPDATE;
+    IF NOT FOUND THEN
+        RETURN;
+    END IF;
+
+    IF applied > 0 THEN
+        SELECT daily_cap INTO cap FROM reputation_settings;
+        -- Reversed gains still count, so undoing and redoing an event does
+        -- not free up room
+        SELECT COALESCE(SUM(points), 0) INTO gained
+        FROM reputation_events
+        WHERE user_id = target_user
+            AND points > 0
+            AND reverses_id IS NULL
+            AND created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC';
+        applied := LEAST(applied, GREATEST(COALESCE(cap, applied) - gained, 0));
+    END IF;
+
+    INSERT INTO reputation_events (user_id, event_type, source_id, actor_id, points, base_points)
+    VALUES (target_user, kind, source, actor, applied, base_points);
+
+    UPDATE users
+    SET reputation_score = COALESCE(reputation_score, 0) + applied
+    WHERE id = target_user;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Append the reversal of every not yet reversed event about a source
+CREATE OR REPLACE FUNCTION reverse_reputation_event(kind reputation_event_type, source UUID)
+RETURNS VOID AS $$
+DECLARE
+    original reputation_events;
+BEGIN
+    FOR original IN
+        SELECT events.*
+        FROM reputation_events events
+        JOIN users ON users.id = events.user_id
+        WHERE events.event_type = kind
+            AND events.source_id = source
+            AND events.reverses_id IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM reputation_events reversal WHERE reversal.reverses_id = events.id
+            )
+        FOR UPDATE OF users
+    LOOP
+        INSERT INTO reputation_events (
+            user_id, event_type, source_id, actor_id, points, base_points, reverses_id
+        )
+        VALUES (
+            original.user_id, kind, source, original.actor_id,
+            -original.points, -original.base_points, original.id
+        );
+
+        UPDATE users
+        SET reputation_score = COALESCE(reputation_score, 0) - original.points
+        WHERE id = original.user_id;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION reputation_weight(kind reputation_event_type, rating INTEGER DEFAULT 0)
+RETURNS DECIMAL(10,2) AS $$
+    SELECT COALESCE(
+        (SELECT points FROM reputation_weights WHERE event_type = kind AND rating_value = rating),
+        0
+    );
+$$ LANGUAGE SQL STABLE;
+
+-- A rating is worth points to the author of what was rated; rating your own
+-- work is worth nothing
+CREATE OR REPLACE FUNCTION apply_rating_reputation()
+RETURNS TRIGGER AS $$
+DECLARE
+    kind reputation_event_type := TG_ARGV[0];
+    author UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' AND OLD.rating_value = NEW.rating_value THEN
+        RETURN NULL;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM reverse_reputation_event(kind, OLD.id);
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        IF kind = 'task_rated' THEN
+            SELECT user_id INTO author FROM problems_or_tasks WHERE id = NEW.task_id;
+        ELSE
+            SELECT user_id INTO author FROM submissions WHERE id = NEW.submission_id;
+        END IF;
+
+        IF author IS NOT NULL AND author <> NEW.rater_id THEN
+            PERFORM record_reputation_event(
+                author, kind, NEW.id, NEW.rater_id, reputation_weight(kind, NEW.rating_value)
+            );
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_ratings_apply_reputation AFTER INSERT OR UPDATE OR DELETE ON task_ratings
+    FOR EACH ROW EXECUTE FUNCTION apply_rating_reputation('task_rated');
+
+CREATE TRIGGER submission_ratings_apply_reputation AFTER INSERT OR UPDATE OR DELETE ON submission_ratings
+    FOR EACH ROW EXECUTE FUNCTION apply_rating_reputation('submission_rated');
+
+CREATE OR REPLACE FUNCTION apply_featured_reputation()
+RETURNS TRIGGER AS $$
+DECLARE
+    was_featured BOOLEAN := TG_OP <> 'INSERT' AND COALESCE(OLD.is_featured, FALSE);
+    is_featured BOOLEAN := TG_OP <> 'DELETE' AND COALESCE(NEW.is_featured, FALSE);
+BEGIN
+    IF was_featured AND NOT is_featured THEN
+        PERFORM reverse_reputation_event('submission_featured', OLD.id);
+    ELSIF is_featured AND NOT was_featured THEN
+        PERFORM record_reputation_event(
+            NEW.user_id, 'submission_featured', NEW.id, NULL,
+            reputation_weight('submission_featured')
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_apply_featured_reputation AFTER INSERT OR UPDATE OF is_featured OR DELETE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION apply_featured_reputation();
+
+-- Removal by a moderator costs the author points; restoring the comment
+-- gives them back
+CREATE OR REPLACE FUNCTION apply_comment_removal_reputation()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.removed_by IS NOT NULL AND NEW.removed_by IS NULL THEN
+        PERFORM reverse_reputation_event('comment_removed', NEW.id);
+    ELSIF OLD.removed_by IS NULL AND NEW.removed_by IS NOT NULL
+        AND NEW.removed_by <> NEW.user_id
+    THEN
+        PERFORM record_reputation_event(
+            NEW.user_id, 'comment_removed', NEW.id, NEW.removed_by,
+            reputation_weight('comment_removed')
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_comments_apply_removal_reputation AFTER UPDATE OF removed_by ON task_comments
+    FOR EACH ROW EXECUTE FUNCTION apply_comment_removal_reputation();
+
+CREATE TRIGGER submission_comments_apply_removal_reputation AFTER UPDATE OF removed_by ON submission_comments
+    FOR EACH ROW EXECUTE FUNCTION apply_comment_removal_reputation();
+
+-- Credit existing ratings and featured submissions, uncapped and dated
+-- when they happened
+INSERT INTO reputation_events (user_id, event_type, source_id, actor_id, points, base_points, created_at)
+SELECT tasks.user_id, 'task_rated', ratings.id, ratings.rater_id,
+    reputation_weight('task_rated', ratings.rating_value),
+    reputation_weight('task_rated', ratings.rating_value),
+    ratings.updated_at
+FROM task_ratings ratings
+JOIN problems_or_tasks tasks ON tasks.id = ratings.task_id
+WHERE tasks.user_id <> ratings.rater_id;
+
+INSERT INTO reputation_events (user_id, event_type, source_id, actor_id, points, base_points, created_at)
+SELECT submissions.user_id, 'submission_rated', ratings.id, ratings.rater_id,
+    reputation_weight('submission_rated', ratings.rating_value),
+    reputation_weight('submission_rated', ratings.rating_value),
+    ratings.updated_at
+FROM submission_ratings ratings
+JOIN submissions ON submissions.id = ratings.submission_id
+WHERE submissions.user_id <> ratings.rater_id;
+
+INSERT INTO reputation_events (user_id, event_type, source_id, points, base_points, created_at)
+SELECT user_id, 'submission_featured', id,
+    reputation_weight('submission_featured'),
+    reputation_weight('submission_featured'),
+    updated_at
+FROM submissions
+WHERE is_featured;
+
+UPDATE users
+SET reputation_score = COALESCE(
+    (SELECT SUM(points) FROM reputation_events WHERE reputation_events.user_id = users.id),
+    0
+);
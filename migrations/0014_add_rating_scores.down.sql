@@ -0,0 +1,14 @@
+DROP TRIGGER IF EXISTS rating_settings_refresh_scores ON rating_settings;
+DROP FUNCTION IF EXISTS refresh_scores_on_settings_change();
+DROP TRIGGER IF EXISTS submission_ratings_refresh_scores ON submission_ratings;
+DROP TRIGGER IF EXISTS task_ratings_refresh_scores ON task_ratings;
+DROP FUNCTION IF EXISTS refresh_rated_scores();
+DROP FUNCTION IF EXISTS refresh_all_rating_scores();
+DROP FUNCTION IF EXISTS refresh_submission_rating_scores(UUID);
+DROP FUNCTION IF EXISTS refresh_task_rating_scores(UUID);
+DROP INDEX IF EXISTS idx_problems_or_tasks_bayesian_rating;
+ALTER TABLE submissions DROP COLUMN IF EXISTS wilson_score, DROP COLUMN IF EXISTS bayesian_rating;
+ALTER TABLE problems_or_tasks DROP COLUMN IF EXISTS wilson_score, DROP COLUMN IF EXISTS bayesian_rating;
+DROP FUNCTION IF EXISTS wilson_lower_bound(BIGINT, BIGINT);
+DROP FUNCTION IF EXISTS bayesian_average(BIGINT, BIGINT);
+DROP TABLE IF EXISTS rating_settings;
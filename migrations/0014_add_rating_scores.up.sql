@@ -0,0 +1,152 @@
+-- Ranking scores for rated tasks and submissions
+--
+-- A plain mean ranks a single 4 above fifty 3.9s. Alongside the mean, every
+-- rated row now stores:
+-- - bayesian_rating: the mean pulled towards a prior, as if `prior_weight`
+--   extra ratings of `prior_mean` had been given
+-- - wilson_score: the lower bound of the Wilson score interval for the share
+--   of positive ratings (at least `positive_min_rating`)
+-- All of them are kept up to date from the ratings tables.
+
+-- Single row of scoring settings; changing them rescores everything
+CREATE TABLE rating_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    prior_mean DOUBLE PRECISION NOT NULL DEFAULT 2.5 CHECK (prior_mean BETWEEN 1 AND 4),
+    prior_weight DOUBLE PRECISION NOT NULL DEFAULT 5 CHECK (prior_weight > 0),
+    positive_min_rating SMALLINT NOT NULL DEFAULT 3 CHECK (positive_min_rating BETWEEN 1 AND 4),
+    -- Standard normal quantile of the interval; 1.96 for 95% confidence
+    confidence_z DOUBLE PRECISION NOT NULL DEFAULT 1.96 CHECK (confidence_z > 0),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TRIGGER update_rating_settings_updated_at BEFORE UPDATE ON rating_settings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+INSERT INTO rating_settings DEFAULT VALUES;
+
+CREATE OR REPLACE FUNCTION bayesian_average(rating_count BIGINT, rating_sum BIGINT)
+RETURNS DOUBLE PRECISION AS $$
+    SELECT (prior_weight * prior_mean + rating_sum) / (prior_weight + rating_count)
+    FROM rating_settings;
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION wilson_lower_bound(positive BIGINT, total BIGINT)
+RETURNS DOUBLE PRECISION AS $$
+    SELECT CASE
+        WHEN total = 0 THEN 0
+        ELSE (
+            share + z * z / (2 * total)
+            - z * sqrt((share * (1 - share) + z * z / (4 * total)) / total)
+        ) / (1 + z * z / total)
+    END
+    FROM (
+        SELECT positive::DOUBLE PRECISION / NULLIF(total, 0) AS share, confidence_z AS z
+        FROM rating_settings
+    ) params;
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE problems_or_tasks
+    ADD COLUMN bayesian_rating DOUBLE PRECISION NOT NULL DEFAULT bayesian_average(0, 0),
+    ADD COLUMN wilson_score DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+ALTER TABLE submissions
+    ADD COLUMN bayesian_rating DOUBLE PRECISION NOT NULL DEFAULT bayesian_average(0, 0),
+    ADD COLUMN wilson_score DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_problems_or_tasks_bayesian_rating
+    ON problems_or_tasks(bayesian_rating DESC, created_at DESC, id DESC)
+    WHERE deleted_at IS NULL;
+
+CREATE OR REPLACE FUNCTION refresh_task_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE problems_or_tasks
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM task_ratings
+        WHERE task_id = target
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION refresh_submission_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE submissions
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM submission_ratings
+        WHERE submission_id = target
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+-- Rescore every task and submission, e.g. after the settings changed
+CREATE OR REPLACE FUNCTION refresh_all_rating_scores()
+RETURNS VOID AS $$
+BEGIN
+    PERFORM refresh_task_rating_scores(id) FROM problems_or_tasks;
+    PERFORM refresh_submission_rating_scores(id) FROM submissions;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION refresh_rated_scores()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_TABLE_NAME = 'task_ratings' THEN
+        IF TG_OP <> 'INSERT' THEN
+            PERFORM refresh_task_rating_scores(OLD.task_id);
+        END IF;
+        IF TG_OP <> 'DELETE' AND (TG_OP = 'INSERT' OR NEW.task_id <> OLD.task_id) THEN
+            PERFORM refresh_task_rating_scores(NEW.task_id);
+        END IF;
+    ELSE
+        IF TG_OP <> 'INSERT' THEN
+            PERFORM refresh_submission_rating_scores(OLD.submission_id);
+        END IF;
+        IF TG_OP <> 'DELETE' AND (TG_OP = 'INSERT' OR NEW.submission_id <> OLD.submission_id) THEN
+            PERFORM refresh_submission_rating_scores(NEW.submission_id);
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_ratings_refresh_scores AFTER INSERT OR UPDATE OR DELETE ON task_ratings
+    FOR EACH ROW EXECUTE FUNCTION refresh_rated_scores();
+
+CREATE TRIGGER submission_ratings_refresh_scores AFTER INSERT OR UPDATE OR DELETE ON submission_ratings
+    FOR EACH ROW EXECUTE FUNCTION refresh_rated_scores();
+
+CREATE OR REPLACE FUNCTION refresh_scores_on_settings_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_all_rating_scores();
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER rating_settings_refresh_scores AFTER UPDATE ON rating_settings
+    FOR EACH ROW EXECUTE FUNCTION refresh_scores_on_settings_change();
+
+SELECT refresh_all_rating_scores();
@@ -0,0 +1,98 @@
+CREATE OR REPLACE FUNCTION apply_rating_reputation()
+RETURNS TRIGGER AS $$
+DECLARE
+    kind reputation_event_type := TG_ARGV[0];
+    author UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' AND OLD.rating_value = NEW.rating_value THEN
+        RETURN NULL;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM reverse_reputation_event(kind, OLD.id);
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        IF kind = 'task_rated' THEN
+            SELECT user_id INTO author FROM problems_or_tasks WHERE id = NEW.task_id;
+        ELSE
+            SELECT user_id INTO author FROM submissions WHERE id = NEW.submission_id;
+        END IF;
+
+        IF author IS NOT NULL AND author <> NEW.rater_id THEN
+            PERFORM record_reputation_event(
+                author, kind, NEW.id, NEW.rater_id, reputation_weight(kind, NEW.rating_value)
+            );
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION refresh_task_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE problems_or_tasks
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM task_ratings
+        WHERE task_id = target
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION refresh_submission_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE submissions
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM submission_ratings
+        WHERE submission_id = target
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+DROP TRIGGER IF EXISTS submission_ratings_delete_flags ON submission_ratings;
+DROP TRIGGER IF EXISTS task_ratings_delete_flags ON task_ratings;
+DROP FUNCTION IF EXISTS delete_rating_flags();
+DROP TRIGGER IF EXISTS rating_flags_apply_status ON rating_flags;
+DROP FUNCTION IF EXISTS apply_rating_flag_status();
+DROP FUNCTION IF EXISTS sync_rating_neutralization(rating_kind, UUID);
+DROP TABLE IF EXISTS rating_flags;
+DROP VIEW IF EXISTS rating_origins;
+DROP INDEX IF EXISTS idx_submission_ratings_created_at;
+DROP INDEX IF EXISTS idx_task_ratings_created_at;
+ALTER TABLE submission_ratings
+    DROP COLUMN IF EXISTS neutralized_at,
+    DROP COLUMN IF EXISTS session_id,
+    DROP COLUMN IF EXISTS client_ip;
+ALTER TABLE task_ratings
+    DROP COLUMN IF EXISTS neutralized_at,
+    DROP COLUMN IF EXISTS session_id,
+    DROP COLUMN IF EXISTS client_ip;
+DROP TYPE IF EXISTS rating_flag_status;
+DROP TYPE IF EXISTS rating_flag_reason;
+DROP TYPE IF EXISTS rating_kind;
+
+SELECT refresh_all_rating_scores();
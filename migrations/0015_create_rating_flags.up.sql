@@ -0,0 +1,204 @@
+-- Rating abuse detection
+--
+-- Ratings now record where they came from. A background scan looks for
+-- coordinated rating (vote rings, bursts from fresh accounts, several
+-- raters sharing an IP or session) and flags the ratings involved for
+-- moderator review. Confirming a flag neutralizes the rating: it is kept,
+-- but no longer counts towards averages, scores or reputation.
+CREATE TYPE rating_kind AS ENUM ('task', 'submission');
+
+CREATE TYPE rating_flag_reason AS ENUM ('vote_ring', 'new_account_burst', 'shared_origin');
+
+CREATE TYPE rating_flag_status AS ENUM ('pending', 'confirmed', 'dismissed');
+
+ALTER TABLE task_ratings
+    ADD COLUMN client_ip INET,
+    -- Identifier of the login session or device the rating was given from
+    ADD COLUMN session_id TEXT,
+    ADD COLUMN neutralized_at TIMESTAMP WITH TIME ZONE;
+
+ALTER TABLE submission_ratings
+    ADD COLUMN client_ip INET,
+    ADD COLUMN session_id TEXT,
+    ADD COLUMN neutralized_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX idx_task_ratings_created_at ON task_ratings(created_at);
+CREATE INDEX idx_submission_ratings_created_at ON submission_ratings(created_at);
+
+-- Every rating with the author of the rated task or submission
+CREATE VIEW rating_origins AS
+    SELECT
+        'task'::rating_kind AS kind,
+        r.id,
+        r.task_id AS target_id,
+        t.user_id AS author_id,
+        r.rater_id,
+        r.client_ip,
+        r.session_id,
+        r.created_at
+    FROM task_ratings r
+    JOIN problems_or_tasks t ON t.id = r.task_id
+    UNION ALL
+    SELECT
+        'submission'::rating_kind,
+        r.id,
+        r.submission_id,
+        s.user_id,
+        r.rater_id,
+        r.client_ip,
+        r.session_id,
+        r.created_at
+    FROM submission_ratings r
+    JOIN submissions s ON s.id = r.submission_id;
+
+CREATE TABLE rating_flags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    rating_kind rating_kind NOT NULL,
+    rating_id UUID NOT NULL,
+    reason rating_flag_reason NOT NULL,
+    -- What the scan saw, e.g. the ring partner or the shared IP
+    details JSONB NOT NULL DEFAULT '{}',
+    status rating_flag_status NOT NULL DEFAULT 'pending',
+    reviewed_by UUID,
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    FOREIGN KEY (reviewed_by) REFERENCES users(id) ON DELETE SET NULL,
+    -- A dismissed flag is not raised again for the same reason
+    UNIQUE(rating_kind, rating_id, reason)
+);
+
+CREATE INDEX idx_rating_flags_status ON rating_flags(status, created_at DESC, id DESC);
+
+-- A rating is neutralized while at least one of its flags is confirmed
+CREATE OR REPLACE FUNCTION sync_rating_neutralization(kind rating_kind, target UUID)
+RETURNS VOID AS $$
+DECLARE
+    confirmed BOOLEAN := EXISTS (
+        SELECT 1 FROM rating_flags
+        WHERE rating_kind = kind AND rating_id = target AND status = 'confirmed'
+    );
+BEGIN
+    IF kind = 'task' THEN
+        UPDATE task_ratings
+        SET neutralized_at = CASE WHEN confirmed THEN COALESCE(neutralized_at, NOW()) END
+        WHERE id = target AND (neutralized_at IS NULL) = confirmed;
+    ELSE
+        UPDATE submission_ratings
+        SET neutralized_at = CASE WHEN confirmed THEN COALESCE(neutralized_at, NOW()) END
+        WHERE id = target AND (neutralized_at IS NULL) = confirmed;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION apply_rating_flag_status()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM sync_rating_neutralization(OLD.rating_kind, OLD.rating_id);
+    ELSE
+        PERFORM sync_rating_neutralization(NEW.rating_kind, NEW.rating_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER rating_flags_apply_status AFTER INSERT OR UPDATE OF status OR DELETE ON rating_flags
+    FOR EACH ROW EXECUTE FUNCTION apply_rating_flag_status();
+
+-- Flags have no foreign key to the polymorphic rating; drop them with it
+CREATE OR REPLACE FUNCTION delete_rating_flags()
+RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM rating_flags
+    WHERE rating_kind = TG_ARGV[0]::rating_kind AND rating_id = OLD.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_ratings_delete_flags AFTER DELETE ON task_ratings
+    FOR EACH ROW EXECUTE FUNCTION delete_rating_flags('task');
+
+CREATE TRIGGER submission_ratings_delete_flags AFTER DELETE ON submission_ratings
+    FOR EACH ROW EXECUTE FUNCTION delete_rating_flags('submission');
+
+-- Neutralized ratings no longer count towards the scores
+CREATE OR REPLACE FUNCTION refresh_task_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE problems_or_tasks
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM task_ratings
+        WHERE task_id = target AND neutralized_at IS NULL
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION refresh_submission_rating_scores(target UUID)
+RETURNS VOID AS $$
+    UPDATE submissions
+    SET
+        average_rating = ROUND(stats.average, 2),
+        total_ratings = stats.total,
+        bayesian_rating = bayesian_average(stats.total, stats.rating_sum),
+        wilson_score = wilson_lower_bound(stats.positive, stats.total)
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COALESCE(SUM(rating_value), 0) AS rating_sum,
+            COALESCE(AVG(rating_value), 0) AS average,
+            COUNT(*) FILTER (
+                WHERE rating_value >= (SELECT positive_min_rating FROM rating_settings)
+            ) AS positive
+        FROM submission_ratings
+        WHERE submission_id = target AND neutralized_at IS NULL
+    ) stats
+    WHERE id = target;
+$$ LANGUAGE SQL;
+
+-- ...nor towards the author's reputation: neutralizing a rating reverses
+-- its event and lifting the neutralization records it again
+CREATE OR REPLACE FUNCTION apply_rating_reputation()
+RETURNS TRIGGER AS $$
+DECLARE
+    kind reputation_event_type := TG_ARGV[0];
+    counted_before BOOLEAN := TG_OP <> 'INSERT' AND OLD.neutralized_at IS NULL;
+    counted_after BOOLEAN := TG_OP <> 'DELETE' AND NEW.neutralized_at IS NULL;
+    author UUID;
+BEGIN
+    IF TG_OP = 'UPDATE' AND counted_before = counted_after
+        AND (NOT counted_after OR OLD.rating_value = NEW.rating_value)
+    THEN
+        RETURN NULL;
+    END IF;
+
+    IF counted_before THEN
+        PERFORM reverse_reputation_event(kind, OLD.id);
+    END IF;
+
+    IF counted_after THEN
+        IF kind = 'task_rated' THEN
+            SELECT user_id INTO author FROM problems_or_tasks WHERE id = NEW.task_id;
+        ELSE
+            SELECT user_id INTO author FROM submissions WHERE id = NEW.submission_id;
+        END IF;
+
+        IF author IS NOT NULL AND author <> NEW.rater_id THEN
+            PERFORM record_reputation_event(
+                author, kind, NEW.id, NEW.rater_id, reputation_weight(kind, NEW.rating_value)
+            );
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
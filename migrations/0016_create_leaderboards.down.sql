@@ -0,0 +1,17 @@
+DROP TRIGGER IF EXISTS answer_attempts_queue_leaderboard_delta ON answer_attempts;
+DROP FUNCTION IF EXISTS queue_answer_leaderboard_delta();
+DROP TRIGGER IF EXISTS grading_runs_queue_leaderboard_delta ON grading_runs;
+DROP FUNCTION IF EXISTS queue_grading_leaderboard_delta();
+DROP FUNCTION IF EXISTS queue_solution_leaderboard_delta(UUID, UUID, BOOLEAN, TIMESTAMP WITH TIME ZONE);
+DROP TRIGGER IF EXISTS task_ratings_queue_leaderboard_delta ON task_ratings;
+DROP FUNCTION IF EXISTS queue_task_rating_leaderboard_delta();
+DROP TRIGGER IF EXISTS reputation_events_queue_leaderboard_delta ON reputation_events;
+DROP FUNCTION IF EXISTS queue_reputation_leaderboard_delta();
+DROP TABLE IF EXISTS leaderboard_deltas;
+DROP VIEW IF EXISTS leaderboard_facts;
+DROP VIEW IF EXISTS accepted_solutions;
+DROP TRIGGER IF EXISTS reputation_events_set_task ON reputation_events;
+DROP FUNCTION IF EXISTS set_reputation_event_task();
+DROP FUNCTION IF EXISTS reputation_source_task(reputation_event_type, UUID);
+ALTER TABLE reputation_events DROP COLUMN IF EXISTS task_id;
+DROP TYPE IF EXISTS leaderboard_metric;
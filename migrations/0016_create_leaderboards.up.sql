@@ -0,0 +1,225 @@
+-- Leaderboards
+--
+-- Users are ranked by reputation, by the tasks they solved and by the
+-- positive ratings their tasks received, overall and per tag, all time and
+-- per week and month. The rankings live in Redis sorted sets: every change
+-- to a score is queued in leaderboard_deltas for a worker to apply, and the
+-- sets are periodically rebuilt from leaderboard_facts.
+CREATE TYPE leaderboard_metric AS ENUM ('reputation', 'accepted_submissions', 'task_ratings');
+
+-- Reputation events remember the task they are about, so they can be
+-- counted per tag even after their source is gone
+ALTER TABLE reputation_events ADD COLUMN task_id UUID;
+
+CREATE OR REPLACE FUNCTION reputation_source_task(kind reputation_event_type, source UUID)
+RETURNS UUID AS $$
+    SELECT CASE kind
+        WHEN 'task_rated' THEN (SELECT task_id FROM task_ratings WHERE id = source)
+        WHEN 'submission_rated' THEN (
+            SELECT s.task_id
+            FROM submission_ratings r
+            JOIN submissions s ON s.id = r.submission_id
+            WHERE r.id = source
+        )
+        WHEN 'submission_featured' THEN (SELECT task_id FROM submissions WHERE id = source)
+        WHEN 'comment_removed' THEN COALESCE(
+            (SELECT task_id FROM task_comments WHERE id = source),
+            (
+                SELECT s.task_id
+                FROM submission_comments c
+                JOIN submissions s ON s.id = c.submission_id
+                WHERE c.id = source
+            )
+        )
+    END;
+$$ LANGUAGE SQL STABLE;
+
+-- A reversal may be appended after its source was deleted; it belongs to
+-- the task of the event it reverses
+CREATE OR REPLACE FUNCTION set_reputation_event_task()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.reverses_id IS NOT NULL THEN
+        SELECT task_id INTO NEW.task_id FROM reputation_events WHERE id = NEW.reverses_id;
+    ELSE
+        NEW.task_id := reputation_source_task(NEW.event_type, NEW.source_id);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reputation_events_set_task BEFORE INSERT ON reputation_events
+    FOR EACH ROW EXECUTE FUNCTION set_reputation_event_task();
+
+ALTER TABLE reputation_events DISABLE TRIGGER reputation_events_append_only;
+
+UPDATE reputation_events
+SET task_id = reputation_source_task(event_type, source_id)
+WHERE reverses_id IS NULL;
+
+UPDATE reputation_events reversal
+SET task_id = original.task_id
+FROM reputation_events original
+WHERE original.id = reversal.reverses_id;
+
+ALTER TABLE reputation_events ENABLE TRIGGER reputation_events_append_only;
+
+-- Every accepted solution: code submissions graded 'AC' and correct
+-- answers. Solving your own task does not count.
+CREATE VIEW accepted_solutions AS
+    SELECT s.user_id, s.task_id, runs.finished_at AS accepted_at
+    FROM grading_runs runs
+    JOIN submissions s ON s.id = runs.submission_id
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE runs.verdict = 'AC' AND t.user_id <> s.user_id
+    UNION ALL
+    SELECT attempts.user_id, attempts.task_id, attempts.created_at
+    FROM answer_attempts attempts
+    JOIN problems_or_tasks t ON t.id = attempts.task_id
+    WHERE attempts.verdict = 'correct' AND t.user_id <> attempts.user_id;
+
+-- What each leaderboard sums up: points gained, tasks solved (when first
+-- solved) and positive ratings received on tasks (when given)
+CREATE VIEW leaderboard_facts AS
+    SELECT
+        'reputation'::leaderboard_metric AS metric,
+        user_id,
+        task_id,
+        points::DOUBLE PRECISION AS amount,
+        created_at AS occurred_at
+    FROM reputation_events
+    UNION ALL
+    SELECT 'accepted_submissions', user_id, task_id, 1, MIN(accepted_at)
+    FROM accepted_solutions
+    GROUP BY user_id, task_id
+    UNION ALL
+    SELECT 'task_ratings', t.user_id, r.task_id, 1, r.created_at
+    FROM task_ratings r
+    JOIN problems_or_tasks t ON t.id = r.task_id
+    WHERE r.rater_id <> t.user_id
+        AND r.neutralized_at IS NULL
+        AND r.rating_value >= (SELECT positive_min_rating FROM rating_settings);
+
+-- Score changes waiting to be applied to the Redis sorted sets
+CREATE TABLE leaderboard_deltas (
+    id BIGSERIAL PRIMARY KEY,
+    metric leaderboard_metric NOT NULL,
+    user_id UUID NOT NULL,
+    -- Decides the tag leaderboards the change also applies to
+    task_id UUID,
+    amount DOUBLE PRECISION NOT NULL,
+    -- Decides the weekly and monthly leaderboards it applies to
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE OR REPLACE FUNCTION queue_reputation_leaderboard_delta()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.points <> 0 THEN
+        INSERT INTO leaderboard_deltas (metric, user_id, task_id, amount, occurred_at)
+        VALUES ('reputation', NEW.user_id, NEW.task_id, NEW.points, NEW.created_at);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reputation_events_queue_leaderboard_delta AFTER INSERT ON reputation_events
+    FOR EACH ROW EXECUTE FUNCTION queue_reputation_leaderboard_delta();
+
+CREATE OR REPLACE FUNCTION queue_task_rating_leaderboard_delta()
+RETURNS TRIGGER AS $$
+DECLARE
+    positive_min SMALLINT := (SELECT positive_min_rating FROM rating_settings);
+    author UUID;
+    counted_before BOOLEAN := FALSE;
+    counted_after BOOLEAN := FALSE;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        SELECT user_id INTO author FROM problems_or_tasks WHERE id = OLD.task_id;
+        counted_before := author IS NOT NULL AND OLD.rater_id <> author
+            AND OLD.neutralized_at IS NULL AND OLD.rating_value >= positive_min;
+        IF counted_before THEN
+            INSERT INTO leaderboard_deltas (metric, user_id, task_id, amount, occurred_at)
+            VALUES ('task_ratings', author, OLD.task_id, -1, OLD.created_at);
+        END IF;
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        SELECT user_id INTO author FROM problems_or_tasks WHERE id = NEW.task_id;
+        counted_after := author IS NOT NULL AND NEW.rater_id <> author
+            AND NEW.neutralized_at IS NULL AND NEW.rating_value >= positive_min;
+        IF counted_after THEN
+            INSERT INTO leaderboard_deltas (metric, user_id, task_id, amount, occurred_at)
+            VALUES ('task_ratings', author, NEW.task_id, 1, NEW.created_at);
+        END IF;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER task_ratings_queue_leaderboard_delta AFTER INSERT OR UPDATE OR DELETE ON task_ratings
+    FOR EACH ROW EXECUTE FUNCTION queue_task_rating_leaderboard_delta();
+
+-- A task counts once, when it is first solved; a regraded run can take
+-- that back
+CREATE OR REPLACE FUNCTION queue_solution_leaderboard_delta(
+    solver UUID,
+    task UUID,
+    accepted BOOLEAN,
+    occurred_at TIMESTAMP WITH TIME ZONE
+)
+RETURNS VOID AS $$
+DECLARE
+    solutions BIGINT := (
+        SELECT COUNT(*) FROM accepted_solutions WHERE user_id = solver AND task_id = task
+    );
+BEGIN
+    IF (accepted AND solutions = 1) OR (NOT accepted AND solutions = 0) THEN
+        INSERT INTO leaderboard_deltas (metric, user_id, task_id, amount, occurred_at)
+        VALUES ('accepted_submissions', solver, task, CASE WHEN accepted THEN 1 ELSE -1 END, occurred_at);
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION queue_grading_leaderboard_delta()
+RETURNS TRIGGER AS $$
+DECLARE
+    solver UUID;
+    task UUID;
+    author UUID;
+BEGIN
+    IF COALESCE(NEW.verdict = 'AC', FALSE) = COALESCE(OLD.verdict = 'AC', FALSE) THEN
+        RETURN NULL;
+    END IF;
+
+    SELECT s.user_id, s.task_id, t.user_id INTO solver, task, author
+    FROM submissions s
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE s.id = NEW.submission_id;
+
+    IF solver IS NOT NULL AND solver <> author THEN
+        PERFORM queue_solution_leaderboard_delta(
+            solver, task, NEW.verdict = 'AC', COALESCE(NEW.finished_at, OLD.finished_at, NOW())
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER grading_runs_queue_leaderboard_delta AFTER UPDATE OF verdict ON grading_runs
+    FOR EACH ROW EXECUTE FUNCTION queue_grading_leaderboard_delta();
+
+CREATE OR REPLACE FUNCTION queue_answer_leaderboard_delta()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.verdict = 'correct' AND NOT EXISTS (
+        SELECT 1 FROM problems_or_tasks WHERE id = NEW.task_id AND user_id = NEW.user_id
+    ) THEN
+        PERFORM queue_solution_leaderboard_delta(NEW.user_id, NEW.task_id, TRUE, NEW.created_at);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER answer_attempts_queue_leaderboard_delta AFTER INSERT ON answer_attempts
+    FOR EACH ROW EXECUTE FUNCTION queue_answer_leaderboard_delta();
@@ -0,0 +1,11 @@
+DROP VIEW IF EXISTS contest_attempts;
+DROP TRIGGER IF EXISTS submissions_lock_contest ON submissions;
+DROP FUNCTION IF EXISTS lock_contest_submission();
+DROP TRIGGER IF EXISTS contest_submissions_check ON contest_submissions;
+DROP FUNCTION IF EXISTS check_contest_submission();
+DROP FUNCTION IF EXISTS contest_is_running(UUID);
+DROP TABLE IF EXISTS contest_submissions;
+DROP TABLE IF EXISTS contest_participants;
+DROP TABLE IF EXISTS contest_tasks;
+DROP TABLE IF EXISTS contests;
+DROP TYPE IF EXISTS contest_scoring;
@@ -0,0 +1,155 @@
+-- Contests
+--
+-- A contest runs a curated set of tasks for registered participants between
+-- starts_at and ends_at. Every version a participant submits to a contest
+-- task within that window is an attempt, judged by the task's test cases or
+-- expected answer like any other submission. Outside the window contest
+-- submissions are locked.
+CREATE TYPE contest_scoring AS ENUM ('icpc', 'points');
+
+CREATE TABLE contests (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    created_by UUID NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    scoring contest_scoring NOT NULL DEFAULT 'icpc',
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Added per rejected attempt before a task was solved or got its best
+    -- score
+    penalty_minutes INTEGER NOT NULL DEFAULT 20 CHECK (penalty_minutes >= 0),
+    -- The public scoreboard stops showing results this long before the end,
+    -- until they are published
+    freeze_minutes INTEGER NOT NULL DEFAULT 60 CHECK (freeze_minutes >= 0),
+    results_published_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (ends_at > starts_at),
+    FOREIGN KEY (created_by) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_contests_created_at ON contests(created_at DESC, id DESC);
+
+CREATE TRIGGER update_contests_updated_at BEFORE UPDATE ON contests
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE contest_tasks (
+    contest_id UUID NOT NULL,
+    task_id UUID NOT NULL,
+    position INTEGER NOT NULL,
+    -- Worth of a full score in points contests
+    points INTEGER NOT NULL DEFAULT 100 CHECK (points > 0),
+    PRIMARY KEY (contest_id, task_id),
+    UNIQUE (contest_id, position),
+    FOREIGN KEY (contest_id) REFERENCES contests(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE
+);
+
+CREATE TABLE contest_participants (
+    contest_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    registered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (contest_id, user_id),
+    FOREIGN KEY (contest_id) REFERENCES contests(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_contest_participants_user_id ON contest_participants(user_id);
+
+-- The submission a participant works on for a contest task; every
+-- submitted version of it is an attempt
+CREATE TABLE contest_submissions (
+    submission_id UUID PRIMARY KEY,
+    contest_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    task_id UUID NOT NULL,
+    UNIQUE (contest_id, user_id, task_id),
+    FOREIGN KEY (submission_id) REFERENCES submissions(id) ON DELETE CASCADE,
+    FOREIGN KEY (contest_id, user_id)
+        REFERENCES contest_participants(contest_id, user_id) ON DELETE CASCADE,
+    FOREIGN KEY (contest_id, task_id)
+        REFERENCES contest_tasks(contest_id, task_id) ON DELETE CASCADE
+);
+
+CREATE OR REPLACE FUNCTION contest_is_running(contest UUID)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM contests
+        WHERE id = contest AND NOW() >= starts_at AND NOW() < ends_at
+    );
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION check_contest_submission()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM submissions
+        WHERE id = NEW.submission_id AND user_id = NEW.user_id AND task_id = NEW.task_id
+    ) THEN
+        RAISE EXCEPTION 'submission % is not by % for task %',
+            NEW.submission_id, NEW.user_id, NEW.task_id
+            USING ERRCODE = 'check_violation';
+    END IF;
+    IF NOT contest_is_running(NEW.contest_id) THEN
+        RAISE EXCEPTION 'contest % is not running', NEW.contest_id
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contest_submissions_check BEFORE INSERT ON contest_submissions
+    FOR EACH ROW EXECUTE FUNCTION check_contest_submission();
+
+-- New versions of a contest submission can only be saved while the contest
+-- runs
+CREATE OR REPLACE FUNCTION lock_contest_submission()
+RETURNS TRIGGER AS $$
+DECLARE
+    contest UUID := (SELECT contest_id FROM contest_submissions WHERE submission_id = NEW.id);
+BEGIN
+    IF contest IS NOT NULL
+        AND NEW.current_version <> OLD.current_version
+        AND NOT contest_is_running(contest)
+    THEN
+        RAISE EXCEPTION 'contest % is not running', contest
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_lock_contest AFTER UPDATE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION lock_contest_submission();
+
+-- Every version submitted to a contest during it, with how it was judged:
+-- the latest completed grading run of the version for code, its answer
+-- check otherwise. Both are NULL while it waits to be judged.
+CREATE VIEW contest_attempts AS
+    SELECT
+        cs.contest_id,
+        cs.user_id,
+        cs.task_id,
+        cs.submission_id,
+        rev.version AS submission_version,
+        rev.created_at AS submitted_at,
+        run.verdict AS grading_verdict,
+        run.score,
+        run.max_score,
+        attempt.verdict AS answer_verdict
+    FROM contest_submissions cs
+    JOIN contests c ON c.id = cs.contest_id
+    JOIN submission_revisions rev
+        ON rev.submission_id = cs.submission_id AND rev.status = 'submitted'
+    LEFT JOIN LATERAL (
+        SELECT runs.verdict, runs.score, runs.max_score
+        FROM grading_runs runs
+        WHERE runs.submission_id = cs.submission_id
+            AND runs.submission_version = rev.version
+            AND runs.status = 'completed'
+        ORDER BY runs.finished_at DESC
+        LIMIT 1
+    ) run ON TRUE
+    LEFT JOIN answer_attempts attempt
+        ON attempt.submission_id = cs.submission_id AND attempt.submission_version = rev.version
+    WHERE rev.created_at >= c.starts_at AND rev.created_at < c.ends_at;
@@ -0,0 +1,20 @@
+DROP TRIGGER IF EXISTS answer_attempts_queue_badge_check ON answer_attempts;
+DROP FUNCTION IF EXISTS queue_answer_badge_check();
+DROP TRIGGER IF EXISTS grading_runs_queue_badge_check ON grading_runs;
+DROP FUNCTION IF EXISTS queue_grading_badge_check();
+DROP TRIGGER IF EXISTS submissions_queue_badge_check ON submissions;
+DROP FUNCTION IF EXISTS queue_submission_badge_check();
+DROP TRIGGER IF EXISTS submission_ratings_queue_badge_check ON submission_ratings;
+DROP TRIGGER IF EXISTS task_ratings_queue_badge_check ON task_ratings;
+DROP TRIGGER IF EXISTS submission_comment_replies_queue_badge_check ON submission_comment_replies;
+DROP TRIGGER IF EXISTS task_comment_replies_queue_badge_check ON task_comment_replies;
+DROP TRIGGER IF EXISTS submission_comments_queue_badge_check ON submission_comments;
+DROP TRIGGER IF EXISTS task_comments_queue_badge_check ON task_comments;
+DROP TRIGGER IF EXISTS problems_or_tasks_queue_badge_check ON problems_or_tasks;
+DROP FUNCTION IF EXISTS queue_badge_check();
+DROP TABLE IF EXISTS badge_checks;
+DROP VIEW IF EXISTS user_activity;
+DROP TABLE IF EXISTS user_badges;
+DROP TABLE IF EXISTS badges;
+DROP TYPE IF EXISTS badge_event_type;
+DROP TYPE IF EXISTS badge_rule;
@@ -0,0 +1,174 @@
+-- Badges
+--
+-- The catalog lists every badge with the rule that earns it: reaching a
+-- threshold on one of the user's counts. Whenever a user does something a
+-- rule counts, the database queues a badge check; the badges worker takes
+-- the checks, evaluates the rules they trigger and awards every badge
+-- whose threshold the user reached. A badge is awarded once and kept.
+CREATE TYPE badge_rule AS ENUM (
+    'submissions_made',
+    'featured_submissions',
+    'tasks_authored',
+    'tasks_solved',
+    'comments_posted',
+    'ratings_given',
+    'activity_streak'
+);
+
+CREATE TYPE badge_event_type AS ENUM (
+    'task_created',
+    'submission_submitted',
+    'submission_featured',
+    'task_solved',
+    'comment_posted',
+    'rating_given',
+    -- Evaluate every rule, e.g. after the catalog changed
+    'recheck'
+);
+
+CREATE TABLE badges (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    slug VARCHAR(64) UNIQUE NOT NULL CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    name VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    rule badge_rule NOT NULL,
+    -- Count the rule must reach
+    threshold INTEGER NOT NULL CHECK (threshold > 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (rule, threshold)
+);
+
+INSERT INTO badges (slug, name, description, rule, threshold) VALUES
+    ('first-submission', 'First Steps', 'Submitted a first solution', 'submissions_made', 1),
+    ('prolific-submitter', 'Prolific', 'Submitted 50 solutions', 'submissions_made', 50),
+    ('first-featured', 'In the Spotlight', 'Had a first submission featured', 'featured_submissions', 1),
+    ('featured-10', 'Showcase', 'Had 10 submissions featured', 'featured_submissions', 10),
+    ('first-task', 'Problem Setter', 'Authored a first task', 'tasks_authored', 1),
+    ('task-author-10', 'Curator', 'Authored 10 tasks', 'tasks_authored', 10),
+    ('first-solve', 'Solver', 'Solved a first task', 'tasks_solved', 1),
+    ('solver-25', 'Problem Crusher', 'Solved 25 tasks', 'tasks_solved', 25),
+    ('commenter-10', 'Conversationalist', 'Posted 10 comments or replies', 'comments_posted', 10),
+    ('critic-25', 'Critic', 'Rated 25 tasks or submissions', 'ratings_given', 25),
+    ('streak-7', 'On a Roll', 'Active 7 days in a row', 'activity_streak', 7),
+    ('streak-30', 'Unstoppable', 'Active 30 days in a row', 'activity_streak', 30);
+
+CREATE TABLE user_badges (
+    user_id UUID NOT NULL,
+    badge_id UUID NOT NULL,
+    awarded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, badge_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (badge_id) REFERENCES badges(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_user_badges_user_id ON user_badges(user_id, awarded_at DESC);
+
+-- Everything a user did, by when it happened: tasks and submissions
+-- created, comments and replies posted, ratings given
+CREATE VIEW user_activity AS
+    SELECT user_id, 'task'::TEXT AS kind, created_at AS occurred_at FROM problems_or_tasks
+    UNION ALL
+    SELECT user_id, 'submission', created_at FROM submissions
+    UNION ALL
+    SELECT user_id, 'comment', created_at FROM task_comments
+    UNION ALL
+    SELECT user_id, 'comment', created_at FROM submission_comments
+    UNION ALL
+    SELECT user_id, 'comment', created_at FROM task_comment_replies
+    UNION ALL
+    SELECT user_id, 'comment', created_at FROM submission_comment_replies
+    UNION ALL
+    SELECT rater_id, 'rating', created_at FROM task_ratings
+    UNION ALL
+    SELECT rater_id, 'rating', created_at FROM submission_ratings;
+
+-- Badge checks waiting for the worker
+CREATE TABLE badge_checks (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL,
+    event badge_event_type NOT NULL,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Queue a check for the user in the column named by the second trigger
+-- argument
+CREATE OR REPLACE FUNCTION queue_badge_check()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO badge_checks (user_id, event)
+    VALUES ((to_jsonb(NEW) ->> TG_ARGV[1])::UUID, TG_ARGV[0]::badge_event_type);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER problems_or_tasks_queue_badge_check AFTER INSERT ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('task_created', 'user_id');
+
+CREATE TRIGGER task_comments_queue_badge_check AFTER INSERT ON task_comments
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('comment_posted', 'user_id');
+
+CREATE TRIGGER submission_comments_queue_badge_check AFTER INSERT ON submission_comments
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('comment_posted', 'user_id');
+
+CREATE TRIGGER task_comment_replies_queue_badge_check AFTER INSERT ON task_comment_replies
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('comment_posted', 'user_id');
+
+CREATE TRIGGER submission_comment_replies_queue_badge_check AFTER INSERT ON submission_comment_replies
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('comment_posted', 'user_id');
+
+CREATE TRIGGER task_ratings_queue_badge_check AFTER INSERT ON task_ratings
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('rating_given', 'rater_id');
+
+CREATE TRIGGER submission_ratings_queue_badge_check AFTER INSERT ON submission_ratings
+    FOR EACH ROW EXECUTE FUNCTION queue_badge_check('rating_given', 'rater_id');
+
+CREATE OR REPLACE FUNCTION queue_submission_badge_check()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status = 'submitted'
+        AND (TG_OP = 'INSERT' OR OLD.status IS DISTINCT FROM NEW.status)
+    THEN
+        INSERT INTO badge_checks (user_id, event) VALUES (NEW.user_id, 'submission_submitted');
+    END IF;
+    IF COALESCE(NEW.is_featured, FALSE)
+        AND (TG_OP = 'INSERT' OR NOT COALESCE(OLD.is_featured, FALSE))
+    THEN
+        INSERT INTO badge_checks (user_id, event) VALUES (NEW.user_id, 'submission_featured');
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_queue_badge_check AFTER INSERT OR UPDATE OF status, is_featured ON submissions
+    FOR EACH ROW EXECUTE FUNCTION queue_submission_badge_check();
+
+CREATE OR REPLACE FUNCTION queue_grading_badge_check()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.verdict = 'AC' AND OLD.verdict IS DISTINCT FROM NEW.verdict THEN
+        INSERT INTO badge_checks (user_id, event)
+        SELECT user_id, 'task_solved' FROM submissions WHERE id = NEW.submission_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER grading_runs_queue_badge_check AFTER UPDATE OF verdict ON grading_runs
+    FOR EACH ROW EXECUTE FUNCTION queue_grading_badge_check();
+
+CREATE OR REPLACE FUNCTION queue_answer_badge_check()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.verdict = 'correct' THEN
+        INSERT INTO badge_checks (user_id, event) VALUES (NEW.user_id, 'task_solved');
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER answer_attempts_queue_badge_check AFTER INSERT ON answer_attempts
+    FOR EACH ROW EXECUTE FUNCTION queue_answer_badge_check();
+
+-- Award what existing users already achieved on the worker's first run
+INSERT INTO badge_checks (user_id, event)
+SELECT id, 'recheck' FROM users;
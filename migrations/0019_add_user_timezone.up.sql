@@ -0,0 +1,3 @@
+-- Time zone a user's days start and end in, for their activity calendar
+-- and streaks; an IANA name such as 'Europe/Berlin'
+ALTER TABLE users ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
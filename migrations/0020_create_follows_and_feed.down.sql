@@ -0,0 +1,11 @@
+DROP FUNCTION IF EXISTS user_feed(UUID);
+DROP VIEW IF EXISTS feed_events;
+DROP TYPE IF EXISTS feed_event_kind;
+DROP INDEX IF EXISTS idx_task_comments_task_created;
+DROP INDEX IF EXISTS idx_submissions_task_submitted;
+DROP TRIGGER IF EXISTS submissions_set_featured_at ON submissions;
+DROP FUNCTION IF EXISTS set_submission_featured_at();
+ALTER TABLE submissions DROP COLUMN IF EXISTS featured_at;
+DROP TABLE IF EXISTS feed_reads;
+DROP TABLE IF EXISTS task_follows;
+DROP TABLE IF EXISTS user_follows;
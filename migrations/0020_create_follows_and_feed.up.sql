@@ -0,0 +1,117 @@
+-- Follows and the activity feed
+--
+-- Users follow other users and tasks. The feed is assembled when it is
+-- read (fan-out on read) from `feed_events`: new tasks by followed users,
+-- new submissions and comments on followed tasks, and submissions of
+-- either that got featured. Everything newer than the user's read marker
+-- is unread.
+CREATE TABLE user_follows (
+    follower_id UUID NOT NULL,
+    followee_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CHECK (follower_id <> followee_id),
+    FOREIGN KEY (follower_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (followee_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_user_follows_follower ON user_follows(follower_id, created_at DESC, followee_id DESC);
+CREATE INDEX idx_user_follows_followee ON user_follows(followee_id, created_at DESC, follower_id DESC);
+
+CREATE TABLE task_follows (
+    user_id UUID NOT NULL,
+    task_id UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, task_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES problems_or_tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_follows_user ON task_follows(user_id, created_at DESC, task_id DESC);
+CREATE INDEX idx_task_follows_task ON task_follows(task_id);
+
+-- Feed items up to this point have been seen
+CREATE TABLE feed_reads (
+    user_id UUID PRIMARY KEY,
+    read_until TIMESTAMP WITH TIME ZONE NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- When a submission was last featured; NULL while it is not
+ALTER TABLE submissions ADD COLUMN featured_at TIMESTAMP WITH TIME ZONE;
+
+UPDATE submissions SET featured_at = updated_at WHERE is_featured;
+
+CREATE OR REPLACE FUNCTION set_submission_featured_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NOT COALESCE(NEW.is_featured, FALSE) THEN
+        NEW.featured_at := NULL;
+    ELSIF TG_OP = 'INSERT' OR NOT COALESCE(OLD.is_featured, FALSE) THEN
+        NEW.featured_at := NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_set_featured_at BEFORE INSERT OR UPDATE OF is_featured ON submissions
+    FOR EACH ROW EXECUTE FUNCTION set_submission_featured_at();
+
+CREATE INDEX idx_submissions_task_submitted ON submissions(task_id, submitted_at DESC);
+CREATE INDEX idx_task_comments_task_created ON task_comments(task_id, created_at DESC);
+
+CREATE TYPE feed_event_kind AS ENUM (
+    'task_created',
+    'submission_submitted',
+    'submission_featured',
+    'task_commented',
+    'task_comment_replied'
+);
+
+-- Everything that can show up in a feed. `id` is the task, submission,
+-- comment or reply; `actor_id` whoever did it.
+CREATE VIEW feed_events AS
+    SELECT
+        tasks.id,
+        'task_created'::feed_event_kind AS kind,
+        tasks.user_id AS actor_id,
+        tasks.id AS task_id,
+        NULL::UUID AS submission_id,
+        tasks.created_at
+    FROM problems_or_tasks tasks
+    WHERE tasks.deleted_at IS NULL
+    UNION ALL
+    SELECT s.id, 'submission_submitted', s.user_id, s.task_id, s.id, s.submitted_at
+    FROM submissions s
+    WHERE s.status <> 'draft' AND s.submitted_at IS NOT NULL AND s.deleted_at IS NULL
+    UNION ALL
+    SELECT s.id, 'submission_featured', s.user_id, s.task_id, s.id, s.featured_at
+    FROM submissions s
+    WHERE s.featured_at IS NOT NULL AND s.deleted_at IS NULL
+    UNION ALL
+    SELECT comments.id, 'task_commented', comments.user_id, comments.task_id, NULL, comments.created_at
+    FROM task_comments comments
+    WHERE comments.deleted_at IS NULL
+    UNION ALL
+    SELECT replies.id, 'task_comment_replied', replies.user_id, comments.task_id, NULL, replies.created_at
+    FROM task_comment_replies replies
+    JOIN task_comments comments ON comments.id = replies.task_comment_id
+    WHERE replies.deleted_at IS NULL AND comments.deleted_at IS NULL;
+
+-- What `viewer` sees in their feed, in no particular order; their own
+-- doings are left out
+CREATE OR REPLACE FUNCTION user_feed(viewer UUID)
+RETURNS SETOF feed_events AS $$
+    SELECT events.*
+    FROM feed_events events
+    WHERE events.actor_id <> viewer
+        AND CASE events.kind
+            WHEN 'task_created' THEN
+                events.actor_id IN (SELECT followee_id FROM user_follows WHERE follower_id = viewer)
+            WHEN 'submission_featured' THEN
+                events.actor_id IN (SELECT followee_id FROM user_follows WHERE follower_id = viewer)
+                OR events.task_id IN (SELECT task_id FROM task_follows WHERE user_id = viewer)
+            ELSE
+                events.task_id IN (SELECT task_id FROM task_follows WHERE user_id = viewer)
+        END
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,29 @@
+-- Everything that can show up in a feed. `id` is the task, submission,
+-- comment or reply; `actor_id` whoever did it.
+CREATE OR REPLACE VIEW feed_events AS
+    SELECT
+        tasks.id,
+        'task_created'::feed_event_kind AS kind,
+        tasks.user_id AS actor_id,
+        tasks.id AS task_id,
+        NULL::UUID AS submission_id,
+        tasks.created_at
+    FROM problems_or_tasks tasks
+    WHERE tasks.deleted_at IS NULL
+    UNION ALL
+    SELECT s.id, 'submission_submitted', s.user_id, s.task_id, s.id, s.submitted_at
+    FROM submissions s
+    WHERE s.status <> 'draft' AND s.submitted_at IS NOT NULL AND s.deleted_at IS NULL
+    UNION ALL
+    SELECT s.id, 'submission_featured', s.user_id, s.task_id, s.id, s.featured_at
+    FROM submissions s
+    WHERE s.featured_at IS NOT NULL AND s.deleted_at IS NULL
+    UNION ALL
+    SELECT comments.id, 'task_commented', comments.user_id, comments.task_id, NULL, comments.created_at
+    FROM task_comments comments
+    WHERE comments.deleted_at IS NULL
+    UNION ALL
+    SELECT replies.id, 'task_comment_replied', replies.user_id, comments.task_id, NULL, replies.created_at
+    FROM task_comment_replies replies
+    JOIN task_comments comments ON comments.id = replies.task_comment_id
+    WHERE replies.deleted_at IS NULL AND comments.deleted_at IS NULL;
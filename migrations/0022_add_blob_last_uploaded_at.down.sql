@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_blobs_last_uploaded_at;
+CREATE INDEX idx_blobs_created_at ON blobs(created_at, id);
+
+ALTER TABLE blobs DROP COLUMN IF EXISTS last_uploaded_at;
@@ -0,0 +1,8 @@
+-- Uploading a file again restarts its grace period: orphaned blobs are
+-- cleaned up by when they were last uploaded, not when they were first.
+ALTER TABLE blobs ADD COLUMN last_uploaded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
+
+UPDATE blobs SET last_uploaded_at = COALESCE(created_at, last_uploaded_at);
+
+DROP INDEX IF EXISTS idx_blobs_created_at;
+CREATE INDEX idx_blobs_last_uploaded_at ON blobs(last_uploaded_at, id);
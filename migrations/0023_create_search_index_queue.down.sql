@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS submissions_queue_search_index_update ON submissions;
+DROP TRIGGER IF EXISTS submissions_queue_search_index ON submissions;
+DROP FUNCTION IF EXISTS queue_submission_search_index();
+
+DROP TRIGGER IF EXISTS problems_or_tasks_queue_search_index_update ON problems_or_tasks;
+DROP TRIGGER IF EXISTS problems_or_tasks_queue_search_index ON problems_or_tasks;
+DROP FUNCTION IF EXISTS queue_task_search_index();
+
+DROP TABLE IF EXISTS search_index_queue;
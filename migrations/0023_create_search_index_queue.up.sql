@@ -0,0 +1,54 @@
+-- Changes waiting for the search-indexer to apply to the Tantivy index
+--
+-- Every task and submission write is queued here, whichever process made
+-- it, so a single indexer can hold the index's writer lock while any number
+-- of API instances read the index.
+CREATE TABLE search_index_queue (
+    id BIGSERIAL PRIMARY KEY,
+    task_id UUID NOT NULL,
+    -- NULL when the task itself changed, which re-indexes its submissions too
+    submission_id UUID
+);
+
+CREATE OR REPLACE FUNCTION queue_task_search_index()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO search_index_queue (task_id)
+    VALUES (COALESCE(NEW.id, OLD.id));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Views, ratings and counters are not indexed and change far more often
+CREATE TRIGGER problems_or_tasks_queue_search_index AFTER INSERT OR DELETE ON problems_or_tasks
+    FOR EACH ROW EXECUTE FUNCTION queue_task_search_index();
+
+CREATE TRIGGER problems_or_tasks_queue_search_index_update AFTER UPDATE ON problems_or_tasks
+    FOR EACH ROW
+    WHEN (
+        (OLD.user_id, OLD.title, OLD.content, OLD.tags, OLD.difficulty, OLD.created_at, OLD.deleted_at)
+        IS DISTINCT FROM
+        (NEW.user_id, NEW.title, NEW.content, NEW.tags, NEW.difficulty, NEW.created_at, NEW.deleted_at)
+    )
+    EXECUTE FUNCTION queue_task_search_index();
+
+CREATE OR REPLACE FUNCTION queue_submission_search_index()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO search_index_queue (task_id, submission_id)
+    VALUES (COALESCE(NEW.task_id, OLD.task_id), COALESCE(NEW.id, OLD.id));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER submissions_queue_search_index AFTER INSERT OR DELETE ON submissions
+    FOR EACH ROW EXECUTE FUNCTION queue_submission_search_index();
+
+CREATE TRIGGER submissions_queue_search_index_update AFTER UPDATE ON submissions
+    FOR EACH ROW
+    WHEN (
+        (OLD.user_id, OLD.task_id, OLD.content, OLD.status, OLD.created_at, OLD.deleted_at)
+        IS DISTINCT FROM
+        (NEW.user_id, NEW.task_id, NEW.content, NEW.status, NEW.created_at, NEW.deleted_at)
+    )
+    EXECUTE FUNCTION queue_submission_search_index();
@@ -0,0 +1,61 @@
+DROP TRIGGER IF EXISTS contests_queue_badge_checks ON contests;
+DROP FUNCTION IF EXISTS queue_contest_badge_checks();
+
+DROP TRIGGER IF EXISTS contests_queue_search_index ON contests;
+DROP FUNCTION IF EXISTS queue_contest_search_index();
+DROP TRIGGER IF EXISTS contest_submissions_queue_search_index ON contest_submissions;
+DROP FUNCTION IF EXISTS queue_contest_submission_search_index();
+
+DROP TRIGGER IF EXISTS contests_queue_leaderboard_deltas ON contests;
+DROP FUNCTION IF EXISTS queue_contest_leaderboard_deltas();
+
+CREATE OR REPLACE FUNCTION queue_answer_leaderboard_delta()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.verdict = 'correct' AND NOT EXISTS (
+        SELECT 1 FROM problems_or_tasks WHERE id = NEW.task_id AND user_id = NEW.user_id
+    ) THEN
+        PERFORM queue_solution_leaderboard_delta(NEW.user_id, NEW.task_id, TRUE, NEW.created_at);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION queue_grading_leaderboard_delta()
+RETURNS TRIGGER AS $$
+DECLARE
+    solver UUID;
+    task UUID;
+    author UUID;
+BEGIN
+    IF COALESCE(NEW.verdict = 'AC', FALSE) = COALESCE(OLD.verdict = 'AC', FALSE) THEN
+        RETURN NULL;
+    END IF;
+
+    SELECT s.user_id, s.task_id, t.user_id INTO solver, task, author
+    FROM submissions s
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE s.id = NEW.submission_id;
+
+    IF solver IS NOT NULL AND solver <> author THEN
+        PERFORM queue_solution_leaderboard_delta(
+            solver, task, NEW.verdict = 'AC', COALESCE(NEW.finished_at, OLD.finished_at, NOW())
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP FUNCTION IF EXISTS submission_results_hidden(UUID);
+
+CREATE OR REPLACE VIEW accepted_solutions AS
+    SELECT s.user_id, s.task_id, runs.finished_at AS accepted_at
+    FROM grading_runs runs
+    JOIN submissions s ON s.id = runs.submission_id
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE runs.verdict = 'AC' AND t.user_id <> s.user_id
+    UNION ALL
+    SELECT attempts.user_id, attempts.task_id, attempts.created_at
+    FROM answer_attempts attempts
+    JOIN problems_or_tasks t ON t.id = attempts.task_id
+    WHERE attempts.verdict = 'correct' AND t.user_id <> attempts.user_id;
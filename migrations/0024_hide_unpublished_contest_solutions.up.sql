@@ -0,0 +1,143 @@
+-- Contest solutions stay off the solved-tasks leaderboards and out of badge
+-- counts until the contest's results are published, so the frozen
+-- scoreboard can not be read from them. Once published they count as
+-- solved at the publication. The search index likewise shows contest
+-- submissions to the task's author only once published.
+
+-- Every accepted solution: code submissions graded 'AC' and correct
+-- answers. Solving your own task does not count.
+CREATE OR REPLACE VIEW accepted_solutions AS
+    SELECT s.user_id, s.task_id, GREATEST(runs.finished_at, contests.results_published_at) AS accepted_at
+    FROM grading_runs runs
+    JOIN submissions s ON s.id = runs.submission_id
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    LEFT JOIN contest_submissions cs ON cs.submission_id = s.id
+    LEFT JOIN contests ON contests.id = cs.contest_id
+    WHERE runs.verdict = 'AC' AND t.user_id <> s.user_id
+        AND (cs.submission_id IS NULL OR contests.results_published_at IS NOT NULL)
+    UNION ALL
+    SELECT attempts.user_id, attempts.task_id, GREATEST(attempts.created_at, contests.results_published_at)
+    FROM answer_attempts attempts
+    JOIN problems_or_tasks t ON t.id = attempts.task_id
+    LEFT JOIN contest_submissions cs ON cs.submission_id = attempts.submission_id
+    LEFT JOIN contests ON contests.id = cs.contest_id
+    WHERE attempts.verdict = 'correct' AND t.user_id <> attempts.user_id
+        AND (cs.submission_id IS NULL OR contests.results_published_at IS NOT NULL);
+
+-- Whether a submission belongs to a contest whose results are not out yet
+CREATE OR REPLACE FUNCTION submission_results_hidden(submission UUID)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1
+        FROM contest_submissions cs
+        JOIN contests c ON c.id = cs.contest_id
+        WHERE cs.submission_id = submission AND c.results_published_at IS NULL
+    );
+$$ LANGUAGE SQL STABLE;
+
+CREATE OR REPLACE FUNCTION queue_grading_leaderboard_delta()
+RETURNS TRIGGER AS $$
+DECLARE
+    solver UUID;
+    task UUID;
+    author UUID;
+BEGIN
+    IF COALESCE(NEW.verdict = 'AC', FALSE) = COALESCE(OLD.verdict = 'AC', FALSE)
+        OR submission_results_hidden(NEW.submission_id)
+    THEN
+        RETURN NULL;
+    END IF;
+
+    SELECT s.user_id, s.task_id, t.user_id INTO solver, task, author
+    FROM submissions s
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE s.id = NEW.submission_id;
+
+    IF solver IS NOT NULL AND solver <> author THEN
+        PERFORM queue_solution_leaderboard_delta(
+            solver, task, NEW.verdict = 'AC', COALESCE(NEW.finished_at, OLD.finished_at, NOW())
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION queue_answer_leaderboard_delta()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.verdict = 'correct' AND NOT submission_results_hidden(NEW.submission_id) AND NOT EXISTS (
+        SELECT 1 FROM problems_or_tasks WHERE id = NEW.task_id AND user_id = NEW.user_id
+    ) THEN
+        PERFORM queue_solution_leaderboard_delta(NEW.user_id, NEW.task_id, TRUE, NEW.created_at);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Publishing counts the tasks participants solved during the contest that
+-- they had not solved otherwise
+CREATE OR REPLACE FUNCTION queue_contest_leaderboard_deltas()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO leaderboard_deltas (metric, user_id, task_id, amount, occurred_at)
+    SELECT 'accepted_submissions', solutions.user_id, solutions.task_id, 1, MIN(solutions.accepted_at)
+    FROM accepted_solutions solutions
+    WHERE (solutions.user_id, solutions.task_id) IN (
+        SELECT user_id, task_id FROM contest_submissions WHERE contest_id = NEW.id
+    )
+    GROUP BY solutions.user_id, solutions.task_id
+    HAVING MIN(solutions.accepted_at) = NEW.results_published_at;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contests_queue_leaderboard_deltas AFTER UPDATE OF results_published_at ON contests
+    FOR EACH ROW
+    WHEN (OLD.results_published_at IS NULL AND NEW.results_published_at IS NOT NULL)
+    EXECUTE FUNCTION queue_contest_leaderboard_deltas();
+
+-- Linking a submission to a contest, or publishing the results, changes who
+-- finds it in search
+CREATE OR REPLACE FUNCTION queue_contest_submission_search_index()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO search_index_queue (task_id, submission_id)
+    VALUES (COALESCE(NEW.task_id, OLD.task_id), COALESCE(NEW.submission_id, OLD.submission_id));
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contest_submissions_queue_search_index AFTER INSERT OR DELETE ON contest_submissions
+    FOR EACH ROW EXECUTE FUNCTION queue_contest_submission_search_index();
+
+CREATE OR REPLACE FUNCTION queue_contest_search_index()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO search_index_queue (task_id, submission_id)
+    SELECT task_id, submission_id FROM contest_submissions WHERE contest_id = NEW.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contests_queue_search_index AFTER UPDATE OF results_published_at ON contests
+    FOR EACH ROW
+    WHEN (OLD.results_published_at IS NULL AND NEW.results_published_at IS NOT NULL)
+    EXECUTE FUNCTION queue_contest_search_index();
+
+-- Publishing also lets the participants' contest solutions count towards
+-- their badges
+CREATE OR REPLACE FUNCTION queue_contest_badge_checks()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO badge_checks (user_id, event)
+    SELECT DISTINCT user_id, 'task_solved'::badge_event_type
+    FROM contest_submissions
+    WHERE contest_id = NEW.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contests_queue_badge_checks AFTER UPDATE OF results_published_at ON contests
+    FOR EACH ROW
+    WHEN (OLD.results_published_at IS NULL AND NEW.results_published_at IS NOT NULL)
+    EXECUTE FUNCTION queue_contest_badge_checks();
@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// What a user did on one day of their time zone
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActivityDay {
+    pub day: NaiveDate,
+    /// Tasks created
+    pub tasks: i64,
+    /// Submissions created, drafts included
+    pub submissions: i64,
+    /// Comments and replies posted on tasks and submissions
+    pub comments: i64,
+    /// Ratings given to tasks and submissions
+    pub ratings: i64,
+}
+
+impl ActivityDay {
+    /// A day without activity
+    pub fn empty(day: NaiveDate) -> Self {
+        Self {
+            day,
+            tasks: 0,
+            submissions: 0,
+            comments: 0,
+            ratings: 0,
+        }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.tasks + self.submissions + self.comments + self.ratings
+    }
+}
+
+/// Runs of consecutive active days in a user's time zone
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActivityStreak {
+    /// Days in the run that ends today, or yesterday while today is still
+    /// open; 0 once a day was missed
+    pub current: i32,
+    pub current_since: Option<NaiveDate>,
+    pub longest: i32,
+    pub last_active_on: Option<NaiveDate>,
+}
+
+/// A user's time zone and the date it is there now
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UserTimezone {
+    pub user_id: Uuid,
+    pub timezone: String,
+    pub today: NaiveDate,
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Mirrors the `badge_rule` Postgres enum: the count a badge's threshold
+/// applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "badge_rule", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeRule {
+    /// Submissions submitted for grading
+    SubmissionsMade,
+    FeaturedSubmissions,
+    /// Tasks created, deleted ones included
+    TasksAuthored,
+    /// Other users' tasks solved
+    TasksSolved,
+    /// Comments and replies on tasks and submissions
+    CommentsPosted,
+    /// Ratings given to tasks and submissions
+    RatingsGiven,
+    /// Longest run of consecutive days with any activity, in the user's
+    /// time zone
+    ActivityStreak,
+}
+
+impl BadgeRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BadgeRule::SubmissionsMade => "submissions_made",
+            BadgeRule::FeaturedSubmissions => "featured_submissions",
+            BadgeRule::TasksAuthored => "tasks_authored",
+            BadgeRule::TasksSolved => "tasks_solved",
+            BadgeRule::CommentsPosted => "comments_posted",
+            BadgeRule::RatingsGiven => "ratings_given",
+            BadgeRule::ActivityStreak => "activity_streak",
+        }
+    }
+
+    /// Whether the count can have changed after `event`
+    pub fn triggered_by(&self, event: BadgeEventType) -> bool {
+        match (self, event) {
+            (_, BadgeEventType::Recheck) => true,
+            // Any activity can extend a streak
+            (BadgeRule::ActivityStreak, _) => true,
+            (BadgeRule::SubmissionsMade, BadgeEventType::SubmissionSubmitted) => true,
+            (BadgeRule::FeaturedSubmissions, BadgeEventType::SubmissionFeatured) => true,
+            (BadgeRule::TasksAuthored, BadgeEventType::TaskCreated) => true,
+            (BadgeRule::TasksSolved, BadgeEventType::TaskSolved) => true,
+            (BadgeRule::CommentsPosted, BadgeEventType::CommentPosted) => true,
+            (BadgeRule::RatingsGiven, BadgeEventType::RatingGiven) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Mirrors the `badge_event_type` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "badge_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BadgeEventType {
+    TaskCreated,
+    SubmissionSubmitted,
+    SubmissionFeatured,
+    TaskSolved,
+    CommentPosted,
+    RatingGiven,
+    /// Evaluate every rule
+    Recheck,
+}
+
+/// A badge of the catalog
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Badge {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub rule: BadgeRule,
+    /// Count the rule must reach
+    pub threshold: i32,
+    /// Users who earned it
+    pub holders: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A badge a user earned
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UserBadge {
+    pub badge_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub rule: BadgeRule,
+    pub threshold: i32,
+    pub awarded_at: DateTime<Utc>,
+}
+
+/// A queued badge check for a user
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct BadgeCheck {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub event: BadgeEventType,
+    pub occurred_at: DateTime<Utc>,
+}
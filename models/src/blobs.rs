@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// An uploaded file, stored once per distinct content
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Blob {
+    pub id: Uuid,
+    /// Lowercase hex SHA-256 of the content
+    pub sha256: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Refreshed whenever the same content is uploaded again
+    pub last_uploaded_at: DateTime<Utc>,
+}
+
+impl Blob {
+    /// Object name in the blob store, sharded on the first two hex digits:
+    /// `blobs/ab/abcdef...`
+    pub fn storage_key(sha256: &str) -> String {
+        format!("blobs/{}/{}", &sha256[..2], sha256)
+    }
+
+    /// Whether `value` looks like a lowercase hex SHA-256
+    pub fn is_sha256(value: &str) -> bool {
+        value.len() == 64
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{AnswerVerdict, GradingVerdict};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Mirrors the `contest_scoring` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "contest_scoring", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContestScoring {
+    /// Ranked by tasks solved, then by penalty time
+    Icpc,
+    /// Ranked by points of the best attempt at each task, then by penalty
+    /// time
+    Points,
+}
+
+/// Where a contest is at a point in time
+///
+/// ```text
+/// upcoming -> running -> frozen -> ended -> published
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContestPhase {
+    Upcoming,
+    Running,
+    /// Still running, but the public scoreboard no longer changes
+    Frozen,
+    /// Over, results not published yet
+    Ended,
+    Published,
+}
+
+/// A timed set of tasks for registered participants
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Contest {
+    pub id: Uuid,
+    /// The organizer
+    pub created_by: Uuid,
+    pub title: String,
+    pub description: String,
+    pub scoring: ContestScoring,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// Added per rejected attempt before a task was solved or got its best
+    /// score
+    pub penalty_minutes: i32,
+    /// How long before the end the public scoreboard freezes
+    pub freeze_minutes: i32,
+    pub results_published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Contest {
+    pub fn phase(&self, at: DateTime<Utc>) -> ContestPhase {
+        if self.results_published_at.is_some() {
+            ContestPhase::Published
+        } else if at < self.starts_at {
+            ContestPhase::Upcoming
+        } else if at >= self.ends_at {
+            ContestPhase::Ended
+        } else if at >= self.frozen_at() {
+            ContestPhase::Frozen
+        } else {
+            ContestPhase::Running
+        }
+    }
+
+    /// Whether submissions are accepted at `at`
+    pub fn is_running(&self, at: DateTime<Utc>) -> bool {
+        at >= self.starts_at && at < self.ends_at
+    }
+
+    /// When the public scoreboard stops changing; never before the start
+    pub fn frozen_at(&self) -> DateTime<Utc> {
+        (self.ends_at - Duration::minutes(self.freeze_minutes as i64)).max(self.starts_at)
+    }
+}
+
+/// A task of a contest
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContestTask {
+    pub contest_id: Uuid,
+    pub task_id: Uuid,
+    /// 1-based order within the contest
+    pub position: i32,
+    /// Worth of a full score in points contests
+    pub points: i32,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContestParticipant {
+    pub contest_id: Uuid,
+    pub user_id: Uuid,
+    pub registered_at: DateTime<Utc>,
+}
+
+/// A version submitted to a contest task during the contest, with how it
+/// was judged; both verdicts are `None` while it waits to be judged
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContestAttempt {
+    pub contest_id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    pub submitted_at: DateTime<Utc>,
+    /// Verdict of the latest completed grading run, for code submissions
+    pub grading_verdict: Option<GradingVerdict>,
+    pub score: Option<i32>,
+    pub max_score: Option<i32>,
+    /// Verdict of the check against the task's expected answer
+    pub answer_verdict: Option<AnswerVerdict>,
+}
+
+impl ContestAttempt {
+    pub fn is_judged(&self) -> bool {
+        self.grading_verdict.is_some() || self.answer_verdict.is_some()
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.grading_verdict == Some(GradingVerdict::AC)
+            || self.answer_verdict == Some(AnswerVerdict::Correct)
+    }
+
+    /// Share of the task's points the attempt earned, from 0 to 1; `None`
+    /// while it waits to be judged
+    pub fn score_fraction(&self) -> Option<f64> {
+        if !self.is_judged() {
+            return None;
+        }
+        match (self.grading_verdict, self.score, self.max_score) {
+            (Some(_), Some(score), Some(max)) if max > 0 => Some(score as f64 / max as f64),
+            _ if self.is_accepted() => Some(1.0),
+            _ => Some(0.0),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UserFollow {
+    pub follower_id: Uuid,
+    pub followee_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskFollow {
+    pub user_id: Uuid,
+    pub task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Mirrors the `feed_event_kind` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "feed_event_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FeedEventKind {
+    /// A followed user created a task
+    TaskCreated,
+    /// Someone submitted a solution to a followed task
+    SubmissionSubmitted,
+    /// A submission by a followed user or to a followed task was featured
+    SubmissionFeatured,
+    TaskCommented,
+    TaskCommentReplied,
+}
+
+/// One entry of a user's activity feed
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct FeedItem {
+    /// The task, submission, comment or reply the entry is about
+    pub id: Uuid,
+    pub kind: FeedEventKind,
+    pub actor_id: Uuid,
+    /// Absent when the account no longer exists
+    pub actor_name: Option<String>,
+    pub task_id: Uuid,
+    pub task_title: Option<String>,
+    pub submission_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Newer than the user's read marker
+    pub unread: bool,
+}
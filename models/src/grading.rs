@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use std::{fmt, str::FromStr};
+use uuid::Uuid;
+
+/// Mirrors the `programming_language` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "programming_language", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProgrammingLanguage {
+    C,
+    Cpp,
+    Python,
+    Rust,
+}
+
+impl ProgrammingLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProgrammingLanguage::C => "c",
+            ProgrammingLanguage::Cpp => "cpp",
+            ProgrammingLanguage::Python => "python",
+            ProgrammingLanguage::Rust => "rust",
+        }
+    }
+}
+
+impl fmt::Display for ProgrammingLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProgrammingLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c" => Ok(ProgrammingLanguage::C),
+            "cpp" => Ok(ProgrammingLanguage::Cpp),
+            "python" => Ok(ProgrammingLanguage::Python),
+            "rust" => Ok(ProgrammingLanguage::Rust),
+            _ => Err(format!("Unknown programming language '{}'", s)),
+        }
+    }
+}
+
+/// Mirrors the `grading_run_status` Postgres enum
+///
+/// ```text
+/// queued -> running -> completed | failed
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "grading_run_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GradingRunStatus {
+    Queued,
+    Running,
+    /// Every test ran; the verdict is the submission's
+    Completed,
+    /// The grader itself failed, see `GradingRun::error`
+    Failed,
+}
+
+/// Mirrors the `grading_verdict` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "grading_verdict")]
+pub enum GradingVerdict {
+    /// Accepted
+    AC,
+    /// Wrong answer
+    WA,
+    /// Time limit exceeded
+    TLE,
+    /// Memory limit exceeded
+    MLE,
+    /// Runtime error: non-zero exit, crash or output limit
+    RE,
+    /// Compilation error
+    CE,
+}
+
+/// One attempt at grading one version of a submission
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct GradingRun {
+    pub id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    pub language: ProgrammingLanguage,
+    pub status: GradingRunStatus,
+    /// First failing verdict in test order, `AC` when every test passed
+    pub verdict: Option<GradingVerdict>,
+    pub passed_tests: i32,
+    pub total_tests: i32,
+    /// Points of the passed tests
+    pub score: i32,
+    pub max_score: i32,
+    /// Compiler diagnostics, kept on compilation errors
+    pub compile_output: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one test case within a grading run
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct GradingResult {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    /// `None` once the test case has been deleted
+    pub test_case_id: Option<Uuid>,
+    pub position: i32,
+    pub verdict: GradingVerdict,
+    /// `max_points` when the test passed, 0 otherwise
+    pub points: i32,
+    pub max_points: i32,
+    /// CPU time used
+    pub time_ms: i32,
+    /// Peak resident memory
+    pub memory_kb: i32,
+    /// 128 + signal number when killed by a signal, `None` on a wall time
+    /// timeout
+    pub exit_code: Option<i32>,
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Mirrors the `leaderboard_metric` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "leaderboard_metric", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Reputation points gained
+    Reputation,
+    /// Other users' tasks solved, counted when first solved
+    AcceptedSubmissions,
+    /// Positive ratings the user's tasks received
+    TaskRatings,
+}
+
+impl LeaderboardMetric {
+    pub const ALL: [LeaderboardMetric; 3] = [
+        LeaderboardMetric::Reputation,
+        LeaderboardMetric::AcceptedSubmissions,
+        LeaderboardMetric::TaskRatings,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardMetric::Reputation => "reputation",
+            LeaderboardMetric::AcceptedSubmissions => "accepted_submissions",
+            LeaderboardMetric::TaskRatings => "task_ratings",
+        }
+    }
+}
+
+/// Period a leaderboard sums over; weeks start on Monday, in UTC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardWindow {
+    Week,
+    Month,
+    #[default]
+    AllTime,
+}
+
+impl LeaderboardWindow {
+    pub const ALL: [LeaderboardWindow; 3] = [
+        LeaderboardWindow::Week,
+        LeaderboardWindow::Month,
+        LeaderboardWindow::AllTime,
+    ];
+
+    /// Start of the period containing `at`; `None` for all time
+    pub fn start(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let day = at.date_naive();
+        let first = match self {
+            LeaderboardWindow::Week => {
+                day - Duration::days(day.weekday().num_days_from_monday() as i64)
+            }
+            LeaderboardWindow::Month => day.with_day(1)?,
+            LeaderboardWindow::AllTime => return None,
+        };
+        Some(Utc.from_utc_datetime(&first.and_time(NaiveTime::MIN)))
+    }
+
+    /// Start of the period after the one containing `at`; `None` for all
+    /// time
+    pub fn end(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = self.start(at)?;
+        match self {
+            LeaderboardWindow::Week => Some(start + Duration::weeks(1)),
+            LeaderboardWindow::Month => start.checked_add_months(chrono::Months::new(1)),
+            LeaderboardWindow::AllTime => None,
+        }
+    }
+}
+
+/// A queued change to a leaderboard score
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LeaderboardDelta {
+    pub id: i64,
+    pub metric: LeaderboardMetric,
+    pub user_id: Uuid,
+    /// Tags of the task the change is about; it applies to their
+    /// leaderboards as well as the global one
+    pub tags: Vec<String>,
+    pub amount: f64,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// A user's score on one leaderboard; `tag` is `None` on the global one
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LeaderboardStanding {
+    pub metric: LeaderboardMetric,
+    pub tag: Option<String>,
+    pub user_id: Uuid,
+    pub score: f64,
+}
+
+/// Every non-zero score, as of `taken_at`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub week: Vec<LeaderboardStanding>,
+    pub month: Vec<LeaderboardStanding>,
+    pub all_time: Vec<LeaderboardStanding>,
+}
+
+impl LeaderboardSnapshot {
+    pub fn standings(&self, window: LeaderboardWindow) -> &[LeaderboardStanding] {
+        match window {
+            LeaderboardWindow::Week => &self.week,
+            LeaderboardWindow::Month => &self.month,
+            LeaderboardWindow::AllTime => &self.all_time,
+        }
+    }
+}
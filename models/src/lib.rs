@@ -1,21 +1,57 @@
 pub mod account;
+pub mod activity;
+pub mod badges;
+pub mod blobs;
+pub mod contests;
+pub mod follows;
+pub mod grading;
+pub mod leaderboards;
+pub mod personal_access_tokens;
 pub mod problems_or_tasks;
+pub mod rating_abuse;
+pub mod rating_settings;
+pub mod rating_stats;
+pub mod reputation;
+pub mod search;
 pub mod submission_comment_reply;
 pub mod submission_comments;
 pub mod submission_ratings;
+pub mod submission_revisions;
 pub mod submissions;
+pub mod tags;
+pub mod task_answers;
 pub mod task_comment_reply;
 pub mod task_comments;
 pub mod task_ratings;
+pub mod task_revisions;
+pub mod task_test_cases;
 pub mod users;
 
 pub use account::*;
+pub use activity::*;
+pub use badges::*;
+pub use blobs::*;
+pub use contests::*;
+pub use follows::*;
+pub use grading::*;
+pub use leaderboards::*;
+pub use personal_access_tokens::*;
 pub use problems_or_tasks::*;
+pub use rating_abuse::*;
+pub use rating_settings::*;
+pub use rating_stats::*;
+pub use reputation::*;
+pub use search::*;
 pub use submission_comment_reply::*;
 pub use submission_comments::*;
 pub use submission_ratings::*;
+pub use submission_revisions::*;
 pub use submissions::*;
+pub use tags::*;
+pub use task_answers::*;
 pub use task_comment_reply::*;
 pub use task_comments::*;
 pub use task_ratings::*;
+pub use task_revisions::*;
+pub use task_test_cases::*;
 pub use users::*;
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use std::{fmt, str::FromStr};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Deserialize, Serialize)]
+pub struct PersonalAccessToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PersonalAccessToken {
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        self.scopes.iter().any(|s| s == scope.as_str())
+    }
+}
+
+/// Permissions that can be granted to a personal access token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum TokenScope {
+    #[serde(rename = "tasks:read")]
+    TasksRead,
+    #[serde(rename = "tasks:write")]
+    TasksWrite,
+    #[serde(rename = "submissions:read")]
+    SubmissionsRead,
+    #[serde(rename = "submissions:write")]
+    SubmissionsWrite,
+    #[serde(rename = "comments:write")]
+    CommentsWrite,
+    #[serde(rename = "ratings:write")]
+    RatingsWrite,
+}
+
+impl TokenScope {
+    pub const ALL: [TokenScope; 6] = [
+        TokenScope::TasksRead,
+        TokenScope::TasksWrite,
+        TokenScope::SubmissionsRead,
+        TokenScope::SubmissionsWrite,
+        TokenScope::CommentsWrite,
+        TokenScope::RatingsWrite,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenScope::TasksRead => "tasks:read",
+            TokenScope::TasksWrite => "tasks:write",
+            TokenScope::SubmissionsRead => "submissions:read",
+            TokenScope::SubmissionsWrite => "submissions:write",
+            TokenScope::CommentsWrite => "comments:write",
+            TokenScope::RatingsWrite => "ratings:write",
+        }
+    }
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TokenScope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| format!("Unknown token scope '{}'", s))
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Deserialize, Serialize, FromRow)]
@@ -11,12 +12,59 @@ pub struct ProblemOrTask {
     pub content: String,
     pub file_url: Option<String>,
     pub tags: Vec<String>,
-    pub difficulty: String,
+    pub difficulty: Difficulty,
     pub average_rating: f64,
     pub total_ratings: i32,
+    /// Mean rating pulled towards the configured prior; the default
+    /// "top rated" rank
+    pub bayesian_rating: f64,
+    /// Lower bound of the Wilson score interval for the share of positive
+    /// ratings
+    pub wilson_score: f64,
     pub total_submissions: i32,
     pub view_count: i32,
+    /// Bumped on every change to the title, content, file, tags or difficulty
+    pub current_revision: i32,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+/// Mirrors the `task_difficulty` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "task_difficulty", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("Unknown difficulty '{}'", s)),
+        }
+    }
+}
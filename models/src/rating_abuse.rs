@@ -0,0 +1,101 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Mirrors the `rating_kind` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "rating_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RatingKind {
+    Task,
+    Submission,
+}
+
+/// Mirrors the `rating_flag_reason` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "rating_flag_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RatingFlagReason {
+    /// Two users who keep rating each other's work
+    VoteRing,
+    /// Several freshly created accounts rating the same thing at once
+    NewAccountBurst,
+    /// Different raters of the same thing sharing an IP or session
+    SharedOrigin,
+}
+
+/// Mirrors the `rating_flag_status` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "rating_flag_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RatingFlagStatus {
+    Pending,
+    /// A moderator agreed; the rating is neutralized
+    Confirmed,
+    Dismissed,
+}
+
+/// A suspicious rating awaiting or after moderator review
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RatingFlag {
+    pub id: Uuid,
+    pub rating_kind: RatingKind,
+    pub rating_id: Uuid,
+    pub reason: RatingFlagReason,
+    /// What the scan saw, e.g. the ring partner or the shared IP
+    pub details: serde_json::Value,
+    pub status: RatingFlagStatus,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where a rating was given from, kept for abuse detection
+///
+/// Handlers take it from the request with `shared::extractors::RequestOrigin`;
+/// a rating created without an origin is never caught by the shared origin
+/// rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RatingOrigin {
+    pub client_ip: Option<IpAddr>,
+    /// Identifier of the login session or device
+    pub session_id: Option<String>,
+}
+
+/// When a pattern of ratings is suspicious enough to flag
+#[derive(Debug, Clone, Copy)]
+pub struct RatingAbuseThresholds {
+    /// Only ratings given this recently are looked at
+    pub lookback: TimeDelta,
+    /// Vote ring: ratings each of the two users gave the other's work
+    pub ring_min_ratings: i64,
+    /// Vote ring: smallest share of a user's ratings that went to the other
+    pub ring_min_share: f64,
+    /// Accounts younger than this when they rated count as new
+    pub new_account_age: TimeDelta,
+    /// Burst: ratings of new accounts this close together on one target
+    pub burst_window: TimeDelta,
+    pub burst_min_ratings: i64,
+    /// Shared origin: distinct raters of one target from the same IP
+    pub ip_min_raters: i64,
+    /// Shared origin: distinct raters of one target from the same session
+    pub session_min_raters: i64,
+}
+
+impl Default for RatingAbuseThresholds {
+    fn default() -> Self {
+        Self {
+            lookback: TimeDelta::days(30),
+            ring_min_ratings: 3,
+            ring_min_share: 0.5,
+            new_account_age: TimeDelta::days(3),
+            burst_window: TimeDelta::hours(1),
+            burst_min_ratings: 3,
+            // Households and offices share an address
+            ip_min_raters: 3,
+            session_min_raters: 2,
+        }
+    }
+}
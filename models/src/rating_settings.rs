@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+
+/// How the ranking scores of rated tasks and submissions are computed
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RatingSettings {
+    /// Rating the Bayesian average starts from
+    pub prior_mean: f64,
+    /// How many ratings the prior counts as
+    pub prior_weight: f64,
+    /// Lowest rating counted as positive by the Wilson score
+    pub positive_min_rating: i16,
+    /// Standard normal quantile of the Wilson interval; 1.96 for 95%
+    pub confidence_z: f64,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+
+/// How many ratings of a task or submission have a given value
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RatingCount {
+    pub rating_value: i32,
+    pub count: i64,
+}
+
+/// Width of the buckets of a rating time series
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingInterval {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl RatingInterval {
+    /// The `date_trunc` field, which is also a valid one unit `interval`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatingInterval::Day => "day",
+            RatingInterval::Week => "week",
+            RatingInterval::Month => "month",
+        }
+    }
+}
+
+/// Ratings given during one bucket of a time series, by when they were
+/// first given
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RatingActivity {
+    /// Start of the bucket, in UTC
+    pub bucket_start: DateTime<Utc>,
+    pub ratings: i64,
+    /// Mean current value of those ratings; `None` for empty buckets
+    pub average_rating: Option<f64>,
+}
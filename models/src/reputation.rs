@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Mirrors the `reputation_event_type` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "reputation_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationEventType {
+    /// One of the user's tasks was rated
+    TaskRated,
+    /// One of the user's submissions was rated
+    SubmissionRated,
+    SubmissionFeatured,
+    /// A moderator removed one of the user's comments
+    CommentRemoved,
+}
+
+impl ReputationEventType {
+    /// Whether the points depend on the rating value
+    pub fn is_rated(&self) -> bool {
+        matches!(
+            self,
+            ReputationEventType::TaskRated | ReputationEventType::SubmissionRated
+        )
+    }
+}
+
+/// One entry of a user's append-only reputation ledger
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ReputationEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub event_type: ReputationEventType,
+    /// The rating, submission or comment the event is about
+    pub source_id: Uuid,
+    /// The rater or moderator who caused it
+    pub actor_id: Option<Uuid>,
+    /// Points applied to the score, after the daily cap
+    pub points: Decimal,
+    /// Points the event was worth before the daily cap
+    pub base_points: Decimal,
+    /// Set on reversals: the event this one undoes
+    pub reverses_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Points awarded for an event; `rating_value` is 1-4 for rated events and
+/// 0 otherwise
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ReputationWeight {
+    pub event_type: ReputationEventType,
+    pub rating_value: i16,
+    pub points: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
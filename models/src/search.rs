@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// What a search hit points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultKind {
+    Task,
+    Submission,
+    TaskComment,
+    SubmissionComment,
+    TaskCommentReply,
+    SubmissionCommentReply,
+}
+
+/// A single ranked full-text search result
+///
+/// `task_id` is the task the hit belongs to (the hit itself for tasks) and
+/// `title` is that task's title, so every hit can be linked and labelled.
+/// `snippet` is an excerpt of the matched text with the matching terms
+/// wrapped in `<mark>` tags.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// How many search hits carry a facet value
+#[derive(Debug, Clone, PartialEq, Eq, FromRow, Serialize, Deserialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Difficulty and tag counts over all hits of a search, most frequent first
+///
+/// A submission or discussion hit counts towards the facets of its task.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFacets {
+    pub difficulties: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+}
@@ -0,0 +1,22 @@
+use crate::SubmissionStatus;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Immutable snapshot of a submission, written by the database whenever a
+/// draft is saved or the submission is (re)submitted
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SubmissionRevision {
+    pub id: Uuid,
+    pub submission_id: Uuid,
+    /// Starts at 1 and matches `Submission::current_version` when current
+    pub version: i32,
+    pub content: String,
+    pub file_url: Option<String>,
+    /// `Draft` for a saved draft, `Submitted` for a submitted snapshot
+    pub status: SubmissionStatus,
+    /// Revision of the task the snapshot was written against
+    pub task_revision: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
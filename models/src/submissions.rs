@@ -1,7 +1,9 @@
+use crate::ProgrammingLanguage;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -11,12 +13,89 @@ pub struct Submission {
     pub task_id: Uuid,
     pub content: String,
     pub file_url: Option<String>,
-    pub status: Option<String>,
+    pub status: SubmissionStatus,
     pub average_rating: Decimal,
     pub total_ratings: i32,
+    /// Mean rating pulled towards the configured prior; the default
+    /// "top rated" rank
+    pub bayesian_rating: f64,
+    /// Lower bound of the Wilson score interval for the share of positive
+    /// ratings
+    pub wilson_score: f64,
     pub is_featured: bool,
     pub submitted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Revision of the task the content was written against
+    pub task_revision: Option<i32>,
+    /// Latest entry in `submission_revisions`, bumped on every saved change
+    pub current_version: i32,
+    /// Set on code submissions, which are graded against the task's tests
+    pub language: Option<ProgrammingLanguage>,
+}
+
+/// Mirrors the `submission_status` Postgres enum
+///
+/// Valid transitions are also enforced by a trigger on `submissions`:
+///
+/// ```text
+/// draft        -> submitted | withdrawn
+/// submitted    -> under_review | accepted | rejected | withdrawn
+/// under_review -> accepted | rejected | withdrawn
+/// rejected     -> submitted (resubmission)
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "submission_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    Draft,
+    Submitted,
+    UnderReview,
+    Accepted,
+    Rejected,
+    Withdrawn,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Draft => "draft",
+            SubmissionStatus::Submitted => "submitted",
+            SubmissionStatus::UnderReview => "under_review",
+            SubmissionStatus::Accepted => "accepted",
+            SubmissionStatus::Rejected => "rejected",
+            SubmissionStatus::Withdrawn => "withdrawn",
+        }
+    }
+
+    /// Whether new content may be submitted as a new version
+    pub fn can_resubmit(&self) -> bool {
+        matches!(
+            self,
+            SubmissionStatus::Submitted | SubmissionStatus::Rejected
+        )
+    }
+}
+
+impl fmt::Display for SubmissionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubmissionStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(SubmissionStatus::Draft),
+            "submitted" => Ok(SubmissionStatus::Submitted),
+            "under_review" => Ok(SubmissionStatus::UnderReview),
+            "accepted" => Ok(SubmissionStatus::Accepted),
+            "rejected" => Ok(SubmissionStatus::Rejected),
+            "withdrawn" => Ok(SubmissionStatus::Withdrawn),
+            _ => Err(format!("Unknown submission status '{}'", s)),
+        }
+    }
 }
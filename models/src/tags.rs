@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// A canonical tag; task tags are stored as tag slugs
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Number of live tasks carrying the tag
+    pub usage_count: i32,
+    /// Slugs that resolve to this tag, e.g. "rust-lang" for "rust"
+    pub aliases: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Tag {
+    pub const MAX_SLUG_LENGTH: usize = 64;
+
+    /// Mirrors the `slugify_tag` SQL function:
+    /// `"Rust Lang "` -> `"rust-lang"`, `"C++"` -> `"c++"`
+    pub fn slugify(raw: &str) -> String {
+        let mut slug = String::with_capacity(raw.len());
+        for c in raw.trim().to_lowercase().chars() {
+            if c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '+' | '#' | '.') {
+                slug.push(c);
+            } else if !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+
+        let slug = slug.trim_matches('-');
+        let end = slug.len().min(Self::MAX_SLUG_LENGTH);
+        slug[..end].trim_end_matches('-').to_string()
+    }
+}
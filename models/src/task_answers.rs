@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Mirrors the `answer_match_mode` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "answer_match_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerMatchMode {
+    /// Equal once surrounding whitespace is trimmed
+    Exact,
+    /// Equal ignoring case and runs of whitespace
+    Normalized,
+    /// The whole answer matches a regular expression
+    Regex,
+    /// A number within `tolerance` of the expected one
+    Numeric,
+    /// One of several acceptable answers
+    AnswerSet,
+}
+
+/// Mirrors the `answer_verdict` Postgres enum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, sqlx::Type)]
+#[sqlx(type_name = "answer_verdict", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerVerdict {
+    Correct,
+    Incorrect,
+}
+
+/// The hidden expected answer of a task; only ever shown to its author
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskAnswer {
+    pub task_id: Uuid,
+    pub mode: AnswerMatchMode,
+    /// The expected answer, or every acceptable one in `AnswerSet` mode
+    pub answers: Vec<String>,
+    /// Used by `Regex` and `AnswerSet`
+    pub case_insensitive: bool,
+    /// Used by `Numeric`
+    pub tolerance: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One check of a submitted version against the expected answer
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct AnswerAttempt {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    /// 1 for the user's first attempt at the task
+    pub attempt_number: i32,
+    pub verdict: AnswerVerdict,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A user's attempts at a task with an expected answer
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskAnswerProgress {
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub attempts: i32,
+    /// First correct attempt
+    pub solved_at: Option<DateTime<Utc>>,
+    pub last_verdict: Option<AnswerVerdict>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+}
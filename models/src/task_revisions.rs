@@ -0,0 +1,20 @@
+use crate::Difficulty;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// Immutable snapshot of a task, written by the database on every change
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskRevision {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Starts at 1 and matches `ProblemOrTask::current_revision` when current
+    pub revision: i32,
+    pub title: String,
+    pub content: String,
+    pub file_url: Option<String>,
+    pub tags: Vec<String>,
+    pub difficulty: Difficulty,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+/// An input / expected output pair code submissions are graded against
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TaskTestCase {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// 1-based order in which tests run, unique per task
+    pub position: i32,
+    /// Fed to the program on stdin
+    pub input: String,
+    pub expected_output: String,
+    pub time_limit_ms: i32,
+    pub memory_limit_kb: i32,
+    /// Sample cases are visible to everyone, the others only to the author
+    pub is_sample: bool,
+    /// Awarded when the case passes
+    pub points: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -20,4 +20,6 @@ pub struct User {
     pub email_verified_at: Option<DateTime<Utc>>,
     pub updated_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    /// Set while `avatar_url` points at an uploaded avatar
+    pub avatar_hash: Option<String>,
 }
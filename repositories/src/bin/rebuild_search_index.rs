@@ -0,0 +1,49 @@
+//! Rebuild the Tantivy search index from the database.
+//!
+//! ```text
+//! cargo run -p repositories --features tantivy --bin rebuild-search-index [INDEX_PATH]
+//! ```
+//!
+//! Reads `DATABASE_URL`, and `SEARCH_INDEX_PATH` when no path is given. A
+//! running `search-indexer` holds the index lock and rebuilds the index
+//! itself when it starts, so restart it instead, or point this at a new
+//! directory and swap it in.
+
+use repositories::repositories::TantivySearchRepository;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let Some(path) = env::args()
+        .nth(1)
+        .or_else(|| env::var("SEARCH_INDEX_PATH").ok())
+    else {
+        eprintln!("usage: rebuild-search-index [INDEX_PATH] (or set SEARCH_INDEX_PATH)");
+        return ExitCode::FAILURE;
+    };
+
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to the database");
+
+    let result = match TantivySearchRepository::open(&path) {
+        Ok(index) => index.rebuild(&pool).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(count) => {
+            println!("Indexed {} documents into {}", count, path);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to rebuild search index: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
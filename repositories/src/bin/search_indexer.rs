@@ -0,0 +1,69 @@
+//! Keep the Tantivy search index up to date.
+//!
+//! ```text
+//! cargo run -p repositories --features tantivy --bin search-indexer [INDEX_PATH]
+//! ```
+//!
+//! Reads `DATABASE_URL`, and `SEARCH_INDEX_PATH` when no path is given.
+//! Rebuilds the index from the database when it starts, then applies the
+//! task and submission changes the database queues, whichever process made
+//! them. It holds the index's writer lock, so run exactly one per index;
+//! API instances only read it.
+
+use repositories::repositories::TantivySearchRepository;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Changes applied per index commit
+const BATCH_SIZE: i64 = 500;
+
+/// How long to wait for new changes once the queue is empty
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let Some(path) = env::args()
+        .nth(1)
+        .or_else(|| env::var("SEARCH_INDEX_PATH").ok())
+    else {
+        eprintln!("usage: search-indexer [INDEX_PATH] (or set SEARCH_INDEX_PATH)");
+        return ExitCode::FAILURE;
+    };
+
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to the database");
+
+    let index = match TantivySearchRepository::open(&path) {
+        Ok(index) => index,
+        Err(e) => {
+            eprintln!("Failed to open search index {}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match index.rebuild(&pool).await {
+        Ok(count) => println!("Indexed {} documents into {}", count, path),
+        Err(e) => {
+            eprintln!("Failed to rebuild search index: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    loop {
+        match index.sync(&pool, BATCH_SIZE).await {
+            Ok(0) => tokio::time::sleep(POLL_INTERVAL).await,
+            Ok(_) => {}
+            Err(e) => {
+                // The batch stays queued and is retried
+                eprintln!("Failed to update search index: {}", e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
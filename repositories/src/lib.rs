@@ -1,2 +1,6 @@
+pub mod pagination;
+pub mod search_query;
+pub mod task_events;
+pub mod task_query;
 pub mod traits;
-pub mod repositories;
\ No newline at end of file
+pub mod repositories;
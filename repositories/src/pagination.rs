@@ -0,0 +1,200 @@
+//! Keyset (cursor) pagination shared by all list queries.
+//!
+//! Lists are ordered by `(created_at, id)`, so the position after the last
+//! row of a page is fully described by that pair. It is handed to clients as
+//! an opaque base64 cursor and turned back into a `WHERE (created_at, id) < ..`
+//! (or `>` for ascending lists) predicate on the next request. Lists sorted
+//! by another column (views, rating, ...) also carry that column's value as
+//! the cursor `rank` and page on `(rank, created_at, id)`.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use models::{
+    AnswerAttempt, Contest, FeedItem, GradingRun, ProblemOrTask, RatingFlag, ReputationEvent,
+    Submission, SubmissionComment, SubmissionCommentReply, SubmissionRating, SubmissionRevision,
+    TaskComment, TaskCommentReply, TaskRating, TaskRevision,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Position of a row in a `(created_at, id)` ordered list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+    pub rank: Option<f64>,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, id: Uuid) -> Self {
+        Self {
+            created_at,
+            id,
+            rank: None,
+        }
+    }
+
+    pub fn with_rank(mut self, rank: f64) -> Self {
+        self.rank = Some(rank);
+        self
+    }
+
+    pub fn encode(&self) -> String {
+        let mut raw = format!("{}:{}", self.created_at.timestamp_micros(), self.id);
+        if let Some(rank) = self.rank {
+            raw.push_str(&format!(":{}", rank));
+        }
+
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Returns `None` for anything that was not produced by [`Cursor::encode`]
+    pub fn decode(value: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let mut parts = raw.splitn(3, ':');
+
+        let created_at = DateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+        let id = Uuid::parse_str(parts.next()?).ok()?;
+        let rank = match parts.next() {
+            Some(rank) => Some(rank.parse::<f64>().ok().filter(|r| r.is_finite())?),
+            None => None,
+        };
+
+        Some(Self {
+            created_at,
+            id,
+            rank,
+        })
+    }
+}
+
+/// Which page of a list to fetch
+#[derive(Debug, Clone, Copy)]
+pub struct PageRequest {
+    pub limit: i64,
+    pub cursor: Option<Cursor>,
+}
+
+impl PageRequest {
+    pub const DEFAULT_LIMIT: i64 = 20;
+    pub const MAX_LIMIT: i64 = 100;
+
+    /// Build a page request, clamping the limit to `1..=MAX_LIMIT`
+    pub fn new(limit: Option<i64>, cursor: Option<Cursor>) -> Self {
+        Self {
+            limit: limit
+                .unwrap_or(Self::DEFAULT_LIMIT)
+                .clamp(1, Self::MAX_LIMIT),
+            cursor,
+        }
+    }
+
+    /// The first page with the given limit
+    pub fn first(limit: i64) -> Self {
+        Self::new(Some(limit), None)
+    }
+
+    pub fn cursor_created_at(&self) -> Option<DateTime<Utc>> {
+        self.cursor.map(|c| c.created_at)
+    }
+
+    pub fn cursor_id(&self) -> Option<Uuid> {
+        self.cursor.map(|c| c.id)
+    }
+
+    pub fn cursor_rank(&self) -> Option<f64> {
+        self.cursor.and_then(|c| c.rank)
+    }
+
+    /// One extra row is fetched to find out whether another page exists
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+/// One page of results plus the cursor for the next page, if any
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from rows fetched with [`PageRequest::fetch_limit`]
+    pub fn from_rows(rows: Vec<T>, page: &PageRequest) -> Self
+    where
+        T: Keyset,
+    {
+        Self::from_rows_by(rows, page, Keyset::cursor)
+    }
+
+    /// Like [`Page::from_rows`], with a custom cursor for the last row
+    pub fn from_rows_by(
+        mut rows: Vec<T>,
+        page: &PageRequest,
+        cursor: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let limit = page.limit as usize;
+        let next_cursor = if rows.len() > limit {
+            rows.truncate(limit);
+            rows.last().map(|row| cursor(row).encode())
+        } else {
+            None
+        };
+
+        Self {
+            items: rows,
+            next_cursor,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// Rows that can be paginated on `(created_at, id)`
+pub trait Keyset {
+    fn cursor(&self) -> Cursor;
+}
+
+macro_rules! impl_keyset {
+    ($($model:ty),* $(,)?) => {
+        $(
+            impl Keyset for $model {
+                fn cursor(&self) -> Cursor {
+                    Cursor::new(self.created_at, self.id)
+                }
+            }
+        )*
+    };
+}
+
+impl_keyset!(
+    AnswerAttempt,
+    Contest,
+    FeedItem,
+    GradingRun,
+    ProblemOrTask,
+    RatingFlag,
+    ReputationEvent,
+    Submission,
+    SubmissionComment,
+    SubmissionCommentReply,
+    SubmissionRating,
+    SubmissionRevision,
+    TaskComment,
+    TaskCommentReply,
+    TaskRating,
+    TaskRevision,
+);
@@ -0,0 +1,143 @@
+use crate::traits::ActivityRepositoryTrait;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use models::{ActivityDay, ActivityStreak, UserTimezone};
+use sqlx::{PgPool, query_as, query_scalar};
+use uuid::Uuid;
+
+pub struct ActivityRepository {
+    pool: PgPool,
+}
+
+impl ActivityRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ActivityRepositoryTrait for ActivityRepository {
+    async fn find_timezone(&self, user_id: Uuid) -> Result<Option<UserTimezone>, sqlx::Error> {
+        query_as!(
+            UserTimezone,
+            r#"
+            SELECT
+                id as "user_id",
+                timezone::TEXT as "timezone!",
+                (NOW() AT TIME ZONE timezone)::DATE as "today!"
+            FROM users
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn set_timezone(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+    ) -> Result<Option<UserTimezone>, sqlx::Error> {
+        query_as!(
+            UserTimezone,
+            r#"
+            UPDATE users
+            SET timezone = $2
+            WHERE id = $1
+            RETURNING
+                id as "user_id",
+                timezone::TEXT as "timezone!",
+                (NOW() AT TIME ZONE timezone)::DATE as "today!"
+            "#,
+            user_id,
+            timezone
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn is_known_timezone(&self, timezone: &str) -> Result<bool, sqlx::Error> {
+        query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM pg_timezone_names WHERE name = $1) as "known!""#,
+            timezone
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn find_days(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ActivityDay>, sqlx::Error> {
+        query_as!(
+            ActivityDay,
+            r#"
+            SELECT
+                (occurred_at AT TIME ZONE $2)::DATE as "day!",
+                COUNT(*) FILTER (WHERE kind = 'task') as "tasks!",
+                COUNT(*) FILTER (WHERE kind = 'submission') as "submissions!",
+                COUNT(*) FILTER (WHERE kind = 'comment') as "comments!",
+                COUNT(*) FILTER (WHERE kind = 'rating') as "ratings!"
+            FROM user_activity
+            WHERE user_id = $1
+                AND occurred_at >= $3::DATE::TIMESTAMP AT TIME ZONE $2
+                AND occurred_at < ($4::DATE + 1)::TIMESTAMP AT TIME ZONE $2
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            user_id,
+            timezone,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_streak(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+    ) -> Result<ActivityStreak, sqlx::Error> {
+        // Consecutive days minus their position is the same within a run,
+        // so each run is one group. The latest run is current while it
+        // reaches yesterday.
+        query_as!(
+            ActivityStreak,
+            r#"
+            WITH days AS (
+                SELECT DISTINCT (occurred_at AT TIME ZONE $2)::DATE AS day
+                FROM user_activity
+                WHERE user_id = $1
+            ),
+            runs AS (
+                SELECT MIN(day) AS started_on, MAX(day) AS ended_on, COUNT(*)::INTEGER AS length
+                FROM (
+                    SELECT day, day - (ROW_NUMBER() OVER (ORDER BY day))::INTEGER AS run
+                    FROM days
+                ) numbered
+                GROUP BY run
+            ),
+            current_run AS (
+                SELECT started_on, length FROM runs
+                WHERE ended_on >= (NOW() AT TIME ZONE $2)::DATE - 1
+                ORDER BY ended_on DESC
+                LIMIT 1
+            )
+            SELECT
+                COALESCE((SELECT length FROM current_run), 0) as "current!",
+                (SELECT started_on FROM current_run) as "current_since",
+                COALESCE((SELECT MAX(length) FROM runs), 0) as "longest!",
+                (SELECT MAX(ended_on) FROM runs) as "last_active_on"
+            "#,
+            user_id,
+            timezone
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,200 @@
+use crate::traits::BadgeRepositoryTrait;
+use async_trait::async_trait;
+use models::{Badge, BadgeCheck, BadgeEventType, BadgeRule, UserBadge};
+use sqlx::{PgPool, query, query_as, query_scalar};
+use uuid::Uuid;
+
+pub struct BadgeRepository {
+    pool: PgPool,
+}
+
+impl BadgeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BadgeRepositoryTrait for BadgeRepository {
+    async fn find_catalog(&self) -> Result<Vec<Badge>, sqlx::Error> {
+        query_as!(
+            Badge,
+            r#"
+            SELECT
+                badges.id,
+                badges.slug,
+                badges.name,
+                badges.description,
+                badges.rule as "rule: BadgeRule",
+                badges.threshold,
+                (SELECT COUNT(*) FROM user_badges WHERE badge_id = badges.id) as "holders!",
+                badges.created_at as "created_at!"
+            FROM badges
+            ORDER BY badges.rule, badges.threshold
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<UserBadge>, sqlx::Error> {
+        query_as!(
+            UserBadge,
+            r#"
+            SELECT
+                badges.id as "badge_id",
+                badges.slug,
+                badges.name,
+                badges.description,
+                badges.rule as "rule: BadgeRule",
+                badges.threshold,
+                user_badges.awarded_at
+            FROM user_badges
+            JOIN badges ON badges.id = user_badges.badge_id
+            WHERE user_badges.user_id = $1
+            ORDER BY user_badges.awarded_at DESC, badges.rule, badges.threshold
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn take_checks(&self, limit: i64) -> Result<Vec<BadgeCheck>, sqlx::Error> {
+        query_as!(
+            BadgeCheck,
+            r#"
+            DELETE FROM badge_checks
+            WHERE id IN (
+                SELECT id FROM badge_checks
+                ORDER BY id
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, user_id, event as "event: BadgeEventType", occurred_at
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|mut checks| {
+            checks.sort_by_key(|check| check.id);
+            checks
+        })
+    }
+
+    async fn queue_recheck_all(&self) -> Result<u64, sqlx::Error> {
+        let result =
+            query!("INSERT INTO badge_checks (user_id, event) SELECT id, 'recheck' FROM users")
+                .execute(&self.pool)
+                .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn count(&self, user_id: Uuid, rule: BadgeRule) -> Result<i64, sqlx::Error> {
+        let count = match rule {
+            BadgeRule::SubmissionsMade => {
+                query_scalar!(
+                    r#"
+                    SELECT COUNT(*) as "count!" FROM submissions
+                    WHERE user_id = $1 AND status <> 'draft' AND deleted_at IS NULL
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::FeaturedSubmissions => {
+                query_scalar!(
+                    r#"
+                    SELECT COUNT(*) as "count!" FROM submissions
+                    WHERE user_id = $1 AND is_featured AND deleted_at IS NULL
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::TasksAuthored => {
+                query_scalar!(
+                    r#"SELECT COUNT(*) as "count!" FROM problems_or_tasks WHERE user_id = $1"#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::TasksSolved => {
+                query_scalar!(
+                    r#"
+                    SELECT COUNT(DISTINCT task_id) as "count!" FROM accepted_solutions
+                    WHERE user_id = $1
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::CommentsPosted => {
+                query_scalar!(
+                    r#"
+                    SELECT COUNT(*) as "count!" FROM user_activity
+                    WHERE user_id = $1 AND kind = 'comment'
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::RatingsGiven => {
+                query_scalar!(
+                    r#"
+                    SELECT COUNT(*) as "count!" FROM user_activity
+                    WHERE user_id = $1 AND kind = 'rating'
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+            BadgeRule::ActivityStreak => {
+                // Consecutive days minus their position is the same within
+                // a run, so each run is one group
+                query_scalar!(
+                    r#"
+                    WITH days AS (
+                        SELECT DISTINCT (activity.occurred_at AT TIME ZONE users.timezone)::DATE AS day
+                        FROM user_activity activity
+                        JOIN users ON users.id = activity.user_id
+                        WHERE activity.user_id = $1
+                    ),
+                    runs AS (
+                        SELECT day - (ROW_NUMBER() OVER (ORDER BY day))::INTEGER AS run
+                        FROM days
+                    )
+                    SELECT COALESCE(MAX(length), 0) as "count!"
+                    FROM (SELECT COUNT(*) AS length FROM runs GROUP BY run) lengths
+                    "#,
+                    user_id
+                )
+                .fetch_one(&self.pool)
+                .await?
+            }
+        };
+        Ok(count)
+    }
+
+    async fn award(&self, user_id: Uuid, badge_ids: &[Uuid]) -> Result<Vec<Uuid>, sqlx::Error> {
+        query_scalar!(
+            r#"
+            INSERT INTO user_badges (user_id, badge_id)
+            SELECT $1, badge_id FROM UNNEST($2::UUID[]) AS badge_id
+            ON CONFLICT (user_id, badge_id) DO NOTHING
+            RETURNING badge_id
+            "#,
+            user_id,
+            badge_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,123 @@
+use crate::traits::BlobRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::Blob;
+use sqlx::{PgPool, Postgres, Transaction, query, query_as};
+use uuid::Uuid;
+
+/// A blob row deleted by a transaction that is still open
+///
+/// Uploads of the same content wait on the row until the deletion is
+/// committed or dropped, so delete the stored bytes before `commit`; dropping
+/// it rolls the deletion back.
+pub struct BlobDeletion {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl BlobDeletion {
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
+    }
+}
+
+pub struct BlobRepository {
+    pool: PgPool,
+}
+
+impl BlobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BlobRepositoryTrait for BlobRepository {
+    async fn create(
+        &self,
+        sha256: &str,
+        content_type: &str,
+        size_bytes: i64,
+        uploaded_by: Uuid,
+    ) -> Result<Blob, sqlx::Error> {
+        // Uploading the content again restarts its grace period
+        query_as!(
+            Blob,
+            r#"
+            INSERT INTO blobs (sha256, content_type, size_bytes, uploaded_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (sha256) DO UPDATE SET last_uploaded_at = NOW()
+            RETURNING
+                id, sha256, content_type, size_bytes, uploaded_by,
+                created_at as "created_at!: DateTime<Utc>", last_uploaded_at
+            "#,
+            sha256,
+            content_type,
+            size_bytes,
+            uploaded_by
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn find_by_sha256(&self, sha256: &str) -> Result<Option<Blob>, sqlx::Error> {
+        query_as!(
+            Blob,
+            r#"
+            SELECT
+                id, sha256, content_type, size_bytes, uploaded_by,
+                created_at as "created_at!: DateTime<Utc>", last_uploaded_at
+            FROM blobs
+            WHERE sha256 = $1
+            "#,
+            sha256
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_orphans(
+        &self,
+        older_than: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Blob>, sqlx::Error> {
+        query_as!(
+            Blob,
+            r#"
+            SELECT
+                id, sha256, content_type, size_bytes, uploaded_by,
+                created_at as "created_at!: DateTime<Utc>", last_uploaded_at
+            FROM blobs
+            WHERE last_uploaded_at < $1 AND NOT blob_is_referenced(sha256)
+            ORDER BY last_uploaded_at, id
+            LIMIT $2
+            "#,
+            older_than,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn delete_if_orphaned(
+        &self,
+        sha256: &str,
+        older_than: DateTime<Utc>,
+    ) -> Result<Option<BlobDeletion>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let deleted = query!(
+            r#"
+            DELETE FROM blobs
+            WHERE sha256 = $1 AND last_uploaded_at < $2 AND NOT blob_is_referenced(sha256)
+            "#,
+            sha256,
+            older_than
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+
+        Ok(deleted.then_some(BlobDeletion { tx }))
+    }
+}
@@ -0,0 +1,432 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::{ContestRepositoryTrait, NewContest, NewContestTask};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{
+    AnswerVerdict, Contest, ContestAttempt, ContestParticipant, ContestScoring, ContestTask,
+    GradingVerdict,
+};
+use sqlx::{PgConnection, PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct ContestRepository {
+    pool: PgPool,
+}
+
+impl ContestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Insert the tasks of a contest, numbered in the given order
+    async fn insert_tasks(
+        conn: &mut PgConnection,
+        contest_id: Uuid,
+        tasks: Vec<NewContestTask>,
+    ) -> Result<(), sqlx::Error> {
+        let task_ids: Vec<Uuid> = tasks.iter().map(|t| t.task_id).collect();
+        let points: Vec<Option<i32>> = tasks.iter().map(|t| t.points).collect();
+
+        query!(
+            r#"
+            INSERT INTO contest_tasks (contest_id, task_id, position, points)
+            SELECT $1, t.task_id, t.position::INTEGER, COALESCE(t.points, 100)
+            FROM UNNEST($2::UUID[], $3::INTEGER[]) WITH ORDINALITY AS t(task_id, points, position)
+            "#,
+            contest_id,
+            &task_ids,
+            &points as &[Option<i32>]
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ContestRepositoryTrait for ContestRepository {
+    async fn create(
+        &self,
+        contest: NewContest,
+        tasks: Vec<NewContestTask>,
+    ) -> Result<Contest, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = query_as!(
+            Contest,
+            r#"
+            INSERT INTO contests (
+                id, created_by, title, description, scoring, starts_at, ends_at,
+                penalty_minutes, freeze_minutes
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, 20), COALESCE($9, 60))
+            RETURNING
+                id, created_by, title, description,
+                scoring as "scoring: ContestScoring",
+                starts_at, ends_at, penalty_minutes, freeze_minutes, results_published_at,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            contest.created_by,
+            contest.title,
+            contest.description,
+            contest.scoring as ContestScoring,
+            contest.starts_at,
+            contest.ends_at,
+            contest.penalty_minutes,
+            contest.freeze_minutes
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_tasks(&mut tx, created.id, tasks).await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Contest>, sqlx::Error> {
+        query_as!(
+            Contest,
+            r#"
+            SELECT
+                id, created_by, title, description,
+                scoring as "scoring: ContestScoring",
+                starts_at, ends_at, penalty_minutes, freeze_minutes, results_published_at,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM contests
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_all(&self, page: PageRequest) -> Result<Page<Contest>, sqlx::Error> {
+        query_as!(
+            Contest,
+            r#"
+            SELECT
+                id, created_by, title, description,
+                scoring as "scoring: ContestScoring",
+                starts_at, ends_at, penalty_minutes, freeze_minutes, results_published_at,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM contests
+            WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+    ) -> Result<Option<Contest>, sqlx::Error> {
+        query_as!(
+            Contest,
+            r#"
+            SELECT
+                c.id, c.created_by, c.title, c.description,
+                c.scoring as "scoring: ContestScoring",
+                c.starts_at, c.ends_at, c.penalty_minutes, c.freeze_minutes,
+                c.results_published_at,
+                c.created_at as "created_at!: DateTime<Utc>",
+                c.updated_at as "updated_at!: DateTime<Utc>"
+            FROM contests c
+            JOIN contest_submissions cs ON cs.contest_id = c.id
+            WHERE cs.submission_id = $1
+            "#,
+            submission_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        title: Option<String>,
+        description: Option<String>,
+        scoring: Option<ContestScoring>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+        penalty_minutes: Option<i32>,
+        freeze_minutes: Option<i32>,
+    ) -> Result<Contest, sqlx::Error> {
+        query_as!(
+            Contest,
+            r#"
+            UPDATE contests
+            SET
+                title = COALESCE($2, title),
+                description = COALESCE($3, description),
+                scoring = COALESCE($4, scoring),
+                starts_at = COALESCE($5, starts_at),
+                ends_at = COALESCE($6, ends_at),
+                penalty_minutes = COALESCE($7, penalty_minutes),
+                freeze_minutes = COALESCE($8, freeze_minutes)
+            WHERE id = $1
+            RETURNING
+                id, created_by, title, description,
+                scoring as "scoring: ContestScoring",
+                starts_at, ends_at, penalty_minutes, freeze_minutes, results_published_at,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            title,
+            description,
+            scoring as Option<ContestScoring>,
+            starts_at,
+            ends_at,
+            penalty_minutes,
+            freeze_minutes
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn publish_results(&self, id: Uuid) -> Result<Contest, sqlx::Error> {
+        query_as!(
+            Contest,
+            r#"
+            UPDATE contests
+            SET results_published_at = COALESCE(results_published_at, NOW())
+            WHERE id = $1
+            RETURNING
+                id, created_by, title, description,
+                scoring as "scoring: ContestScoring",
+                starts_at, ends_at, penalty_minutes, freeze_minutes, results_published_at,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        query!("DELETE FROM contests WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_tasks(&self, contest_id: Uuid) -> Result<Vec<ContestTask>, sqlx::Error> {
+        query_as!(
+            ContestTask,
+            r#"
+            SELECT contest_id, task_id, position, points
+            FROM contest_tasks
+            WHERE contest_id = $1
+            ORDER BY position
+            "#,
+            contest_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn set_tasks(
+        &self,
+        contest_id: Uuid,
+        tasks: Vec<NewContestTask>,
+    ) -> Result<Vec<ContestTask>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        query!(
+            "SELECT id FROM contests WHERE id = $1 FOR UPDATE",
+            contest_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        query!(
+            "DELETE FROM contest_tasks WHERE contest_id = $1",
+            contest_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        Self::insert_tasks(&mut tx, contest_id, tasks).await?;
+
+        tx.commit().await?;
+        self.find_tasks(contest_id).await
+    }
+
+    async fn register(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ContestParticipant, sqlx::Error> {
+        query!(
+            r#"
+            INSERT INTO contest_participants (contest_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (contest_id, user_id) DO NOTHING
+            "#,
+            contest_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.find_participant(contest_id, user_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn unregister(&self, contest_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error> {
+        query!(
+            "DELETE FROM contest_participants WHERE contest_id = $1 AND user_id = $2",
+            contest_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_participant(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ContestParticipant>, sqlx::Error> {
+        query_as!(
+            ContestParticipant,
+            r#"
+            SELECT contest_id, user_id, registered_at
+            FROM contest_participants
+            WHERE contest_id = $1 AND user_id = $2
+            "#,
+            contest_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_participants(
+        &self,
+        contest_id: Uuid,
+    ) -> Result<Vec<ContestParticipant>, sqlx::Error> {
+        query_as!(
+            ContestParticipant,
+            r#"
+            SELECT contest_id, user_id, registered_at
+            FROM contest_participants
+            WHERE contest_id = $1
+            ORDER BY registered_at, user_id
+            "#,
+            contest_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_submission_id(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        query!(
+            r#"
+            SELECT submission_id
+            FROM contest_submissions
+            WHERE contest_id = $1 AND user_id = $2 AND task_id = $3
+            "#,
+            contest_id,
+            user_id,
+            task_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map(|row| row.map(|row| row.submission_id))
+    }
+
+    async fn link_submission(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+        task_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        query!(
+            r#"
+            INSERT INTO contest_submissions (submission_id, contest_id, user_id, task_id)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            submission_id,
+            contest_id,
+            user_id,
+            task_id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn find_attempts(&self, contest_id: Uuid) -> Result<Vec<ContestAttempt>, sqlx::Error> {
+        query_as!(
+            ContestAttempt,
+            r#"
+            SELECT
+                contest_id as "contest_id!",
+                user_id as "user_id!",
+                task_id as "task_id!",
+                submission_id as "submission_id!",
+                submission_version as "submission_version!",
+                submitted_at as "submitted_at!: DateTime<Utc>",
+                grading_verdict as "grading_verdict: GradingVerdict",
+                score, max_score,
+                answer_verdict as "answer_verdict: AnswerVerdict"
+            FROM contest_attempts
+            WHERE contest_id = $1
+            ORDER BY submitted_at, submission_id
+            "#,
+            contest_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_attempts_by_user(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<ContestAttempt>, sqlx::Error> {
+        query_as!(
+            ContestAttempt,
+            r#"
+            SELECT
+                contest_id as "contest_id!",
+                user_id as "user_id!",
+                task_id as "task_id!",
+                submission_id as "submission_id!",
+                submission_version as "submission_version!",
+                submitted_at as "submitted_at!: DateTime<Utc>",
+                grading_verdict as "grading_verdict: GradingVerdict",
+                score, max_score,
+                answer_verdict as "answer_verdict: AnswerVerdict"
+            FROM contest_attempts
+            WHERE contest_id = $1 AND user_id = $2
+            ORDER BY submitted_at, submission_id
+            "#,
+            contest_id,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,659 @@
+//! Repository wrappers that report writes to a task's comments, replies,
+//! ratings and submissions as live task events.
+//!
+//! Events are published once the write has succeeded and carry only what any
+//! viewer of the task may see: drafts are never announced and submission
+//! events hold the status, not the content. A failed lookup while building an
+//! event is logged and the event dropped; the write still succeeds.
+
+use crate::pagination::{Page, PageRequest};
+use crate::task_events::{TaskEventKind, TaskEventPublisher};
+use crate::traits::{
+    SubmissionRepositoryTrait, TaskCommentReplyRepositoryTrait, TaskCommentRepositoryTrait,
+    TaskRatingRepositoryTrait,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{
+    ProgrammingLanguage, RatingActivity, RatingCount, RatingInterval, RatingOrigin, Submission,
+    SubmissionStatus, TaskComment, TaskCommentReply, TaskRating,
+};
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn log_event_error(result: Result<(), sqlx::Error>, kind: TaskEventKind, id: Uuid) {
+    if let Err(e) = result {
+        tracing::warn!("Failed to build {} event for {}: {}", kind, id, e);
+    }
+}
+
+pub struct EventPublishingTaskCommentRepository {
+    inner: Arc<dyn TaskCommentRepositoryTrait>,
+    events: Arc<dyn TaskEventPublisher>,
+}
+
+impl EventPublishingTaskCommentRepository {
+    pub fn new(
+        inner: Arc<dyn TaskCommentRepositoryTrait>,
+        events: Arc<dyn TaskEventPublisher>,
+    ) -> Self {
+        Self { inner, events }
+    }
+
+    async fn publish_comment(
+        &self,
+        kind: TaskEventKind,
+        comment: &TaskComment,
+    ) -> Result<(), sqlx::Error> {
+        let comment_count = self.inner.count_by_task(comment.task_id).await?;
+        self.events
+            .publish(
+                comment.task_id,
+                kind,
+                json!({ "comment": comment, "comment_count": comment_count }),
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn publish_removal(
+        &self,
+        kind: TaskEventKind,
+        comment: Option<TaskComment>,
+    ) -> Result<(), sqlx::Error> {
+        let Some(comment) = comment else {
+            return Ok(());
+        };
+        let comment_count = self.inner.count_by_task(comment.task_id).await?;
+        self.events
+            .publish(
+                comment.task_id,
+                kind,
+                json!({ "comment_id": comment.id, "comment_count": comment_count }),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskCommentRepositoryTrait for EventPublishingTaskCommentRepository {
+    async fn create(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+        comment: String,
+    ) -> Result<TaskComment, sqlx::Error> {
+        let comment = self.inner.create(task_id, user_id, comment).await?;
+
+        let kind = TaskEventKind::CommentCreated;
+        log_event_error(self.publish_comment(kind, &comment).await, kind, comment.id);
+        Ok(comment)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskComment>, sqlx::Error> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskComment>, sqlx::Error> {
+        self.inner.find_by_task(task_id, page).await
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskComment>, sqlx::Error> {
+        self.inner.find_by_user(user_id, page).await
+    }
+
+    async fn find_all(&self, page: PageRequest) -> Result<Page<TaskComment>, sqlx::Error> {
+        self.inner.find_all(page).await
+    }
+
+    async fn count_by_task(&self, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        self.inner.count_by_task(task_id).await
+    }
+
+    async fn update(&self, id: Uuid, comment: String) -> Result<TaskComment, sqlx::Error> {
+        let comment = self.inner.update(id, comment).await?;
+
+        let kind = TaskEventKind::CommentUpdated;
+        log_event_error(self.publish_comment(kind, &comment).await, kind, id);
+        Ok(comment)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.delete(id).await?;
+
+        let kind = TaskEventKind::CommentDeleted;
+        let comment = self.inner.find_by_id(id).await;
+        log_event_error(
+            async { self.publish_removal(kind, comment?).await }.await,
+            kind,
+            id,
+        );
+        Ok(())
+    }
+
+    async fn remove(&self, id: Uuid, moderator_id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.remove(id, moderator_id).await?;
+
+        let kind = TaskEventKind::CommentDeleted;
+        let comment = self.inner.find_by_id(id).await;
+        log_event_error(
+            async { self.publish_removal(kind, comment?).await }.await,
+            kind,
+            id,
+        );
+        Ok(())
+    }
+
+    async fn hard_delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let comment = self.inner.find_by_id(id).await;
+        self.inner.hard_delete(id).await?;
+
+        let kind = TaskEventKind::CommentDeleted;
+        log_event_error(
+            async { self.publish_removal(kind, comment?).await }.await,
+            kind,
+            id,
+        );
+        Ok(())
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.restore(id).await?;
+
+        let kind = TaskEventKind::CommentRestored;
+        let result = async {
+            match self.inner.find_by_id(id).await? {
+                Some(comment) => self.publish_comment(kind, &comment).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+}
+
+pub struct EventPublishingTaskCommentReplyRepository {
+    inner: Arc<dyn TaskCommentReplyRepositoryTrait>,
+    comments: Arc<dyn TaskCommentRepositoryTrait>,
+    events: Arc<dyn TaskEventPublisher>,
+}
+
+impl EventPublishingTaskCommentReplyRepository {
+    /// `comments` is used to look up the task each reply belongs to
+    pub fn new(
+        inner: Arc<dyn TaskCommentReplyRepositoryTrait>,
+        comments: Arc<dyn TaskCommentRepositoryTrait>,
+        events: Arc<dyn TaskEventPublisher>,
+    ) -> Self {
+        Self {
+            inner,
+            comments,
+            events,
+        }
+    }
+
+    /// Publish `kind` for `reply`; with `include_reply` the whole reply is
+    /// sent, otherwise only its id
+    async fn publish_reply(
+        &self,
+        kind: TaskEventKind,
+        reply: &TaskCommentReply,
+        include_reply: bool,
+    ) -> Result<(), sqlx::Error> {
+        let Some(comment) = self.comments.find_by_id(reply.task_comment_id).await? else {
+            return Ok(());
+        };
+        let reply_count = self.inner.count_by_comment(comment.id).await?;
+        let data = if include_reply {
+            json!({ "comment_id": comment.id, "reply": reply, "reply_count": reply_count })
+        } else {
+            json!({ "comment_id": comment.id, "reply_id": reply.id, "reply_count": reply_count })
+        };
+        self.events.publish(comment.task_id, kind, data).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskCommentReplyRepositoryTrait for EventPublishingTaskCommentReplyRepository {
+    async fn create(
+        &self,
+        task_comment_id: Uuid,
+        user_id: Uuid,
+        reply: String,
+    ) -> Result<TaskCommentReply, sqlx::Error> {
+        let reply = self.inner.create(task_comment_id, user_id, reply).await?;
+
+        let kind = TaskEventKind::ReplyCreated;
+        log_event_error(self.publish_reply(kind, &reply, true).await, kind, reply.id);
+        Ok(reply)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskCommentReply>, sqlx::Error> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_comment(
+        &self,
+        task_comment_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error> {
+        self.inner.find_by_comment(task_comment_id, page).await
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error> {
+        self.inner.find_by_user(user_id, page).await
+    }
+
+    async fn find_all(&self, page: PageRequest) -> Result<Page<TaskCommentReply>, sqlx::Error> {
+        self.inner.find_all(page).await
+    }
+
+    async fn count_by_comment(&self, task_comment_id: Uuid) -> Result<i64, sqlx::Error> {
+        self.inner.count_by_comment(task_comment_id).await
+    }
+
+    async fn update(&self, id: Uuid, reply: String) -> Result<TaskCommentReply, sqlx::Error> {
+        let reply = self.inner.update(id, reply).await?;
+
+        let kind = TaskEventKind::ReplyUpdated;
+        log_event_error(self.publish_reply(kind, &reply, true).await, kind, id);
+        Ok(reply)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.delete(id).await?;
+
+        let kind = TaskEventKind::ReplyDeleted;
+        let result = async {
+            match self.inner.find_by_id(id).await? {
+                Some(reply) => self.publish_reply(kind, &reply, false).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+
+    async fn hard_delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let reply = self.inner.find_by_id(id).await;
+        self.inner.hard_delete(id).await?;
+
+        let kind = TaskEventKind::ReplyDeleted;
+        let result = async {
+            match reply? {
+                Some(reply) => self.publish_reply(kind, &reply, false).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+
+    async fn restore(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.restore(id).await?;
+
+        let kind = TaskEventKind::ReplyRestored;
+        let result = async {
+            match self.inner.find_by_id(id).await? {
+                Some(reply) => self.publish_reply(kind, &reply, true).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+}
+
+pub struct EventPublishingTaskRatingRepository {
+    inner: Arc<dyn TaskRatingRepositoryTrait>,
+    events: Arc<dyn TaskEventPublisher>,
+}
+
+impl EventPublishingTaskRatingRepository {
+    pub fn new(
+        inner: Arc<dyn TaskRatingRepositoryTrait>,
+        events: Arc<dyn TaskEventPublisher>,
+    ) -> Self {
+        Self { inner, events }
+    }
+
+    /// Publish the task's new rating summary; who rated is not included
+    async fn publish_summary(&self, task_id: Uuid) -> Result<(), sqlx::Error> {
+        let average_rating = self.inner.get_average_rating(task_id).await?;
+        let rating_count = self.inner.get_rating_count(task_id).await?;
+        self.events
+            .publish(
+                task_id,
+                TaskEventKind::RatingChanged,
+                json!({ "average_rating": average_rating, "rating_count": rating_count }),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TaskRatingRepositoryTrait for EventPublishingTaskRatingRepository {
+    async fn create(
+        &self,
+        task_id: Uuid,
+        rater_id: Uuid,
+        rating_value: i32,
+        origin: &RatingOrigin,
+    ) -> Result<TaskRating, sqlx::Error> {
+        let rating = self
+            .inner
+            .create(task_id, rater_id, rating_value, origin)
+            .await?;
+
+        let kind = TaskEventKind::RatingChanged;
+        log_event_error(self.publish_summary(task_id).await, kind, task_id);
+        Ok(rating)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskRating>, sqlx::Error> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error> {
+        self.inner.find_by_task(task_id, page).await
+    }
+
+    async fn find_by_rater(
+        &self,
+        rater_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error> {
+        self.inner.find_by_rater(rater_id, page).await
+    }
+
+    async fn find_by_task_and_rater(
+        &self,
+        task_id: Uuid,
+        rater_id: Uuid,
+    ) -> Result<Option<TaskRating>, sqlx::Error> {
+        self.inner.find_by_task_and_rater(task_id, rater_id).await
+    }
+
+    async fn get_average_rating(&self, task_id: Uuid) -> Result<Option<f64>, sqlx::Error> {
+        self.inner.get_average_rating(task_id).await
+    }
+
+    async fn get_rating_count(&self, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        self.inner.get_rating_count(task_id).await
+    }
+
+    async fn get_distribution(&self, task_id: Uuid) -> Result<Vec<RatingCount>, sqlx::Error> {
+        self.inner.get_distribution(task_id).await
+    }
+
+    async fn get_activity(
+        &self,
+        task_id: Uuid,
+        interval: RatingInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RatingActivity>, sqlx::Error> {
+        self.inner.get_activity(task_id, interval, from, to).await
+    }
+
+    async fn update_rating(&self, id: Uuid, rating_value: i32) -> Result<TaskRating, sqlx::Error> {
+        let rating = self.inner.update_rating(id, rating_value).await?;
+
+        let kind = TaskEventKind::RatingChanged;
+        log_event_error(
+            self.publish_summary(rating.task_id).await,
+            kind,
+            rating.task_id,
+        );
+        Ok(rating)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let rating = self.inner.find_by_id(id).await;
+        self.inner.delete(id).await?;
+
+        let kind = TaskEventKind::RatingChanged;
+        let result = async {
+            match rating? {
+                Some(rating) => self.publish_summary(rating.task_id).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+
+    async fn delete_by_task_and_rater(
+        &self,
+        task_id: Uuid,
+        rater_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        self.inner
+            .delete_by_task_and_rater(task_id, rater_id)
+            .await?;
+
+        let kind = TaskEventKind::RatingChanged;
+        log_event_error(self.publish_summary(task_id).await, kind, task_id);
+        Ok(())
+    }
+}
+
+pub struct EventPublishingSubmissionRepository {
+    inner: Arc<dyn SubmissionRepositoryTrait>,
+    events: Arc<dyn TaskEventPublisher>,
+}
+
+impl EventPublishingSubmissionRepository {
+    pub fn new(
+        inner: Arc<dyn SubmissionRepositoryTrait>,
+        events: Arc<dyn TaskEventPublisher>,
+    ) -> Self {
+        Self { inner, events }
+    }
+
+    /// Publish `kind` for `submission` unless it is a draft, which only its
+    /// owner may know about
+    async fn publish_submission(
+        &self,
+        kind: TaskEventKind,
+        submission: &Submission,
+    ) -> Result<(), sqlx::Error> {
+        if submission.status == SubmissionStatus::Draft {
+            return Ok(());
+        }
+        let submission_count = self.inner.count_by_task(submission.task_id).await?;
+        self.events
+            .publish(
+                submission.task_id,
+                kind,
+                json!({
+                    "submission_id": submission.id,
+                    "status": submission.status,
+                    "submission_count": submission_count,
+                }),
+            )
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubmissionRepositoryTrait for EventPublishingSubmissionRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        content: String,
+        file_url: Option<String>,
+        status: Option<SubmissionStatus>,
+        average_rating: Decimal,
+        total_ratings: i32,
+        is_featured: bool,
+        submitted_at: Option<DateTime<Utc>>,
+        language: Option<ProgrammingLanguage>,
+    ) -> Result<Submission, sqlx::Error> {
+        let submission = self
+            .inner
+            .create(
+                user_id,
+                task_id,
+                content,
+                file_url,
+                status,
+                average_rating,
+                total_ratings,
+                is_featured,
+                submitted_at,
+                language,
+            )
+            .await?;
+
+        let kind = TaskEventKind::SubmissionCreated;
+        log_event_error(
+            self.publish_submission(kind, &submission).await,
+            kind,
+            submission.id,
+        );
+        Ok(submission)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Submission>, sqlx::Error> {
+        self.inner.find_by_id(id).await
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
+        self.inner.find_by_user(user_id, page).await
+    }
+
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
+        self.inner.find_by_task(task_id, page).await
+    }
+
+    async fn find_all(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error> {
+        self.inner.find_all(page).await
+    }
+
+    async fn find_by_status(
+        &self,
+        status: SubmissionStatus,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
+        self.inner.find_by_status(status, page).await
+    }
+
+    async fn find_featured(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error> {
+        self.inner.find_featured(page).await
+    }
+
+    async fn count_by_task(&self, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        self.inner.count_by_task(task_id).await
+    }
+
+    async fn update_status(&self, id: Uuid, status: SubmissionStatus) -> Result<(), sqlx::Error> {
+        self.inner.update_status(id, status).await?;
+
+        let kind = TaskEventKind::SubmissionUpdated;
+        let result = async {
+            match self.inner.find_by_id(id).await? {
+                Some(submission) => self.publish_submission(kind, &submission).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+
+    async fn update_submission(
+        &self,
+        id: Uuid,
+        content: Option<String>,
+        file_url: Option<String>,
+        status: Option<SubmissionStatus>,
+        is_featured: Option<bool>,
+        average_rating: Option<Decimal>,
+        total_ratings: Option<i32>,
+        language: Option<ProgrammingLanguage>,
+    ) -> Result<Submission, sqlx::Error> {
+        let submission = self
+            .inner
+            .update_submission(
+                id,
+                content,
+                file_url,
+                status,
+                is_featured,
+                average_rating,
+                total_ratings,
+                language,
+            )
+            .await?;
+
+        let kind = TaskEventKind::SubmissionUpdated;
+        log_event_error(self.publish_submission(kind, &submission).await, kind, id);
+        Ok(submission)
+    }
+
+    async fn resubmit(
+        &self,
+        id: Uuid,
+        content: String,
+        file_url: Option<String>,
+        language: Option<ProgrammingLanguage>,
+    ) -> Result<Submission, sqlx::Error> {
+        let submission = self.inner.resubmit(id, content, file_url, language).await?;
+
+        let kind = TaskEventKind::SubmissionUpdated;
+        log_event_error(self.publish_submission(kind, &submission).await, kind, id);
+        Ok(submission)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        self.inner.delete(id).await?;
+
+        let kind = TaskEventKind::SubmissionDeleted;
+        let result = async {
+            match self.inner.find_by_id(id).await? {
+                Some(submission) => self.publish_submission(kind, &submission).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        log_event_error(result, kind, id);
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::FeedRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{FeedEventKind, FeedItem};
+use sqlx::{PgPool, query_as, query_scalar};
+use uuid::Uuid;
+
+pub struct FeedRepository {
+    pool: PgPool,
+}
+
+impl FeedRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FeedRepositoryTrait for FeedRepository {
+    async fn find_feed(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<FeedItem>, sqlx::Error> {
+        query_as!(
+            FeedItem,
+            r#"
+            SELECT
+                feed.id as "id!",
+                feed.kind as "kind!: FeedEventKind",
+                feed.actor_id as "actor_id!",
+                users.display_name as "actor_name?",
+                feed.task_id as "task_id!",
+                tasks.title as "task_title?",
+                feed.submission_id,
+                feed.created_at as "created_at!: DateTime<Utc>",
+                feed.created_at > COALESCE(
+                    (SELECT read_until FROM feed_reads WHERE user_id = $1),
+                    '-infinity'
+                ) as "unread!"
+            FROM user_feed($1) feed
+            LEFT JOIN users ON users.id = feed.actor_id
+            LEFT JOIN problems_or_tasks tasks ON tasks.id = feed.task_id
+            WHERE $2::timestamptz IS NULL OR (feed.created_at, feed.id) < ($2, $3::uuid)
+            ORDER BY feed.created_at DESC, feed.id DESC
+            LIMIT $4
+            "#,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn count_unread(&self, user_id: Uuid, cap: i64) -> Result<i64, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM (
+                SELECT 1
+                FROM user_feed($1) feed
+                WHERE feed.created_at > COALESCE(
+                    (SELECT read_until FROM feed_reads WHERE user_id = $1),
+                    '-infinity'
+                )
+                LIMIT $2
+            ) unread
+            "#,
+            user_id,
+            cap
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn find_read_until(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        query_scalar!(
+            r#"SELECT read_until FROM feed_reads WHERE user_id = $1"#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn mark_read(
+        &self,
+        user_id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, sqlx::Error> {
+        query_scalar!(
+            r#"
+            INSERT INTO feed_reads (user_id, read_until)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id)
+            DO UPDATE SET read_until = GREATEST(feed_reads.read_until, EXCLUDED.read_until)
+            RETURNING read_until
+            "#,
+            user_id,
+            until
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+}
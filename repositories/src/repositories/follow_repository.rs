@@ -0,0 +1,201 @@
+use crate::pagination::{Cursor, Page, PageRequest};
+use crate::traits::FollowRepositoryTrait;
+use async_trait::async_trait;
+use models::{TaskFollow, UserFollow};
+use sqlx::{PgPool, query_as};
+use uuid::Uuid;
+
+pub struct FollowRepository {
+    pool: PgPool,
+}
+
+impl FollowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FollowRepositoryTrait for FollowRepository {
+    async fn follow_user(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<UserFollow, sqlx::Error> {
+        query_as!(
+            UserFollow,
+            r#"
+            INSERT INTO user_follows (follower_id, followee_id)
+            VALUES ($1, $2)
+            ON CONFLICT (follower_id, followee_id)
+            DO UPDATE SET created_at = user_follows.created_at
+            RETURNING follower_id, followee_id, created_at
+            "#,
+            follower_id,
+            followee_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn unfollow_user(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<Option<UserFollow>, sqlx::Error> {
+        query_as!(
+            UserFollow,
+            r#"
+            DELETE FROM user_follows
+            WHERE follower_id = $1 AND followee_id = $2
+            RETURNING follower_id, followee_id, created_at
+            "#,
+            follower_id,
+            followee_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_followers(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<UserFollow>, sqlx::Error> {
+        query_as!(
+            UserFollow,
+            r#"
+            SELECT follower_id, followee_id, created_at
+            FROM user_follows
+            WHERE followee_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, follower_id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, follower_id DESC
+            LIMIT $4
+            "#,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| {
+            Page::from_rows_by(rows, &page, |follow| {
+                Cursor::new(follow.created_at, follow.follower_id)
+            })
+        })
+    }
+
+    async fn find_following(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<UserFollow>, sqlx::Error> {
+        query_as!(
+            UserFollow,
+            r#"
+            SELECT follower_id, followee_id, created_at
+            FROM user_follows
+            WHERE follower_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, followee_id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, followee_id DESC
+            LIMIT $4
+            "#,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| {
+            Page::from_rows_by(rows, &page, |follow| {
+                Cursor::new(follow.created_at, follow.followee_id)
+            })
+        })
+    }
+
+    async fn follow_task(&self, user_id: Uuid, task_id: Uuid) -> Result<TaskFollow, sqlx::Error> {
+        query_as!(
+            TaskFollow,
+            r#"
+            INSERT INTO task_follows (user_id, task_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, task_id)
+            DO UPDATE SET created_at = task_follows.created_at
+            RETURNING user_id, task_id, created_at
+            "#,
+            user_id,
+            task_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn unfollow_task(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<TaskFollow>, sqlx::Error> {
+        query_as!(
+            TaskFollow,
+            r#"
+            DELETE FROM task_follows
+            WHERE user_id = $1 AND task_id = $2
+            RETURNING user_id, task_id, created_at
+            "#,
+            user_id,
+            task_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_task_follow(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<TaskFollow>, sqlx::Error> {
+        query_as!(
+            TaskFollow,
+            r#"
+            SELECT user_id, task_id, created_at
+            FROM task_follows
+            WHERE user_id = $1 AND task_id = $2
+            "#,
+            user_id,
+            task_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_followed_tasks(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskFollow>, sqlx::Error> {
+        query_as!(
+            TaskFollow,
+            r#"
+            SELECT user_id, task_id, created_at
+            FROM task_follows
+            WHERE user_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, task_id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, task_id DESC
+            LIMIT $4
+            "#,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| {
+            Page::from_rows_by(rows, &page, |follow| {
+                Cursor::new(follow.created_at, follow.task_id)
+            })
+        })
+    }
+}
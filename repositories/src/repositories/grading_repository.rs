@@ -0,0 +1,269 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::{GradingRepositoryTrait, NewGradingResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{GradingResult, GradingRun, GradingRunStatus, GradingVerdict, ProgrammingLanguage};
+use sqlx::{PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct GradingRepository {
+    pool: PgPool,
+}
+
+impl GradingRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GradingRepositoryTrait for GradingRepository {
+    async fn enqueue(
+        &self,
+        submission_id: Uuid,
+        submission_version: i32,
+        language: ProgrammingLanguage,
+    ) -> Result<GradingRun, sqlx::Error> {
+        query_as!(
+            GradingRun,
+            r#"
+            INSERT INTO grading_runs (id, submission_id, submission_version, language)
+            VALUES ($1, $2, $3, $4)
+            RETURNING
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            "#,
+            Uuid::new_v4(),
+            submission_id,
+            submission_version,
+            language as ProgrammingLanguage
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn claim_next(&self) -> Result<Option<GradingRun>, sqlx::Error> {
+        query_as!(
+            GradingRun,
+            r#"
+            UPDATE grading_runs
+            SET status = 'running', started_at = NOW()
+            WHERE id = (
+                SELECT id FROM grading_runs
+                WHERE status = 'queued'
+                ORDER BY created_at, id
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            "#
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn complete(
+        &self,
+        run_id: Uuid,
+        verdict: GradingVerdict,
+        compile_output: Option<String>,
+        results: Vec<NewGradingResult>,
+    ) -> Result<GradingRun, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // A requeued run may be graded twice; the last attempt wins
+        query!("DELETE FROM grading_results WHERE run_id = $1", run_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for result in &results {
+            query!(
+                r#"
+                INSERT INTO grading_results (
+                    id, run_id, test_case_id, position, verdict,
+                    points, max_points, time_ms, memory_kb, exit_code
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+                Uuid::new_v4(),
+                run_id,
+                result.test_case_id,
+                result.position,
+                result.verdict as GradingVerdict,
+                result.points,
+                result.max_points,
+                result.time_ms,
+                result.memory_kb,
+                result.exit_code
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let passed = results
+            .iter()
+            .filter(|r| r.verdict == GradingVerdict::AC)
+            .count() as i32;
+        let score: i32 = results.iter().map(|r| r.points).sum();
+        let max_score: i32 = results.iter().map(|r| r.max_points).sum();
+
+        let run = query_as!(
+            GradingRun,
+            r#"
+            UPDATE grading_runs
+            SET
+                status = 'completed',
+                verdict = $2,
+                passed_tests = $3,
+                total_tests = $4,
+                score = $5,
+                max_score = $6,
+                compile_output = $7,
+                error = NULL,
+                finished_at = NOW()
+            WHERE id = $1
+            RETURNING
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            "#,
+            run_id,
+            verdict as GradingVerdict,
+            passed,
+            results.len() as i32,
+            score,
+            max_score,
+            compile_output
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(run)
+    }
+
+    async fn fail(&self, run_id: Uuid, error: &str) -> Result<GradingRun, sqlx::Error> {
+        query_as!(
+            GradingRun,
+            r#"
+            UPDATE grading_runs
+            SET status = 'failed', error = $2, finished_at = NOW()
+            WHERE id = $1
+            RETURNING
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            "#,
+            run_id,
+            error
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn requeue_stale(&self, started_before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = query!(
+            r#"
+            UPDATE grading_runs
+            SET status = 'queued', started_at = NULL
+            WHERE status = 'running' AND started_at < $1
+            "#,
+            started_before
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<GradingRun>, sqlx::Error> {
+        query_as!(
+            GradingRun,
+            r#"
+            SELECT
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            FROM grading_runs
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_runs_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<GradingRun>, sqlx::Error> {
+        query_as!(
+            GradingRun,
+            r#"
+            SELECT
+                id, submission_id, submission_version,
+                language as "language: ProgrammingLanguage",
+                status as "status: GradingRunStatus",
+                verdict as "verdict: GradingVerdict",
+                passed_tests, total_tests, score, max_score, compile_output, error,
+                created_at as "created_at!: DateTime<Utc>",
+                started_at, finished_at
+            FROM grading_runs
+            WHERE submission_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            submission_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_results(&self, run_id: Uuid) -> Result<Vec<GradingResult>, sqlx::Error> {
+        query_as!(
+            GradingResult,
+            r#"
+            SELECT
+                id, run_id, test_case_id, position,
+                verdict as "verdict: GradingVerdict",
+                points, max_points, time_ms, memory_kb, exit_code
+            FROM grading_results
+            WHERE run_id = $1
+            ORDER BY position
+            "#,
+            run_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,114 @@
+use crate::traits::LeaderboardRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{
+    LeaderboardDelta, LeaderboardMetric, LeaderboardSnapshot, LeaderboardStanding,
+    LeaderboardWindow,
+};
+use sqlx::{PgConnection, PgPool, query, query_as};
+
+pub struct LeaderboardRepository {
+    pool: PgPool,
+}
+
+impl LeaderboardRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Non-zero scores from facts since `since`, globally and per tag
+async fn standings(
+    conn: &mut PgConnection,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<LeaderboardStanding>, sqlx::Error> {
+    query_as!(
+        LeaderboardStanding,
+        r#"
+        SELECT
+            metric as "metric!: LeaderboardMetric",
+            NULL::TEXT as "tag",
+            user_id as "user_id!",
+            SUM(amount)::FLOAT8 as "score!"
+        FROM leaderboard_facts
+        WHERE $1::timestamptz IS NULL OR occurred_at >= $1
+        GROUP BY metric, user_id
+        HAVING SUM(amount) <> 0
+        UNION ALL
+        SELECT facts.metric, tags.slug::TEXT, facts.user_id, SUM(facts.amount)::FLOAT8
+        FROM leaderboard_facts facts
+        JOIN task_tags ON task_tags.task_id = facts.task_id
+        JOIN tags ON tags.id = task_tags.tag_id
+        WHERE $1::timestamptz IS NULL OR facts.occurred_at >= $1
+        GROUP BY facts.metric, tags.slug, facts.user_id
+        HAVING SUM(facts.amount) <> 0
+        "#,
+        since
+    )
+    .fetch_all(conn)
+    .await
+}
+
+#[async_trait]
+impl LeaderboardRepositoryTrait for LeaderboardRepository {
+    async fn take_deltas(&self, limit: i64) -> Result<Vec<LeaderboardDelta>, sqlx::Error> {
+        query_as!(
+            LeaderboardDelta,
+            r#"
+            WITH taken AS (
+                DELETE FROM leaderboard_deltas
+                WHERE id IN (
+                    SELECT id FROM leaderboard_deltas
+                    ORDER BY id
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING *
+            )
+            SELECT
+                taken.id,
+                taken.metric as "metric: LeaderboardMetric",
+                taken.user_id,
+                ARRAY(
+                    SELECT tags.slug::TEXT
+                    FROM task_tags
+                    JOIN tags ON tags.id = task_tags.tag_id
+                    WHERE task_tags.task_id = taken.task_id
+                    ORDER BY tags.slug
+                ) as "tags!",
+                taken.amount,
+                taken.occurred_at
+            FROM taken
+            ORDER BY taken.id
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn snapshot(&self, at: DateTime<Utc>) -> Result<LeaderboardSnapshot, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // Every statement sees the same data, so a delta is either part of
+        // the snapshot and dropped, or neither
+        query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .execute(&mut *tx)
+            .await?;
+        query!("DELETE FROM leaderboard_deltas")
+            .execute(&mut *tx)
+            .await?;
+
+        let week = standings(&mut tx, LeaderboardWindow::Week.start(at)).await?;
+        let month = standings(&mut tx, LeaderboardWindow::Month.start(at)).await?;
+        let all_time = standings(&mut tx, None).await?;
+        tx.commit().await?;
+
+        Ok(LeaderboardSnapshot {
+            taken_at: at,
+            week,
+            month,
+            all_time,
+        })
+    }
+}
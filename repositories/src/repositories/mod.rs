@@ -1,21 +1,63 @@
 pub mod account_repository;
+pub mod activity_repository;
+pub mod badge_repository;
+pub mod blob_repository;
+pub mod contest_repository;
+pub mod event_publishing_repository;
+pub mod feed_repository;
+pub mod follow_repository;
+pub mod grading_repository;
+pub mod leaderboard_repository;
+pub mod personal_access_token_repository;
 pub mod problems_or_tasks_repository;
+pub mod rating_flag_repository;
+pub mod rating_settings_repository;
+pub mod reputation_repository;
+pub mod search_repository;
 pub mod submission_comment_replies_repository;
 pub mod submission_comment_repository;
 pub mod submission_rating_repository;
+pub mod submission_revision_repository;
 pub mod submissions_repository;
+pub mod tag_repository;
+#[cfg(feature = "tantivy")]
+pub mod tantivy_search_repository;
+pub mod task_answer_repository;
 pub mod task_comment_replies_repository;
 pub mod task_comment_repository;
 pub mod task_rating_repository;
+pub mod task_revision_repository;
+pub mod task_test_case_repository;
 pub mod user_repository;
 
 pub use account_repository::*;
+pub use activity_repository::*;
+pub use badge_repository::*;
+pub use blob_repository::*;
+pub use contest_repository::*;
+pub use event_publishing_repository::*;
+pub use feed_repository::*;
+pub use follow_repository::*;
+pub use grading_repository::*;
+pub use leaderboard_repository::*;
+pub use personal_access_token_repository::*;
 pub use problems_or_tasks_repository::*;
+pub use rating_flag_repository::*;
+pub use rating_settings_repository::*;
+pub use reputation_repository::*;
+pub use search_repository::*;
 pub use submission_comment_replies_repository::*;
 pub use submission_comment_repository::*;
 pub use submission_rating_repository::*;
+pub use submission_revision_repository::*;
 pub use submissions_repository::*;
+pub use tag_repository::*;
+#[cfg(feature = "tantivy")]
+pub use tantivy_search_repository::*;
+pub use task_answer_repository::*;
 pub use task_comment_replies_repository::*;
 pub use task_comment_repository::*;
 pub use task_rating_repository::*;
+pub use task_revision_repository::*;
+pub use task_test_case_repository::*;
 pub use user_repository::*;
@@ -0,0 +1,161 @@
+use crate::traits::PersonalAccessTokenRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::PersonalAccessToken;
+use sqlx::{PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct PersonalAccessTokenRepository {
+    pool: PgPool,
+}
+
+impl PersonalAccessTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PersonalAccessTokenRepositoryTrait for PersonalAccessTokenRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        name: String,
+        token_prefix: String,
+        token_hash: String,
+        scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<PersonalAccessToken, sqlx::Error> {
+        let id = Uuid::new_v4();
+
+        query_as!(
+            PersonalAccessToken,
+            r#"
+            INSERT INTO personal_access_tokens (
+                id, user_id, name, token_prefix, token_hash, scopes, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id, user_id, name, token_prefix, token_hash, scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            user_id,
+            name,
+            token_prefix,
+            token_hash,
+            &scopes,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PersonalAccessToken>, sqlx::Error> {
+        query_as!(
+            PersonalAccessToken,
+            r#"
+            SELECT
+                id, user_id, name, token_prefix, token_hash, scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM personal_access_tokens
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_active_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error> {
+        query_as!(
+            PersonalAccessToken,
+            r#"
+            SELECT
+                id, user_id, name, token_prefix, token_hash, scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM personal_access_tokens
+            WHERE token_hash = $1
+                AND revoked_at IS NULL
+                AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<PersonalAccessToken>, sqlx::Error> {
+        query_as!(
+            PersonalAccessToken,
+            r#"
+            SELECT
+                id, user_id, name, token_prefix, token_hash, scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM personal_access_tokens
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn touch_last_used(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        // Only write once a minute to avoid a row update on every request
+        query!(
+            r#"
+            UPDATE personal_access_tokens
+            SET last_used_at = NOW()
+            WHERE id = $1
+                AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<PersonalAccessToken, sqlx::Error> {
+        query_as!(
+            PersonalAccessToken,
+            r#"
+            UPDATE personal_access_tokens
+            SET revoked_at = COALESCE(revoked_at, NOW())
+            WHERE id = $1 AND user_id = $2
+            RETURNING
+                id, user_id, name, token_prefix, token_hash, scopes,
+                expires_at as "expires_at: DateTime<Utc>",
+                last_used_at as "last_used_at: DateTime<Utc>",
+                revoked_at as "revoked_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+}
@@ -1,9 +1,11 @@
+use crate::pagination::{Page, PageRequest};
+use crate::task_query::TaskQuery;
 use crate::traits::ProblemOrTaskRepositoryTrait;
 use async_trait::async_trait;
 use chrono::Utc;
-use models::ProblemOrTask;
+use models::{Difficulty, ProblemOrTask};
 use serde_json::json;
-use sqlx::{PgPool, query, query_as};
+use sqlx::{PgPool, QueryBuilder, query, query_as};
 use uuid::Uuid;
 
 pub struct ProblemOrTaskRepository {
@@ -25,7 +27,7 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
         content: String,
         file_url: Option<String>,
         tags: Vec<String>,
-        difficulty: String,
+        difficulty: Difficulty,
     ) -> Result<ProblemOrTask, sqlx::Error> {
         let id = Uuid::new_v4();
 
@@ -38,12 +40,17 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
             VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING
                 id, user_id, title, content, file_url,
-                tags as "tags!: Vec<String>",
-                difficulty,
-                average_rating as "average_rating!: f64",
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
+                average_rating::FLOAT8 as "average_rating!: f64",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 total_submissions as "total_submissions!: i32",
                 view_count as "view_count!: i32",
+                current_revision,
                 created_at as "created_at!: chrono::DateTime<chrono::Utc>",
                 updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
                 deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
@@ -54,7 +61,7 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
             content,
             file_url,
             json!(tags),
-            difficulty
+            difficulty as Difficulty
         )
         .fetch_one(&self.pool)
         .await?;
@@ -68,12 +75,17 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
             r#"
             SELECT
                 id, user_id, title, content, file_url,
-                tags as "tags!: Vec<String>",
-                difficulty,
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
                 average_rating::FLOAT8 as "average_rating!: f64",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 total_submissions as "total_submissions!: i32",
                 view_count as "view_count!: i32",
+                current_revision,
                 created_at as "created_at!: chrono::DateTime<chrono::Utc>",
                 updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
                 deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
@@ -86,53 +98,93 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
         .await
     }
 
-    async fn find_all(&self) -> Result<Vec<ProblemOrTask>, sqlx::Error> {
+    async fn find_all(&self, page: PageRequest) -> Result<Page<ProblemOrTask>, sqlx::Error> {
         query_as!(
             ProblemOrTask,
             r#"
             SELECT
                 id, user_id, title, content, file_url,
-                tags as "tags!: Vec<String>",
-                difficulty,
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
                 average_rating::FLOAT8 as "average_rating!: f64",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 total_submissions as "total_submissions!: i32",
                 view_count as "view_count!: i32",
+                current_revision,
                 created_at as "created_at!: chrono::DateTime<chrono::Utc>",
                 updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
                 deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
             FROM problems_or_tasks
             WHERE deleted_at IS NULL
-            ORDER BY created_at DESC
-            "#
+                AND ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ProblemOrTask>, sqlx::Error> {
+    async fn query(&self, query: &TaskQuery) -> Result<Page<ProblemOrTask>, sqlx::Error> {
+        let mut builder = QueryBuilder::new("");
+        query.push_sql(&mut builder);
+
+        let rows = builder
+            .build_query_as::<ProblemOrTask>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Page::from_rows_by(rows, &query.page, |task| {
+            query.cursor_for(task)
+        }))
+    }
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<ProblemOrTask>, sqlx::Error> {
         query_as!(
             ProblemOrTask,
             r#"
             SELECT
                 id, user_id, title, content, file_url,
-                tags as "tags!: Vec<String>",
-                difficulty,
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
                 average_rating::FLOAT8 as "average_rating!: f64",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 total_submissions as "total_submissions!: i32",
                 view_count as "view_count!: i32",
+                current_revision,
                 created_at as "created_at!: chrono::DateTime<chrono::Utc>",
                 updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
                 deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
             FROM problems_or_tasks
             WHERE user_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn update(
@@ -142,7 +194,7 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
         content: Option<String>,
         file_url: Option<String>,
         tags: Option<Vec<String>>,
-        difficulty: Option<String>,
+        difficulty: Option<Difficulty>,
     ) -> Result<ProblemOrTask, sqlx::Error> {
         query_as!(
             ProblemOrTask,
@@ -158,12 +210,17 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
             WHERE id = $1
             RETURNING
                 id, user_id, title, content, file_url,
-                tags as "tags!: Vec<String>",
-                difficulty,
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
                 average_rating::FLOAT8 as "average_rating!: f64",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 total_submissions as "total_submissions!: i32",
                 view_count as "view_count!: i32",
+                current_revision,
                 created_at as "created_at!: chrono::DateTime<chrono::Utc>",
                 updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
                 deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
@@ -172,8 +229,51 @@ impl ProblemOrTaskRepositoryTrait for ProblemOrTaskRepository {
             title,
             content,
             file_url,
-            json!(tags),
-            difficulty,
+            tags.map(|t| json!(t)),
+            difficulty as Option<Difficulty>,
+            Utc::now()
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn restore_revision(
+        &self,
+        id: Uuid,
+        revision: i32,
+    ) -> Result<ProblemOrTask, sqlx::Error> {
+        query_as!(
+            ProblemOrTask,
+            r#"
+            UPDATE problems_or_tasks p
+            SET
+                title = r.title,
+                content = r.content,
+                file_url = r.file_url,
+                tags = r.tags,
+                difficulty = r.difficulty,
+                updated_at = $3
+            FROM task_revisions r
+            WHERE p.id = $1 AND r.task_id = p.id AND r.revision = $2
+            RETURNING
+                p.id, p.user_id, p.title, p.content, p.file_url,
+                ARRAY(
+                    SELECT jsonb_array_elements_text(COALESCE(p.tags, '[]'::jsonb))
+                ) as "tags!: Vec<String>",
+                p.difficulty as "difficulty: Difficulty",
+                p.average_rating::FLOAT8 as "average_rating!: f64",
+                p.total_ratings as "total_ratings!: i32",
+                p.bayesian_rating,
+                p.wilson_score,
+                p.total_submissions as "total_submissions!: i32",
+                p.view_count as "view_count!: i32",
+                p.current_revision,
+                p.created_at as "created_at!: chrono::DateTime<chrono::Utc>",
+                p.updated_at as "updated_at!: chrono::DateTime<chrono::Utc>",
+                p.deleted_at as "deleted_at: chrono::DateTime<chrono::Utc>"
+            "#,
+            id,
+            revision,
             Utc::now()
         )
         .fetch_one(&self.pool)
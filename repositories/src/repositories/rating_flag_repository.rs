@@ -0,0 +1,277 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::RatingFlagRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{RatingAbuseThresholds, RatingFlag, RatingFlagReason, RatingFlagStatus, RatingKind};
+use sqlx::{PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct RatingFlagRepository {
+    pool: PgPool,
+}
+
+impl RatingFlagRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RatingFlagRepositoryTrait for RatingFlagRepository {
+    async fn flag_vote_rings(
+        &self,
+        thresholds: &RatingAbuseThresholds,
+    ) -> Result<u64, sqlx::Error> {
+        let result = query!(
+            r#"
+            WITH recent AS (
+                SELECT kind, id, rater_id, author_id
+                FROM rating_origins
+                WHERE created_at >= $1 AND rater_id <> author_id
+            ),
+            pairs AS (
+                SELECT rater_id, author_id, COUNT(*) AS given
+                FROM recent
+                GROUP BY rater_id, author_id
+            ),
+            totals AS (
+                SELECT rater_id, COUNT(*) AS total
+                FROM recent
+                GROUP BY rater_id
+            ),
+            rings AS (
+                SELECT
+                    pairs.rater_id,
+                    pairs.author_id,
+                    pairs.given,
+                    back.given AS received,
+                    pairs.given::FLOAT8 / totals.total AS share
+                FROM pairs
+                JOIN pairs back
+                    ON back.rater_id = pairs.author_id AND back.author_id = pairs.rater_id
+                JOIN totals ON totals.rater_id = pairs.rater_id
+                WHERE pairs.given >= $2
+                  AND back.given >= $2
+                  AND pairs.given::FLOAT8 / totals.total >= $3
+            )
+            INSERT INTO rating_flags (rating_kind, rating_id, reason, details)
+            SELECT
+                recent.kind,
+                recent.id,
+                'vote_ring',
+                jsonb_build_object(
+                    'partner_id', rings.author_id,
+                    'given', rings.given,
+                    'received', rings.received,
+                    'share', ROUND(rings.share::NUMERIC, 2)
+                )
+            FROM recent
+            JOIN rings ON rings.rater_id = recent.rater_id AND rings.author_id = recent.author_id
+            ON CONFLICT (rating_kind, rating_id, reason) DO NOTHING
+            "#,
+            Utc::now() - thresholds.lookback,
+            thresholds.ring_min_ratings,
+            thresholds.ring_min_share
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn flag_new_account_bursts(
+        &self,
+        thresholds: &RatingAbuseThresholds,
+    ) -> Result<u64, sqlx::Error> {
+        let result = query!(
+            r#"
+            WITH fresh AS (
+                SELECT
+                    origins.kind,
+                    origins.id,
+                    origins.target_id,
+                    origins.created_at,
+                    origins.created_at - users.created_at AS account_age
+                FROM rating_origins origins
+                JOIN users ON users.id = origins.rater_id
+                WHERE origins.created_at >= $1
+                  AND origins.created_at - users.created_at < make_interval(secs => $2)
+            ),
+            bursts AS (
+                SELECT fresh.kind, fresh.id, fresh.target_id, fresh.account_age, COUNT(*) AS size
+                FROM fresh
+                JOIN fresh nearby
+                    ON nearby.kind = fresh.kind
+                    AND nearby.target_id = fresh.target_id
+                    AND nearby.created_at
+                        BETWEEN fresh.created_at - make_interval(secs => $3)
+                        AND fresh.created_at + make_interval(secs => $3)
+                GROUP BY fresh.kind, fresh.id, fresh.target_id, fresh.account_age
+                HAVING COUNT(*) >= $4
+            )
+            INSERT INTO rating_flags (rating_kind, rating_id, reason, details)
+            SELECT
+                kind,
+                id,
+                'new_account_burst',
+                jsonb_build_object(
+                    'target_id', target_id,
+                    'account_age_seconds', EXTRACT(EPOCH FROM account_age)::BIGINT,
+                    'burst_size', size
+                )
+            FROM bursts
+            ON CONFLICT (rating_kind, rating_id, reason) DO NOTHING
+            "#,
+            Utc::now() - thresholds.lookback,
+            thresholds.new_account_age.num_seconds() as f64,
+            thresholds.burst_window.num_seconds() as f64,
+            thresholds.burst_min_ratings
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn flag_shared_origins(
+        &self,
+        thresholds: &RatingAbuseThresholds,
+    ) -> Result<u64, sqlx::Error> {
+        let result = query!(
+            r#"
+            WITH recent AS (
+                SELECT kind, id, target_id, rater_id, client_ip, session_id
+                FROM rating_origins
+                WHERE created_at >= $1
+            ),
+            shared AS (
+                SELECT kind, target_id, client_ip, NULL::TEXT AS session_id,
+                    COUNT(DISTINCT rater_id) AS raters
+                FROM recent
+                WHERE client_ip IS NOT NULL
+                GROUP BY kind, target_id, client_ip
+                HAVING COUNT(DISTINCT rater_id) >= $2
+                UNION ALL
+                SELECT kind, target_id, NULL::INET, session_id, COUNT(DISTINCT rater_id)
+                FROM recent
+                WHERE session_id IS NOT NULL
+                GROUP BY kind, target_id, session_id
+                HAVING COUNT(DISTINCT rater_id) >= $3
+            )
+            INSERT INTO rating_flags (rating_kind, rating_id, reason, details)
+            SELECT DISTINCT ON (recent.kind, recent.id)
+                recent.kind,
+                recent.id,
+                'shared_origin',
+                jsonb_strip_nulls(jsonb_build_object(
+                    'target_id', recent.target_id,
+                    'client_ip', host(shared.client_ip),
+                    'session_id', shared.session_id,
+                    'raters', shared.raters
+                ))
+            FROM recent
+            JOIN shared
+                ON shared.kind = recent.kind
+                AND shared.target_id = recent.target_id
+                AND (shared.client_ip = recent.client_ip OR shared.session_id = recent.session_id)
+            ORDER BY recent.kind, recent.id, shared.raters DESC
+            ON CONFLICT (rating_kind, rating_id, reason) DO NOTHING
+            "#,
+            Utc::now() - thresholds.lookback,
+            thresholds.ip_min_raters,
+            thresholds.session_min_raters
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RatingFlag>, sqlx::Error> {
+        query_as!(
+            RatingFlag,
+            r#"
+            SELECT
+                id,
+                rating_kind as "rating_kind: RatingKind",
+                rating_id,
+                reason as "reason: RatingFlagReason",
+                details,
+                status as "status: RatingFlagStatus",
+                reviewed_by,
+                reviewed_at,
+                created_at as "created_at!: DateTime<Utc>"
+            FROM rating_flags
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_by_status(
+        &self,
+        status: Option<RatingFlagStatus>,
+        page: PageRequest,
+    ) -> Result<Page<RatingFlag>, sqlx::Error> {
+        query_as!(
+            RatingFlag,
+            r#"
+            SELECT
+                id,
+                rating_kind as "rating_kind: RatingKind",
+                rating_id,
+                reason as "reason: RatingFlagReason",
+                details,
+                status as "status: RatingFlagStatus",
+                reviewed_by,
+                reviewed_at,
+                created_at as "created_at!: DateTime<Utc>"
+            FROM rating_flags
+            WHERE ($1::rating_flag_status IS NULL OR status = $1)
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            status as Option<RatingFlagStatus>,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn review(
+        &self,
+        id: Uuid,
+        status: RatingFlagStatus,
+        moderator_id: Option<Uuid>,
+    ) -> Result<Option<RatingFlag>, sqlx::Error> {
+        query_as!(
+            RatingFlag,
+            r#"
+            UPDATE rating_flags
+            SET
+                status = $2::rating_flag_status,
+                reviewed_by = CASE WHEN $2 = 'pending' THEN NULL ELSE $3::uuid END,
+                reviewed_at = CASE WHEN $2 = 'pending' THEN NULL ELSE NOW() END
+            WHERE id = $1
+            RETURNING
+                id,
+                rating_kind as "rating_kind: RatingKind",
+                rating_id,
+                reason as "reason: RatingFlagReason",
+                details,
+                status as "status: RatingFlagStatus",
+                reviewed_by,
+                reviewed_at,
+                created_at as "created_at!: DateTime<Utc>"
+            "#,
+            id,
+            status as RatingFlagStatus,
+            moderator_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
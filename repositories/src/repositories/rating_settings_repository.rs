@@ -0,0 +1,68 @@
+use crate::traits::RatingSettingsRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::RatingSettings;
+use sqlx::{PgPool, query, query_as};
+
+pub struct RatingSettingsRepository {
+    pool: PgPool,
+}
+
+impl RatingSettingsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RatingSettingsRepositoryTrait for RatingSettingsRepository {
+    async fn find(&self) -> Result<RatingSettings, sqlx::Error> {
+        query_as!(
+            RatingSettings,
+            r#"
+            SELECT
+                prior_mean, prior_weight, positive_min_rating, confidence_z,
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM rating_settings
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn update(
+        &self,
+        prior_mean: f64,
+        prior_weight: f64,
+        positive_min_rating: i16,
+        confidence_z: f64,
+    ) -> Result<RatingSettings, sqlx::Error> {
+        query_as!(
+            RatingSettings,
+            r#"
+            UPDATE rating_settings
+            SET
+                prior_mean = $1,
+                prior_weight = $2,
+                positive_min_rating = $3,
+                confidence_z = $4
+            RETURNING
+                prior_mean, prior_weight, positive_min_rating, confidence_z,
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            prior_mean,
+            prior_weight,
+            positive_min_rating,
+            confidence_z
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn refresh_all_scores(&self) -> Result<(), sqlx::Error> {
+        query!("SELECT refresh_all_rating_scores()")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::ReputationRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{ReputationEvent, ReputationEventType, ReputationWeight};
+use rust_decimal::Decimal;
+use sqlx::{PgPool, query, query_as, query_scalar};
+use uuid::Uuid;
+
+pub struct ReputationRepository {
+    pool: PgPool,
+}
+
+impl ReputationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReputationRepositoryTrait for ReputationRepository {
+    async fn find_events_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<ReputationEvent>, sqlx::Error> {
+        query_as!(
+            ReputationEvent,
+            r#"
+            SELECT
+                id, user_id,
+                event_type as "event_type: ReputationEventType",
+                source_id, actor_id, points, base_points, reverses_id,
+                created_at as "created_at!: DateTime<Utc>"
+            FROM reputation_events
+            WHERE user_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_weights(&self) -> Result<Vec<ReputationWeight>, sqlx::Error> {
+        query_as!(
+            ReputationWeight,
+            r#"
+            SELECT
+                event_type as "event_type: ReputationEventType",
+                rating_value, points,
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM reputation_weights
+            ORDER BY event_type, rating_value
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn set_weight(
+        &self,
+        event_type: ReputationEventType,
+        rating_value: i16,
+        points: Decimal,
+    ) -> Result<Option<ReputationWeight>, sqlx::Error> {
+        query_as!(
+            ReputationWeight,
+            r#"
+            UPDATE reputation_weights
+            SET points = $3
+            WHERE event_type = $1 AND rating_value = $2
+            RETURNING
+                event_type as "event_type: ReputationEventType",
+                rating_value, points,
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            event_type as ReputationEventType,
+            rating_value,
+            points
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_daily_cap(&self) -> Result<Decimal, sqlx::Error> {
+        query_scalar!("SELECT daily_cap FROM reputation_settings")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn set_daily_cap(&self, daily_cap: Decimal) -> Result<Decimal, sqlx::Error> {
+        query_scalar!(
+            "UPDATE reputation_settings SET daily_cap = $1 RETURNING daily_cap",
+            daily_cap
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn recompute(&self, user_id: Uuid) -> Result<Option<Decimal>, sqlx::Error> {
+        query_scalar!(
+            r#"
+            UPDATE users
+            SET reputation_score = COALESCE(
+                (SELECT SUM(points) FROM reputation_events WHERE user_id = $1),
+                0
+            )
+            WHERE id = $1
+            RETURNING reputation_score as "reputation_score!"
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn recompute_all(&self) -> Result<u64, sqlx::Error> {
+        let result = query!(
+            r#"
+            WITH ledger AS (
+                SELECT users.id, COALESCE(SUM(events.points), 0) AS score
+                FROM users
+                LEFT JOIN reputation_events events ON events.user_id = users.id
+                GROUP BY users.id
+            )
+            UPDATE users
+            SET reputation_score = ledger.score
+            FROM ledger
+            WHERE users.id = ledger.id
+              AND users.reputation_score IS DISTINCT FROM ledger.score
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,187 @@
+use crate::pagination::Page;
+use crate::search_query::{MAX_TAG_FACETS, SearchQuery};
+use crate::task_query::TagMatch;
+use crate::traits::SearchRepositoryTrait;
+use async_trait::async_trait;
+use models::{FacetCount, SearchFacets, SearchHit};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// `ts_headline` options for result snippets
+const HEADLINE_OPTIONS: &str = "StartSel=<mark>, StopSel=</mark>, MinWords=15, MaxWords=35, MaxFragments=2, FragmentDelimiter=\" ... \"";
+
+/// Matching tasks, ranked on the weighted title + content vector
+const TASK_HITS: &str = r#"
+    SELECT 'task'::text AS kind, t.id, t.id AS task_id, t.title, t.content AS body,
+           ts_rank(t.search_vector, q.query)::FLOAT8 AS rank, t.created_at
+    FROM problems_or_tasks t, q
+    WHERE t.deleted_at IS NULL AND t.search_vector @@ q.query
+"#;
+
+/// Matching submissions of the viewer or to the viewer's tasks, as others
+/// could read answers off them; drafts and withdrawn submissions are private,
+/// contest submissions are the submitter's until the results are published
+const SUBMISSION_HITS: &str = r#"
+    SELECT 'submission'::text AS kind, s.id, s.task_id, t.title, s.content AS body,
+           ts_rank(s.search_vector, q.query)::FLOAT8 AS rank, s.created_at
+    FROM submissions s
+    JOIN problems_or_tasks t ON t.id = s.task_id, q
+    WHERE s.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND s.status NOT IN ('draft', 'withdrawn')
+      AND (q.viewer = s.user_id OR (q.viewer = t.user_id AND NOT EXISTS (
+          SELECT 1 FROM contest_submissions cs
+          JOIN contests c ON c.id = cs.contest_id
+          WHERE cs.submission_id = s.id AND c.results_published_at IS NULL
+      )))
+      AND s.search_vector @@ q.query
+"#;
+
+/// Matching comments and replies on visible tasks and submissions
+const DISCUSSION_HITS: &str = r#"
+    SELECT 'task_comment'::text AS kind, c.id, c.task_id, t.title, c.comment AS body,
+           ts_rank(c.search_vector, q.query)::FLOAT8 AS rank, c.created_at
+    FROM task_comments c
+    JOIN problems_or_tasks t ON t.id = c.task_id, q
+    WHERE c.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND c.search_vector @@ q.query
+    UNION ALL
+    SELECT 'task_comment_reply'::text AS kind, r.id, c.task_id, t.title, r.reply AS body,
+           ts_rank(r.search_vector, q.query)::FLOAT8 AS rank, r.created_at
+    FROM task_comment_replies r
+    JOIN task_comments c ON c.id = r.task_comment_id
+    JOIN problems_or_tasks t ON t.id = c.task_id, q
+    WHERE r.deleted_at IS NULL AND c.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND r.search_vector @@ q.query
+    UNION ALL
+    SELECT 'submission_comment'::text AS kind, c.id, s.task_id, t.title, c.comment AS body,
+           ts_rank(c.search_vector, q.query)::FLOAT8 AS rank, c.created_at
+    FROM submission_comments c
+    JOIN submissions s ON s.id = c.submission_id
+    JOIN problems_or_tasks t ON t.id = s.task_id, q
+    WHERE c.deleted_at IS NULL AND s.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND s.status NOT IN ('draft', 'withdrawn')
+      AND c.search_vector @@ q.query
+    UNION ALL
+    SELECT 'submission_comment_reply'::text AS kind, r.id, s.task_id, t.title, r.reply AS body,
+           ts_rank(r.search_vector, q.query)::FLOAT8 AS rank, r.created_at
+    FROM submission_comment_replies r
+    JOIN submission_comments c ON c.id = r.submission_comment_id
+    JOIN submissions s ON s.id = c.submission_id
+    JOIN problems_or_tasks t ON t.id = s.task_id, q
+    WHERE r.deleted_at IS NULL AND c.deleted_at IS NULL
+      AND s.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND s.status NOT IN ('draft', 'withdrawn')
+      AND r.search_vector @@ q.query
+"#;
+
+/// Postgres full-text search over the `search_vector` columns
+pub struct SearchRepository {
+    pool: PgPool,
+}
+
+impl SearchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Start `builder` with the `q` and `hits` CTEs: the parsed query and
+    /// every hit in scope that passes the facet filters, joined to its task
+    /// as `t`
+    fn push_hits(builder: &mut QueryBuilder<'_, Postgres>, query: &SearchQuery) {
+        let sources: Vec<&str> = [
+            (query.scope.includes_tasks(), TASK_HITS),
+            (query.scope.includes_submissions(), SUBMISSION_HITS),
+            (query.scope.includes_discussions(), DISCUSSION_HITS),
+        ]
+        .into_iter()
+        .filter_map(|(included, sql)| included.then_some(sql))
+        .collect();
+
+        builder.push("WITH q AS (SELECT websearch_to_tsquery('english', ");
+        builder.push_bind(query.text.clone());
+        builder.push(") AS query, ");
+        builder.push_bind(query.viewer);
+        builder.push("::UUID AS viewer), hits AS (SELECT hits.*, t.difficulty, t.tags FROM (");
+        builder.push(sources.join(" UNION ALL "));
+        builder.push(") hits JOIN problems_or_tasks t ON t.id = hits.task_id WHERE TRUE");
+
+        if !query.tags.is_empty() {
+            builder.push(match query.tag_match {
+                TagMatch::Any => " AND t.tags ?| ",
+                TagMatch::All => " AND t.tags ?& ",
+            });
+            builder.push_bind(query.tags.clone());
+        }
+
+        if let Some(difficulty) = query.difficulty {
+            builder.push(" AND t.difficulty = ");
+            builder.push_bind(difficulty);
+        }
+
+        builder.push(")");
+    }
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for SearchRepository {
+    async fn search(&self, query: &SearchQuery) -> Result<Page<SearchHit>, sqlx::Error> {
+        // Rank and page first, so snippets are only built for the returned rows
+        let mut builder = QueryBuilder::new("");
+        Self::push_hits(&mut builder, query);
+        builder.push(" SELECT hits.kind, hits.id, hits.task_id, hits.title, ");
+        builder.push("ts_headline('english', hits.body, q.query, ");
+        builder.push_bind(HEADLINE_OPTIONS);
+        builder.push(") AS snippet, hits.rank, hits.created_at FROM hits, q");
+
+        if let Some(cursor) = query.page.cursor {
+            builder.push(" WHERE (hits.rank, hits.created_at, hits.id) < (");
+            builder.push_bind(cursor.rank.unwrap_or(f64::MAX));
+            builder.push(", ");
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(cursor.id);
+            builder.push(")");
+        }
+
+        builder.push(" ORDER BY hits.rank DESC, hits.created_at DESC, hits.id DESC LIMIT ");
+        builder.push_bind(query.page.fetch_limit());
+
+        let rows = builder
+            .build_query_as::<SearchHit>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Page::from_rows_by(
+            rows,
+            &query.page,
+            SearchQuery::cursor_for,
+        ))
+    }
+
+    async fn facets(&self, query: &SearchQuery) -> Result<SearchFacets, sqlx::Error> {
+        let mut builder = QueryBuilder::new("");
+        Self::push_hits(&mut builder, query);
+        builder.push(
+            " SELECT difficulty::TEXT AS value, COUNT(*) AS count FROM hits \
+             GROUP BY difficulty ORDER BY count DESC, value",
+        );
+        let difficulties = builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut builder = QueryBuilder::new("");
+        Self::push_hits(&mut builder, query);
+        builder.push(
+            " SELECT tag AS value, COUNT(*) AS count \
+             FROM hits, jsonb_array_elements_text(COALESCE(hits.tags, '[]'::jsonb)) AS tag \
+             GROUP BY tag ORDER BY count DESC, value LIMIT ",
+        );
+        builder.push_bind(MAX_TAG_FACETS as i64);
+        let tags = builder
+            .build_query_as::<FacetCount>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(SearchFacets { difficulties, tags })
+    }
+}
@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 use uuid::Uuid;
 
+use crate::pagination::{Page, PageRequest};
 use crate::traits::SubmissionCommentReplyRepositoryTrait;
 
 
@@ -73,7 +74,11 @@ impl SubmissionCommentReplyRepositoryTrait for SubmissionCommentReplyRepository
         .await
     }
 
-    async fn find_by_comment(&self, submission_comment_id: Uuid) -> Result<Vec<SubmissionCommentReply>, sqlx::Error> {
+    async fn find_by_comment(
+        &self,
+        submission_comment_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error> {
         query_as!(
             SubmissionCommentReply,
             r#"
@@ -88,15 +93,25 @@ impl SubmissionCommentReplyRepositoryTrait for SubmissionCommentReplyRepository
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comment_replies
             WHERE submission_comment_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at ASC
+                AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
             "#,
-            submission_comment_id
+            submission_comment_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<SubmissionCommentReply>, sqlx::Error> {
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error> {
         query_as!(
             SubmissionCommentReply,
             r#"
@@ -111,15 +126,24 @@ impl SubmissionCommentReplyRepositoryTrait for SubmissionCommentReplyRepository
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comment_replies
             WHERE user_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_all(&self) -> Result<Vec<SubmissionCommentReply>, sqlx::Error> {
+    async fn find_all(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error> {
         query_as!(
             SubmissionCommentReply,
             r#"
@@ -133,11 +157,17 @@ impl SubmissionCommentReplyRepositoryTrait for SubmissionCommentReplyRepository
                 updated_at as "updated_at!: DateTime<Utc>",
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comment_replies
-            ORDER BY created_at DESC
-            "#
+            WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn count_by_comment(&self, submission_comment_id: Uuid) -> Result<i64, sqlx::Error> {
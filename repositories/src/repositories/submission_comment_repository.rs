@@ -4,6 +4,7 @@ use models::SubmissionComment;
 use sqlx::{PgPool, query, query_as, query_scalar};
 use uuid::Uuid;
 
+use crate::pagination::{Page, PageRequest};
 use crate::traits::SubmissionCommentRepositoryTrait;
 
 pub struct SubmissionCommentRepository {
@@ -72,7 +73,11 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
         .await
     }
 
-    async fn find_by_submission(&self, submission_id: Uuid) -> Result<Vec<SubmissionComment>, sqlx::Error> {
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionComment>, sqlx::Error> {
         query_as!(
             SubmissionComment,
             r#"
@@ -87,15 +92,25 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comments
             WHERE submission_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at ASC
+                AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
             "#,
-            submission_id
+            submission_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<SubmissionComment>, sqlx::Error> {
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionComment>, sqlx::Error> {
         query_as!(
             SubmissionComment,
             r#"
@@ -110,15 +125,21 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comments
             WHERE user_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_all(&self) -> Result<Vec<SubmissionComment>, sqlx::Error> {
+    async fn find_all(&self, page: PageRequest) -> Result<Page<SubmissionComment>, sqlx::Error> {
         query_as!(
             SubmissionComment,
             r#"
@@ -132,11 +153,17 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
                 updated_at as "updated_at!: DateTime<Utc>",
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM submission_comments
-            ORDER BY created_at DESC
-            "#
+            WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn count_by_submission(&self, submission_id: Uuid) -> Result<i64, sqlx::Error> {
@@ -195,6 +222,18 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
         Ok(())
     }
 
+    async fn remove(&self, id: Uuid, moderator_id: Uuid) -> Result<(), sqlx::Error> {
+        query!(
+            "UPDATE submission_comments SET deleted_at = $1, removed_by = $2 WHERE id = $3",
+            Some(Utc::now()),
+            moderator_id,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     async fn hard_delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
         query!(
             "DELETE FROM submission_comments WHERE id = $1",
@@ -207,7 +246,7 @@ impl SubmissionCommentRepositoryTrait for SubmissionCommentRepository {
 
     async fn restore(&self, id: Uuid) -> Result<(), sqlx::Error> {
         query!(
-            "UPDATE submission_comments SET deleted_at = NULL WHERE id = $1",
+            "UPDATE submission_comments SET deleted_at = NULL, removed_by = NULL WHERE id = $1",
             id
         )
         .execute(&self.pool)
@@ -1,8 +1,10 @@
 use chrono::{DateTime, Utc};
-use models::SubmissionRating;
+use models::{RatingActivity, RatingCount, RatingInterval, RatingOrigin, SubmissionRating};
 use sqlx::{PgPool, query, query_as, query_scalar};
+use sqlx::types::ipnet::IpNet;
 use uuid::Uuid;
 use async_trait::async_trait;
+use crate::pagination::{Page, PageRequest};
 use crate::traits::SubmissionRatingRepositoryTrait;
 
 pub struct SubmissionRatingRepository {
@@ -22,14 +24,17 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
         submission_id: Uuid,
         rater_id: Uuid,
         rating_value: i32,
+        origin: &RatingOrigin,
     ) -> Result<SubmissionRating, sqlx::Error> {
         let id = Uuid::new_v4();
 
         query_as!(
             SubmissionRating,
             r#"
-            INSERT INTO submission_ratings (id, submission_id, rater_id, rating_value)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO submission_ratings (
+                id, submission_id, rater_id, rating_value, client_ip, session_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING
                 id,
                 submission_id,
@@ -42,6 +47,8 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
             submission_id,
             rater_id,
             rating_value,
+            origin.client_ip.map(IpNet::from),
+            origin.session_id.as_deref(),
         )
         .fetch_one(&self.pool)
         .await
@@ -92,7 +99,11 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
         .await
     }
 
-    async fn find_by_submission(&self, submission_id: Uuid) -> Result<Vec<SubmissionRating>, sqlx::Error> {
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRating>, sqlx::Error> {
         query_as!(
             SubmissionRating,
             r#"
@@ -105,15 +116,25 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
                 updated_at as "updated_at!: DateTime<Utc>"
             FROM submission_ratings
             WHERE submission_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            submission_id
+            submission_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_rater(&self, rater_id: Uuid) -> Result<Vec<SubmissionRating>, sqlx::Error> {
+    async fn find_by_rater(
+        &self,
+        rater_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRating>, sqlx::Error> {
         query_as!(
             SubmissionRating,
             r#"
@@ -126,12 +147,18 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
                 updated_at as "updated_at!: DateTime<Utc>"
             FROM submission_ratings
             WHERE rater_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            rater_id
+            rater_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn get_average_rating(&self, submission_id: Uuid) -> Result<Option<f64>, sqlx::Error> {
@@ -139,7 +166,7 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
             r#"
             SELECT AVG(rating_value)::FLOAT8 as "avg"
             FROM submission_ratings
-            WHERE submission_id = $1
+            WHERE submission_id = $1 AND neutralized_at IS NULL
             "#,
             submission_id
         )
@@ -152,7 +179,7 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
             r#"
             SELECT COUNT(*)::BIGINT as "count!"
             FROM submission_ratings
-            WHERE submission_id = $1
+            WHERE submission_id = $1 AND neutralized_at IS NULL
             "#,
             submission_id
         )
@@ -213,4 +240,61 @@ impl SubmissionRatingRepositoryTrait for SubmissionRatingRepository {
         .await?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn get_distribution(&self, submission_id: Uuid) -> Result<Vec<RatingCount>, sqlx::Error> {
+        query_as!(
+            RatingCount,
+            r#"
+            SELECT
+                scale.value as "rating_value!",
+                COUNT(r.id) as "count!"
+            FROM generate_series(1, 4) AS scale(value)
+            LEFT JOIN submission_ratings r
+                ON r.submission_id = $1
+                AND r.rating_value = scale.value
+                AND r.neutralized_at IS NULL
+            GROUP BY scale.value
+            ORDER BY scale.value
+            "#,
+            submission_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn get_activity(
+        &self,
+        submission_id: Uuid,
+        interval: RatingInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RatingActivity>, sqlx::Error> {
+        query_as!(
+            RatingActivity,
+            r#"
+            SELECT
+                buckets.bucket_start as "bucket_start!: DateTime<Utc>",
+                COUNT(r.id) as "ratings!",
+                AVG(r.rating_value)::FLOAT8 as "average_rating"
+            FROM generate_series(
+                date_trunc($2, $3::timestamptz, 'UTC'),
+                $4::timestamptz,
+                ('1 ' || $2)::interval
+            ) AS buckets(bucket_start)
+            LEFT JOIN submission_ratings r
+                ON r.submission_id = $1
+                AND r.neutralized_at IS NULL
+                AND r.created_at >= buckets.bucket_start
+                AND r.created_at < buckets.bucket_start + ('1 ' || $2)::interval
+            GROUP BY buckets.bucket_start
+            ORDER BY buckets.bucket_start
+            "#,
+            submission_id,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,72 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::SubmissionRevisionRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{SubmissionRevision, SubmissionStatus};
+use sqlx::{PgPool, query_as};
+use uuid::Uuid;
+
+pub struct SubmissionRevisionRepository {
+    pool: PgPool,
+}
+
+impl SubmissionRevisionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SubmissionRevisionRepositoryTrait for SubmissionRevisionRepository {
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRevision>, sqlx::Error> {
+        query_as!(
+            SubmissionRevision,
+            r#"
+            SELECT
+                id, submission_id, version, content, file_url,
+                status as "status: SubmissionStatus",
+                task_revision,
+                created_at as "created_at!: DateTime<Utc>"
+            FROM submission_revisions
+            WHERE submission_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            submission_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_by_version(
+        &self,
+        submission_id: Uuid,
+        version: i32,
+    ) -> Result<Option<SubmissionRevision>, sqlx::Error> {
+        query_as!(
+            SubmissionRevision,
+            r#"
+            SELECT
+                id, submission_id, version, content, file_url,
+                status as "status: SubmissionStatus",
+                task_revision,
+                created_at as "created_at!: DateTime<Utc>"
+            FROM submission_revisions
+            WHERE submission_id = $1 AND version = $2
+            "#,
+            submission_id,
+            version
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
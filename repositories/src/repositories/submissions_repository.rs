@@ -1,9 +1,10 @@
+use crate::pagination::{Page, PageRequest};
 use crate::traits::SubmissionRepositoryTrait;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use models::Submission;
+use models::{ProgrammingLanguage, Submission, SubmissionStatus};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, query, query_as};
+use sqlx::{PgPool, query, query_as, query_scalar};
 use uuid::Uuid;
 
 pub struct SubmissionRepository {
@@ -24,11 +25,12 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
         task_id: Uuid,
         content: String,
         file_url: Option<String>,
-        status: Option<String>,
+        status: Option<SubmissionStatus>,
         average_rating: Decimal,
         total_ratings: i32,
         is_featured: bool,
         submitted_at: Option<DateTime<Utc>>,
+        language: Option<ProgrammingLanguage>,
     ) -> Result<Submission, sqlx::Error> {
         let id = Uuid::new_v4();
 
@@ -37,32 +39,39 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
             r#"
             INSERT INTO submissions (
                 id, user_id, task_id, content, file_url, status,
-                average_rating, total_ratings, is_featured, submitted_at
+                average_rating, total_ratings, is_featured, submitted_at, language
             )
             VALUES (
-                $1, $2, $3, $4, $5, COALESCE($6, 'draft'),
-                $7, $8, $9, $10
+                $1, $2, $3, $4, $5, COALESCE($6, 'draft'::submission_status),
+                $7, $8, $9, $10, $11
             )
             RETURNING
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             "#,
             id,
             user_id,
             task_id,
             content,
             file_url,
-            status,
+            status as Option<SubmissionStatus>,
             average_rating,
             total_ratings,
             is_featured,
             submitted_at,
+            language as Option<ProgrammingLanguage>,
         )
         .fetch_one(&self.pool)
         .await?;
@@ -75,14 +84,20 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE id = $1
             "#,
@@ -92,123 +107,208 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
         .await
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Submission>, sqlx::Error> {
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
         query_as!(
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE user_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<Submission>, sqlx::Error> {
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
         query_as!(
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE task_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            task_id
+            task_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_all(&self) -> Result<Vec<Submission>, sqlx::Error> {
+    async fn find_all(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error> {
         query_as!(
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE deleted_at IS NULL
-            ORDER BY created_at DESC
-            "#
+                AND ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_status(&self, status: &str) -> Result<Vec<Submission>, sqlx::Error> {
+    async fn find_by_status(
+        &self,
+        status: SubmissionStatus,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error> {
         query_as!(
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE status = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            status
+            status as SubmissionStatus,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_featured(&self) -> Result<Vec<Submission>, sqlx::Error> {
+    async fn find_featured(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error> {
         query_as!(
             Submission,
             r#"
             SELECT
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             FROM submissions
             WHERE is_featured = true AND deleted_at IS NULL
-            ORDER BY created_at DESC
-            "#
+                AND ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn count_by_task(&self, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT COUNT(*)::BIGINT as "count!"
+            FROM submissions
+            WHERE task_id = $1 AND status NOT IN ('draft', 'withdrawn') AND deleted_at IS NULL
+            "#,
+            task_id
+        )
+        .fetch_one(&self.pool)
+        .await
     }
 
-    async fn update_status(&self, id: Uuid, status: &str) -> Result<(), sqlx::Error> {
+    async fn update_status(&self, id: Uuid, status: SubmissionStatus) -> Result<(), sqlx::Error> {
         query!(
             "UPDATE submissions SET status = $1, updated_at = $2 WHERE id = $3",
-            status,
+            status as SubmissionStatus,
             Utc::now(),
             id
         )
@@ -222,10 +322,11 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
         id: Uuid,
         content: Option<String>,
         file_url: Option<String>,
-        status: Option<String>,
+        status: Option<SubmissionStatus>,
         is_featured: Option<bool>,
         average_rating: Option<Decimal>,
         total_ratings: Option<i32>,
+        language: Option<ProgrammingLanguage>,
     ) -> Result<Submission, sqlx::Error> {
         query_as!(
             Submission,
@@ -238,31 +339,84 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
                 is_featured = COALESCE($5, is_featured),
                 average_rating = COALESCE($6, average_rating),
                 total_ratings = COALESCE($7, total_ratings),
-                updated_at = $8
+                language = COALESCE($8, language),
+                updated_at = $9
             WHERE id = $1
             RETURNING
-                id, user_id, task_id, content, file_url, status,
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
                 average_rating as "average_rating!: Decimal",
                 total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
                 is_featured as "is_featured!: bool",
                 submitted_at as "submitted_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
                 updated_at as "updated_at!: DateTime<Utc>",
-                deleted_at as "deleted_at: DateTime<Utc>"
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
             "#,
             id,
             content,
             file_url,
-            status,
+            status as Option<SubmissionStatus>,
             is_featured,
             average_rating,
             total_ratings,
+            language as Option<ProgrammingLanguage>,
             Utc::now()
         )
         .fetch_one(&self.pool)
         .await
     }
 
+    async fn resubmit(
+        &self,
+        id: Uuid,
+        content: String,
+        file_url: Option<String>,
+        language: Option<ProgrammingLanguage>,
+    ) -> Result<Submission, sqlx::Error> {
+        query_as!(
+            Submission,
+            r#"
+            UPDATE submissions
+            SET
+                content = $2,
+                file_url = $3,
+                language = COALESCE($5, language),
+                status = 'submitted',
+                submitted_at = $4,
+                updated_at = $4
+            WHERE id = $1
+            RETURNING
+                id, user_id, task_id, content, file_url,
+                status as "status: SubmissionStatus",
+                average_rating as "average_rating!: Decimal",
+                total_ratings as "total_ratings!: i32",
+                bayesian_rating,
+                wilson_score,
+                is_featured as "is_featured!: bool",
+                submitted_at as "submitted_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>",
+                deleted_at as "deleted_at: DateTime<Utc>",
+                task_revision,
+                current_version,
+                language as "language: ProgrammingLanguage"
+            "#,
+            id,
+            content,
+            file_url,
+            Utc::now(),
+            language as Option<ProgrammingLanguage>
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
         query!(
             "UPDATE submissions SET deleted_at = $1 WHERE id = $2",
@@ -273,4 +427,4 @@ impl SubmissionRepositoryTrait for SubmissionRepository {
         .await?;
         Ok(())
     }
-}
\ No newline at end of file
+}
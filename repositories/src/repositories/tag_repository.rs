@@ -0,0 +1,218 @@
+use crate::traits::TagRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::Tag;
+use sqlx::{PgPool, query, query_as, query_scalar};
+use uuid::Uuid;
+
+pub struct TagRepository {
+    pool: PgPool,
+}
+
+impl TagRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TagRepositoryTrait for TagRepository {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>, sqlx::Error> {
+        query_as!(
+            Tag,
+            r#"
+            SELECT
+                t.id, t.slug, t.name, t.description, t.usage_count,
+                ARRAY(
+                    SELECT a.alias::TEXT FROM tag_aliases a WHERE a.tag_id = t.id ORDER BY a.alias
+                ) as "aliases!: Vec<String>",
+                t.created_at as "created_at!: DateTime<Utc>",
+                t.updated_at as "updated_at!: DateTime<Utc>"
+            FROM tags t
+            WHERE t.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Tag>, sqlx::Error> {
+        query_as!(
+            Tag,
+            r#"
+            SELECT
+                t.id, t.slug, t.name, t.description, t.usage_count,
+                ARRAY(
+                    SELECT a.alias::TEXT FROM tag_aliases a WHERE a.tag_id = t.id ORDER BY a.alias
+                ) as "aliases!: Vec<String>",
+                t.created_at as "created_at!: DateTime<Utc>",
+                t.updated_at as "updated_at!: DateTime<Utc>"
+            FROM tags t
+            WHERE t.slug = $1
+               OR t.id = (SELECT tag_id FROM tag_aliases WHERE alias = $1)
+            "#,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn autocomplete(&self, prefix: &str, limit: i64) -> Result<Vec<Tag>, sqlx::Error> {
+        // Slugs never contain LIKE wildcards
+        query_as!(
+            Tag,
+            r#"
+            SELECT
+                t.id, t.slug, t.name, t.description, t.usage_count,
+                ARRAY(
+                    SELECT a.alias::TEXT FROM tag_aliases a WHERE a.tag_id = t.id ORDER BY a.alias
+                ) as "aliases!: Vec<String>",
+                t.created_at as "created_at!: DateTime<Utc>",
+                t.updated_at as "updated_at!: DateTime<Utc>"
+            FROM tags t
+            WHERE t.slug LIKE $1 || '%'
+               OR EXISTS (
+                   SELECT 1 FROM tag_aliases a
+                   WHERE a.tag_id = t.id AND a.alias LIKE $1 || '%'
+               )
+            ORDER BY (t.slug LIKE $1 || '%') DESC, t.usage_count DESC, t.slug ASC
+            LIMIT $2
+            "#,
+            prefix,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_popular(&self, limit: i64) -> Result<Vec<Tag>, sqlx::Error> {
+        query_as!(
+            Tag,
+            r#"
+            SELECT
+                t.id, t.slug, t.name, t.description, t.usage_count,
+                ARRAY(
+                    SELECT a.alias::TEXT FROM tag_aliases a WHERE a.tag_id = t.id ORDER BY a.alias
+                ) as "aliases!: Vec<String>",
+                t.created_at as "created_at!: DateTime<Utc>",
+                t.updated_at as "updated_at!: DateTime<Utc>"
+            FROM tags t
+            WHERE t.usage_count > 0
+            ORDER BY t.usage_count DESC, t.slug ASC
+            LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn resolve_slugs(&self, slugs: &[String]) -> Result<Vec<String>, sqlx::Error> {
+        query_scalar!(
+            r#"
+            SELECT COALESCE(t.slug, s.slug) as "slug!"
+            FROM UNNEST($1::TEXT[]) WITH ORDINALITY AS s(slug, position)
+            LEFT JOIN tag_aliases a ON a.alias = s.slug
+            LEFT JOIN tags t ON t.id = a.tag_id
+            ORDER BY s.position
+            "#,
+            slugs
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> Result<Tag, sqlx::Error> {
+        query!(
+            r#"
+            UPDATE tags
+            SET
+                name = COALESCE($2, name),
+                description = COALESCE($3, description)
+            WHERE id = $1
+            "#,
+            id,
+            name,
+            description
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.find_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn add_alias(&self, id: Uuid, alias: &str) -> Result<Tag, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        // 1. Point the alias at the tag
+        query!(
+            r#"
+            INSERT INTO tag_aliases (alias, tag_id)
+            VALUES ($1, $2)
+            ON CONFLICT (alias) DO UPDATE SET tag_id = EXCLUDED.tag_id
+            "#,
+            alias,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // 2. Merge a tag that used the alias as its slug
+        let merged = query_scalar!(
+            "SELECT id FROM tags WHERE slug = $1 AND id <> $2",
+            alias,
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(merged) = merged {
+            query!(
+                "UPDATE tag_aliases SET tag_id = $1 WHERE tag_id = $2",
+                id,
+                merged
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            // Rewriting the tags runs them through the normalize trigger,
+            // which now resolves the alias
+            query!(
+                "UPDATE problems_or_tasks SET tags = tags WHERE tags ? $1",
+                alias
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            query!("DELETE FROM tags WHERE id = $1", merged)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.find_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    async fn remove_alias(&self, id: Uuid, alias: &str) -> Result<(), sqlx::Error> {
+        let result = query!(
+            "DELETE FROM tag_aliases WHERE tag_id = $1 AND alias = $2",
+            id,
+            alias
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,710 @@
+use crate::pagination::Page;
+use crate::search_query::{MAX_TAG_FACETS, SearchQuery, SearchScope};
+use crate::task_query::{TASK_COLUMNS, TagMatch};
+use crate::traits::SearchRepositoryTrait;
+use async_trait::async_trait;
+use chrono::DateTime;
+use models::{FacetCount, ProblemOrTask, SearchFacets, SearchHit, SearchResultKind};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tantivy::collector::{FacetCollector, FacetCounts, ScoreSegmentTweaker, ScoreTweaker, TopDocs};
+use tantivy::columnar::Column;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+use tantivy::schema::{
+    FAST, Facet, FacetOptions, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TEXT,
+    Value,
+};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{
+    DocId, Index, IndexReader, IndexSettings, IndexWriter, ReloadPolicy, Score, SegmentReader,
+    TantivyDocument, TantivyError, Term, doc,
+};
+use uuid::Uuid;
+
+/// Memory budget of the index writer
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+
+/// Length of the content excerpt used when no term could be highlighted
+const FALLBACK_SNIPPET_CHARS: usize = 150;
+
+/// Tasks and submissions in an embedded Tantivy index
+///
+/// An alternative to [`SearchRepository`](super::SearchRepository) that keeps
+/// search load off Postgres and tolerates typos: every term also matches
+/// words one edit away, ranked below exact matches. Task titles are boosted
+/// over content and tags. Every document carries its task's `difficulty` and
+/// `tag` facets, which back the facet filters and counts.
+///
+/// Only tasks and submissions are indexed; `SearchScope::Discussions` returns
+/// nothing.
+///
+/// Only one process may write the index: the `search-indexer` binary opens
+/// it with [`open`](Self::open) and applies the task and submission changes
+/// the database queues in `search_index_queue`, whichever process made them.
+/// API instances open it with [`open_read_only`](Self::open_read_only) and
+/// pick up its commits, so they need the index directory on a shared
+/// filesystem. `rebuild-search-index` refills the index from scratch.
+#[derive(Clone)]
+pub struct TantivySearchRepository {
+    index: Arc<SearchIndex>,
+}
+
+struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    /// `None` when opened read-only
+    writer: Option<Mutex<IndexWriter>>,
+    fields: Fields,
+}
+
+#[derive(Clone, Copy)]
+struct Fields {
+    id: Field,
+    /// The id as two integers, the tie-breaker of the hit order
+    id_high: Field,
+    id_low: Field,
+    kind: Field,
+    task_id: Field,
+    title: Field,
+    task_title: Field,
+    content: Field,
+    tags: Field,
+    tag_facets: Field,
+    difficulty: Field,
+    created_at: Field,
+    /// Submitter of a submission
+    user_id: Field,
+    /// Author of the task a submission belongs to, left out until the
+    /// results of its contest are published
+    task_author_id: Field,
+}
+
+impl Fields {
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            id: builder.add_text_field("id", STRING | STORED),
+            id_high: builder.add_u64_field("id_high", FAST),
+            id_low: builder.add_u64_field("id_low", FAST),
+            kind: builder.add_text_field("kind", STRING | STORED),
+            task_id: builder.add_text_field("task_id", STRING | STORED),
+            title: builder.add_text_field("title", TEXT),
+            // Title of the task a document belongs to, shown with every hit
+            task_title: builder.add_text_field("task_title", STORED),
+            content: builder.add_text_field("content", TEXT | STORED),
+            tags: builder.add_text_field("tags", TEXT),
+            tag_facets: builder.add_facet_field("tag", FacetOptions::default()),
+            difficulty: builder.add_facet_field("difficulty", FacetOptions::default()),
+            created_at: builder.add_i64_field("created_at", INDEXED | STORED | FAST),
+            user_id: builder.add_text_field("user_id", STRING),
+            task_author_id: builder.add_text_field("task_author_id", STRING),
+        };
+
+        (builder.build(), fields)
+    }
+}
+
+/// Tantivy failures surface through the repository error type
+fn index_error(e: impl std::error::Error + Send + Sync + 'static) -> sqlx::Error {
+    sqlx::Error::Io(std::io::Error::other(e))
+}
+
+fn term_query(field: Field, text: &str) -> Box<dyn Query> {
+    Box::new(TermQuery::new(
+        Term::from_field_text(field, text),
+        IndexRecordOption::Basic,
+    ))
+}
+
+fn facet_query(field: Field, value: &str) -> Box<dyn Query> {
+    Box::new(TermQuery::new(
+        Term::from_facet(field, &Facet::from_path([value])),
+        IndexRecordOption::Basic,
+    ))
+}
+
+/// The `limit` most frequent top-level facet values, ties by value
+fn facet_counts(counts: &FacetCounts, limit: usize) -> Vec<FacetCount> {
+    let mut counts: Vec<FacetCount> = counts
+        .get(Facet::root())
+        .filter_map(|(facet, count)| {
+            Some(FacetCount {
+                value: facet.to_path().last()?.to_string(),
+                count: count as i64,
+            })
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    counts.truncate(limit);
+    counts
+}
+
+/// Searchable submissions; drafts, withdrawn submissions and those of
+/// deleted tasks are private or gone and never indexed
+const SUBMISSION_ROWS: &str = r#"
+    SELECT s.id, s.task_id, s.user_id, s.content,
+           (EXTRACT(EPOCH FROM COALESCE(s.created_at, NOW())) * 1000000)::BIGINT AS created_at,
+           submission_results_hidden(s.id) AS results_hidden
+    FROM submissions s
+    JOIN problems_or_tasks t ON t.id = s.task_id
+    WHERE s.deleted_at IS NULL AND t.deleted_at IS NULL
+      AND s.status NOT IN ('draft', 'withdrawn')
+"#;
+
+#[derive(sqlx::FromRow)]
+struct SubmissionRow {
+    id: Uuid,
+    task_id: Uuid,
+    user_id: Uuid,
+    content: String,
+    /// Microseconds since the epoch
+    created_at: i64,
+    /// Made in a contest whose results are not published yet
+    results_hidden: bool,
+}
+
+impl TantivySearchRepository {
+    /// Open the index stored in `path` for writing, creating it if it does
+    /// not exist
+    ///
+    /// Takes the index's writer lock, so this fails while another process
+    /// writes the index. An index built with an older schema is replaced by
+    /// an empty one; `rebuild` fills it again.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, sqlx::Error> {
+        let path = path.as_ref();
+        let (schema, fields) = Fields::schema();
+        std::fs::create_dir_all(path)?;
+        let directory = MmapDirectory::open(path).map_err(index_error)?;
+
+        let index = match Index::open_or_create(directory, schema.clone()) {
+            Err(TantivyError::SchemaError(_)) => {
+                std::fs::remove_dir_all(path)?;
+                std::fs::create_dir_all(path)?;
+                let directory = MmapDirectory::open(path).map_err(index_error)?;
+                Index::create(directory, schema, IndexSettings::default())
+            }
+            index => index,
+        }
+        .map_err(index_error)?;
+        let writer = index.writer(WRITER_MEMORY_BYTES).map_err(index_error)?;
+        Self::from_index(index, fields, Some(writer))
+    }
+
+    /// Open the index stored in `path` for searching only
+    ///
+    /// Commits of the writing process show up within moments. Fails when
+    /// `path` holds no index or one built with another schema; starting the
+    /// `search-indexer` creates it.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self, sqlx::Error> {
+        let (schema, fields) = Fields::schema();
+        let index = Index::open_in_dir(path).map_err(index_error)?;
+        if index.schema() != schema {
+            return Err(index_error(TantivyError::SchemaError(
+                "the search index was built with another schema".to_string(),
+            )));
+        }
+        Self::from_index(index, fields, None)
+    }
+
+    /// A throwaway index held in memory
+    pub fn in_memory() -> Result<Self, sqlx::Error> {
+        let (schema, fields) = Fields::schema();
+        let index = Index::create_in_ram(schema);
+        let writer = index.writer(WRITER_MEMORY_BYTES).map_err(index_error)?;
+        Self::from_index(index, fields, Some(writer))
+    }
+
+    fn from_index(
+        index: Index,
+        fields: Fields,
+        writer: Option<IndexWriter>,
+    ) -> Result<Self, sqlx::Error> {
+        // The writer reloads after its own commits; readers watch for them
+        let reload_policy = match writer {
+            Some(_) => ReloadPolicy::Manual,
+            None => ReloadPolicy::OnCommitWithDelay,
+        };
+        let reader = index
+            .reader_builder()
+            .reload_policy(reload_policy)
+            .try_into()
+            .map_err(index_error)?;
+
+        Ok(Self {
+            index: Arc::new(SearchIndex {
+                index,
+                reader,
+                writer: writer.map(Mutex::new),
+                fields,
+            }),
+        })
+    }
+
+    /// Run `f` against the index on the blocking thread pool
+    async fn blocking<T, F>(&self, f: F) -> Result<T, sqlx::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SearchIndex) -> tantivy::Result<T> + Send + 'static,
+    {
+        let index = self.index.clone();
+        tokio::task::spawn_blocking(move || f(&index))
+            .await
+            .map_err(index_error)?
+            .map_err(index_error)
+    }
+
+    /// Apply a batch of the changes queued in `search_index_queue`
+    ///
+    /// Returns:
+    /// - The number of changes applied
+    ///
+    /// The batch stays queued unless the index commits it. A changed task
+    /// re-indexes its submissions too, as they carry its title and facets.
+    pub async fn sync(&self, pool: &PgPool, limit: i64) -> Result<usize, sqlx::Error> {
+        // 1. Take a batch of changes
+        let mut tx = pool.begin().await?;
+        let changes = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+            r#"
+            DELETE FROM search_index_queue
+            WHERE id IN (
+                SELECT id FROM search_index_queue
+                ORDER BY id
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING task_id, submission_id
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        let changed_tasks: HashSet<Uuid> = changes
+            .iter()
+            .filter(|(_, submission_id)| submission_id.is_none())
+            .map(|(task_id, _)| *task_id)
+            .collect();
+        let changed_submissions: HashSet<Uuid> = changes
+            .iter()
+            .filter(|(task_id, _)| !changed_tasks.contains(task_id))
+            .filter_map(|(_, submission_id)| *submission_id)
+            .collect();
+
+        // 2. Load what is searchable of them now
+        let task_ids: Vec<Uuid> = changed_tasks.iter().copied().collect();
+        let submission_ids: Vec<Uuid> = changed_submissions.iter().copied().collect();
+        let submissions = sqlx::query_as::<_, SubmissionRow>(&format!(
+            "{} AND (s.id = ANY($1) OR s.task_id = ANY($2))",
+            SUBMISSION_ROWS
+        ))
+        .bind(&submission_ids)
+        .bind(&task_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let needed_tasks: Vec<Uuid> = changed_tasks
+            .iter()
+            .copied()
+            .chain(submissions.iter().map(|row| row.task_id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let tasks = sqlx::query_as::<_, ProblemOrTask>(&format!(
+            "SELECT {} FROM problems_or_tasks WHERE deleted_at IS NULL AND id = ANY($1)",
+            TASK_COLUMNS
+        ))
+        .bind(&needed_tasks)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // 3. Replace their documents, and only then drop the batch
+        self.blocking(move |index| {
+            let tasks_by_id: HashMap<Uuid, &ProblemOrTask> =
+                tasks.iter().map(|t| (t.id, t)).collect();
+
+            let mut writer = index.lock_writer()?;
+            for task_id in &changed_tasks {
+                writer.delete_term(Term::from_field_text(
+                    index.fields.task_id,
+                    &task_id.to_string(),
+                ));
+                if let Some(task) = tasks_by_id.get(task_id) {
+                    writer.add_document(index.task_document(task))?;
+                }
+            }
+            for id in &changed_submissions {
+                writer.delete_term(index.id_term(*id));
+            }
+            index.add_submissions(&writer, &submissions, &tasks_by_id)?;
+            index.commit(&mut writer)
+        })
+        .await?;
+        tx.commit().await?;
+
+        Ok(changes.len())
+    }
+
+    /// Replace the whole index with the current contents of the database
+    ///
+    /// Returns:
+    /// - The number of indexed documents
+    pub async fn rebuild(&self, pool: &PgPool) -> Result<usize, sqlx::Error> {
+        // 1. Load everything searchable
+        let tasks = sqlx::query_as::<_, ProblemOrTask>(&format!(
+            "SELECT {} FROM problems_or_tasks WHERE deleted_at IS NULL",
+            TASK_COLUMNS
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        let submissions = sqlx::query_as::<_, SubmissionRow>(SUBMISSION_ROWS)
+            .fetch_all(pool)
+            .await?;
+
+        // 2. Swap the index contents in a single commit
+        self.blocking(move |index| {
+            let tasks_by_id: HashMap<Uuid, &ProblemOrTask> =
+                tasks.iter().map(|t| (t.id, t)).collect();
+
+            let mut writer = index.lock_writer()?;
+            writer.delete_all_documents()?;
+
+            for task in &tasks {
+                writer.add_document(index.task_document(task))?;
+            }
+            let count = tasks.len() + index.add_submissions(&writer, &submissions, &tasks_by_id)?;
+
+            index.commit(&mut writer)?;
+            Ok(count)
+        })
+        .await
+    }
+}
+
+impl SearchIndex {
+    fn lock_writer(&self) -> tantivy::Result<MutexGuard<'_, IndexWriter>> {
+        let writer = self.writer.as_ref().ok_or_else(|| {
+            TantivyError::InvalidArgument("the search index was opened read-only".to_string())
+        })?;
+        // A panic mid-write leaves nothing half applied that a commit would expose
+        Ok(writer.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn commit(&self, writer: &mut IndexWriter) -> tantivy::Result<()> {
+        writer.commit()?;
+        self.reader.reload()
+    }
+
+    fn id_term(&self, id: Uuid) -> Term {
+        Term::from_field_text(self.fields.id, &id.to_string())
+    }
+
+    fn task_document(&self, task: &ProblemOrTask) -> TantivyDocument {
+        let f = self.fields;
+        let (id_high, id_low) = task.id.as_u64_pair();
+        let mut document = doc!(
+            f.id => task.id.to_string(),
+            f.id_high => id_high,
+            f.id_low => id_low,
+            f.kind => "task",
+            f.task_id => task.id.to_string(),
+            f.title => task.title.as_str(),
+            f.task_title => task.title.as_str(),
+            f.content => task.content.as_str(),
+            f.tags => task.tags.join(" "),
+            f.created_at => task.created_at.timestamp_micros(),
+        );
+        self.add_task_facets(&mut document, task);
+        document
+    }
+
+    /// Contest submissions are for their submitter only until the results
+    /// are published
+    fn submission_document(&self, row: &SubmissionRow, task: &ProblemOrTask) -> TantivyDocument {
+        let f = self.fields;
+        let (id_high, id_low) = row.id.as_u64_pair();
+        let mut document = doc!(
+            f.id => row.id.to_string(),
+            f.id_high => id_high,
+            f.id_low => id_low,
+            f.kind => "submission",
+            f.task_id => task.id.to_string(),
+            f.task_title => task.title.as_str(),
+            f.content => row.content.as_str(),
+            f.created_at => row.created_at,
+            f.user_id => row.user_id.to_string(),
+        );
+        if !row.results_hidden {
+            document.add_text(f.task_author_id, task.user_id.to_string());
+        }
+        self.add_task_facets(&mut document, task);
+        document
+    }
+
+    /// Add a document per submission row whose task is in `tasks_by_id`;
+    /// returns how many were added
+    fn add_submissions(
+        &self,
+        writer: &IndexWriter,
+        submissions: &[SubmissionRow],
+        tasks_by_id: &HashMap<Uuid, &ProblemOrTask>,
+    ) -> tantivy::Result<usize> {
+        let mut count = 0;
+        for row in submissions {
+            let Some(task) = tasks_by_id.get(&row.task_id) else {
+                continue;
+            };
+            writer.add_document(self.submission_document(row, task))?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn add_task_facets(&self, document: &mut TantivyDocument, task: &ProblemOrTask) {
+        let f = self.fields;
+        document.add_facet(f.difficulty, Facet::from_path([task.difficulty.as_str()]));
+        for tag in &task.tags {
+            document.add_facet(f.tag_facets, Facet::from_path([tag.as_str()]));
+        }
+    }
+
+    /// Exact matches (title boosted) plus typo-tolerant matches of every term
+    fn parse(&self, text: &str) -> (Box<dyn Query>, Box<dyn Query>) {
+        let f = self.fields;
+        let default_fields = vec![f.title, f.content, f.tags];
+
+        let mut exact_parser = QueryParser::for_index(&self.index, default_fields.clone());
+        exact_parser.set_field_boost(f.title, 2.0);
+        let (exact, _) = exact_parser.parse_query_lenient(text);
+
+        let mut fuzzy_parser = QueryParser::for_index(&self.index, default_fields.clone());
+        for field in default_fields {
+            fuzzy_parser.set_field_fuzzy(field, false, 1, true);
+        }
+        let (fuzzy, _) = fuzzy_parser.parse_query_lenient(text);
+
+        let matches: Box<dyn Query> = Box::new(BooleanQuery::new(vec![
+            (Occur::Should, exact.box_clone()),
+            (Occur::Should, fuzzy),
+        ]));
+
+        (exact, matches)
+    }
+
+    /// The documents `query` finds, regardless of page, and the exact part
+    /// of it to highlight; `None` when the scope has nothing indexed
+    fn build_query(&self, query: &SearchQuery) -> Option<(BooleanQuery, Box<dyn Query>)> {
+        let f = self.fields;
+        let kind = match query.scope {
+            SearchScope::All => None,
+            SearchScope::Tasks => Some("task"),
+            SearchScope::Submissions => Some("submission"),
+            SearchScope::Discussions => return None,
+        };
+
+        let (exact, matches) = self.parse(&query.text);
+        let mut clauses = vec![(Occur::Must, matches)];
+        if let Some(kind) = kind {
+            clauses.push((Occur::Must, term_query(f.kind, kind)));
+        }
+
+        // Submissions only for their submitter and the task's author
+        let mut visible = vec![(Occur::Should, term_query(f.kind, "task"))];
+        if let Some(viewer) = query.viewer {
+            let viewer = viewer.to_string();
+            visible.push((Occur::Should, term_query(f.user_id, &viewer)));
+            visible.push((Occur::Should, term_query(f.task_author_id, &viewer)));
+        }
+        clauses.push((Occur::Must, Box::new(BooleanQuery::new(visible))));
+
+        if !query.tags.is_empty() {
+            let occur = match query.tag_match {
+                TagMatch::Any => Occur::Should,
+                TagMatch::All => Occur::Must,
+            };
+            let tags = query
+                .tags
+                .iter()
+                .map(|tag| (occur, facet_query(f.tag_facets, tag)))
+                .collect();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(tags))));
+        }
+
+        if let Some(difficulty) = query.difficulty {
+            clauses.push((Occur::Must, facet_query(f.difficulty, difficulty.as_str())));
+        }
+
+        Some((BooleanQuery::new(clauses), exact))
+    }
+
+    fn search(&self, query: &SearchQuery) -> tantivy::Result<Page<SearchHit>> {
+        let f = self.fields;
+
+        // 1. Build the query
+        let Some((matches, exact)) = self.build_query(query) else {
+            return Ok(Page::from_rows_by(
+                Vec::new(),
+                &query.page,
+                SearchQuery::cursor_for,
+            ));
+        };
+
+        // 2. Collect the page in (rank, created_at, id) order after the cursor
+        let after = query.page.cursor.map(|cursor| {
+            let (id_high, id_low) = cursor.id.as_u64_pair();
+            (
+                cursor.rank.unwrap_or(f64::MAX),
+                cursor.created_at.timestamp_micros(),
+                id_high,
+                id_low,
+            )
+        });
+        let collector =
+            TopDocs::with_limit(query.page.fetch_limit() as usize).tweak_score(PageOrder { after });
+        let searcher = self.reader.searcher();
+        let top = searcher.search(&matches, &collector)?;
+
+        let mut hits = Vec::with_capacity(top.len());
+        for (key, address) in top {
+            let Some((rank, ..)) = key else {
+                continue;
+            };
+            let document: TantivyDocument = searcher.doc(address)?;
+            if let Some(hit) = self.to_hit(&document, rank) {
+                hits.push(hit);
+            }
+        }
+
+        // 3. Highlight only the hits that are returned
+        let mut generator = SnippetGenerator::create(&searcher, &*exact, f.content)?;
+        generator.set_max_num_chars(FALLBACK_SNIPPET_CHARS * 2);
+        for hit in &mut hits {
+            let mut snippet = generator.snippet(&hit.snippet);
+            snippet.set_snippet_prefix_postfix("<mark>", "</mark>");
+            hit.snippet = if snippet.is_empty() {
+                hit.snippet.chars().take(FALLBACK_SNIPPET_CHARS).collect()
+            } else {
+                snippet.to_html()
+            };
+        }
+
+        Ok(Page::from_rows_by(
+            hits,
+            &query.page,
+            SearchQuery::cursor_for,
+        ))
+    }
+
+    fn facets(&self, query: &SearchQuery) -> tantivy::Result<SearchFacets> {
+        let Some((matches, _)) = self.build_query(query) else {
+            return Ok(SearchFacets::default());
+        };
+
+        let mut difficulties = FacetCollector::for_field("difficulty");
+        difficulties.add_facet(Facet::root());
+        let mut tags = FacetCollector::for_field("tag");
+        tags.add_facet(Facet::root());
+
+        let (difficulties, tags) = self
+            .reader
+            .searcher()
+            .search(&matches, &(difficulties, tags))?;
+
+        Ok(SearchFacets {
+            difficulties: facet_counts(&difficulties, usize::MAX),
+            tags: facet_counts(&tags, MAX_TAG_FACETS),
+        })
+    }
+
+    /// Read a stored document back; `snippet` holds the full content until
+    /// highlighting
+    fn to_hit(&self, document: &TantivyDocument, rank: f64) -> Option<SearchHit> {
+        let f = self.fields;
+        let text = |field: Field| document.get_first(field).and_then(|v| v.as_str());
+
+        let kind = match text(f.kind)? {
+            "task" => SearchResultKind::Task,
+            "submission" => SearchResultKind::Submission,
+            _ => return None,
+        };
+
+        Some(SearchHit {
+            kind,
+            id: text(f.id)?.parse().ok()?,
+            task_id: text(f.task_id)?.parse().ok()?,
+            title: text(f.task_title)?.to_string(),
+            snippet: text(f.content)?.to_string(),
+            rank,
+            created_at: DateTime::from_timestamp_micros(
+                document.get_first(f.created_at).and_then(|v| v.as_i64())?,
+            )?,
+        })
+    }
+}
+
+/// Position of a hit in result order: rank, creation time in microseconds and
+/// id, compared in that order
+type HitKey = (f64, i64, u64, u64);
+
+/// Orders hits by [`HitKey`], leaving out those at or before the cursor
+///
+/// Hits before the cursor get no key and sort below every other hit, so the
+/// collector keeps only as many documents as a page needs.
+struct PageOrder {
+    after: Option<HitKey>,
+}
+
+struct SegmentPageOrder {
+    created_at: Column<i64>,
+    id_high: Column<u64>,
+    id_low: Column<u64>,
+    after: Option<HitKey>,
+}
+
+impl ScoreTweaker<Option<HitKey>> for PageOrder {
+    type Child = SegmentPageOrder;
+
+    fn segment_tweaker(&self, segment: &SegmentReader) -> tantivy::Result<SegmentPageOrder> {
+        let fast_fields = segment.fast_fields();
+        Ok(SegmentPageOrder {
+            created_at: fast_fields.i64("created_at")?,
+            id_high: fast_fields.u64("id_high")?,
+            id_low: fast_fields.u64("id_low")?,
+            after: self.after,
+        })
+    }
+}
+
+impl ScoreSegmentTweaker<Option<HitKey>> for SegmentPageOrder {
+    fn score(&mut self, doc: DocId, score: Score) -> Option<HitKey> {
+        let key = (
+            f64::from(score),
+            self.created_at.first(doc)?,
+            self.id_high.first(doc)?,
+            self.id_low.first(doc)?,
+        );
+        match self.after {
+            Some(after) if key >= after => None,
+            _ => Some(key),
+        }
+    }
+}
+
+#[async_trait]
+impl SearchRepositoryTrait for TantivySearchRepository {
+    async fn search(&self, query: &SearchQuery) -> Result<Page<SearchHit>, sqlx::Error> {
+        let query = query.clone();
+        self.blocking(move |index| index.search(&query)).await
+    }
+
+    async fn facets(&self, query: &SearchQuery) -> Result<SearchFacets, sqlx::Error> {
+        let query = query.clone();
+        self.blocking(move |index| index.facets(&query)).await
+    }
+}
@@ -0,0 +1,161 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::TaskAnswerRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{AnswerAttempt, AnswerMatchMode, AnswerVerdict, TaskAnswer, TaskAnswerProgress};
+use sqlx::{PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct TaskAnswerRepository {
+    pool: PgPool,
+}
+
+impl TaskAnswerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TaskAnswerRepositoryTrait for TaskAnswerRepository {
+    async fn upsert(
+        &self,
+        task_id: Uuid,
+        mode: AnswerMatchMode,
+        answers: Vec<String>,
+        case_insensitive: bool,
+        tolerance: Option<f64>,
+    ) -> Result<TaskAnswer, sqlx::Error> {
+        query_as!(
+            TaskAnswer,
+            r#"
+            INSERT INTO task_answers (task_id, mode, answers, case_insensitive, tolerance)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (task_id) DO UPDATE SET
+                mode = EXCLUDED.mode,
+                answers = EXCLUDED.answers,
+                case_insensitive = EXCLUDED.case_insensitive,
+                tolerance = EXCLUDED.tolerance
+            RETURNING
+                task_id,
+                mode as "mode: AnswerMatchMode",
+                answers, case_insensitive, tolerance,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            task_id,
+            mode as AnswerMatchMode,
+            &answers,
+            case_insensitive,
+            tolerance
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn find_by_task(&self, task_id: Uuid) -> Result<Option<TaskAnswer>, sqlx::Error> {
+        query_as!(
+            TaskAnswer,
+            r#"
+            SELECT
+                task_id,
+                mode as "mode: AnswerMatchMode",
+                answers, case_insensitive, tolerance,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM task_answers
+            WHERE task_id = $1
+            "#,
+            task_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn delete(&self, task_id: Uuid) -> Result<(), sqlx::Error> {
+        query!("DELETE FROM task_answers WHERE task_id = $1", task_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_progress(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TaskAnswerProgress>, sqlx::Error> {
+        query_as!(
+            TaskAnswerProgress,
+            r#"
+            SELECT
+                task_id, user_id, attempts, solved_at,
+                last_verdict as "last_verdict: AnswerVerdict",
+                last_attempt_at
+            FROM task_answer_progress
+            WHERE task_id = $1 AND user_id = $2
+            "#,
+            task_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_attempts_by_user(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, sqlx::Error> {
+        query_as!(
+            AnswerAttempt,
+            r#"
+            SELECT
+                id, task_id, user_id, submission_id, submission_version, attempt_number,
+                verdict as "verdict: AnswerVerdict",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM answer_attempts
+            WHERE task_id = $1 AND user_id = $2
+              AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $5
+            "#,
+            task_id,
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_attempts_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, sqlx::Error> {
+        query_as!(
+            AnswerAttempt,
+            r#"
+            SELECT
+                id, task_id, user_id, submission_id, submission_version, attempt_number,
+                verdict as "verdict: AnswerVerdict",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM answer_attempts
+            WHERE submission_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            submission_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+}
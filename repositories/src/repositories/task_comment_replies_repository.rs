@@ -4,6 +4,7 @@ use sqlx::{PgPool, query, query_as, query_scalar};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::pagination::{Page, PageRequest};
 use crate::traits::TaskCommentReplyRepositoryTrait;
 
 pub struct TaskCommentReplyRepository {
@@ -72,7 +73,11 @@ impl TaskCommentReplyRepositoryTrait for TaskCommentReplyRepository {
         .await
     }
 
-    async fn find_by_comment(&self, task_comment_id: Uuid) -> Result<Vec<TaskCommentReply>, sqlx::Error> {
+    async fn find_by_comment(
+        &self,
+        task_comment_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error> {
         query_as!(
             TaskCommentReply,
             r#"
@@ -87,15 +92,25 @@ impl TaskCommentReplyRepositoryTrait for TaskCommentReplyRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comment_replies
             WHERE task_comment_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at ASC
+                AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
             "#,
-            task_comment_id
+            task_comment_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<TaskCommentReply>, sqlx::Error> {
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error> {
         query_as!(
             TaskCommentReply,
             r#"
@@ -110,15 +125,21 @@ impl TaskCommentReplyRepositoryTrait for TaskCommentReplyRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comment_replies
             WHERE user_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_all(&self) -> Result<Vec<TaskCommentReply>, sqlx::Error> {
+    async fn find_all(&self, page: PageRequest) -> Result<Page<TaskCommentReply>, sqlx::Error> {
         query_as!(
             TaskCommentReply,
             r#"
@@ -132,11 +153,17 @@ impl TaskCommentReplyRepositoryTrait for TaskCommentReplyRepository {
                 updated_at as "updated_at!: DateTime<Utc>",
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comment_replies
-            ORDER BY created_at DESC
-            "#
+            WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn count_by_comment(&self, task_comment_id: Uuid) -> Result<i64, sqlx::Error> {
@@ -4,6 +4,7 @@ use models::TaskComment;
 use sqlx::{PgPool, query, query_as, query_scalar};
 use uuid::Uuid;
 
+use crate::pagination::{Page, PageRequest};
 use crate::traits::TaskCommentRepositoryTrait;
 
 pub struct TaskCommentRepository {
@@ -72,7 +73,11 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
         .await
     }
 
-    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<TaskComment>, sqlx::Error> {
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskComment>, sqlx::Error> {
         query_as!(
             TaskComment,
             r#"
@@ -87,15 +92,25 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comments
             WHERE task_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at ASC
+                AND ($2::timestamptz IS NULL OR (created_at, id) > ($2, $3::uuid))
+            ORDER BY created_at ASC, id ASC
+            LIMIT $4
             "#,
-            task_id
+            task_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<TaskComment>, sqlx::Error> {
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskComment>, sqlx::Error> {
         query_as!(
             TaskComment,
             r#"
@@ -110,15 +125,21 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comments
             WHERE user_id = $1 AND deleted_at IS NULL
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            user_id
+            user_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_all(&self) -> Result<Vec<TaskComment>, sqlx::Error> {
+    async fn find_all(&self, page: PageRequest) -> Result<Page<TaskComment>, sqlx::Error> {
         query_as!(
             TaskComment,
             r#"
@@ -132,11 +153,17 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
                 updated_at as "updated_at!: DateTime<Utc>",
                 deleted_at as "deleted_at: DateTime<Utc>"
             FROM task_comments
-            ORDER BY created_at DESC
-            "#
+            WHERE ($1::timestamptz IS NULL OR (created_at, id) < ($1, $2::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $3
+            "#,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn count_by_task(&self, task_id: Uuid) -> Result<i64, sqlx::Error> {
@@ -191,6 +218,18 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
         Ok(())
     }
 
+    async fn remove(&self, id: Uuid, moderator_id: Uuid) -> Result<(), sqlx::Error> {
+        query!(
+            "UPDATE task_comments SET deleted_at = $1, removed_by = $2 WHERE id = $3",
+            Some(Utc::now()),
+            moderator_id,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     async fn hard_delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
         query!("DELETE FROM task_comments WHERE id = $1", id)
             .execute(&self.pool)
@@ -200,7 +239,7 @@ impl TaskCommentRepositoryTrait for TaskCommentRepository {
 
     async fn restore(&self, id: Uuid) -> Result<(), sqlx::Error> {
         query!(
-            "UPDATE task_comments SET deleted_at = NULL WHERE id = $1",
+            "UPDATE task_comments SET deleted_at = NULL, removed_by = NULL WHERE id = $1",
             id
         )
         .execute(&self.pool)
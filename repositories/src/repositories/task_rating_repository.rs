@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use sqlx::{PgPool, query, query_as, query_scalar};
+use sqlx::types::ipnet::IpNet;
 use uuid::Uuid;
-use models::TaskRating;
+use models::{RatingActivity, RatingCount, RatingInterval, RatingOrigin, TaskRating};
 use chrono::{DateTime, Utc};
+use crate::pagination::{Page, PageRequest};
 use crate::traits::TaskRatingRepositoryTrait;
 
 pub struct TaskRatingRepository {
@@ -22,17 +24,20 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
         task_id: Uuid,
         rater_id: Uuid,
         rating_value: i32,
+        origin: &RatingOrigin,
     ) -> Result<TaskRating, sqlx::Error> {
         let id = Uuid::new_v4();
 
         let task_rating = query_as!(
             TaskRating,
             r#"
-            INSERT INTO task_ratings (id, task_id, rater_id, rating_value)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO task_ratings (id, task_id, rater_id, rating_value, client_ip, session_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT (task_id, rater_id) 
             DO UPDATE SET 
                 rating_value = EXCLUDED.rating_value,
+                client_ip = COALESCE(EXCLUDED.client_ip, task_ratings.client_ip),
+                session_id = COALESCE(EXCLUDED.session_id, task_ratings.session_id),
                 updated_at = NOW()
             RETURNING
                 id,
@@ -45,7 +50,9 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
             id,
             task_id,
             rater_id,
-            rating_value
+            rating_value,
+            origin.client_ip.map(IpNet::from),
+            origin.session_id.as_deref()
         )
         .fetch_one(&self.pool)
         .await?;
@@ -73,7 +80,11 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
         .await
     }
 
-    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<TaskRating>, sqlx::Error> {
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error> {
         query_as!(
             TaskRating,
             r#"
@@ -86,15 +97,25 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
                 updated_at as "updated_at!: DateTime<Utc>"
             FROM task_ratings
             WHERE task_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            task_id
+            task_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
-    async fn find_by_rater(&self, rater_id: Uuid) -> Result<Vec<TaskRating>, sqlx::Error> {
+    async fn find_by_rater(
+        &self,
+        rater_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error> {
         query_as!(
             TaskRating,
             r#"
@@ -107,12 +128,18 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
                 updated_at as "updated_at!: DateTime<Utc>"
             FROM task_ratings
             WHERE rater_id = $1
-            ORDER BY created_at DESC
+                AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
             "#,
-            rater_id
+            rater_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
         )
         .fetch_all(&self.pool)
         .await
+        .map(|rows| Page::from_rows(rows, &page))
     }
 
     async fn find_by_task_and_rater(
@@ -145,7 +172,7 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
             r#"
             SELECT AVG(rating_value)::FLOAT8 as "avg"
             FROM task_ratings
-            WHERE task_id = $1
+            WHERE task_id = $1 AND neutralized_at IS NULL
             "#,
             task_id
         )
@@ -160,7 +187,7 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
             r#"
             SELECT COUNT(*)
             FROM task_ratings
-            WHERE task_id = $1
+            WHERE task_id = $1 AND neutralized_at IS NULL
             "#,
             task_id
         )
@@ -224,4 +251,61 @@ impl TaskRatingRepositoryTrait for TaskRatingRepository {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn get_distribution(&self, task_id: Uuid) -> Result<Vec<RatingCount>, sqlx::Error> {
+        query_as!(
+            RatingCount,
+            r#"
+            SELECT
+                scale.value as "rating_value!",
+                COUNT(r.id) as "count!"
+            FROM generate_series(1, 4) AS scale(value)
+            LEFT JOIN task_ratings r
+                ON r.task_id = $1
+                AND r.rating_value = scale.value
+                AND r.neutralized_at IS NULL
+            GROUP BY scale.value
+            ORDER BY scale.value
+            "#,
+            task_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn get_activity(
+        &self,
+        task_id: Uuid,
+        interval: RatingInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RatingActivity>, sqlx::Error> {
+        query_as!(
+            RatingActivity,
+            r#"
+            SELECT
+                buckets.bucket_start as "bucket_start!: DateTime<Utc>",
+                COUNT(r.id) as "ratings!",
+                AVG(r.rating_value)::FLOAT8 as "average_rating"
+            FROM generate_series(
+                date_trunc($2, $3::timestamptz, 'UTC'),
+                $4::timestamptz,
+                ('1 ' || $2)::interval
+            ) AS buckets(bucket_start)
+            LEFT JOIN task_ratings r
+                ON r.task_id = $1
+                AND r.neutralized_at IS NULL
+                AND r.created_at >= buckets.bucket_start
+                AND r.created_at < buckets.bucket_start + ('1 ' || $2)::interval
+            GROUP BY buckets.bucket_start
+            ORDER BY buckets.bucket_start
+            "#,
+            task_id,
+            interval.as_str(),
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,72 @@
+use crate::pagination::{Page, PageRequest};
+use crate::traits::TaskRevisionRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{Difficulty, TaskRevision};
+use sqlx::{PgPool, query_as};
+use uuid::Uuid;
+
+pub struct TaskRevisionRepository {
+    pool: PgPool,
+}
+
+impl TaskRevisionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TaskRevisionRepositoryTrait for TaskRevisionRepository {
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRevision>, sqlx::Error> {
+        query_as!(
+            TaskRevision,
+            r#"
+            SELECT
+                id, task_id, revision, title, content, file_url,
+                ARRAY(SELECT jsonb_array_elements_text(tags)) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM task_revisions
+            WHERE task_id = $1
+              AND ($2::timestamptz IS NULL OR (created_at, id) < ($2, $3::uuid))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $4
+            "#,
+            task_id,
+            page.cursor_created_at(),
+            page.cursor_id(),
+            page.fetch_limit()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map(|rows| Page::from_rows(rows, &page))
+    }
+
+    async fn find_by_revision(
+        &self,
+        task_id: Uuid,
+        revision: i32,
+    ) -> Result<Option<TaskRevision>, sqlx::Error> {
+        query_as!(
+            TaskRevision,
+            r#"
+            SELECT
+                id, task_id, revision, title, content, file_url,
+                ARRAY(SELECT jsonb_array_elements_text(tags)) as "tags!: Vec<String>",
+                difficulty as "difficulty: Difficulty",
+                created_at as "created_at!: DateTime<Utc>"
+            FROM task_revisions
+            WHERE task_id = $1 AND revision = $2
+            "#,
+            task_id,
+            revision
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
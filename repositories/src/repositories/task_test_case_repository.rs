@@ -0,0 +1,259 @@
+use crate::traits::{NewTestCase, TaskTestCaseRepositoryTrait};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::TaskTestCase;
+use sqlx::{PgConnection, PgPool, query, query_as};
+use uuid::Uuid;
+
+pub struct TaskTestCaseRepository {
+    pool: PgPool,
+}
+
+impl TaskTestCaseRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Lock the task so concurrent writers see consistent positions, and
+    /// return its last position
+    async fn lock_task(conn: &mut PgConnection, task_id: Uuid) -> Result<i32, sqlx::Error> {
+        query!(
+            "SELECT id FROM problems_or_tasks WHERE id = $1 FOR UPDATE",
+            task_id
+        )
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        query!(
+            r#"SELECT COALESCE(MAX(position), 0) as "position!" FROM task_test_cases WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map(|row| row.position)
+    }
+
+    async fn insert(
+        conn: &mut PgConnection,
+        task_id: Uuid,
+        position: i32,
+        test_case: NewTestCase,
+    ) -> Result<TaskTestCase, sqlx::Error> {
+        query_as!(
+            TaskTestCase,
+            r#"
+            INSERT INTO task_test_cases (
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points
+            )
+            VALUES (
+                $1, $2, $3, $4, $5,
+                COALESCE($6, 2000), COALESCE($7, 262144), $8, COALESCE($9, 1)
+            )
+            RETURNING
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            Uuid::new_v4(),
+            task_id,
+            position,
+            test_case.input,
+            test_case.expected_output,
+            test_case.time_limit_ms,
+            test_case.memory_limit_kb,
+            test_case.is_sample,
+            test_case.points
+        )
+        .fetch_one(&mut *conn)
+        .await
+    }
+}
+
+#[async_trait]
+impl TaskTestCaseRepositoryTrait for TaskTestCaseRepository {
+    async fn create(
+        &self,
+        task_id: Uuid,
+        test_case: NewTestCase,
+    ) -> Result<TaskTestCase, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let last = Self::lock_task(&mut tx, task_id).await?;
+        let test_case = Self::insert(&mut tx, task_id, last + 1, test_case).await?;
+
+        tx.commit().await?;
+        Ok(test_case)
+    }
+
+    async fn import(
+        &self,
+        task_id: Uuid,
+        test_cases: Vec<NewTestCase>,
+        replace: bool,
+    ) -> Result<Vec<TaskTestCase>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut last = Self::lock_task(&mut tx, task_id).await?;
+        if replace {
+            query!("DELETE FROM task_test_cases WHERE task_id = $1", task_id)
+                .execute(&mut *tx)
+                .await?;
+            last = 0;
+        }
+
+        let mut created = Vec::with_capacity(test_cases.len());
+        for test_case in test_cases {
+            last += 1;
+            created.push(Self::insert(&mut tx, task_id, last, test_case).await?);
+        }
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskTestCase>, sqlx::Error> {
+        query_as!(
+            TaskTestCase,
+            r#"
+            SELECT
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM task_test_cases
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<TaskTestCase>, sqlx::Error> {
+        query_as!(
+            TaskTestCase,
+            r#"
+            SELECT
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM task_test_cases
+            WHERE task_id = $1
+            ORDER BY position
+            "#,
+            task_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn find_samples_by_task(&self, task_id: Uuid) -> Result<Vec<TaskTestCase>, sqlx::Error> {
+        query_as!(
+            TaskTestCase,
+            r#"
+            SELECT
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            FROM task_test_cases
+            WHERE task_id = $1 AND is_sample
+            ORDER BY position
+            "#,
+            task_id
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: Option<String>,
+        expected_output: Option<String>,
+        time_limit_ms: Option<i32>,
+        memory_limit_kb: Option<i32>,
+        is_sample: Option<bool>,
+        points: Option<i32>,
+    ) -> Result<TaskTestCase, sqlx::Error> {
+        query_as!(
+            TaskTestCase,
+            r#"
+            UPDATE task_test_cases
+            SET
+                input = COALESCE($2, input),
+                expected_output = COALESCE($3, expected_output),
+                time_limit_ms = COALESCE($4, time_limit_ms),
+                memory_limit_kb = COALESCE($5, memory_limit_kb),
+                is_sample = COALESCE($6, is_sample),
+                points = COALESCE($7, points)
+            WHERE id = $1
+            RETURNING
+                id, task_id, position, input, expected_output,
+                time_limit_ms, memory_limit_kb, is_sample, points,
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            input,
+            expected_output,
+            time_limit_ms,
+            memory_limit_kb,
+            is_sample,
+            points
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    async fn reorder(&self, task_id: Uuid, ids: &[Uuid]) -> Result<Vec<TaskTestCase>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::lock_task(&mut tx, task_id).await?;
+
+        // The unique (task_id, position) constraint is deferrable, so it is
+        // checked once the whole permutation is applied
+        query!(
+            r#"
+            UPDATE task_test_cases t
+            SET position = o.position::INTEGER
+            FROM UNNEST($2::UUID[]) WITH ORDINALITY AS o(id, position)
+            WHERE t.id = o.id AND t.task_id = $1
+            "#,
+            task_id,
+            ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        self.find_by_task(task_id).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let deleted = query!(
+            "DELETE FROM task_test_cases WHERE id = $1 RETURNING task_id, position",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(deleted) = deleted {
+            query!(
+                "UPDATE task_test_cases SET position = position - 1 WHERE task_id = $1 AND position > $2",
+                deleted.task_id,
+                deleted.position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
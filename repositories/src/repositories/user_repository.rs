@@ -58,7 +58,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             "#,
             email,
             password_hash,
@@ -93,7 +94,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             FROM users
             WHERE id = $1
             "#,
@@ -103,6 +105,35 @@ impl UserRepositoryTrait for UserRepository {
         .await
     }
     
+    async fn get_users_by_ids(&self, user_ids: &[Uuid]) -> Result<Vec<User>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"
+            SELECT
+                id,
+                email,
+                password_hash,
+                display_name,
+                bio,
+                first_name,
+                last_name,
+                avatar_url,
+                reputation_score as "reputation_score!: Decimal",
+                total_ratings_given as "total_ratings_given!: i32",
+                total_ratings_received as "total_ratings_received!: i32",
+                email_verified_at as "email_verified_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
+            FROM users
+            WHERE id = ANY($1)
+            "#,
+            user_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+    
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error> {
         sqlx::query_as!(
             User,
@@ -121,7 +152,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             FROM users
             WHERE email = $1
             "#,
@@ -149,7 +181,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             FROM users
             WHERE display_name = $1
             "#,
@@ -186,6 +219,8 @@ impl UserRepositoryTrait for UserRepository {
                 first_name = COALESCE($6, first_name),
                 last_name = COALESCE($7, last_name),
                 avatar_url = COALESCE($8, avatar_url),
+                -- An explicitly set URL replaces any uploaded avatar
+                avatar_hash = CASE WHEN $8::text IS NULL THEN avatar_hash END,
                 reputation_score = COALESCE($9, reputation_score),
                 total_ratings_given = COALESCE($10, total_ratings_given),
                 total_ratings_received = COALESCE($11, total_ratings_received),
@@ -206,7 +241,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             "#,
             user_id,
             email,
@@ -225,6 +261,46 @@ impl UserRepositoryTrait for UserRepository {
         .await
     }
 
+    async fn set_avatar(
+        &self,
+        user_id: &Uuid,
+        avatar_url: Option<&str>,
+        avatar_hash: Option<&str>,
+    ) -> Result<User, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users
+            SET
+                avatar_url = $2,
+                avatar_hash = $3,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING
+                id,
+                email,
+                password_hash,
+                display_name,
+                bio,
+                first_name,
+                last_name,
+                avatar_url,
+                reputation_score as "reputation_score!: Decimal",
+                total_ratings_given as "total_ratings_given!: i32",
+                total_ratings_received as "total_ratings_received!: i32",
+                email_verified_at as "email_verified_at: DateTime<Utc>",
+                created_at as "created_at!: DateTime<Utc>",
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
+            "#,
+            user_id,
+            avatar_url,
+            avatar_hash
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
     async fn delete_user(&self, user_id: &Uuid) -> Result<User, sqlx::Error> {
         sqlx::query_as!(
             User,
@@ -245,7 +321,8 @@ impl UserRepositoryTrait for UserRepository {
                 total_ratings_received as "total_ratings_received!: i32",
                 email_verified_at as "email_verified_at: DateTime<Utc>",
                 created_at as "created_at!: DateTime<Utc>",
-                updated_at as "updated_at!: DateTime<Utc>"
+                updated_at as "updated_at!: DateTime<Utc>",
+                avatar_hash
             "#,
             user_id
         )
@@ -0,0 +1,104 @@
+//! Full-text search requests.
+//!
+//! A [`SearchQuery`] describes what to search for and where; it is backend
+//! agnostic so every [`SearchRepositoryTrait`](crate::traits::SearchRepositoryTrait)
+//! implementation can serve it.
+
+use crate::pagination::{Cursor, PageRequest};
+use crate::task_query::TagMatch;
+use models::{Difficulty, SearchHit};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Which content a search runs over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchScope {
+    #[default]
+    All,
+    Tasks,
+    Submissions,
+    /// Comments and replies on both tasks and submissions
+    Discussions,
+}
+
+impl SearchScope {
+    pub fn includes_tasks(&self) -> bool {
+        matches!(self, SearchScope::All | SearchScope::Tasks)
+    }
+
+    pub fn includes_submissions(&self) -> bool {
+        matches!(self, SearchScope::All | SearchScope::Submissions)
+    }
+
+    pub fn includes_discussions(&self) -> bool {
+        matches!(self, SearchScope::All | SearchScope::Discussions)
+    }
+}
+
+/// Most tag counts returned with [`SearchFacets`](models::SearchFacets)
+pub const MAX_TAG_FACETS: usize = 20;
+
+/// Search text, scope, facet filters and page
+///
+/// `text` uses web search syntax: `"quoted phrases"`, `or` and `-excluded`
+/// terms are supported. `tags` and `difficulty` filter on the task a hit
+/// belongs to, so they narrow submissions and discussions as well.
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    pub scope: SearchScope,
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub difficulty: Option<Difficulty>,
+    pub page: PageRequest,
+    /// Who searches. Submissions can hold a task's answer, so only their
+    /// owner and the task's author find them; without a viewer no
+    /// submission is found
+    pub viewer: Option<Uuid>,
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn scope(mut self, scope: SearchScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn tags(mut self, tags: Vec<String>, tag_match: TagMatch) -> Self {
+        self.tags = tags;
+        self.tag_match = tag_match;
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    pub fn page(mut self, page: PageRequest) -> Self {
+        self.page = page;
+        self
+    }
+
+    pub fn viewer(mut self, user_id: Uuid) -> Self {
+        self.viewer = Some(user_id);
+        self
+    }
+
+    /// Results are ranked, so only a cursor that carries a rank can resume them
+    pub fn cursor_matches(&self) -> bool {
+        self.page.cursor.is_none_or(|cursor| cursor.rank.is_some())
+    }
+
+    /// Cursor pointing just after `hit` in rank order
+    pub fn cursor_for(hit: &SearchHit) -> Cursor {
+        Cursor::new(hit.created_at, hit.id).with_rank(hit.rank)
+    }
+}
@@ -0,0 +1,75 @@
+//! Live updates of a task page.
+//!
+//! The event publishing repositories in
+//! [`crate::repositories::event_publishing_repository`] report every write to
+//! a task's comments, replies, ratings and submissions to a
+//! [`TaskEventPublisher`], which fans them out to the clients watching the
+//! task.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// What happened on a task page; the SSE event name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TaskEventKind {
+    #[serde(rename = "comment.created")]
+    CommentCreated,
+    #[serde(rename = "comment.updated")]
+    CommentUpdated,
+    #[serde(rename = "comment.deleted")]
+    CommentDeleted,
+    #[serde(rename = "comment.restored")]
+    CommentRestored,
+    #[serde(rename = "reply.created")]
+    ReplyCreated,
+    #[serde(rename = "reply.updated")]
+    ReplyUpdated,
+    #[serde(rename = "reply.deleted")]
+    ReplyDeleted,
+    #[serde(rename = "reply.restored")]
+    ReplyRestored,
+    #[serde(rename = "rating.changed")]
+    RatingChanged,
+    #[serde(rename = "submission.created")]
+    SubmissionCreated,
+    #[serde(rename = "submission.updated")]
+    SubmissionUpdated,
+    #[serde(rename = "submission.deleted")]
+    SubmissionDeleted,
+}
+
+impl TaskEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskEventKind::CommentCreated => "comment.created",
+            TaskEventKind::CommentUpdated => "comment.updated",
+            TaskEventKind::CommentDeleted => "comment.deleted",
+            TaskEventKind::CommentRestored => "comment.restored",
+            TaskEventKind::ReplyCreated => "reply.created",
+            TaskEventKind::ReplyUpdated => "reply.updated",
+            TaskEventKind::ReplyDeleted => "reply.deleted",
+            TaskEventKind::ReplyRestored => "reply.restored",
+            TaskEventKind::RatingChanged => "rating.changed",
+            TaskEventKind::SubmissionCreated => "submission.created",
+            TaskEventKind::SubmissionUpdated => "submission.updated",
+            TaskEventKind::SubmissionDeleted => "submission.deleted",
+        }
+    }
+}
+
+impl fmt::Display for TaskEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Delivers task events to the clients watching a task
+///
+/// Publishing happens after the write has committed and must never fail it;
+/// implementations log delivery errors instead of returning them.
+#[async_trait]
+pub trait TaskEventPublisher: Send + Sync {
+    async fn publish(&self, task_id: Uuid, kind: TaskEventKind, data: serde_json::Value);
+}
@@ -0,0 +1,227 @@
+//! Filtered and sorted task listing.
+//!
+//! [`TaskQuery`] is built up with chained setters and turned into SQL by
+//! [`TaskQuery::push_sql`]; the repository only has to run it.
+
+use crate::pagination::{Cursor, PageRequest};
+use chrono::{DateTime, Utc};
+use models::{Difficulty, ProblemOrTask};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Columns selected for every `ProblemOrTask` read through a query builder
+pub(crate) const TASK_COLUMNS: &str = r#"
+    id, user_id, title, content, file_url,
+    ARRAY(SELECT jsonb_array_elements_text(COALESCE(tags, '[]'::jsonb))) AS tags,
+    difficulty,
+    COALESCE(average_rating, 0)::FLOAT8 AS average_rating,
+    COALESCE(total_ratings, 0) AS total_ratings,
+    bayesian_rating, wilson_score,
+    COALESCE(total_submissions, 0) AS total_submissions,
+    COALESCE(view_count, 0) AS view_count,
+    current_revision,
+    created_at, updated_at, deleted_at
+"#;
+
+/// Whether a task must carry any or all of the requested tags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSort {
+    #[default]
+    Newest,
+    MostViewed,
+    /// Bayesian average, so a few high ratings do not outrank many good ones
+    TopRated,
+    /// Plain mean rating
+    HighestAverage,
+    /// Wilson lower bound of the share of positive ratings
+    MostPositive,
+    MostSubmitted,
+}
+
+impl TaskSort {
+    /// SQL expression the list is ordered by, ahead of `(created_at, id)`
+    fn rank_expression(&self) -> Option<&'static str> {
+        match self {
+            TaskSort::Newest => None,
+            TaskSort::MostViewed => Some("COALESCE(view_count, 0)::FLOAT8"),
+            TaskSort::TopRated => Some("bayesian_rating"),
+            TaskSort::HighestAverage => Some("COALESCE(average_rating, 0)::FLOAT8"),
+            TaskSort::MostPositive => Some("wilson_score"),
+            TaskSort::MostSubmitted => Some("COALESCE(total_submissions, 0)::FLOAT8"),
+        }
+    }
+
+    /// The rank value of a task, matching [`TaskSort::rank_expression`]
+    fn rank_of(&self, task: &ProblemOrTask) -> Option<f64> {
+        match self {
+            TaskSort::Newest => None,
+            TaskSort::MostViewed => Some(task.view_count.into()),
+            TaskSort::TopRated => Some(task.bayesian_rating),
+            TaskSort::HighestAverage => Some(task.average_rating),
+            TaskSort::MostPositive => Some(task.wilson_score),
+            TaskSort::MostSubmitted => Some(task.total_submissions.into()),
+        }
+    }
+}
+
+/// Filters, sort order and page for listing tasks
+#[derive(Debug, Clone, Default)]
+pub struct TaskQuery {
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub difficulty: Option<Difficulty>,
+    pub author_id: Option<Uuid>,
+    pub min_rating: Option<f64>,
+    pub max_rating: Option<f64>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub sort: TaskSort,
+    pub page: PageRequest,
+}
+
+impl TaskQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tags(mut self, tags: Vec<String>, tag_match: TagMatch) -> Self {
+        self.tags = tags;
+        self.tag_match = tag_match;
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    pub fn author(mut self, author_id: Uuid) -> Self {
+        self.author_id = Some(author_id);
+        self
+    }
+
+    pub fn rating_between(mut self, min: Option<f64>, max: Option<f64>) -> Self {
+        self.min_rating = min;
+        self.max_rating = max;
+        self
+    }
+
+    pub fn created_between(
+        mut self,
+        after: Option<DateTime<Utc>>,
+        before: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.created_after = after;
+        self.created_before = before;
+        self
+    }
+
+    pub fn sort_by(mut self, sort: TaskSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn page(mut self, page: PageRequest) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// A ranked sort can only resume from a cursor that carries a rank
+    pub fn cursor_matches_sort(&self) -> bool {
+        match self.page.cursor {
+            Some(cursor) => self.sort.rank_expression().is_none() || cursor.rank.is_some(),
+            None => true,
+        }
+    }
+
+    /// Cursor pointing just after `task` in this query's order
+    pub fn cursor_for(&self, task: &ProblemOrTask) -> Cursor {
+        let cursor = Cursor::new(task.created_at, task.id);
+        match self.sort.rank_of(task) {
+            Some(rank) => cursor.with_rank(rank),
+            None => cursor,
+        }
+    }
+
+    /// Append the full `SELECT` for this query to `builder`
+    pub(crate) fn push_sql(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push("SELECT ");
+        builder.push(TASK_COLUMNS);
+        builder.push(" FROM problems_or_tasks WHERE deleted_at IS NULL");
+
+        // `?|` / `?&` are served by the GIN index on tags
+        if !self.tags.is_empty() {
+            builder.push(match self.tag_match {
+                TagMatch::Any => " AND tags ?| ",
+                TagMatch::All => " AND tags ?& ",
+            });
+            builder.push_bind(self.tags.clone());
+        }
+
+        if let Some(difficulty) = self.difficulty {
+            builder.push(" AND difficulty = ");
+            builder.push_bind(difficulty);
+        }
+
+        if let Some(author_id) = self.author_id {
+            builder.push(" AND user_id = ");
+            builder.push_bind(author_id);
+        }
+
+        if let Some(min_rating) = self.min_rating {
+            builder.push(" AND COALESCE(average_rating, 0) >= ");
+            builder.push_bind(min_rating);
+        }
+
+        if let Some(max_rating) = self.max_rating {
+            builder.push(" AND COALESCE(average_rating, 0) <= ");
+            builder.push_bind(max_rating);
+        }
+
+        if let Some(created_after) = self.created_after {
+            builder.push(" AND created_at >= ");
+            builder.push_bind(created_after);
+        }
+
+        if let Some(created_before) = self.created_before {
+            builder.push(" AND created_at < ");
+            builder.push_bind(created_before);
+        }
+
+        let rank = self.sort.rank_expression();
+
+        if let Some(cursor) = self.page.cursor {
+            match (rank, cursor.rank) {
+                (Some(rank), Some(cursor_rank)) => {
+                    builder.push(format!(" AND ({}, created_at, id) < (", rank));
+                    builder.push_bind(cursor_rank);
+                    builder.push(", ");
+                }
+                _ => {
+                    builder.push(" AND (created_at, id) < (");
+                }
+            }
+            builder.push_bind(cursor.created_at);
+            builder.push(", ");
+            builder.push_bind(cursor.id);
+            builder.push(")");
+        }
+
+        builder.push(" ORDER BY ");
+        if let Some(rank) = rank {
+            builder.push(format!("{} DESC, ", rank));
+        }
+        builder.push("created_at DESC, id DESC LIMIT ");
+        builder.push_bind(self.page.fetch_limit());
+    }
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use models::{ActivityDay, ActivityStreak, UserTimezone};
+use uuid::Uuid;
+
+/// Per-day activity read from when tasks, submissions, comments and ratings
+/// were created, in each user's time zone
+#[async_trait]
+pub trait ActivityRepositoryTrait: Send + Sync {
+    /// `None` when the user does not exist
+    async fn find_timezone(&self, user_id: Uuid) -> Result<Option<UserTimezone>, sqlx::Error>;
+
+    /// Returns `None` when the user does not exist
+    async fn set_timezone(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+    ) -> Result<Option<UserTimezone>, sqlx::Error>;
+
+    /// Whether Postgres knows `timezone` by that name
+    async fn is_known_timezone(&self, timezone: &str) -> Result<bool, sqlx::Error>;
+
+    /// Days from `from` to `to` inclusive on which the user did anything,
+    /// oldest first
+    async fn find_days(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<ActivityDay>, sqlx::Error>;
+
+    async fn find_streak(
+        &self,
+        user_id: Uuid,
+        timezone: &str,
+    ) -> Result<ActivityStreak, sqlx::Error>;
+}
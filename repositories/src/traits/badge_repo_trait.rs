@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use models::{Badge, BadgeCheck, BadgeRule, UserBadge};
+use uuid::Uuid;
+
+/// The badge catalog and the badges users earned
+///
+/// The database queues a check whenever a user does something a badge rule
+/// counts; the badges worker takes the checks and awards what the rules
+/// allow.
+#[async_trait]
+pub trait BadgeRepositoryTrait: Send + Sync {
+    /// Every badge, by rule and threshold
+    async fn find_catalog(&self) -> Result<Vec<Badge>, sqlx::Error>;
+
+    /// Badges a user earned, most recent first
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<UserBadge>, sqlx::Error>;
+
+    /// Remove and return up to `limit` queued checks, oldest first
+    async fn take_checks(&self, limit: i64) -> Result<Vec<BadgeCheck>, sqlx::Error>;
+
+    /// Queue a check of every rule for every user
+    async fn queue_recheck_all(&self) -> Result<u64, sqlx::Error>;
+
+    /// A user's current count for a rule
+    async fn count(&self, user_id: Uuid, rule: BadgeRule) -> Result<i64, sqlx::Error>;
+
+    /// Award badges to a user; ones they already have are left alone.
+    /// Returns the ids of the badges newly awarded.
+    async fn award(&self, user_id: Uuid, badge_ids: &[Uuid]) -> Result<Vec<Uuid>, sqlx::Error>;
+}
@@ -0,0 +1,41 @@
+use crate::repositories::BlobDeletion;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::Blob;
+use uuid::Uuid;
+
+/// Metadata of uploaded files; the bytes live in a `BlobStore`
+#[async_trait]
+pub trait BlobRepositoryTrait: Send + Sync {
+    /// Record an uploaded blob, or return the existing row when the same
+    /// content was uploaded before, refreshing its `last_uploaded_at`
+    async fn create(
+        &self,
+        sha256: &str,
+        content_type: &str,
+        size_bytes: i64,
+        uploaded_by: Uuid,
+    ) -> Result<Blob, sqlx::Error>;
+
+    async fn find_by_sha256(&self, sha256: &str) -> Result<Option<Blob>, sqlx::Error>;
+
+    /// Blobs last uploaded before `older_than` that no `file_url` points at,
+    /// oldest first
+    async fn find_orphans(
+        &self,
+        older_than: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Blob>, sqlx::Error>;
+
+    /// Delete the row unless something started referencing the blob or it
+    /// was uploaded again since it was found
+    ///
+    /// Returns the open deletion, or `None` when the blob is kept. Delete
+    /// the stored bytes before committing it: an upload of the same content
+    /// waits until then, so it never records a row whose bytes are gone.
+    async fn delete_if_orphaned(
+        &self,
+        sha256: &str,
+        older_than: DateTime<Utc>,
+    ) -> Result<Option<BlobDeletion>, sqlx::Error>;
+}
@@ -0,0 +1,127 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{Contest, ContestAttempt, ContestParticipant, ContestScoring, ContestTask};
+use uuid::Uuid;
+
+/// A contest to create; `None` penalty and freeze fall back to the column
+/// defaults
+#[derive(Debug, Clone)]
+pub struct NewContest {
+    pub created_by: Uuid,
+    pub title: String,
+    pub description: String,
+    pub scoring: ContestScoring,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub penalty_minutes: Option<i32>,
+    pub freeze_minutes: Option<i32>,
+}
+
+/// A task of a contest, in contest order; `None` points fall back to the
+/// column default
+#[derive(Debug, Clone, Copy)]
+pub struct NewContestTask {
+    pub task_id: Uuid,
+    pub points: Option<i32>,
+}
+
+/// Contests, their tasks and participants, and the submissions made in them
+///
+/// Submissions are only linked to a contest while it runs; the database
+/// also refuses new versions of a linked submission outside that window.
+#[async_trait]
+#[allow(clippy::too_many_arguments)]
+pub trait ContestRepositoryTrait: Send + Sync {
+    /// Create a contest with its tasks
+    async fn create(
+        &self,
+        contest: NewContest,
+        tasks: Vec<NewContestTask>,
+    ) -> Result<Contest, sqlx::Error>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Contest>, sqlx::Error>;
+
+    /// All contests, newest first
+    async fn find_all(&self, page: PageRequest) -> Result<Page<Contest>, sqlx::Error>;
+
+    /// The contest a submission was made in, if any
+    async fn find_by_submission(&self, submission_id: Uuid)
+    -> Result<Option<Contest>, sqlx::Error>;
+
+    async fn update(
+        &self,
+        id: Uuid,
+        title: Option<String>,
+        description: Option<String>,
+        scoring: Option<ContestScoring>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+        penalty_minutes: Option<i32>,
+        freeze_minutes: Option<i32>,
+    ) -> Result<Contest, sqlx::Error>;
+
+    /// Make the results public; keeps the first publication time
+    async fn publish_results(&self, id: Uuid) -> Result<Contest, sqlx::Error>;
+
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+
+    /// Tasks of a contest in contest order
+    async fn find_tasks(&self, contest_id: Uuid) -> Result<Vec<ContestTask>, sqlx::Error>;
+
+    /// Replace the tasks of a contest, in the given order
+    async fn set_tasks(
+        &self,
+        contest_id: Uuid,
+        tasks: Vec<NewContestTask>,
+    ) -> Result<Vec<ContestTask>, sqlx::Error>;
+
+    /// Register a user; registering again keeps the first registration
+    async fn register(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ContestParticipant, sqlx::Error>;
+
+    /// Remove a registration along with the user's contest submissions
+    async fn unregister(&self, contest_id: Uuid, user_id: Uuid) -> Result<(), sqlx::Error>;
+
+    async fn find_participant(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ContestParticipant>, sqlx::Error>;
+
+    /// Participants in registration order
+    async fn find_participants(
+        &self,
+        contest_id: Uuid,
+    ) -> Result<Vec<ContestParticipant>, sqlx::Error>;
+
+    /// The submission a participant works on for a contest task
+    async fn find_submission_id(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error>;
+
+    /// Make `submission_id` the participant's submission for a contest task
+    async fn link_submission(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+        task_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Every attempt made during a contest, oldest first
+    async fn find_attempts(&self, contest_id: Uuid) -> Result<Vec<ContestAttempt>, sqlx::Error>;
+
+    /// A participant's attempts at a contest, oldest first
+    async fn find_attempts_by_user(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<ContestAttempt>, sqlx::Error>;
+}
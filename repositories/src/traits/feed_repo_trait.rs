@@ -0,0 +1,31 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::FeedItem;
+use uuid::Uuid;
+
+/// A user's activity feed, assembled from their follows when read
+#[async_trait]
+pub trait FeedRepositoryTrait: Send + Sync {
+    /// The feed of `user_id`, newest first
+    async fn find_feed(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<FeedItem>, sqlx::Error>;
+
+    /// Unread entries of the feed, counting no further than `cap`
+    async fn count_unread(&self, user_id: Uuid, cap: i64) -> Result<i64, sqlx::Error>;
+
+    /// Where the user's read marker is; `None` when they never marked their
+    /// feed read
+    async fn find_read_until(&self, user_id: Uuid) -> Result<Option<DateTime<Utc>>, sqlx::Error>;
+
+    /// Mark the entries up to `until` read. The marker never moves back.
+    /// Returns where it is now.
+    async fn mark_read(
+        &self,
+        user_id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, sqlx::Error>;
+}
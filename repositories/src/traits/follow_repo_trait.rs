@@ -0,0 +1,59 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::{TaskFollow, UserFollow};
+use uuid::Uuid;
+
+/// Who follows which users and tasks
+#[async_trait]
+pub trait FollowRepositoryTrait: Send + Sync {
+    /// Follow a user; following them again keeps the original follow
+    async fn follow_user(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<UserFollow, sqlx::Error>;
+
+    /// Returns the removed follow, or `None` when there was none
+    async fn unfollow_user(
+        &self,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> Result<Option<UserFollow>, sqlx::Error>;
+
+    /// Users following `user_id`, most recent first
+    async fn find_followers(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<UserFollow>, sqlx::Error>;
+
+    /// Users `user_id` follows, most recent first
+    async fn find_following(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<UserFollow>, sqlx::Error>;
+
+    /// Follow a task; following it again keeps the original follow
+    async fn follow_task(&self, user_id: Uuid, task_id: Uuid) -> Result<TaskFollow, sqlx::Error>;
+
+    /// Returns the removed follow, or `None` when there was none
+    async fn unfollow_task(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<TaskFollow>, sqlx::Error>;
+
+    async fn find_task_follow(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Option<TaskFollow>, sqlx::Error>;
+
+    /// Tasks `user_id` follows, most recent first
+    async fn find_followed_tasks(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskFollow>, sqlx::Error>;
+}
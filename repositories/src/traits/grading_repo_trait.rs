@@ -0,0 +1,66 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{GradingResult, GradingRun, GradingVerdict, ProgrammingLanguage};
+use uuid::Uuid;
+
+/// Outcome of one test case, as reported by the grader
+#[derive(Debug, Clone)]
+pub struct NewGradingResult {
+    pub test_case_id: Uuid,
+    pub position: i32,
+    pub verdict: GradingVerdict,
+    pub points: i32,
+    pub max_points: i32,
+    pub time_ms: i32,
+    pub memory_kb: i32,
+    pub exit_code: Option<i32>,
+}
+
+/// Grading runs and their per-test results
+///
+/// Runs are queued by the database when a code submission is submitted and
+/// processed by the `grading-worker`.
+#[async_trait]
+pub trait GradingRepositoryTrait: Send + Sync {
+    /// Queue a run by hand, e.g. to regrade after the tests changed
+    async fn enqueue(
+        &self,
+        submission_id: Uuid,
+        submission_version: i32,
+        language: ProgrammingLanguage,
+    ) -> Result<GradingRun, sqlx::Error>;
+
+    /// Mark the oldest queued run as running and return it; concurrent
+    /// workers never claim the same run
+    async fn claim_next(&self) -> Result<Option<GradingRun>, sqlx::Error>;
+
+    /// Store the results of a run and mark it completed; the run scores
+    /// the points of its results
+    async fn complete(
+        &self,
+        run_id: Uuid,
+        verdict: GradingVerdict,
+        compile_output: Option<String>,
+        results: Vec<NewGradingResult>,
+    ) -> Result<GradingRun, sqlx::Error>;
+
+    /// Mark a run as failed because of a grader error
+    async fn fail(&self, run_id: Uuid, error: &str) -> Result<GradingRun, sqlx::Error>;
+
+    /// Put runs left running since before `started_before` (e.g. by a
+    /// crashed worker) back in the queue; returns how many were requeued
+    async fn requeue_stale(&self, started_before: DateTime<Utc>) -> Result<u64, sqlx::Error>;
+
+    async fn find_run(&self, id: Uuid) -> Result<Option<GradingRun>, sqlx::Error>;
+
+    /// Runs of a submission, newest first
+    async fn find_runs_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<GradingRun>, sqlx::Error>;
+
+    /// Results of a run in test order
+    async fn find_results(&self, run_id: Uuid) -> Result<Vec<GradingResult>, sqlx::Error>;
+}
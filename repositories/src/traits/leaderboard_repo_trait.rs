@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::{LeaderboardDelta, LeaderboardSnapshot};
+
+/// The Postgres side of the leaderboards
+///
+/// The database queues a delta whenever a score changes; the leaderboard
+/// worker takes them to update the Redis sorted sets and periodically
+/// replaces the sets with a snapshot.
+#[async_trait]
+pub trait LeaderboardRepositoryTrait: Send + Sync {
+    /// Remove and return up to `limit` queued deltas, oldest first
+    async fn take_deltas(&self, limit: i64) -> Result<Vec<LeaderboardDelta>, sqlx::Error>;
+
+    /// Every score for all time and for the week and month containing `at`.
+    /// The deltas queued so far are already part of it and are discarded;
+    /// deltas queued afterwards are not.
+    async fn snapshot(&self, at: DateTime<Utc>) -> Result<LeaderboardSnapshot, sqlx::Error>;
+}
@@ -1,22 +1,58 @@
 pub mod account_repo_trait;
+pub mod activity_repo_trait;
+pub mod badge_repo_trait;
+pub mod blob_repo_trait;
+pub mod contest_repo_trait;
+pub mod feed_repo_trait;
+pub mod follow_repo_trait;
+pub mod grading_repo_trait;
+pub mod leaderboard_repo_trait;
+pub mod personal_access_token_repo_trait;
 pub mod problems_or_task_repo_trait;
+pub mod rating_flag_repo_trait;
+pub mod rating_settings_repo_trait;
+pub mod reputation_repo_trait;
+pub mod search_repo_trait;
 pub mod submission_comment_replies_repo_trait;
 pub mod submission_comment_repo_trait;
 pub mod submission_rating_repo_trait;
+pub mod submission_revision_repo_trait;
 pub mod submission_repo_trait;
+pub mod tag_repo_trait;
+pub mod task_answer_repo_trait;
 pub mod task_comment_replies_repo_trait;
 pub mod task_comment_repo_trait;
 pub mod task_rating_repo_trait;
+pub mod task_revision_repo_trait;
+pub mod task_test_case_repo_trait;
 pub mod user_repo_trait;
 
 // Re-export the traits
 pub use account_repo_trait::*;
+pub use activity_repo_trait::*;
+pub use badge_repo_trait::*;
+pub use blob_repo_trait::*;
+pub use contest_repo_trait::*;
+pub use feed_repo_trait::*;
+pub use follow_repo_trait::*;
+pub use grading_repo_trait::*;
+pub use leaderboard_repo_trait::*;
+pub use personal_access_token_repo_trait::*;
 pub use problems_or_task_repo_trait::*;
+pub use rating_flag_repo_trait::*;
+pub use rating_settings_repo_trait::*;
+pub use reputation_repo_trait::*;
+pub use search_repo_trait::*;
 pub use submission_comment_replies_repo_trait::*;
 pub use submission_comment_repo_trait::*;
 pub use submission_rating_repo_trait::*;
+pub use submission_revision_repo_trait::*;
 pub use submission_repo_trait::*;
+pub use tag_repo_trait::*;
+pub use task_answer_repo_trait::*;
 pub use task_comment_replies_repo_trait::*;
 pub use task_comment_repo_trait::*;
 pub use task_rating_repo_trait::*;
+pub use task_revision_repo_trait::*;
+pub use task_test_case_repo_trait::*;
 pub use user_repo_trait::*;
@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use models::PersonalAccessToken;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait PersonalAccessTokenRepositoryTrait: Send + Sync {
+    /// Store a new token (only the hash of the secret is persisted)
+    async fn create(
+        &self,
+        user_id: Uuid,
+        name: String,
+        token_prefix: String,
+        token_hash: String,
+        scopes: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<PersonalAccessToken, sqlx::Error>;
+
+    /// Find a token by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<PersonalAccessToken>, sqlx::Error>;
+
+    /// Find a token that is neither revoked nor expired by its hash
+    async fn find_active_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessToken>, sqlx::Error>;
+
+    /// Find all tokens belonging to a user (including revoked)
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<PersonalAccessToken>, sqlx::Error>;
+
+    /// Record that a token has just been used
+    async fn touch_last_used(&self, id: Uuid) -> Result<(), sqlx::Error>;
+
+    /// Revoke a token owned by the given user
+    async fn revoke(&self, id: Uuid, user_id: Uuid) -> Result<PersonalAccessToken, sqlx::Error>;
+}
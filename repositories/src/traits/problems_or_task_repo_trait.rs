@@ -1,6 +1,8 @@
+use crate::pagination::{Page, PageRequest};
+use crate::task_query::TaskQuery;
 use async_trait::async_trait;
 use uuid::Uuid;
-use models::ProblemOrTask;
+use models::{Difficulty, ProblemOrTask};
 
 #[async_trait]
 pub trait ProblemOrTaskRepositoryTrait: Send + Sync {
@@ -11,12 +13,19 @@ pub trait ProblemOrTaskRepositoryTrait: Send + Sync {
         content: String,
         file_url: Option<String>,
         tags: Vec<String>,
-        difficulty: String,
+        difficulty: Difficulty,
     ) -> Result<ProblemOrTask, sqlx::Error>;
 
     async fn find_by_id(&self, id: Uuid) -> Result<Option<ProblemOrTask>, sqlx::Error>;
-    async fn find_all(&self) -> Result<Vec<ProblemOrTask>, sqlx::Error>;
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<ProblemOrTask>, sqlx::Error>;
+    async fn find_all(&self, page: PageRequest) -> Result<Page<ProblemOrTask>, sqlx::Error>;
+    /// List tasks matching the filters, sort and page of `query`
+    async fn query(&self, query: &TaskQuery) -> Result<Page<ProblemOrTask>, sqlx::Error>;
+
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<ProblemOrTask>, sqlx::Error>;
 
     async fn update(
         &self,
@@ -25,7 +34,15 @@ pub trait ProblemOrTaskRepositoryTrait: Send + Sync {
         content: Option<String>,
         file_url: Option<String>,
         tags: Option<Vec<String>>,
-        difficulty: Option<String>,
+        difficulty: Option<Difficulty>,
+    ) -> Result<ProblemOrTask, sqlx::Error>;
+
+    /// Set the task back to the state stored in `revision`; this records a
+    /// new revision rather than rewriting history
+    async fn restore_revision(
+        &self,
+        id: Uuid,
+        revision: i32,
     ) -> Result<ProblemOrTask, sqlx::Error>;
 
     async fn increment_views(&self, id: Uuid) -> Result<(), sqlx::Error>;
@@ -0,0 +1,48 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::{RatingAbuseThresholds, RatingFlag, RatingFlagStatus};
+use uuid::Uuid;
+
+/// Detection and review of suspicious ratings
+///
+/// The `flag_*` scans only add flags: a rating is flagged at most once per
+/// reason, so rescanning is cheap and a dismissed flag stays dismissed.
+/// Confirming a flag neutralizes its rating, which the database then leaves
+/// out of every average, score and reputation event.
+#[async_trait]
+pub trait RatingFlagRepositoryTrait: Send + Sync {
+    /// Flag the ratings two users gave each other's work when both keep
+    /// rating the other. Returns how many ratings were newly flagged.
+    async fn flag_vote_rings(&self, thresholds: &RatingAbuseThresholds)
+    -> Result<u64, sqlx::Error>;
+
+    /// Flag ratings of a target that arrive together from new accounts
+    async fn flag_new_account_bursts(
+        &self,
+        thresholds: &RatingAbuseThresholds,
+    ) -> Result<u64, sqlx::Error>;
+
+    /// Flag ratings of a target from different raters on one IP or session
+    async fn flag_shared_origins(
+        &self,
+        thresholds: &RatingAbuseThresholds,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RatingFlag>, sqlx::Error>;
+
+    /// Flags with the given status, or all of them, newest first
+    async fn find_by_status(
+        &self,
+        status: Option<RatingFlagStatus>,
+        page: PageRequest,
+    ) -> Result<Page<RatingFlag>, sqlx::Error>;
+
+    /// Record a moderator's decision. A rating is neutralized while any of
+    /// its flags is confirmed. Returns `None` when there is no such flag.
+    async fn review(
+        &self,
+        id: Uuid,
+        status: RatingFlagStatus,
+        moderator_id: Option<Uuid>,
+    ) -> Result<Option<RatingFlag>, sqlx::Error>;
+}
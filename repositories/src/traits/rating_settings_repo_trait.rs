@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use models::RatingSettings;
+
+/// Settings of the ranking scores stored on rated tasks and submissions
+///
+/// The scores themselves are kept up to date by the database whenever a
+/// rating changes.
+#[async_trait]
+pub trait RatingSettingsRepositoryTrait: Send + Sync {
+    async fn find(&self) -> Result<RatingSettings, sqlx::Error>;
+
+    /// Replace the settings and rescore every task and submission
+    async fn update(
+        &self,
+        prior_mean: f64,
+        prior_weight: f64,
+        positive_min_rating: i16,
+        confidence_z: f64,
+    ) -> Result<RatingSettings, sqlx::Error>;
+
+    /// Recompute every rating count, mean and score from the ratings
+    async fn refresh_all_scores(&self) -> Result<(), sqlx::Error>;
+}
@@ -0,0 +1,44 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::{ReputationEvent, ReputationEventType, ReputationWeight};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// The reputation ledger and its configuration
+///
+/// Events are appended by the database when ratings, featured submissions
+/// and moderator removals change; this repository only reads the ledger and
+/// maintains the scores derived from it.
+#[async_trait]
+pub trait ReputationRepositoryTrait: Send + Sync {
+    /// A user's ledger, newest first
+    async fn find_events_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<ReputationEvent>, sqlx::Error>;
+
+    async fn find_weights(&self) -> Result<Vec<ReputationWeight>, sqlx::Error>;
+
+    /// Change the points of an event; applies to future events only.
+    /// Returns `None` when there is no such weight.
+    async fn set_weight(
+        &self,
+        event_type: ReputationEventType,
+        rating_value: i16,
+        points: Decimal,
+    ) -> Result<Option<ReputationWeight>, sqlx::Error>;
+
+    /// Most points a user can gain per UTC day
+    async fn find_daily_cap(&self) -> Result<Decimal, sqlx::Error>;
+
+    async fn set_daily_cap(&self, daily_cap: Decimal) -> Result<Decimal, sqlx::Error>;
+
+    /// Reset a user's score to the sum of their ledger. Returns the score,
+    /// or `None` when the user does not exist.
+    async fn recompute(&self, user_id: Uuid) -> Result<Option<Decimal>, sqlx::Error>;
+
+    /// Reset every score that drifted from its ledger. Returns how many were
+    /// corrected.
+    async fn recompute_all(&self) -> Result<u64, sqlx::Error>;
+}
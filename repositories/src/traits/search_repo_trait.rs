@@ -0,0 +1,14 @@
+use crate::pagination::Page;
+use crate::search_query::SearchQuery;
+use async_trait::async_trait;
+use models::{SearchFacets, SearchHit};
+
+#[async_trait]
+pub trait SearchRepositoryTrait: Send + Sync {
+    /// Run a full-text search, best matches first
+    async fn search(&self, query: &SearchQuery) -> Result<Page<SearchHit>, sqlx::Error>;
+
+    /// Count the difficulties and tags over every hit of `query`; its page
+    /// is ignored
+    async fn facets(&self, query: &SearchQuery) -> Result<SearchFacets, sqlx::Error>;
+}
@@ -1,3 +1,4 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use models::SubmissionCommentReply;
 use uuid::Uuid;
@@ -19,14 +20,21 @@ pub trait SubmissionCommentReplyRepositoryTrait: Send + Sync {
     async fn find_by_comment(
         &self,
         submission_comment_id: Uuid,
-    ) -> Result<Vec<SubmissionCommentReply>, sqlx::Error>;
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error>;
 
     /// Find all replies by a specific user (excluding deleted)
-    async fn find_by_user(&self, user_id: Uuid)
-    -> Result<Vec<SubmissionCommentReply>, sqlx::Error>;
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error>;
 
     /// Find all replies (including deleted)
-    async fn find_all(&self) -> Result<Vec<SubmissionCommentReply>, sqlx::Error>;
+    async fn find_all(
+        &self,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionCommentReply>, sqlx::Error>;
 
     /// Count replies for a comment (excluding deleted)
     async fn count_by_comment(&self, submission_comment_id: Uuid) -> Result<i64, sqlx::Error>;
@@ -1,3 +1,4 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
 use models::SubmissionComment;
@@ -16,13 +17,21 @@ pub trait SubmissionCommentRepositoryTrait: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<SubmissionComment>, sqlx::Error>;
     
     /// Find all comments for a specific submission (excluding deleted)
-    async fn find_by_submission(&self, submission_id: Uuid) -> Result<Vec<SubmissionComment>, sqlx::Error>;
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionComment>, sqlx::Error>;
     
     /// Find all comments by a specific user (excluding deleted)
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<SubmissionComment>, sqlx::Error>;
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionComment>, sqlx::Error>;
     
     /// Find all comments (including deleted)
-    async fn find_all(&self) -> Result<Vec<SubmissionComment>, sqlx::Error>;
+    async fn find_all(&self, page: PageRequest) -> Result<Page<SubmissionComment>, sqlx::Error>;
     
     /// Count comments for a submission (excluding deleted)
     async fn count_by_submission(&self, submission_id: Uuid) -> Result<i64, sqlx::Error>;
@@ -37,9 +46,13 @@ pub trait SubmissionCommentRepositoryTrait: Send + Sync {
     /// Soft delete a comment
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
     
+    /// Soft delete a comment on behalf of a moderator, which costs the
+    /// author reputation unless they are the moderator
+    async fn remove(&self, id: Uuid, moderator_id: Uuid) -> Result<(), sqlx::Error>;
+    
     /// Permanently delete a comment
     async fn hard_delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
     
-    /// Restore a soft-deleted comment
+    /// Restore a soft-deleted comment, undoing any moderator removal
     async fn restore(&self, id: Uuid) -> Result<(), sqlx::Error>;
 }
\ No newline at end of file
@@ -1,15 +1,18 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
-use models::SubmissionRating;
+use chrono::{DateTime, Utc};
+use models::{RatingActivity, RatingCount, RatingInterval, RatingOrigin, SubmissionRating};
 
 #[async_trait]
 pub trait SubmissionRatingRepositoryTrait: Send + Sync {
-    /// Create a new submission rating
+    /// Create a new submission rating; `origin` is kept for abuse detection
     async fn create(
         &self,
         submission_id: Uuid,
         rater_id: Uuid,
         rating_value: i32,
+        origin: &RatingOrigin,
     ) -> Result<SubmissionRating, sqlx::Error>;
     
     /// Find a rating by ID
@@ -23,15 +26,23 @@ pub trait SubmissionRatingRepositoryTrait: Send + Sync {
     ) -> Result<Option<SubmissionRating>, sqlx::Error>;
     
     /// Find all ratings for a specific submission
-    async fn find_by_submission(&self, submission_id: Uuid) -> Result<Vec<SubmissionRating>, sqlx::Error>;
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRating>, sqlx::Error>;
     
     /// Find all ratings by a specific rater
-    async fn find_by_rater(&self, rater_id: Uuid) -> Result<Vec<SubmissionRating>, sqlx::Error>;
+    async fn find_by_rater(
+        &self,
+        rater_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRating>, sqlx::Error>;
     
-    /// Get average rating for a submission
+    /// Get average rating for a submission, without neutralized ratings
     async fn get_average_rating(&self, submission_id: Uuid) -> Result<Option<f64>, sqlx::Error>;
     
-    /// Get total count of ratings for a submission
+    /// Get total count of ratings for a submission, without neutralized ratings
     async fn count_by_submission(&self, submission_id: Uuid) -> Result<i64, sqlx::Error>;
     
     /// Update an existing rating
@@ -50,4 +61,17 @@ pub trait SubmissionRatingRepositoryTrait: Send + Sync {
         submission_id: Uuid,
         rater_id: Uuid,
     ) -> Result<(), sqlx::Error>;
-}
\ No newline at end of file
+    
+    /// Number of ratings per value, 1 to 4, including values nobody gave
+    async fn get_distribution(&self, submission_id: Uuid) -> Result<Vec<RatingCount>, sqlx::Error>;
+    
+    /// Ratings first given in each `interval` from the one containing `from`
+    /// up to `to`, oldest first; empty buckets are included
+    async fn get_activity(
+        &self,
+        submission_id: Uuid,
+        interval: RatingInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RatingActivity>, sqlx::Error>;
+}
@@ -1,10 +1,12 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use uuid::Uuid;
-use models::Submission;
+use models::{ProgrammingLanguage, Submission, SubmissionStatus};
 
 #[async_trait]
+#[allow(clippy::too_many_arguments)]
 pub trait SubmissionRepositoryTrait: Send + Sync {
     async fn create(
         &self,
@@ -12,36 +14,65 @@ pub trait SubmissionRepositoryTrait: Send + Sync {
         task_id: Uuid,
         content: String,
         file_url: Option<String>,
-        status: Option<String>,
+        status: Option<SubmissionStatus>,
         average_rating: Decimal,
         total_ratings: i32,
         is_featured: bool,
         submitted_at: Option<DateTime<Utc>>,
+        language: Option<ProgrammingLanguage>,
     ) -> Result<Submission, sqlx::Error>;
     
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Submission>, sqlx::Error>;
     
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Submission>, sqlx::Error>;
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error>;
+    
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error>;
     
-    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<Submission>, sqlx::Error>;
+    async fn find_all(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error>;
     
-    async fn find_all(&self) -> Result<Vec<Submission>, sqlx::Error>;
+    async fn find_by_status(
+        &self,
+        status: SubmissionStatus,
+        page: PageRequest,
+    ) -> Result<Page<Submission>, sqlx::Error>;
     
-    async fn find_by_status(&self, status: &str) -> Result<Vec<Submission>, sqlx::Error>;
+    async fn find_featured(&self, page: PageRequest) -> Result<Page<Submission>, sqlx::Error>;
     
-    async fn find_featured(&self) -> Result<Vec<Submission>, sqlx::Error>;
+    /// Count the submissions to a task that are neither drafts nor withdrawn
+    /// (excluding deleted)
+    async fn count_by_task(&self, task_id: Uuid) -> Result<i64, sqlx::Error>;
     
-    async fn update_status(&self, id: Uuid, status: &str) -> Result<(), sqlx::Error>;
+    async fn update_status(&self, id: Uuid, status: SubmissionStatus) -> Result<(), sqlx::Error>;
     
     async fn update_submission(
         &self,
         id: Uuid,
         content: Option<String>,
         file_url: Option<String>,
-        status: Option<String>,
+        status: Option<SubmissionStatus>,
         is_featured: Option<bool>,
         average_rating: Option<Decimal>,
         total_ratings: Option<i32>,
+        language: Option<ProgrammingLanguage>,
+    ) -> Result<Submission, sqlx::Error>;
+    
+    /// Submits new content as a new version, moving the submission back to
+    /// `submitted`; earlier versions stay in `submission_revisions`.
+    /// `language` keeps the current one when `None`
+    async fn resubmit(
+        &self,
+        id: Uuid,
+        content: String,
+        file_url: Option<String>,
+        language: Option<ProgrammingLanguage>,
     ) -> Result<Submission, sqlx::Error>;
     
     async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
@@ -0,0 +1,22 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::SubmissionRevision;
+use uuid::Uuid;
+
+/// Read access to submission history; versions are written by the database
+/// whenever a draft is saved or the submission is (re)submitted
+#[async_trait]
+pub trait SubmissionRevisionRepositoryTrait: Send + Sync {
+    /// Versions of a submission, newest first
+    async fn find_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRevision>, sqlx::Error>;
+
+    async fn find_by_version(
+        &self,
+        submission_id: Uuid,
+        version: i32,
+    ) -> Result<Option<SubmissionRevision>, sqlx::Error>;
+}
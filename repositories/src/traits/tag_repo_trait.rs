@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use models::Tag;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait TagRepositoryTrait: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>, sqlx::Error>;
+
+    /// Find a tag by its slug or one of its aliases
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<Tag>, sqlx::Error>;
+
+    /// Tags whose slug or an alias starts with `prefix`, slug matches and
+    /// most used first
+    async fn autocomplete(&self, prefix: &str, limit: i64) -> Result<Vec<Tag>, sqlx::Error>;
+
+    /// Most used tags
+    async fn find_popular(&self, limit: i64) -> Result<Vec<Tag>, sqlx::Error>;
+
+    /// Map each slug to its canonical tag slug; unknown slugs are kept
+    async fn resolve_slugs(&self, slugs: &[String]) -> Result<Vec<String>, sqlx::Error>;
+
+    async fn update(
+        &self,
+        id: Uuid,
+        name: Option<String>,
+        description: Option<String>,
+    ) -> Result<Tag, sqlx::Error>;
+
+    /// Make `alias` resolve to the tag. An existing tag with that slug is
+    /// merged into it: its tasks and aliases move over and it is deleted.
+    async fn add_alias(&self, id: Uuid, alias: &str) -> Result<Tag, sqlx::Error>;
+
+    async fn remove_alias(&self, id: Uuid, alias: &str) -> Result<(), sqlx::Error>;
+}
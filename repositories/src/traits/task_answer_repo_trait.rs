@@ -0,0 +1,47 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::{AnswerAttempt, AnswerMatchMode, TaskAnswer, TaskAnswerProgress};
+use uuid::Uuid;
+
+/// Hidden expected answers of tasks and the attempts checked against them
+///
+/// Attempts are recorded by the database when a submission to a task with
+/// an expected answer is submitted.
+#[async_trait]
+pub trait TaskAnswerRepositoryTrait: Send + Sync {
+    /// Set or replace the expected answer of a task
+    async fn upsert(
+        &self,
+        task_id: Uuid,
+        mode: AnswerMatchMode,
+        answers: Vec<String>,
+        case_insensitive: bool,
+        tolerance: Option<f64>,
+    ) -> Result<TaskAnswer, sqlx::Error>;
+
+    async fn find_by_task(&self, task_id: Uuid) -> Result<Option<TaskAnswer>, sqlx::Error>;
+
+    /// Remove the expected answer; recorded attempts are kept
+    async fn delete(&self, task_id: Uuid) -> Result<(), sqlx::Error>;
+
+    async fn find_progress(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TaskAnswerProgress>, sqlx::Error>;
+
+    /// A user's attempts at a task, newest first
+    async fn find_attempts_by_user(
+        &self,
+        task_id: Uuid,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, sqlx::Error>;
+
+    /// Attempts made with the versions of a submission, newest first
+    async fn find_attempts_by_submission(
+        &self,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, sqlx::Error>;
+}
@@ -1,3 +1,4 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
 use models::TaskCommentReply;
@@ -16,13 +17,21 @@ pub trait TaskCommentReplyRepositoryTrait: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskCommentReply>, sqlx::Error>;
     
     /// Find all replies for a specific task comment (excluding deleted)
-    async fn find_by_comment(&self, task_comment_id: Uuid) -> Result<Vec<TaskCommentReply>, sqlx::Error>;
+    async fn find_by_comment(
+        &self,
+        task_comment_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error>;
     
     /// Find all replies by a specific user (excluding deleted)
-    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<TaskCommentReply>, sqlx::Error>;
+    async fn find_by_user(
+        &self,
+        user_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskCommentReply>, sqlx::Error>;
     
     /// Find all replies (including deleted)
-    async fn find_all(&self) -> Result<Vec<TaskCommentReply>, sqlx::Error>;
+    async fn find_all(&self, page: PageRequest) -> Result<Page<TaskCommentReply>, sqlx::Error>;
     
     /// Count replies for a comment (excluding deleted)
     async fn count_by_comment(&self, task_comment_id: Uuid) -> Result<i64, sqlx::Error>;
@@ -1,21 +1,36 @@
+use crate::pagination::{Page, PageRequest};
 use async_trait::async_trait;
 use uuid::Uuid;
-use models::TaskRating;
+use chrono::{DateTime, Utc};
+use models::{RatingActivity, RatingCount, RatingInterval, RatingOrigin, TaskRating};
 
+/// Neutralized ratings (see `RatingFlagRepositoryTrait`) are still listed
+/// but left out of the average, count, distribution and activity.
 #[async_trait]
 pub trait TaskRatingRepositoryTrait: Send + Sync {
+    /// Rate a task or change the rater's rating; `origin` is kept for abuse
+    /// detection
     async fn create(
         &self,
         task_id: Uuid,
         rater_id: Uuid,
         rating_value: i32,
+        origin: &RatingOrigin,
     ) -> Result<TaskRating, sqlx::Error>;
     
     async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskRating>, sqlx::Error>;
     
-    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<TaskRating>, sqlx::Error>;
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error>;
     
-    async fn find_by_rater(&self, rater_id: Uuid) -> Result<Vec<TaskRating>, sqlx::Error>;
+    async fn find_by_rater(
+        &self,
+        rater_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRating>, sqlx::Error>;
     
     async fn find_by_task_and_rater(
         &self,
@@ -40,4 +55,17 @@ pub trait TaskRatingRepositoryTrait: Send + Sync {
         task_id: Uuid,
         rater_id: Uuid,
     ) -> Result<(), sqlx::Error>;
-}
\ No newline at end of file
+    
+    /// Number of ratings per value, 1 to 4, including values nobody gave
+    async fn get_distribution(&self, task_id: Uuid) -> Result<Vec<RatingCount>, sqlx::Error>;
+    
+    /// Ratings first given in each `interval` from the one containing `from`
+    /// up to `to`, oldest first; empty buckets are included
+    async fn get_activity(
+        &self,
+        task_id: Uuid,
+        interval: RatingInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<RatingActivity>, sqlx::Error>;
+}
@@ -0,0 +1,22 @@
+use crate::pagination::{Page, PageRequest};
+use async_trait::async_trait;
+use models::TaskRevision;
+use uuid::Uuid;
+
+/// Read access to task history; revisions are written by the database
+/// whenever a task changes
+#[async_trait]
+pub trait TaskRevisionRepositoryTrait: Send + Sync {
+    /// Revisions of a task, newest first
+    async fn find_by_task(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRevision>, sqlx::Error>;
+
+    async fn find_by_revision(
+        &self,
+        task_id: Uuid,
+        revision: i32,
+    ) -> Result<Option<TaskRevision>, sqlx::Error>;
+}
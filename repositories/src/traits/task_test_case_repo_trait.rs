@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use models::TaskTestCase;
+use uuid::Uuid;
+
+/// A test case to add to a task; `None` limits and points fall back to the
+/// column defaults
+#[derive(Debug, Clone, Default)]
+pub struct NewTestCase {
+    pub input: String,
+    pub expected_output: String,
+    pub time_limit_ms: Option<i32>,
+    pub memory_limit_kb: Option<i32>,
+    pub is_sample: bool,
+    pub points: Option<i32>,
+}
+
+/// Test cases code submissions to a task are graded against
+#[async_trait]
+#[allow(clippy::too_many_arguments)]
+pub trait TaskTestCaseRepositoryTrait: Send + Sync {
+    /// Append a test case after the task's last one
+    async fn create(
+        &self,
+        task_id: Uuid,
+        test_case: NewTestCase,
+    ) -> Result<TaskTestCase, sqlx::Error>;
+
+    /// Add many test cases at once, in order, after the task's last one;
+    /// with `replace` the task's existing test cases are deleted first
+    async fn import(
+        &self,
+        task_id: Uuid,
+        test_cases: Vec<NewTestCase>,
+        replace: bool,
+    ) -> Result<Vec<TaskTestCase>, sqlx::Error>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TaskTestCase>, sqlx::Error>;
+
+    /// All test cases of a task in run order
+    async fn find_by_task(&self, task_id: Uuid) -> Result<Vec<TaskTestCase>, sqlx::Error>;
+
+    /// Sample test cases of a task in run order
+    async fn find_samples_by_task(&self, task_id: Uuid) -> Result<Vec<TaskTestCase>, sqlx::Error>;
+
+    async fn update(
+        &self,
+        id: Uuid,
+        input: Option<String>,
+        expected_output: Option<String>,
+        time_limit_ms: Option<i32>,
+        memory_limit_kb: Option<i32>,
+        is_sample: Option<bool>,
+        points: Option<i32>,
+    ) -> Result<TaskTestCase, sqlx::Error>;
+
+    /// Give the test cases of a task the order of `ids`, which must list
+    /// every one of them exactly once
+    async fn reorder(
+        &self,
+        task_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<Vec<TaskTestCase>, sqlx::Error>;
+
+    /// Delete a test case and close the gap in the positions after it
+    async fn delete(&self, id: Uuid) -> Result<(), sqlx::Error>;
+}
@@ -5,6 +5,7 @@ use models::User;
 use uuid::Uuid;
 
 #[async_trait]
+#[allow(clippy::too_many_arguments)]
 pub trait UserRepositoryTrait: Send + Sync {
     async fn create_user(
         &self,
@@ -20,6 +21,9 @@ pub trait UserRepositoryTrait: Send + Sync {
     
     async fn get_user_by_id(&self, user_id: &Uuid) -> Result<Option<User>, sqlx::Error>;
     
+    /// The users that exist among `user_ids`, in no particular order
+    async fn get_users_by_ids(&self, user_ids: &[Uuid]) -> Result<Vec<User>, sqlx::Error>;
+    
     async fn get_user_by_email(&self, email: &str) -> Result<Option<User>, sqlx::Error>;
     
     async fn get_user_by_display_name(&self, display_name: &str) -> Result<Option<User>, sqlx::Error>;
@@ -40,5 +44,18 @@ pub trait UserRepositoryTrait: Send + Sync {
         email_verified_at: Option<DateTime<Utc>>,
     ) -> Result<User, sqlx::Error>;
     
+    /// Replace the avatar; `avatar_hash` is set for uploaded avatars and
+    /// `None` for external URLs or no avatar at all
+    ///
+    /// The files of a replaced upload stay in the blob store; change avatars
+    /// through `AvatarService`, which deletes them. The same holds for an
+    /// `avatar_url` passed to `update_user`.
+    async fn set_avatar(
+        &self,
+        user_id: &Uuid,
+        avatar_url: Option<&str>,
+        avatar_hash: Option<&str>,
+    ) -> Result<User, sqlx::Error>;
+    
     async fn delete_user(&self, user_id: &Uuid) -> Result<User, sqlx::Error>;
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use crate::errors::AppError;
 use axum::{extract::Request, http::header::AUTHORIZATION};
+use axum_extra::extract::CookieJar;
 use bcrypt::{DEFAULT_COST, hash, verify};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{
@@ -7,8 +8,19 @@ use jsonwebtoken::{
     errors::{self, ErrorKind},
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+/// Prefix that distinguishes personal access tokens from JWTs
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "cfz_pat_";
+
+/// Cookie carrying the access token when cookie auth is enabled
+pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
+/// Cookie carrying the double-submit CSRF token (readable by the frontend)
+pub const CSRF_TOKEN_COOKIE: &str = "csrf_token";
+/// Header the frontend must echo the CSRF cookie value in
+pub const CSRF_TOKEN_HEADER: &str = "x-csrf-token";
+
 pub enum TokenType {
     Refresh,
     Access,
@@ -32,6 +44,30 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, bcrypt::Bcryp
     verify(password, hash)
 }
 
+/// Hash a personal access token for storage and lookup
+///
+/// Tokens are long random strings, so a fast digest is sufficient
+/// (unlike passwords, which need bcrypt)
+pub fn hash_api_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+pub fn is_personal_access_token(token: &str) -> bool {
+    token.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX)
+}
+
+/// Compare two secrets without leaking the position of the first mismatch
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,        // user_id
@@ -108,3 +144,47 @@ pub fn extract_bearer_token(req: &Request) -> Result<&str, AppError> {
         )
     })
 }
+
+/// Where the access token of a request was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    Header,
+    Cookie,
+}
+
+/// Read the access token from the `Authorization` header, falling back to
+/// the access token cookie when cookie auth is enabled
+pub fn extract_access_token(
+    req: &Request,
+    cookie_auth_enabled: bool,
+) -> Result<(String, TokenSource), AppError> {
+    if !cookie_auth_enabled || req.headers().contains_key(AUTHORIZATION) {
+        return extract_bearer_token(req).map(|t| (t.to_owned(), TokenSource::Header));
+    }
+
+    CookieJar::from_headers(req.headers())
+        .get(ACCESS_TOKEN_COOKIE)
+        .map(|cookie| (cookie.value().to_owned(), TokenSource::Cookie))
+        .ok_or_else(|| AppError::Unauthorized("Missing authorization header".to_string()))
+}
+
+/// Double-submit check: the CSRF header must match the CSRF cookie
+pub fn verify_csrf_token(req: &Request) -> Result<(), AppError> {
+    let header_token = req
+        .headers()
+        .get(CSRF_TOKEN_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .ok_or_else(|| AppError::Forbidden("Missing CSRF token".to_string()))?;
+
+    let jar = CookieJar::from_headers(req.headers());
+    let cookie_token = jar
+        .get(CSRF_TOKEN_COOKIE)
+        .map(|cookie| cookie.value())
+        .ok_or_else(|| AppError::Forbidden("Missing CSRF cookie".to_string()))?;
+
+    if !constant_time_eq(header_token, cookie_token) {
+        return Err(AppError::Forbidden("Invalid CSRF token".to_string()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! The badge rules engine.
+//!
+//! Each badge of the catalog is earned by reaching its threshold on one of
+//! the user's counts (its rule). A queued check names the user and what they
+//! did; the engine works out which rules that can have moved, counts each
+//! of them once per user however many checks there were, and awards every
+//! badge whose threshold the count reached. Awarding is idempotent, so
+//! evaluating the same checks twice changes nothing.
+
+use models::{Badge, BadgeCheck, BadgeEventType, BadgeRule};
+use repositories::traits::BadgeRepositoryTrait;
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// Badges newly awarded to one user
+#[derive(Debug, Clone)]
+pub struct BadgeAward {
+    pub user_id: Uuid,
+    pub badge_ids: Vec<Uuid>,
+}
+
+pub struct BadgeEngine<'a> {
+    repo: &'a dyn BadgeRepositoryTrait,
+    catalog: Vec<Badge>,
+}
+
+impl<'a> BadgeEngine<'a> {
+    /// An engine over the current catalog
+    pub async fn load(repo: &'a dyn BadgeRepositoryTrait) -> Result<Self, sqlx::Error> {
+        let catalog = repo.find_catalog().await?;
+        Ok(Self { repo, catalog })
+    }
+
+    /// Rules with at least one badge that `events` can have earned
+    fn triggered_rules(&self, events: &HashSet<BadgeEventType>) -> Vec<BadgeRule> {
+        let mut rules: Vec<BadgeRule> = Vec::new();
+        for badge in &self.catalog {
+            let triggered = events.iter().any(|event| badge.rule.triggered_by(*event));
+            if triggered && !rules.contains(&badge.rule) {
+                rules.push(badge.rule);
+            }
+        }
+        rules
+    }
+
+    /// Evaluate a batch of checks and award what they earned
+    pub async fn evaluate(&self, checks: &[BadgeCheck]) -> Result<Vec<BadgeAward>, sqlx::Error> {
+        // 1. Group the checks per user
+        let mut events: BTreeMap<Uuid, HashSet<BadgeEventType>> = BTreeMap::new();
+        for check in checks {
+            events.entry(check.user_id).or_default().insert(check.event);
+        }
+
+        let mut awards = Vec::new();
+        for (user_id, events) in events {
+            // 2. Count each triggered rule and collect the badges reached
+            let mut earned = Vec::new();
+            for rule in self.triggered_rules(&events) {
+                let count = self.repo.count(user_id, rule).await?;
+                earned.extend(
+                    self.catalog
+                        .iter()
+                        .filter(|badge| badge.rule == rule && badge.threshold as i64 <= count)
+                        .map(|badge| badge.id),
+                );
+            }
+            if earned.is_empty() {
+                continue;
+            }
+
+            // 3. Award them; badges the user already has are skipped
+            let badge_ids = self.repo.award(user_id, &earned).await?;
+            if !badge_ids.is_empty() {
+                awards.push(BadgeAward { user_id, badge_ids });
+            }
+        }
+        Ok(awards)
+    }
+}
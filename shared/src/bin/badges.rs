@@ -0,0 +1,124 @@
+//! Award badges as users earn them.
+//!
+//! ```text
+//! cargo run -p shared --bin badges catalog
+//! cargo run -p shared --bin badges sync
+//! cargo run -p shared --bin badges recheck
+//! ```
+//!
+//! Reads the same environment as the server. `catalog` lists the badges and
+//! how many users hold each. `sync` evaluates the badge checks as the
+//! database queues them and awards what they earned; several can run at
+//! once. `recheck` queues a check of every rule for every user, for `sync`
+//! to pick up, e.g. after adding a badge to the catalog.
+
+use repositories::repositories::BadgeRepository;
+use repositories::traits::BadgeRepositoryTrait;
+use shared::badges::BadgeEngine;
+use shared::config::Config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+
+const USAGE: &str = "usage: badges catalog
+       badges sync
+       badges recheck";
+
+/// Checks evaluated per round trip
+const BATCH_SIZE: i64 = 500;
+
+/// How long to wait for new checks once the queue is empty
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if !matches!(args.as_slice(), ["catalog"] | ["sync"] | ["recheck"]) {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let repo = BadgeRepository::new(pool);
+
+    let result = match args.as_slice() {
+        ["catalog"] => catalog(&repo).await,
+        ["recheck"] => recheck(&repo).await,
+        _ => sync(&repo).await,
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn catalog(repo: &BadgeRepository) -> Result<(), sqlx::Error> {
+    for badge in repo.find_catalog().await? {
+        println!(
+            "{:<24} {:<22} {:>6} {:>8} holders  {}",
+            badge.slug,
+            badge.rule.as_str(),
+            badge.threshold,
+            badge.holders,
+            badge.name
+        );
+    }
+    Ok(())
+}
+
+async fn recheck(repo: &BadgeRepository) -> Result<(), sqlx::Error> {
+    let queued = repo.queue_recheck_all().await?;
+    println!("Queued a badge check for {} users", queued);
+    Ok(())
+}
+
+/// Evaluate queued checks until stopped. The catalog is reloaded per
+/// batch, so badges added meanwhile are picked up.
+async fn sync(repo: &BadgeRepository) -> Result<(), sqlx::Error> {
+    loop {
+        let checks = match repo.take_checks(BATCH_SIZE).await {
+            Ok(checks) => checks,
+            Err(e) => {
+                eprintln!("Failed to take badge checks: {}", e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+        if checks.is_empty() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            continue;
+        }
+
+        let result = match BadgeEngine::load(repo).await {
+            Ok(engine) => engine.evaluate(&checks).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(awards) => {
+                for award in awards {
+                    println!(
+                        "Awarded {} badge(s) to {}",
+                        award.badge_ids.len(),
+                        award.user_id
+                    );
+                }
+            }
+            Err(e) => {
+                // The batch is gone from the queue; a recheck makes up for it
+                eprintln!("Failed to evaluate {} badge checks: {}", checks.len(), e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Delete uploaded files that no task, submission or revision points at.
+//!
+//! ```text
+//! cargo run -p shared --bin cleanup-orphaned-blobs [GRACE_HOURS]
+//! ```
+//!
+//! Reads the same environment as the server. Blobs uploaded within the last
+//! `GRACE_HOURS` (default 24) are kept, so a file uploaded just before the
+//! task or submission that uses it is saved survives.
+
+use chrono::{Duration, Utc};
+use models::Blob;
+use repositories::repositories::BlobRepository;
+use repositories::traits::BlobRepositoryTrait;
+use shared::config::Config;
+use shared::storage::blob_store_from_config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+
+const DEFAULT_GRACE_HOURS: i64 = 24;
+const BATCH_SIZE: i64 = 100;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let grace_hours = match env::args().nth(1).map(|arg| arg.parse::<i64>()) {
+        None => DEFAULT_GRACE_HOURS,
+        Some(Ok(hours)) if hours >= 0 => hours,
+        Some(_) => {
+            eprintln!("usage: cleanup-orphaned-blobs [GRACE_HOURS]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let blobs = BlobRepository::new(pool);
+    let store = blob_store_from_config(&config);
+    let older_than = Utc::now() - Duration::hours(grace_hours);
+
+    let mut deleted = 0;
+    loop {
+        let orphans = match blobs.find_orphans(older_than, BATCH_SIZE).await {
+            Ok(orphans) => orphans,
+            Err(e) => {
+                eprintln!("Failed to list orphaned blobs: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        if orphans.is_empty() {
+            break;
+        }
+
+        for blob in orphans {
+            // Uploads of the same content wait until the deletion commits,
+            // so they never record a row whose bytes were just removed
+            let deletion = match blobs.delete_if_orphaned(&blob.sha256, older_than).await {
+                Ok(Some(deletion)) => deletion,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Failed to delete blob {}: {}", blob.sha256, e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if let Err(e) = store.delete(&Blob::storage_key(&blob.sha256)).await {
+                eprintln!("Failed to delete stored bytes of {}: {}", blob.sha256, e);
+                return ExitCode::FAILURE;
+            }
+
+            match deletion.commit().await {
+                Ok(()) => deleted += 1,
+                Err(e) => {
+                    eprintln!("Failed to delete blob {}: {}", blob.sha256, e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    println!("Deleted {} orphaned blobs", deleted);
+    ExitCode::SUCCESS
+}
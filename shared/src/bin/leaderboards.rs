@@ -0,0 +1,121 @@
+//! Keep the leaderboards in Redis up to date.
+//!
+//! ```text
+//! cargo run -p shared --bin leaderboards sync [RECONCILE_MINUTES]
+//! cargo run -p shared --bin leaderboards reconcile
+//! ```
+//!
+//! Reads the same environment as the server. `sync` applies score changes
+//! to the sorted sets as the database queues them, and rebuilds the sets
+//! from Postgres when it starts and every `RECONCILE_MINUTES` (60 by
+//! default) after that, which also starts the new week and month. Run a
+//! single `sync` at a time. `reconcile` rebuilds the sets once.
+
+use chrono::Utc;
+use repositories::repositories::LeaderboardRepository;
+use repositories::traits::LeaderboardRepositoryTrait;
+use shared::config::Config;
+use shared::errors::AppError;
+use shared::leaderboards::LeaderboardStore;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+use tokio::time::Instant;
+
+const USAGE: &str = "usage: leaderboards sync [RECONCILE_MINUTES]
+       leaderboards reconcile";
+
+const DEFAULT_RECONCILE_MINUTES: u64 = 60;
+
+/// Deltas applied per round trip
+const BATCH_SIZE: i64 = 1000;
+
+/// How long to wait for new deltas once the queue is empty
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // `None` rebuilds once, `Some(minutes)` syncs
+    let reconcile_minutes = match args.as_slice() {
+        ["sync"] => Some(DEFAULT_RECONCILE_MINUTES),
+        ["sync", minutes] => match minutes.parse::<u64>().ok().filter(|m| *m > 0) {
+            Some(minutes) => Some(minutes),
+            None => {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            }
+        },
+        ["reconcile"] => None,
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let redis = redis::Client::open(config.redis_url.as_str())
+        .expect("Invalid REDIS_URL")
+        .get_multiplexed_async_connection()
+        .await
+        .expect("Failed to connect to Redis");
+    let repo = LeaderboardRepository::new(pool);
+    let store = LeaderboardStore::new(redis);
+
+    let Some(minutes) = reconcile_minutes else {
+        return match reconcile(&repo, &store).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Failed to rebuild the leaderboards: {}", e);
+                ExitCode::FAILURE
+            }
+        };
+    };
+
+    let interval = Duration::from_secs(minutes * 60);
+    let mut next_reconcile = Instant::now();
+    loop {
+        if Instant::now() >= next_reconcile {
+            if let Err(e) = reconcile(&repo, &store).await {
+                eprintln!("Failed to rebuild the leaderboards: {}", e);
+            }
+            next_reconcile = Instant::now() + interval;
+        }
+
+        match sync(&repo, &store).await {
+            Ok(0) => tokio::time::sleep(POLL_INTERVAL).await,
+            Ok(_) => {}
+            Err(e) => {
+                // The batch may be gone from the queue without reaching
+                // Redis, so rebuild instead
+                eprintln!("Failed to update the leaderboards: {}", e);
+                next_reconcile = Instant::now();
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// Apply one batch of queued deltas; returns how many there were
+async fn sync(repo: &LeaderboardRepository, store: &LeaderboardStore) -> Result<usize, AppError> {
+    let deltas = repo.take_deltas(BATCH_SIZE).await?;
+    store.apply(&deltas).await?;
+    Ok(deltas.len())
+}
+
+async fn reconcile(repo: &LeaderboardRepository, store: &LeaderboardStore) -> Result<(), AppError> {
+    let snapshot = repo.snapshot(Utc::now()).await?;
+    store.replace(&snapshot).await?;
+    println!(
+        "Rebuilt the leaderboards from {} all-time scores",
+        snapshot.all_time.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Detect coordinated rating and review what was flagged.
+//!
+//! ```text
+//! cargo run -p shared --bin rating-abuse scan
+//! cargo run -p shared --bin rating-abuse watch [MINUTES]
+//! cargo run -p shared --bin rating-abuse flags [STATUS]
+//! cargo run -p shared --bin rating-abuse confirm FLAG_ID [MODERATOR_ID]
+//! cargo run -p shared --bin rating-abuse dismiss FLAG_ID [MODERATOR_ID]
+//! ```
+//!
+//! Reads the same environment as the server. `scan` looks at the last 30
+//! days of ratings once for vote rings, bursts from new accounts and raters
+//! sharing an IP or session, and flags what it finds; `watch` keeps doing so
+//! every `MINUTES` (15 by default). `STATUS` is one of `pending` (the
+//! default), `confirmed` or `dismissed`. Confirming a flag neutralizes the
+//! rating: it is kept but stops counting towards averages, scores and
+//! reputation. Dismissing its last confirmed flag counts it again.
+
+use models::{RatingAbuseThresholds, RatingFlag, RatingFlagStatus};
+use repositories::pagination::{Cursor, PageRequest};
+use repositories::repositories::RatingFlagRepository;
+use repositories::traits::RatingFlagRepositoryTrait;
+use shared::config::Config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+use uuid::Uuid;
+
+const USAGE: &str = "usage: rating-abuse scan
+       rating-abuse watch [MINUTES]
+       rating-abuse flags [STATUS]
+       rating-abuse confirm FLAG_ID [MODERATOR_ID]
+       rating-abuse dismiss FLAG_ID [MODERATOR_ID]";
+
+const DEFAULT_WATCH_MINUTES: u64 = 15;
+
+fn parse_status(arg: &str) -> Option<RatingFlagStatus> {
+    serde_json::from_value(serde_json::Value::String(arg.to_string())).ok()
+}
+
+fn to_str<T: serde::Serialize>(value: T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+fn print_flag(flag: &RatingFlag) {
+    println!(
+        "{} {} {} rating {} {} {}",
+        flag.id,
+        to_str(flag.status),
+        to_str(flag.rating_kind),
+        flag.rating_id,
+        to_str(flag.reason),
+        flag.details
+    );
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if args.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let flags = RatingFlagRepository::new(pool);
+    let thresholds = RatingAbuseThresholds::default();
+
+    let result = match args.as_slice() {
+        ["scan"] => scan(&flags, &thresholds).await,
+        ["watch", rest @ ..] => {
+            let minutes = match rest {
+                [] => Some(DEFAULT_WATCH_MINUTES),
+                [minutes] => minutes.parse::<u64>().ok().filter(|m| *m > 0),
+                _ => None,
+            };
+            let Some(minutes) = minutes else {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            };
+            loop {
+                if let Err(e) = scan(&flags, &thresholds).await {
+                    eprintln!("Failed to scan ratings: {}", e);
+                }
+                tokio::time::sleep(Duration::from_secs(minutes * 60)).await;
+            }
+        }
+        ["flags", rest @ ..] => {
+            let status = match rest {
+                [] => Some(RatingFlagStatus::Pending),
+                [status] => parse_status(status),
+                _ => None,
+            };
+            let Some(status) = status else {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            };
+            list(&flags, status).await
+        }
+        [action @ ("confirm" | "dismiss"), flag, rest @ ..] if rest.len() <= 1 => {
+            let status = if *action == "confirm" {
+                RatingFlagStatus::Confirmed
+            } else {
+                RatingFlagStatus::Dismissed
+            };
+            let Ok(flag_id) = flag.parse::<Uuid>() else {
+                eprintln!("Invalid flag id '{}'", flag);
+                return ExitCode::FAILURE;
+            };
+            let moderator_id = match rest.first().map(|id| id.parse::<Uuid>()) {
+                None => None,
+                Some(Ok(id)) => Some(id),
+                Some(Err(_)) => {
+                    eprintln!("Invalid moderator id '{}'", rest[0]);
+                    return ExitCode::FAILURE;
+                }
+            };
+            match flags.review(flag_id, status, moderator_id).await {
+                Ok(Some(flag)) => {
+                    print_flag(&flag);
+                    Ok(())
+                }
+                Ok(None) => {
+                    eprintln!("Flag {} not found", flag_id);
+                    return ExitCode::FAILURE;
+                }
+                Err(e) => Err(e),
+            }
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to review ratings: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn scan(
+    flags: &RatingFlagRepository,
+    thresholds: &RatingAbuseThresholds,
+) -> Result<(), sqlx::Error> {
+    let rings = flags.flag_vote_rings(thresholds).await?;
+    let bursts = flags.flag_new_account_bursts(thresholds).await?;
+    let shared = flags.flag_shared_origins(thresholds).await?;
+    println!(
+        "Flagged {} vote ring, {} new account burst and {} shared origin ratings",
+        rings, bursts, shared
+    );
+    Ok(())
+}
+
+async fn list(flags: &RatingFlagRepository, status: RatingFlagStatus) -> Result<(), sqlx::Error> {
+    let mut page = PageRequest::first(PageRequest::MAX_LIMIT);
+    loop {
+        let result = flags.find_by_status(Some(status), page).await?;
+        result.items.iter().for_each(print_flag);
+        match result.next_cursor.as_deref().and_then(Cursor::decode) {
+            Some(cursor) => page.cursor = Some(cursor),
+            None => return Ok(()),
+        }
+    }
+}
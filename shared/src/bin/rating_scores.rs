@@ -0,0 +1,117 @@
+//! Inspect and tune how rated tasks and submissions are ranked.
+//!
+//! ```text
+//! cargo run -p shared --bin rating-scores settings
+//! cargo run -p shared --bin rating-scores set SETTING VALUE
+//! cargo run -p shared --bin rating-scores recompute
+//! ```
+//!
+//! Reads the same environment as the server. `SETTING` is one of
+//! `prior_mean` (1-4), `prior_weight` (> 0), `positive_min_rating` (1-4) or
+//! `confidence_z` (> 0). Changing a setting rescores every task and
+//! submission; `recompute` does so without changing anything, e.g. after
+//! ratings were edited by hand.
+
+use models::RatingSettings;
+use repositories::repositories::RatingSettingsRepository;
+use repositories::traits::RatingSettingsRepositoryTrait;
+use shared::config::Config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: rating-scores settings
+       rating-scores set SETTING VALUE
+       rating-scores recompute";
+
+fn print_settings(settings: &RatingSettings) {
+    println!("prior_mean {}", settings.prior_mean);
+    println!("prior_weight {}", settings.prior_weight);
+    println!("positive_min_rating {}", settings.positive_min_rating);
+    println!("confidence_z {}", settings.confidence_z);
+}
+
+/// Apply `value` to `setting`, or describe why it is not accepted
+fn apply(settings: &mut RatingSettings, setting: &str, value: &str) -> Result<(), String> {
+    let number = value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("'{}' is not a number", value))?;
+    match setting {
+        "prior_mean" if (1.0..=4.0).contains(&number) => settings.prior_mean = number,
+        "prior_mean" => return Err("prior_mean must be between 1 and 4".to_string()),
+        "prior_weight" if number > 0.0 => settings.prior_weight = number,
+        "prior_weight" => return Err("prior_weight must be greater than 0".to_string()),
+        "positive_min_rating" => match value.parse::<i16>() {
+            Ok(rating) if (1..=4).contains(&rating) => settings.positive_min_rating = rating,
+            _ => return Err("positive_min_rating must be a rating from 1 to 4".to_string()),
+        },
+        "confidence_z" if number > 0.0 => settings.confidence_z = number,
+        "confidence_z" => return Err("confidence_z must be greater than 0".to_string()),
+        _ => return Err(format!("Unknown setting '{}'\n{}", setting, USAGE)),
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if !matches!(
+        args.as_slice(),
+        ["settings"] | ["set", _, _] | ["recompute"]
+    ) {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let repository = RatingSettingsRepository::new(pool);
+
+    let result = match args.as_slice() {
+        ["set", setting, value] => {
+            let mut settings = match repository.find().await {
+                Ok(settings) => settings,
+                Err(e) => {
+                    eprintln!("Failed to read the rating settings: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(message) = apply(&mut settings, setting, value) {
+                eprintln!("{}", message);
+                return ExitCode::FAILURE;
+            }
+            repository
+                .update(
+                    settings.prior_mean,
+                    settings.prior_weight,
+                    settings.positive_min_rating,
+                    settings.confidence_z,
+                )
+                .await
+                .map(|settings| print_settings(&settings))
+        }
+        ["recompute"] => repository
+            .refresh_all_scores()
+            .await
+            .map(|()| println!("Rescored every task and submission")),
+        _ => repository
+            .find()
+            .await
+            .map(|settings| print_settings(&settings)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to update the rating scores: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
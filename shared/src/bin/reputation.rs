@@ -0,0 +1,148 @@
+//! Inspect and tune reputation, and reconcile scores with the ledger.
+//!
+//! ```text
+//! cargo run -p shared --bin reputation rules
+//! cargo run -p shared --bin reputation set-weight EVENT [RATING] POINTS
+//! cargo run -p shared --bin reputation set-cap POINTS
+//! cargo run -p shared --bin reputation recompute [USER_ID]
+//! ```
+//!
+//! Reads the same environment as the server. `EVENT` is one of
+//! `task_rated`, `submission_rated`, `submission_featured` or
+//! `comment_removed`; the rated events take the `RATING` (1-4) the points
+//! apply to. New weights and caps only affect events from then on; the
+//! ledger keeps what every past event was worth.
+
+use models::ReputationEventType;
+use repositories::repositories::ReputationRepository;
+use repositories::traits::ReputationRepositoryTrait;
+use rust_decimal::Decimal;
+use shared::config::Config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+use uuid::Uuid;
+
+const USAGE: &str = "usage: reputation rules
+       reputation set-weight EVENT [RATING] POINTS
+       reputation set-cap POINTS
+       reputation recompute [USER_ID]";
+
+fn parse_event_type(arg: &str) -> Option<ReputationEventType> {
+    serde_json::from_value(serde_json::Value::String(arg.to_string())).ok()
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    if args.is_empty() {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let reputation = ReputationRepository::new(pool);
+
+    let result = match args.as_slice() {
+        ["rules"] => rules(&reputation).await,
+        ["set-weight", event, rest @ ..] => {
+            let Some(event_type) = parse_event_type(event) else {
+                eprintln!("Unknown event '{}'\n{}", event, USAGE);
+                return ExitCode::FAILURE;
+            };
+            let parsed = match (event_type.is_rated(), rest) {
+                (true, [rating, points]) => rating
+                    .parse::<i16>()
+                    .ok()
+                    .filter(|rating| (1..=4).contains(rating))
+                    .zip(points.parse::<Decimal>().ok()),
+                (false, [points]) => points.parse::<Decimal>().ok().map(|points| (0, points)),
+                _ => None,
+            };
+            let Some((rating_value, points)) = parsed else {
+                eprintln!("{}", USAGE);
+                return ExitCode::FAILURE;
+            };
+            set_weight(&reputation, event_type, rating_value, points).await
+        }
+        ["set-cap", points] => match points.parse::<Decimal>() {
+            Ok(daily_cap) if daily_cap >= Decimal::ZERO => {
+                reputation.set_daily_cap(daily_cap).await.map(|daily_cap| {
+                    println!("Daily cap set to {}", daily_cap);
+                })
+            }
+            _ => {
+                eprintln!("The daily cap must be a number of at least 0");
+                return ExitCode::FAILURE;
+            }
+        },
+        ["recompute"] => reputation.recompute_all().await.map(|corrected| {
+            println!("Corrected {} reputation scores", corrected);
+        }),
+        ["recompute", user] => {
+            let Ok(user_id) = user.parse::<Uuid>() else {
+                eprintln!("Invalid user id '{}'", user);
+                return ExitCode::FAILURE;
+            };
+            match reputation.recompute(user_id).await {
+                Ok(Some(score)) => {
+                    println!("Reputation of {} is {}", user_id, score);
+                    Ok(())
+                }
+                Ok(None) => {
+                    eprintln!("User {} not found", user_id);
+                    return ExitCode::FAILURE;
+                }
+                Err(e) => Err(e),
+            }
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Failed to update reputation: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn rules(reputation: &ReputationRepository) -> Result<(), sqlx::Error> {
+    println!("daily_cap {}", reputation.find_daily_cap().await?);
+    for weight in reputation.find_weights().await? {
+        let event = serde_json::to_value(weight.event_type).expect("event types serialize");
+        let event = event.as_str().unwrap_or_default();
+        if weight.event_type.is_rated() {
+            println!("{} {} {}", event, weight.rating_value, weight.points);
+        } else {
+            println!("{} {}", event, weight.points);
+        }
+    }
+    Ok(())
+}
+
+async fn set_weight(
+    reputation: &ReputationRepository,
+    event_type: ReputationEventType,
+    rating_value: i16,
+    points: Decimal,
+) -> Result<(), sqlx::Error> {
+    match reputation
+        .set_weight(event_type, rating_value, points)
+        .await?
+    {
+        Some(weight) => println!("Weight set to {}", weight.points),
+        None => eprintln!("No such weight"),
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Curate the tag taxonomy.
+//!
+//! ```text
+//! cargo run -p shared --bin tags show SLUG
+//! cargo run -p shared --bin tags rename SLUG NAME
+//! cargo run -p shared --bin tags describe SLUG DESCRIPTION
+//! cargo run -p shared --bin tags alias SLUG ALIAS
+//! cargo run -p shared --bin tags unalias SLUG ALIAS
+//! ```
+//!
+//! Reads the same environment as the server. `SLUG` may also be one of the
+//! tag's aliases. `alias` makes `ALIAS` resolve to the tag; a tag whose slug
+//! is `ALIAS` is merged into it, taking its tasks and aliases along.
+
+use models::Tag;
+use repositories::repositories::TagRepository;
+use repositories::traits::TagRepositoryTrait;
+use shared::config::Config;
+use sqlx::PgPool;
+use std::env;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: tags show SLUG
+       tags rename SLUG NAME
+       tags describe SLUG DESCRIPTION
+       tags alias SLUG ALIAS
+       tags unalias SLUG ALIAS";
+
+fn print_tag(tag: &Tag) {
+    println!(
+        "{} {} \"{}\" used by {} tasks, aliases: {}",
+        tag.id,
+        tag.slug,
+        tag.name,
+        tag.usage_count,
+        tag.aliases.join(", ")
+    );
+    if let Some(description) = &tag.description {
+        println!("{}", description);
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (action, slug, value) = match args.as_slice() {
+        [action @ "show", slug] => (*action, *slug, None),
+        [
+            action @ ("rename" | "describe" | "alias" | "unalias"),
+            slug,
+            value,
+        ] => (*action, *slug, Some(*value)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = Config::new();
+    let pool = PgPool::connect(&config.database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let tags = TagRepository::new(pool);
+
+    let tag = match tags.find_by_slug(&Tag::slugify(slug)).await {
+        Ok(Some(tag)) => tag,
+        Ok(None) => {
+            eprintln!("Tag '{}' not found", slug);
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("Failed to find tag: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match (action, value) {
+        ("rename", Some(name)) => {
+            let name = name.trim();
+            if name.is_empty() {
+                eprintln!("Tag name can not be empty");
+                return ExitCode::FAILURE;
+            }
+            tags.update(tag.id, Some(name.to_string()), None).await
+        }
+        ("describe", Some(description)) => {
+            tags.update(tag.id, None, Some(description.trim().to_string()))
+                .await
+        }
+        ("alias", Some(alias)) => {
+            let alias = Tag::slugify(alias);
+            if alias.is_empty() || alias == tag.slug {
+                eprintln!("'{}' can not be an alias of '{}'", alias, tag.slug);
+                return ExitCode::FAILURE;
+            }
+            tags.add_alias(tag.id, &alias).await
+        }
+        ("unalias", Some(alias)) => {
+            let alias = Tag::slugify(alias);
+            match tags.remove_alias(tag.id, &alias).await {
+                Ok(()) => tags
+                    .find_by_id(tag.id)
+                    .await
+                    .and_then(|tag| tag.ok_or(sqlx::Error::RowNotFound)),
+                Err(sqlx::Error::RowNotFound) => {
+                    eprintln!("'{}' is not an alias of '{}'", alias, tag.slug);
+                    return ExitCode::FAILURE;
+                }
+                Err(e) => Err(e),
+            }
+        }
+        _ => Ok(tag),
+    };
+
+    match result {
+        Ok(tag) => {
+            print_tag(&tag);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to update tag: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
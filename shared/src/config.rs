@@ -3,6 +3,7 @@ use std::env;
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
+    pub redis_url: String,
     pub access_secret: String,
     pub refresh_secret: String,
     pub access_token_duration: u64,
@@ -15,6 +16,34 @@ pub struct Config {
     pub frontend_activation_url: Option<String>,
     pub frontend_url: String,
     pub environment: String,
+    pub cookie_auth_enabled: bool,
+    /// Directory of the Tantivy search index (`tantivy` feature only),
+    /// written by the search-indexer and read by every API instance
+    pub search_index_path: Option<String>,
+    /// Where uploaded files are kept: `local` (default) or `s3`
+    pub storage_backend: String,
+    /// Root directory of the `local` storage backend
+    pub storage_local_path: String,
+    /// Base URL of the files API, used to build `file_url`s and the signed
+    /// download URLs of the `local` backend
+    pub files_public_url: String,
+    /// Key for signing local download URLs, defaults to ACCESS_SECRET
+    pub file_signing_secret: String,
+    pub max_upload_bytes: usize,
+    /// Base URL of the users API, used to build uploaded avatar URLs
+    pub users_public_url: String,
+    /// Lifetime of signed download URLs
+    pub signed_url_ttl_seconds: u64,
+    /// S3 compatible endpoint of the `s3` backend, e.g. `http://localhost:9000`
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: String,
+    pub s3_access_key_id: Option<String>,
+    pub s3_secret_access_key: Option<String>,
+    /// Task events kept per task for `Last-Event-ID` resumption
+    pub task_event_buffer_size: usize,
+    /// How long a task's event buffer outlives its last event
+    pub task_event_buffer_ttl_seconds: u64,
 }
 
 impl Config {
@@ -23,6 +52,7 @@ impl Config {
             database_url: env::var("DATABASE_URL")
                 .expect("DATABASE_URL must be set")
                 .to_owned(),
+            redis_url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string()),
             access_secret: env::var("ACCESS_SECRET")
                 .expect("ACCESS_SECRET must be set")
                 .to_owned(),
@@ -56,6 +86,49 @@ impl Config {
             environment: env::var("ENVIRONMENT")
                 .expect("ENVIRONMENT must be set")
                 .to_owned(),
+            cookie_auth_enabled: env::var("COOKIE_AUTH_ENABLED")
+                .map(|v| {
+                    v.parse()
+                        .expect("COOKIE_AUTH_ENABLED must be true or false")
+                })
+                .unwrap_or(false),
+            search_index_path: env::var("SEARCH_INDEX_PATH").ok(),
+            storage_backend: env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string()),
+            storage_local_path: env::var("STORAGE_LOCAL_PATH")
+                .unwrap_or_else(|_| "uploads".to_string()),
+            files_public_url: env::var("FILES_PUBLIC_URL")
+                .unwrap_or_else(|_| "/api/files".to_string()),
+            file_signing_secret: env::var("FILE_SIGNING_SECRET")
+                .or_else(|_| env::var("ACCESS_SECRET"))
+                .expect("FILE_SIGNING_SECRET or ACCESS_SECRET must be set"),
+            max_upload_bytes: env::var("MAX_UPLOAD_BYTES")
+                .map(|v| v.parse().expect("MAX_UPLOAD_BYTES must be a number"))
+                .unwrap_or(10 * 1024 * 1024),
+            users_public_url: env::var("USERS_PUBLIC_URL")
+                .unwrap_or_else(|_| "/api/users".to_string()),
+            signed_url_ttl_seconds: env::var("SIGNED_URL_TTL_SECONDS")
+                .map(|v| v.parse().expect("SIGNED_URL_TTL_SECONDS must be a number"))
+                .unwrap_or(900),
+            s3_endpoint: env::var("S3_ENDPOINT").ok(),
+            s3_bucket: env::var("S3_BUCKET").ok(),
+            s3_region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            s3_access_key_id: env::var("S3_ACCESS_KEY_ID").ok(),
+            s3_secret_access_key: env::var("S3_SECRET_ACCESS_KEY").ok(),
+            task_event_buffer_size: env::var("TASK_EVENT_BUFFER_SIZE")
+                .map(|v| v.parse().expect("TASK_EVENT_BUFFER_SIZE must be a number"))
+                .unwrap_or(200),
+            task_event_buffer_ttl_seconds: env::var("TASK_EVENT_BUFFER_TTL_SECONDS")
+                .map(|v| {
+                    v.parse()
+                        .expect("TASK_EVENT_BUFFER_TTL_SECONDS must be a number")
+                })
+                .unwrap_or(3600),
         }
     }
 }
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::storage::StorageError;
+use axum::extract::multipart::MultipartError;
 use axum::http::StatusCode;
 use redis::RedisError;
 use std::fmt;
@@ -27,6 +29,12 @@ pub enum AppError {
     DuplicateEntry(String),
     AlreadyExists(String),
 
+    // 413 Payload Too Large
+    PayloadTooLarge(String),
+
+    // 415 Unsupported Media Type
+    UnsupportedMediaType(String),
+
     // 422 Unprocessable Entity
     UnprocessableEntity(String),
 
@@ -62,6 +70,10 @@ impl AppError {
                 StatusCode::CONFLICT
             }
 
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+
             AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
 
             AppError::TooManyRequests(_) | AppError::RateLimitExceeded => {
@@ -91,6 +103,8 @@ impl AppError {
             AppError::Conflict(_) => "CONFLICT",
             AppError::DuplicateEntry(_) => "DUPLICATE_ENTRY",
             AppError::AlreadyExists(_) => "ALREADY_EXISTS",
+            AppError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            AppError::UnsupportedMediaType(_) => "UNSUPPORTED_MEDIA_TYPE",
             AppError::UnprocessableEntity(_) => "UNPROCESSABLE_ENTITY",
             AppError::TooManyRequests(_) => "TOO_MANY_REQUESTS",
             AppError::RateLimitExceeded => "RATE_LIMIT_EXCEEDED",
@@ -114,6 +128,8 @@ impl AppError {
             | AppError::Conflict(msg)
             | AppError::DuplicateEntry(msg)
             | AppError::AlreadyExists(msg)
+            | AppError::PayloadTooLarge(msg)
+            | AppError::UnsupportedMediaType(msg)
             | AppError::UnprocessableEntity(msg)
             | AppError::TooManyRequests(msg)
             | AppError::DatabaseError(msg)
@@ -142,15 +158,31 @@ impl std::error::Error for AppError {}
 // CONVERSIONS FROM OTHER ERROR TYPES
 // ============================================
 
+/// SQLSTATE Postgres raises for a regular expression it can not compile
+const INVALID_REGULAR_EXPRESSION: &str = "2201B";
+
+/// SQLSTATE of a failed CHECK constraint, also raised by triggers that
+/// guard state changes (submission status transitions, contest windows)
+const CHECK_VIOLATION: &str = "23514";
+
 /// Convert from sqlx errors
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
         match err {
             sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
             sqlx::Error::Database(db_err) => {
-                // Check for unique constraint violations
-                if let Some(constraint) = db_err.constraint() {
+                if db_err.code().as_deref() == Some(CHECK_VIOLATION) {
+                    // The change is not allowed in the row's current state
+                    AppError::Conflict(db_err.message().to_string())
+                } else if let Some(constraint) = db_err.constraint() {
+                    // Check for unique constraint violations
                     AppError::DuplicateEntry(format!("Duplicate entry: {}", constraint))
+                } else if db_err.code().as_deref() == Some(INVALID_REGULAR_EXPRESSION) {
+                    // Patterns supplied by users are compiled by the database
+                    AppError::InvalidInput(format!(
+                        "Invalid regular expression: {}",
+                        db_err.message()
+                    ))
                 } else {
                     AppError::DatabaseError(db_err.to_string())
                 }
@@ -198,3 +230,24 @@ impl From<RedisError> for AppError {
         AppError::ServiceUnavailable(format!("Failed to send redis command: {}", err))
     }
 }
+
+/// Convert from multipart form errors, keeping body size rejections apart
+impl From<MultipartError> for AppError {
+    fn from(err: MultipartError) -> Self {
+        match err.status() {
+            StatusCode::PAYLOAD_TOO_LARGE => AppError::PayloadTooLarge(err.body_text()),
+            _ => AppError::BadRequest(err.body_text()),
+        }
+    }
+}
+
+impl From<StorageError> for AppError {
+    fn from(err: StorageError) -> Self {
+        match err {
+            StorageError::NotFound(_) => AppError::NotFound("File not found".to_string()),
+            StorageError::InvalidKey(_) => AppError::BadRequest(err.to_string()),
+            StorageError::Io(_) => AppError::InternalServerError(err.to_string()),
+            StorageError::Backend(_) => AppError::ServiceUnavailable(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Live task events over Redis.
+//!
+//! Every event is appended to a short per-task Redis stream, which gives it
+//! its id and lets a client that reconnects with `Last-Event-ID` catch up,
+//! and then published on the task's pub/sub channel so every instance can
+//! forward it to its own watchers. Each instance holds a single pattern
+//! subscription and fans messages out in process.
+
+use crate::config::Config;
+use async_trait::async_trait;
+use futures_util::{Stream, StreamExt, stream};
+use redis::aio::MultiplexedConnection;
+use redis::streams::{StreamId, StreamMaxlen, StreamRangeReply};
+use redis::{AsyncCommands, RedisError};
+use repositories::task_events::{TaskEventKind, TaskEventPublisher};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Events a slow watcher may fall behind by before it catches up from the
+/// buffer instead
+const HUB_CAPACITY: usize = 1024;
+
+/// Wait before resubscribing after the pub/sub connection is lost
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Id to resume after when nothing has been buffered yet
+const START_ID: &str = "0-0";
+
+fn buffer_key(task_id: Uuid) -> String {
+    format!("task_event_buffer:{}", task_id)
+}
+
+fn channel(task_id: Uuid) -> String {
+    format!("task_events:{}", task_id)
+}
+
+/// Split a Redis stream id (`<ms>-<seq>`) so ids can be compared
+fn parse_event_id(id: &str) -> Option<(u64, u64)> {
+    let (ms, seq) = id.split_once('-')?;
+    Some((ms.parse().ok()?, seq.parse().ok()?))
+}
+
+/// A change on a task page, as sent to watchers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskEvent {
+    /// Redis stream id, sent as the SSE event id
+    pub id: String,
+    pub task_id: Uuid,
+    pub event: TaskEventKind,
+    pub data: serde_json::Value,
+}
+
+impl TaskEvent {
+    fn from_stream_entry(task_id: Uuid, entry: &StreamId) -> Option<Self> {
+        let event: String = entry.get("event")?;
+        let data: String = entry.get("data")?;
+        Some(Self {
+            id: entry.id.clone(),
+            task_id,
+            event: serde_json::from_value(serde_json::Value::String(event)).ok()?,
+            data: serde_json::from_str(&data).ok()?,
+        })
+    }
+}
+
+/// What a task watcher receives
+#[derive(Debug, Clone)]
+pub enum TaskFeedItem {
+    Event(Arc<TaskEvent>),
+    /// Events were missed and can not be replayed; the client should reload
+    /// the task page
+    Resync,
+}
+
+#[derive(Debug, Clone)]
+enum HubMessage {
+    Event(Arc<TaskEvent>),
+    /// The pub/sub subscription was (re-)established; messages published
+    /// before it may have been missed
+    Subscribed,
+}
+
+/// Events buffered after a given id
+struct Replay {
+    events: Vec<TaskEvent>,
+    /// False when the id is no longer (or never was) in the buffer, so
+    /// events may be missing
+    complete: bool,
+}
+
+#[derive(Clone)]
+pub struct TaskEventBus {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    redis: MultiplexedConnection,
+    client: redis::Client,
+    buffer_size: usize,
+    buffer_ttl_seconds: i64,
+    hub: OnceLock<broadcast::Sender<HubMessage>>,
+}
+
+impl TaskEventBus {
+    pub fn new(redis: MultiplexedConnection, config: &Config) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                redis,
+                client: redis::Client::open(config.redis_url.as_str())
+                    .expect("REDIS_URL must be a valid Redis URL"),
+                buffer_size: config.task_event_buffer_size,
+                buffer_ttl_seconds: config.task_event_buffer_ttl_seconds as i64,
+                hub: OnceLock::new(),
+            }),
+        }
+    }
+
+    async fn try_publish(
+        &self,
+        task_id: Uuid,
+        kind: TaskEventKind,
+        data: serde_json::Value,
+    ) -> Result<(), RedisError> {
+        let mut conn = self.inner.redis.clone();
+        let key = buffer_key(task_id);
+
+        // 1. Buffer it, which assigns the id
+        let id: String = conn
+            .xadd_maxlen(
+                &key,
+                StreamMaxlen::Approx(self.inner.buffer_size),
+                "*",
+                &[("event", kind.as_str()), ("data", &data.to_string())],
+            )
+            .await?;
+
+        // 2. Fan it out
+        let event = TaskEvent {
+            id,
+            task_id,
+            event: kind,
+            data,
+        };
+        let message = serde_json::to_string(&event).expect("task events serialize");
+        let _: () = redis::pipe()
+            .expire(&key, self.inner.buffer_ttl_seconds)
+            .ignore()
+            .publish(channel(task_id), message)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    /// The buffered events of a task after `after`
+    async fn replay(&self, task_id: Uuid, after: &str) -> Result<Replay, RedisError> {
+        if parse_event_id(after).is_none() {
+            return Ok(Replay {
+                events: Vec::new(),
+                complete: false,
+            });
+        }
+
+        // The range is inclusive: finding `after` itself proves nothing was
+        // trimmed in between
+        let mut conn = self.inner.redis.clone();
+        let reply: StreamRangeReply = conn
+            .xrange_count(buffer_key(task_id), after, "+", self.inner.buffer_size + 1)
+            .await?;
+
+        let complete = after == START_ID || reply.ids.first().is_some_and(|e| e.id == after);
+        let events = reply
+            .ids
+            .iter()
+            .filter(|entry| entry.id != after)
+            .filter_map(|entry| TaskEvent::from_stream_entry(task_id, entry))
+            .collect();
+        Ok(Replay { events, complete })
+    }
+
+    /// Id of the newest buffered event of a task
+    async fn latest_id(&self, task_id: Uuid) -> Result<String, RedisError> {
+        let mut conn = self.inner.redis.clone();
+        let reply: StreamRangeReply = conn
+            .xrevrange_count(buffer_key(task_id), "+", "-", 1)
+            .await?;
+        Ok(reply
+            .ids
+            .into_iter()
+            .next()
+            .map(|entry| entry.id)
+            .unwrap_or_else(|| START_ID.to_string()))
+    }
+
+    /// Receive every event published to any task, starting the instance's
+    /// subscription on first use
+    fn subscribe(&self) -> broadcast::Receiver<HubMessage> {
+        self.inner
+            .hub
+            .get_or_init(|| {
+                let (sender, _) = broadcast::channel(HUB_CAPACITY);
+                tokio::spawn(run_hub(self.inner.client.clone(), sender.clone()));
+                sender
+            })
+            .subscribe()
+    }
+
+    /// Follow the events of a task
+    ///
+    /// With `last_event_id`, buffered events after it are sent first; when
+    /// they can no longer be replayed a `Resync` is sent instead. A watcher
+    /// that falls behind catches up from the buffer the same way.
+    pub async fn watch(
+        &self,
+        task_id: Uuid,
+        last_event_id: Option<String>,
+    ) -> Result<impl Stream<Item = TaskFeedItem> + Send + use<>, RedisError> {
+        // 1. Subscribe before reading the buffer so nothing falls in between
+        let receiver = self.subscribe();
+
+        // 2. Replay what the client missed
+        let mut watcher = Watcher {
+            bus: self.clone(),
+            task_id,
+            receiver,
+            cursor: START_ID.to_string(),
+            pending: VecDeque::new(),
+        };
+        match last_event_id {
+            Some(id) => watcher.catch_up_from(&id).await?,
+            None => watcher.cursor = self.latest_id(task_id).await?,
+        }
+
+        // 3. Then follow live
+        Ok(stream::unfold(watcher, |mut watcher| async move {
+            watcher.next().await.map(|item| (item, watcher))
+        }))
+    }
+}
+
+#[async_trait]
+impl TaskEventPublisher for TaskEventBus {
+    async fn publish(&self, task_id: Uuid, kind: TaskEventKind, data: serde_json::Value) {
+        if let Err(e) = self.try_publish(task_id, kind, data).await {
+            tracing::warn!(
+                "Failed to publish {} event for task {}: {}",
+                kind,
+                task_id,
+                e
+            );
+        }
+    }
+}
+
+struct Watcher {
+    bus: TaskEventBus,
+    task_id: Uuid,
+    receiver: broadcast::Receiver<HubMessage>,
+    /// Id of the last event sent, or of the newest buffered one on connect
+    cursor: String,
+    pending: VecDeque<TaskFeedItem>,
+}
+
+impl Watcher {
+    async fn next(&mut self) -> Option<TaskFeedItem> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+
+            match self.receiver.recv().await {
+                Ok(HubMessage::Event(event)) => {
+                    if event.task_id == self.task_id && self.is_new(&event.id) {
+                        self.cursor = event.id.clone();
+                        return Some(TaskFeedItem::Event(event));
+                    }
+                }
+                Ok(HubMessage::Subscribed) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                    let cursor = self.cursor.clone();
+                    if let Err(e) = self.catch_up_from(&cursor).await {
+                        tracing::warn!("Failed to replay events of task {}: {}", self.task_id, e);
+                        self.pending.push_back(TaskFeedItem::Resync);
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Queue the buffered events after `id`, or a `Resync` when some of them
+    /// are gone
+    async fn catch_up_from(&mut self, id: &str) -> Result<(), RedisError> {
+        let replay = self.bus.replay(self.task_id, id).await?;
+        if !replay.complete {
+            self.cursor = self.bus.latest_id(self.task_id).await?;
+            self.pending.push_back(TaskFeedItem::Resync);
+            return Ok(());
+        }
+
+        self.cursor = id.to_string();
+        for event in replay.events {
+            self.cursor = event.id.clone();
+            self.pending.push_back(TaskFeedItem::Event(Arc::new(event)));
+        }
+        Ok(())
+    }
+
+    fn is_new(&self, id: &str) -> bool {
+        match (parse_event_id(id), parse_event_id(&self.cursor)) {
+            (Some(id), Some(cursor)) => id > cursor,
+            _ => false,
+        }
+    }
+}
+
+/// Forward every task event published to Redis to this instance's watchers,
+/// resubscribing whenever the connection drops
+async fn run_hub(client: redis::Client, sender: broadcast::Sender<HubMessage>) {
+    loop {
+        match client.get_async_pubsub().await {
+            Ok(mut pubsub) => match pubsub.psubscribe("task_events:*").await {
+                Ok(()) => {
+                    let _ = sender.send(HubMessage::Subscribed);
+                    let mut messages = pubsub.on_message();
+                    while let Some(message) = messages.next().await {
+                        let event = message
+                            .get_payload::<String>()
+                            .ok()
+                            .and_then(|payload| serde_json::from_str::<TaskEvent>(&payload).ok());
+                        if let Some(event) = event {
+                            let _ = sender.send(HubMessage::Event(Arc::new(event)));
+                        }
+                    }
+                    tracing::warn!("Lost the task event subscription, reconnecting");
+                }
+                Err(e) => tracing::warn!("Failed to subscribe to task events: {}", e),
+            },
+            Err(e) => tracing::warn!("Failed to connect for task events: {}", e),
+        }
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -1,7 +1,18 @@
 use crate::errors::AppError;
-use axum::{extract::FromRequestParts, http::request::Parts};
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use models::{RatingOrigin, TokenScope};
+use std::net::{IpAddr, SocketAddr};
 use uuid::Uuid;
 
+/// Header carrying the id the web client generates for each login
+pub const SESSION_ID_HEADER: &str = "x-session-id";
+
+/// Longest accepted `X-Session-Id`
+const MAX_SESSION_ID_LEN: usize = 128;
+
 /// Extractor for authenticated user ID
 pub struct CurrentUser(pub Uuid);
 
@@ -20,3 +31,129 @@ where
             .ok_or_else(|| AppError::Unauthorized("User not authenticated".to_string()))
     }
 }
+
+/// Scopes granted to the credential used for the current request
+///
+/// Session (JWT) logins carry every scope; personal access tokens
+/// only carry the scopes chosen when they were created.
+#[derive(Debug, Clone)]
+pub enum TokenScopes {
+    Session,
+    PersonalAccessToken(Vec<TokenScope>),
+}
+
+impl TokenScopes {
+    pub fn allows(&self, scope: TokenScope) -> bool {
+        match self {
+            TokenScopes::Session => true,
+            TokenScopes::PersonalAccessToken(scopes) => scopes.contains(&scope),
+        }
+    }
+
+    /// Fail with 403 unless the credential carries the given scope
+    pub fn require(&self, scope: TokenScope) -> Result<(), AppError> {
+        if self.allows(scope) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!(
+                "Token is missing the '{}' scope",
+                scope
+            )))
+        }
+    }
+
+    /// Fail with 403 unless the credential carries at least one of `scopes`
+    pub fn require_any(&self, scopes: &[TokenScope]) -> Result<(), AppError> {
+        if scopes.iter().any(|scope| self.allows(*scope)) {
+            Ok(())
+        } else {
+            let names: Vec<&str> = scopes.iter().map(TokenScope::as_str).collect();
+            Err(AppError::Forbidden(format!(
+                "Token is missing one of the '{}' scopes",
+                names.join("', '")
+            )))
+        }
+    }
+
+    /// Fail with 403 unless the request was made with a session login
+    pub fn require_session(&self) -> Result<(), AppError> {
+        match self {
+            TokenScopes::Session => Ok(()),
+            TokenScopes::PersonalAccessToken(_) => Err(AppError::Forbidden(
+                "This action is not available to personal access tokens".to_string(),
+            )),
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for TokenScopes
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<TokenScopes>()
+            .cloned()
+            .ok_or_else(|| AppError::Unauthorized("User not authenticated".to_string()))
+    }
+}
+
+/// Where the current request came from, recorded with ratings so the
+/// shared origin rule can find raters behind one IP or session
+///
+/// The client IP is the last `X-Forwarded-For` entry, the one appended by
+/// the reverse proxy in front of the API; without the header it is the peer
+/// address, available when the server is run with
+/// `into_make_service_with_connect_info::<SocketAddr>()`. The session id is
+/// the `X-Session-Id` header. Malformed values are rejected with 400.
+pub struct RequestOrigin(pub RatingOrigin);
+
+impl<S> FromRequestParts<S> for RequestOrigin
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let client_ip = match parts.headers.get_all("x-forwarded-for").iter().next_back() {
+            Some(forwarded) => {
+                let ip = forwarded
+                    .to_str()
+                    .ok()
+                    .and_then(|value| value.rsplit(',').next())
+                    .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+                    .ok_or_else(|| {
+                        AppError::BadRequest("Malformed X-Forwarded-For header".to_string())
+                    })?;
+                Some(ip)
+            }
+            None => parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip()),
+        };
+
+        let session_id = match parts.headers.get(SESSION_ID_HEADER) {
+            Some(value) => {
+                let session_id = value
+                    .to_str()
+                    .ok()
+                    .filter(|id| !id.is_empty() && id.len() <= MAX_SESSION_ID_LEN)
+                    .ok_or_else(|| {
+                        AppError::BadRequest("Malformed X-Session-Id header".to_string())
+                    })?;
+                Some(session_id.to_string())
+            }
+            None => None,
+        };
+
+        Ok(RequestOrigin(RatingOrigin {
+            // IPv4 peers of a dual stack socket show up as mapped IPv6
+            client_ip: client_ip.map(|ip| ip.to_canonical()),
+            session_id,
+        }))
+    }
+}
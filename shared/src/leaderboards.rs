@@ -0,0 +1,251 @@
+//! Leaderboards in Redis sorted sets.
+//!
+//! There is one sorted set per metric, scope and period, holding user ids
+//! scored by their total: `leaderboard:<metric>:<scope>:<period>`, where the
+//! scope is `global` or `tag:<slug>` and the period `all`, `week:2026-W07`
+//! or `month:2026-02`. Queued deltas are added to the sets as they come in;
+//! a rebuild replaces them with a snapshot from Postgres. Weekly and monthly
+//! sets expire one period after theirs ended. Each period also has an index
+//! of its sets, so a rebuild can drop the ones nobody scores on anymore.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Datelike, Utc};
+use models::{LeaderboardDelta, LeaderboardMetric, LeaderboardSnapshot, LeaderboardWindow};
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, RedisError};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Members added to a set per command during a rebuild
+const REBUILD_CHUNK: usize = 500;
+
+/// Scores closer to zero than this are zero; reputation points are decimals
+const ZERO_SCORE: f64 = 1e-9;
+
+fn period(window: LeaderboardWindow, at: DateTime<Utc>) -> String {
+    match window {
+        LeaderboardWindow::Week => {
+            let week = at.iso_week();
+            format!("week:{}-W{:02}", week.year(), week.week())
+        }
+        LeaderboardWindow::Month => format!("month:{}", at.format("%Y-%m")),
+        LeaderboardWindow::AllTime => "all".to_string(),
+    }
+}
+
+/// When the sets of the period containing `at` can go, as a Unix timestamp
+fn expires_at(window: LeaderboardWindow, at: DateTime<Utc>) -> Option<i64> {
+    let next = window.end(at)?;
+    window.end(next).map(|end| end.timestamp())
+}
+
+fn key(metric: LeaderboardMetric, tag: Option<&str>, period: &str) -> String {
+    match tag {
+        Some(tag) => format!("leaderboard:{}:tag:{}:{}", metric.as_str(), tag, period),
+        None => format!("leaderboard:{}:global:{}", metric.as_str(), period),
+    }
+}
+
+fn index_key(period: &str) -> String {
+    format!("leaderboard_index:{}", period)
+}
+
+/// Opaque pagination cursor for a position in a leaderboard
+pub fn encode_offset(offset: u64) -> String {
+    URL_SAFE_NO_PAD.encode(format!("offset:{}", offset))
+}
+
+/// Returns `None` for anything that was not produced by [`encode_offset`]
+pub fn decode_offset(cursor: &str) -> Option<u64> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    String::from_utf8(bytes)
+        .ok()?
+        .strip_prefix("offset:")?
+        .parse()
+        .ok()
+}
+
+/// One leaderboard; `tag` is `None` for the global one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub metric: LeaderboardMetric,
+    pub window: LeaderboardWindow,
+    pub tag: Option<String>,
+}
+
+impl Leaderboard {
+    /// Key of the current period's set
+    fn current_key(&self) -> String {
+        key(
+            self.metric,
+            self.tag.as_deref(),
+            &period(self.window, Utc::now()),
+        )
+    }
+}
+
+/// A user's place on a leaderboard; ranks start at 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeaderboardPosition {
+    pub rank: u64,
+    pub score: f64,
+}
+
+#[derive(Clone)]
+pub struct LeaderboardStore {
+    redis: MultiplexedConnection,
+}
+
+impl LeaderboardStore {
+    pub fn new(redis: MultiplexedConnection) -> Self {
+        Self { redis }
+    }
+
+    /// Add queued deltas to every set they belong to
+    pub async fn apply(&self, deltas: &[LeaderboardDelta]) -> Result<(), RedisError> {
+        if deltas.is_empty() {
+            return Ok(());
+        }
+
+        // 1. Add each delta to the global and tag sets of its periods
+        let mut pipe = redis::pipe();
+        let mut updated = Vec::new();
+        for delta in deltas {
+            let member = delta.user_id.to_string();
+            for window in LeaderboardWindow::ALL {
+                let period = period(window, delta.occurred_at);
+                let expires_at = expires_at(window, delta.occurred_at);
+                let index = index_key(&period);
+                let tags = delta.tags.iter().map(|tag| Some(tag.as_str()));
+                for tag in std::iter::once(None).chain(tags) {
+                    let key = key(delta.metric, tag, &period);
+                    pipe.zincr(&key, &member, delta.amount)
+                        .sadd(&index, &key)
+                        .ignore();
+                    if let Some(at) = expires_at {
+                        pipe.expire_at(&key, at).ignore();
+                    }
+                    updated.push((key, member.clone()));
+                }
+                if let Some(at) = expires_at {
+                    pipe.expire_at(&index, at).ignore();
+                }
+            }
+        }
+        let mut conn = self.redis.clone();
+        let scores: Vec<f64> = pipe.query_async(&mut conn).await?;
+
+        // 2. Drop the users whose score ended up at zero
+        let last_scores: HashMap<_, _> = updated.into_iter().zip(scores).collect();
+        let mut pipe = redis::pipe();
+        for ((key, member), score) in &last_scores {
+            if score.abs() < ZERO_SCORE {
+                pipe.zrem(key, member).ignore();
+            }
+        }
+        pipe.query_async::<()>(&mut conn).await
+    }
+
+    /// Replace the current sets with the scores of a snapshot
+    pub async fn replace(&self, snapshot: &LeaderboardSnapshot) -> Result<(), RedisError> {
+        let mut conn = self.redis.clone();
+        for window in LeaderboardWindow::ALL {
+            let period = period(window, snapshot.taken_at);
+            let expires_at = expires_at(window, snapshot.taken_at);
+            let index = index_key(&period);
+
+            let mut sets: HashMap<String, Vec<(f64, String)>> = HashMap::new();
+            for standing in snapshot.standings(window) {
+                sets.entry(key(standing.metric, standing.tag.as_deref(), &period))
+                    .or_default()
+                    .push((standing.score, standing.user_id.to_string()));
+            }
+
+            // 1. Build each set aside and swap it in, so readers never see
+            // it half-filled
+            for (key, members) in &sets {
+                let staging = format!("{}:rebuild", key);
+                let mut pipe = redis::pipe();
+                pipe.del(&staging).ignore();
+                for chunk in members.chunks(REBUILD_CHUNK) {
+                    pipe.zadd_multiple(&staging, chunk).ignore();
+                }
+                pipe.rename(&staging, key).ignore();
+                if let Some(at) = expires_at {
+                    pipe.expire_at(key, at).ignore();
+                }
+                pipe.query_async::<()>(&mut conn).await?;
+            }
+
+            // 2. Drop the sets that are empty now
+            let previous: Vec<String> = conn.smembers(&index).await?;
+            let mut pipe = redis::pipe();
+            for key in previous.iter().filter(|key| !sets.contains_key(*key)) {
+                pipe.del(key).ignore();
+            }
+            pipe.del(&index).ignore();
+            if !sets.is_empty() {
+                pipe.sadd(&index, sets.keys().collect::<Vec<_>>()).ignore();
+                if let Some(at) = expires_at {
+                    pipe.expire_at(&index, at).ignore();
+                }
+            }
+            pipe.query_async::<()>(&mut conn).await?;
+        }
+        Ok(())
+    }
+
+    /// Up to `limit` users from `offset` on, highest score first, and the
+    /// number of users on the leaderboard
+    pub async fn page(
+        &self,
+        leaderboard: &Leaderboard,
+        offset: u64,
+        limit: u64,
+    ) -> Result<(Vec<(Uuid, f64)>, u64), RedisError> {
+        let key = leaderboard.current_key();
+        let start = offset as isize;
+        let stop = (offset + limit) as isize - 1;
+
+        let mut conn = self.redis.clone();
+        let (members, total): (Vec<(String, f64)>, u64) = redis::pipe()
+            .zrevrange_withscores(&key, start, stop)
+            .zcard(&key)
+            .query_async(&mut conn)
+            .await?;
+
+        let entries = members
+            .into_iter()
+            .filter_map(|(member, score)| Some((member.parse().ok()?, score)))
+            .collect();
+        Ok((entries, total))
+    }
+
+    /// Where a user is on a leaderboard; `None` when they have no score
+    pub async fn position(
+        &self,
+        leaderboard: &Leaderboard,
+        user_id: Uuid,
+    ) -> Result<Option<LeaderboardPosition>, RedisError> {
+        let key = leaderboard.current_key();
+        let member = user_id.to_string();
+
+        let mut conn = self.redis.clone();
+        let (rank, score): (Option<u64>, Option<f64>) = redis::pipe()
+            .zrevrank(&key, &member)
+            .zscore(&key, &member)
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(rank.zip(score).map(|(rank, score)| LeaderboardPosition {
+            rank: rank + 1,
+            score,
+        }))
+    }
+
+    /// Number of users on a leaderboard
+    pub async fn size(&self, leaderboard: &Leaderboard) -> Result<u64, RedisError> {
+        let mut conn = self.redis.clone();
+        conn.zcard(leaderboard.current_key()).await
+    }
+}
@@ -5,3 +5,8 @@ pub mod config;
 pub mod auth_utils;
 pub mod state;
 pub mod extractors;
+pub mod pagination;
+pub mod storage;
+pub mod events;
+pub mod leaderboards;
+pub mod badges;
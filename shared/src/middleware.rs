@@ -1,6 +1,10 @@
 use crate::{
-    auth_utils::{extract_bearer_token, extract_user_id},
+    auth_utils::{
+        TokenSource, extract_access_token, extract_bearer_token, extract_user_id, hash_api_token,
+        is_personal_access_token, verify_csrf_token,
+    },
     errors::AppError,
+    extractors::TokenScopes,
     state::AppState,
 };
 use axum::{
@@ -31,16 +35,48 @@ pub async fn error_handler_middleware(req: Request, next: Next) -> Response {
     response
 }
 
+/// Authentication middleware - accepts access JWTs and personal access tokens
+///
+/// When cookie auth is enabled the access JWT may also come from the
+/// `access_token` cookie; state-changing requests authenticated that way
+/// must pass the double-submit CSRF check.
 pub async fn auth_middleware(
     State(app_state): State<AppState>,
     mut req: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let token = extract_bearer_token(&req)?;
-    let user_id = extract_user_id(token, &app_state.config.access_secret)?;
+    let (token, source) = extract_access_token(&req, app_state.config.cookie_auth_enabled)?;
+
+    if source == TokenSource::Cookie && !req.method().is_safe() {
+        verify_csrf_token(&req)?;
+    }
+
+    let (user_id, scopes) = if is_personal_access_token(&token) {
+        let pat = app_state
+            .repos
+            .personal_access_token
+            .find_active_by_hash(&hash_api_token(&token))
+            .await?
+            .ok_or_else(|| {
+                AppError::InvalidToken("Invalid or expired personal access token".to_string())
+            })?;
+
+        app_state
+            .repos
+            .personal_access_token
+            .touch_last_used(pat.id)
+            .await?;
+
+        let scopes = pat.scopes.iter().filter_map(|s| s.parse().ok()).collect();
+        (pat.user_id, TokenScopes::PersonalAccessToken(scopes))
+    } else {
+        let user_id = extract_user_id(&token, &app_state.config.access_secret)?;
+        (user_id, TokenScopes::Session)
+    };
 
-    // Store user_id in request extensions
+    // Store user_id and granted scopes in request extensions
     req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(scopes);
 
     Ok(next.run(req).await)
 }
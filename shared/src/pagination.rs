@@ -0,0 +1,52 @@
+use crate::errors::AppError;
+use repositories::pagination::{Cursor, Page, PageRequest};
+use serde::{Deserialize, Serialize};
+
+/// Query-string parameters accepted by every paginated endpoint
+///
+/// `?limit=20&cursor=<next_cursor from the previous page>`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PaginationQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl PaginationQuery {
+    pub fn page_request(&self) -> Result<PageRequest, AppError> {
+        let cursor = self
+            .cursor
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .map(|c| {
+                Cursor::decode(c)
+                    .ok_or_else(|| AppError::InvalidInput("Invalid pagination cursor".to_string()))
+            })
+            .transpose()?;
+
+        Ok(PageRequest::new(self.limit, cursor))
+    }
+}
+
+/// JSON envelope returned by every paginated endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaginatedResponse<T> {
+    pub status: String,
+    pub data: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> PaginatedResponse<T> {
+    /// Wrap a repository page, converting each item into its response type
+    pub fn from_page<U>(page: Page<U>) -> Self
+    where
+        U: Into<T>,
+    {
+        Self {
+            status: "Success".to_string(),
+            has_more: page.next_cursor.is_some(),
+            data: page.items.into_iter().map(Into::into).collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
@@ -1,17 +1,35 @@
 use crate::config::Config;
+use crate::events::TaskEventBus;
+use crate::leaderboards::LeaderboardStore;
+use crate::storage::{BlobStore, blob_store_from_config};
 use axum::extract::FromRef;
 use redis::aio::MultiplexedConnection;
+#[cfg(feature = "tantivy")]
+use repositories::repositories::TantivySearchRepository;
 use repositories::{
     repositories::{
-        AccountRepository, ProblemOrTaskRepository, SubmissionCommentReplyRepository,
+        AccountRepository, ActivityRepository, BadgeRepository, BlobRepository, ContestRepository,
+        EventPublishingSubmissionRepository, EventPublishingTaskCommentReplyRepository,
+        EventPublishingTaskCommentRepository, EventPublishingTaskRatingRepository, FeedRepository,
+        FollowRepository, GradingRepository, LeaderboardRepository, PersonalAccessTokenRepository,
+        ProblemOrTaskRepository, RatingFlagRepository, RatingSettingsRepository,
+        ReputationRepository, SearchRepository, SubmissionCommentReplyRepository,
         SubmissionCommentRepository, SubmissionRatingRepository, SubmissionRepository,
-        TaskCommentReplyRepository, TaskCommentRepository, TaskRatingRepository, UserRepository,
+        SubmissionRevisionRepository, TagRepository, TaskAnswerRepository,
+        TaskCommentReplyRepository, TaskCommentRepository, TaskRatingRepository,
+        TaskRevisionRepository, TaskTestCaseRepository, UserRepository,
     },
+    task_events::TaskEventPublisher,
     traits::{
-        AccountRepositoryTrait, ProblemOrTaskRepositoryTrait,
-        SubmissionCommentReplyRepositoryTrait, SubmissionCommentRepositoryTrait,
-        SubmissionRatingRepositoryTrait, SubmissionRepositoryTrait,
-        TaskCommentReplyRepositoryTrait, TaskCommentRepositoryTrait, TaskRatingRepositoryTrait,
+        AccountRepositoryTrait, ActivityRepositoryTrait, BadgeRepositoryTrait, BlobRepositoryTrait,
+        ContestRepositoryTrait, FeedRepositoryTrait, FollowRepositoryTrait, GradingRepositoryTrait,
+        LeaderboardRepositoryTrait, PersonalAccessTokenRepositoryTrait,
+        ProblemOrTaskRepositoryTrait, RatingFlagRepositoryTrait, RatingSettingsRepositoryTrait,
+        ReputationRepositoryTrait, SearchRepositoryTrait, SubmissionCommentReplyRepositoryTrait,
+        SubmissionCommentRepositoryTrait, SubmissionRatingRepositoryTrait,
+        SubmissionRepositoryTrait, SubmissionRevisionRepositoryTrait, TagRepositoryTrait,
+        TaskAnswerRepositoryTrait, TaskCommentReplyRepositoryTrait, TaskCommentRepositoryTrait,
+        TaskRatingRepositoryTrait, TaskRevisionRepositoryTrait, TaskTestCaseRepositoryTrait,
         UserRepositoryTrait,
     },
 };
@@ -24,29 +42,72 @@ pub struct AppState {
     pub config: Config,
     pub repos: AppRepositories,
     pub redis: MultiplexedConnection,
+    pub blob_store: Arc<dyn BlobStore>,
+    pub events: TaskEventBus,
+    pub leaderboards: LeaderboardStore,
 }
 
 #[derive(Clone)]
 pub struct AppRepositories {
     pub user: Arc<dyn UserRepositoryTrait>,
     pub account: Arc<dyn AccountRepositoryTrait>,
+    pub personal_access_token: Arc<dyn PersonalAccessTokenRepositoryTrait>,
     pub problem_or_task: Arc<dyn ProblemOrTaskRepositoryTrait>,
     pub submission: Arc<dyn SubmissionRepositoryTrait>,
+    pub submission_revision: Arc<dyn SubmissionRevisionRepositoryTrait>,
     pub task_rating: Arc<dyn TaskRatingRepositoryTrait>,
+    pub task_revision: Arc<dyn TaskRevisionRepositoryTrait>,
     pub submission_rating: Arc<dyn SubmissionRatingRepositoryTrait>,
     pub task_comment: Arc<dyn TaskCommentRepositoryTrait>,
     pub submission_comment: Arc<dyn SubmissionCommentRepositoryTrait>,
     pub task_comment_reply: Arc<dyn TaskCommentReplyRepositoryTrait>,
     pub submission_comment_reply: Arc<dyn SubmissionCommentReplyRepositoryTrait>,
+    pub search: Arc<dyn SearchRepositoryTrait>,
+    pub tag: Arc<dyn TagRepositoryTrait>,
+    pub blob: Arc<dyn BlobRepositoryTrait>,
+    pub task_test_case: Arc<dyn TaskTestCaseRepositoryTrait>,
+    pub grading: Arc<dyn GradingRepositoryTrait>,
+    pub task_answer: Arc<dyn TaskAnswerRepositoryTrait>,
+    pub reputation: Arc<dyn ReputationRepositoryTrait>,
+    pub rating_settings: Arc<dyn RatingSettingsRepositoryTrait>,
+    pub rating_flag: Arc<dyn RatingFlagRepositoryTrait>,
+    pub leaderboard: Arc<dyn LeaderboardRepositoryTrait>,
+    pub contest: Arc<dyn ContestRepositoryTrait>,
+    pub badge: Arc<dyn BadgeRepositoryTrait>,
+    pub activity: Arc<dyn ActivityRepositoryTrait>,
+    pub follow: Arc<dyn FollowRepositoryTrait>,
+    pub feed: Arc<dyn FeedRepositoryTrait>,
 }
 
 impl AppState {
     pub fn new(db: PgPool, config: Config, redis: MultiplexedConnection) -> Self {
+        let repos = AppRepositories::new(db.clone());
+
+        #[cfg(feature = "tantivy")]
+        let repos = match &config.search_index_path {
+            // Read-only: the search-indexer is the index's only writer
+            Some(path) => repos.with_search_index(
+                TantivySearchRepository::open_read_only(path).unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to open the search index in SEARCH_INDEX_PATH, which the search-indexer creates: {}",
+                        e
+                    )
+                }),
+            ),
+            None => repos,
+        };
+
+        let events = TaskEventBus::new(redis.clone(), &config);
+        let repos = repos.with_task_events(Arc::new(events.clone()));
+
         Self {
             db: db.clone(),
             redis: redis.clone(),
+            events,
+            leaderboards: LeaderboardStore::new(redis.clone()),
+            blob_store: blob_store_from_config(&config),
             config,
-            repos: AppRepositories::new(db.clone()),
+            repos,
         }
     }
 }
@@ -56,14 +117,62 @@ impl AppRepositories {
         Self {
             user: Arc::new(UserRepository::new(db.clone())),
             account: Arc::new(AccountRepository::new(db.clone())),
+            personal_access_token: Arc::new(PersonalAccessTokenRepository::new(db.clone())),
             problem_or_task: Arc::new(ProblemOrTaskRepository::new(db.clone())),
             submission: Arc::new(SubmissionRepository::new(db.clone())),
+            submission_revision: Arc::new(SubmissionRevisionRepository::new(db.clone())),
             task_rating: Arc::new(TaskRatingRepository::new(db.clone())),
+            task_revision: Arc::new(TaskRevisionRepository::new(db.clone())),
             submission_rating: Arc::new(SubmissionRatingRepository::new(db.clone())),
             task_comment: Arc::new(TaskCommentRepository::new(db.clone())),
             submission_comment: Arc::new(SubmissionCommentRepository::new(db.clone())),
             task_comment_reply: Arc::new(TaskCommentReplyRepository::new(db.clone())),
-            submission_comment_reply: Arc::new(SubmissionCommentReplyRepository::new(db)),
+            submission_comment_reply: Arc::new(SubmissionCommentReplyRepository::new(db.clone())),
+            search: Arc::new(SearchRepository::new(db.clone())),
+            tag: Arc::new(TagRepository::new(db.clone())),
+            blob: Arc::new(BlobRepository::new(db.clone())),
+            task_test_case: Arc::new(TaskTestCaseRepository::new(db.clone())),
+            grading: Arc::new(GradingRepository::new(db.clone())),
+            task_answer: Arc::new(TaskAnswerRepository::new(db.clone())),
+            reputation: Arc::new(ReputationRepository::new(db.clone())),
+            rating_settings: Arc::new(RatingSettingsRepository::new(db.clone())),
+            rating_flag: Arc::new(RatingFlagRepository::new(db.clone())),
+            leaderboard: Arc::new(LeaderboardRepository::new(db.clone())),
+            contest: Arc::new(ContestRepository::new(db.clone())),
+            badge: Arc::new(BadgeRepository::new(db.clone())),
+            activity: Arc::new(ActivityRepository::new(db.clone())),
+            follow: Arc::new(FollowRepository::new(db.clone())),
+            feed: Arc::new(FeedRepository::new(db)),
         }
     }
+
+    /// Report writes to task comments, replies, ratings and submissions to
+    /// `events`
+    pub fn with_task_events(mut self, events: Arc<dyn TaskEventPublisher>) -> Self {
+        self.submission = Arc::new(EventPublishingSubmissionRepository::new(
+            self.submission,
+            events.clone(),
+        ));
+        self.task_rating = Arc::new(EventPublishingTaskRatingRepository::new(
+            self.task_rating,
+            events.clone(),
+        ));
+        self.task_comment_reply = Arc::new(EventPublishingTaskCommentReplyRepository::new(
+            self.task_comment_reply,
+            self.task_comment.clone(),
+            events.clone(),
+        ));
+        self.task_comment = Arc::new(EventPublishingTaskCommentRepository::new(
+            self.task_comment,
+            events,
+        ));
+        self
+    }
+
+    /// Serve search from `index`; the search-indexer keeps it updated
+    #[cfg(feature = "tantivy")]
+    pub fn with_search_index(mut self, index: TantivySearchRepository) -> Self {
+        self.search = Arc::new(index);
+        self
+    }
 }
@@ -0,0 +1,101 @@
+use super::{BlobStore, StorageError, StoredBlob, UrlSigner, validate_key};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Suffix of the file holding a blob's content type next to its bytes
+const CONTENT_TYPE_SUFFIX: &str = ".content-type";
+
+/// Stores blobs as files below a root directory
+///
+/// Downloads go through `GET {public_url}/download/{key}`, which checks the
+/// signature produced by `signed_url`.
+pub struct LocalBlobStore {
+    root: PathBuf,
+    public_url: String,
+    signer: UrlSigner,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>, public_url: &str, signer: UrlSigner) -> Self {
+        Self {
+            root: root.into(),
+            public_url: public_url.trim_end_matches('/').to_string(),
+            signer,
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    fn content_type_path(path: &Path) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(CONTENT_TYPE_SUFFIX);
+        PathBuf::from(name)
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // Write to a temporary file first so readers never see a partial blob
+        let tmp = path.with_file_name(format!(".{}.tmp", Uuid::new_v4()));
+        fs::write(&tmp, &bytes).await?;
+        fs::write(Self::content_type_path(&path), content_type).await?;
+        fs::rename(&tmp, &path).await?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<StoredBlob, StorageError> {
+        let path = self.path(key)?;
+        let bytes = fs::read(&path).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::NotFound(key.to_string()),
+            _ => StorageError::Io(e),
+        })?;
+        let content_type = fs::read_to_string(Self::content_type_path(&path))
+            .await
+            .unwrap_or_else(|_| "application/octet-stream".to_string());
+
+        Ok(StoredBlob {
+            bytes,
+            content_type,
+        })
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, StorageError> {
+        Ok(fs::try_exists(self.path(key)?).await?)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        for path in [Self::content_type_path(&path), path] {
+            match fs::remove_file(&path).await {
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn signed_url(&self, key: &str, expires_at: DateTime<Utc>) -> String {
+        let expires = expires_at.timestamp();
+        format!(
+            "{}/download/{}?expires={}&signature={}",
+            self.public_url,
+            key,
+            expires,
+            self.signer.sign(key, expires)
+        )
+    }
+}
@@ -0,0 +1,165 @@
+//! Blob storage for uploaded files.
+//!
+//! `BlobStore` hides where the bytes live. The `local` backend writes to a
+//! directory and serves downloads through the files API; the `s3` backend
+//! talks to any S3 compatible service (AWS, MinIO, ...) and hands out
+//! presigned URLs. Which one is used is picked by `STORAGE_BACKEND`.
+
+pub mod local;
+pub mod s3;
+
+pub use local::*;
+pub use s3::*;
+
+use crate::config::Config;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{fmt, io, sync::Arc};
+
+#[derive(Debug)]
+pub enum StorageError {
+    NotFound(String),
+    InvalidKey(String),
+    Io(io::Error),
+    Backend(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::NotFound(key) => write!(f, "Blob '{}' not found", key),
+            StorageError::InvalidKey(key) => write!(f, "Invalid blob key '{}'", key),
+            StorageError::Io(e) => write!(f, "Blob store I/O error: {}", e),
+            StorageError::Backend(msg) => write!(f, "Blob store error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        StorageError::Io(err)
+    }
+}
+
+/// Content read back from a blob store
+#[derive(Debug, Clone)]
+pub struct StoredBlob {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Store `bytes` under `key`, replacing anything already there
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), StorageError>;
+
+    async fn get(&self, key: &str) -> Result<StoredBlob, StorageError>;
+
+    async fn exists(&self, key: &str) -> Result<bool, StorageError>;
+
+    /// Remove `key`; removing a missing key is not an error
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// URL that downloads `key` without further credentials until `expires_at`
+    fn signed_url(&self, key: &str, expires_at: DateTime<Utc>) -> String;
+}
+
+/// Build the blob store selected by `STORAGE_BACKEND`
+pub fn blob_store_from_config(config: &Config) -> Arc<dyn BlobStore> {
+    match config.storage_backend.as_str() {
+        "local" => Arc::new(LocalBlobStore::new(
+            &config.storage_local_path,
+            &config.files_public_url,
+            UrlSigner::new(&config.file_signing_secret),
+        )),
+        "s3" => Arc::new(
+            S3BlobStore::new(S3Config {
+                endpoint: config
+                    .s3_endpoint
+                    .clone()
+                    .expect("S3_ENDPOINT must be set for the s3 storage backend"),
+                bucket: config
+                    .s3_bucket
+                    .clone()
+                    .expect("S3_BUCKET must be set for the s3 storage backend"),
+                region: config.s3_region.clone(),
+                access_key_id: config
+                    .s3_access_key_id
+                    .clone()
+                    .expect("S3_ACCESS_KEY_ID must be set for the s3 storage backend"),
+                secret_access_key: config
+                    .s3_secret_access_key
+                    .clone()
+                    .expect("S3_SECRET_ACCESS_KEY must be set for the s3 storage backend"),
+            })
+            .expect("S3_ENDPOINT must be a valid URL"),
+        ),
+        other => panic!("Unknown STORAGE_BACKEND '{}', expected local or s3", other),
+    }
+}
+
+/// Keys are `/` separated paths of `[A-Za-z0-9._-]` segments; anything
+/// that could escape the storage root is rejected
+pub fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && key.len() <= 512
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
+
+/// Signs `(key, expiry)` pairs for download URLs served by this API
+#[derive(Clone)]
+pub struct UrlSigner {
+    secret: Vec<u8>,
+}
+
+impl UrlSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, key: &str, expires: i64) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(b"blob-download\n");
+        mac.update(key.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+
+    /// Hex signature for downloading `key` until the unix time `expires`
+    pub fn sign(&self, key: &str, expires: i64) -> String {
+        hex::encode(self.mac(key, expires).finalize().into_bytes())
+    }
+
+    /// Whether `signature` is valid for `key` and has not expired yet
+    pub fn verify(&self, key: &str, expires: i64, signature: &str) -> bool {
+        if expires < Utc::now().timestamp() {
+            return false;
+        }
+
+        match hex::decode(signature) {
+            Ok(signature) => self.mac(key, expires).verify_slice(&signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use super::{BlobStore, StorageError, StoredBlob, validate_key};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use reqwest::{Method, StatusCode, header};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// Characters AWS Signature V4 leaves unencoded
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Longest lifetime S3 accepts for a presigned URL (7 days)
+const MAX_PRESIGN_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// e.g. `https://s3.eu-west-1.amazonaws.com` or `http://localhost:9000`
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Stores blobs in a bucket of an S3 compatible service
+///
+/// Requests use path-style addressing (`{endpoint}/{bucket}/{key}`) and are
+/// signed with AWS Signature Version 4, which MinIO and similar services
+/// accept as well.
+pub struct S3BlobStore {
+    client: reqwest::Client,
+    endpoint: Url,
+    config: S3Config,
+}
+
+impl S3BlobStore {
+    pub fn new(config: S3Config) -> Result<Self, url::ParseError> {
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint: Url::parse(config.endpoint.trim_end_matches('/'))?,
+            config,
+        })
+    }
+
+    /// `host[:port]` exactly as sent in the Host header
+    fn host(&self) -> String {
+        let host = self.endpoint.host_str().unwrap_or_default();
+        match self.endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        }
+    }
+
+    /// URI-encoded `/{bucket}/{key}`
+    fn canonical_path(&self, key: &str) -> String {
+        std::iter::once(self.config.bucket.as_str())
+            .chain(key.split('/'))
+            .map(|segment| format!("/{}", utf8_percent_encode(segment, UNRESERVED)))
+            .collect()
+    }
+
+    fn credential_scope(&self, date: &str) -> String {
+        format!("{}/{}/s3/aws4_request", date, self.config.region)
+    }
+
+    fn signature(&self, now: DateTime<Utc>, canonical_request: &str) -> String {
+        let date = now.format("%Y%m%d").to_string();
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            now.format("%Y%m%dT%H%M%SZ"),
+            self.credential_scope(&date),
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let secret = format!("AWS4{}", self.config.secret_access_key);
+        let key = [
+            date.as_str(),
+            self.config.region.as_str(),
+            "s3",
+            "aws4_request",
+        ]
+        .iter()
+        .fold(secret.into_bytes(), |key, part| hmac_sha256(&key, part));
+
+        hex::encode(hmac_sha256(&key, &string_to_sign))
+    }
+
+    /// Send a request signed in the Authorization header
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Option<(Vec<u8>, &str)>,
+    ) -> Result<reqwest::Response, StorageError> {
+        validate_key(key)?;
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let path = self.canonical_path(key);
+        let payload_hash = match &body {
+            Some((bytes, _)) => hex::encode(Sha256::digest(bytes)),
+            None => EMPTY_PAYLOAD_SHA256.to_string(),
+        };
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method,
+            path,
+            self.host(),
+            payload_hash,
+            amz_date,
+            signed_headers,
+            payload_hash
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.config.access_key_id,
+            self.credential_scope(&now.format("%Y%m%d").to_string()),
+            signed_headers,
+            self.signature(now, &canonical_request)
+        );
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint_base(), path))
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header(header::AUTHORIZATION, authorization);
+
+        if let Some((bytes, content_type)) = body {
+            request = request
+                .header(header::CONTENT_TYPE, content_type)
+                .body(bytes);
+        }
+
+        request
+            .send()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    /// `scheme://host[:port]` without a trailing slash
+    fn endpoint_base(&self) -> String {
+        format!("{}://{}", self.endpoint.scheme(), self.host())
+    }
+
+    async fn error(key: &str, response: reqwest::Response) -> StorageError {
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND {
+            return StorageError::NotFound(key.to_string());
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        StorageError::Backend(format!("S3 returned {} for '{}': {}", status, key, body))
+    }
+
+    /// Presigned GET URL, valid for `expires_in` seconds from `now`
+    fn presign(&self, key: &str, now: DateTime<Utc>, expires_in: i64) -> String {
+        let path = self.canonical_path(key);
+        let credential = format!(
+            "{}/{}",
+            self.config.access_key_id,
+            self.credential_scope(&now.format("%Y%m%d").to_string())
+        );
+
+        // Already in canonical (sorted) order
+        let query = format!(
+            "X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential={}&X-Amz-Date={}&X-Amz-Expires={}&X-Amz-SignedHeaders=host",
+            utf8_percent_encode(&credential, UNRESERVED),
+            now.format("%Y%m%dT%H%M%SZ"),
+            expires_in
+        );
+        let canonical_request = format!(
+            "GET\n{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            path,
+            query,
+            self.host()
+        );
+
+        format!(
+            "{}{}?{}&X-Amz-Signature={}",
+            self.endpoint_base(),
+            path,
+            query,
+            self.signature(now, &canonical_request)
+        )
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), StorageError> {
+        let response = self
+            .send(Method::PUT, key, Some((bytes, content_type)))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error(key, response).await);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<StoredBlob, StorageError> {
+        let response = self.send(Method::GET, key, None).await?;
+        if !response.status().is_success() {
+            return Err(Self::error(key, response).await);
+        }
+
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| StorageError::Backend(e.to_string()))?;
+
+        Ok(StoredBlob {
+            bytes: bytes.to_vec(),
+            content_type,
+        })
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, StorageError> {
+        let response = self.send(Method::HEAD, key, None).await?;
+        match response.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            _ => Err(Self::error(key, response).await),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let response = self.send(Method::DELETE, key, None).await?;
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::NOT_FOUND => Ok(()),
+            _ => Err(Self::error(key, response).await),
+        }
+    }
+
+    fn signed_url(&self, key: &str, expires_at: DateTime<Utc>) -> String {
+        let now = Utc::now();
+        let expires_in = (expires_at - now)
+            .num_seconds()
+            .clamp(1, MAX_PRESIGN_SECONDS);
+        self.presign(key, now, expires_in)
+    }
+}
@@ -0,0 +1,285 @@
+// ============================================================================
+// handlers/contest_handlers.rs - Thin HTTP Layer for contests
+// ============================================================================
+
+use crate::schema::request::{
+    ContestSubmissionRequest, CreateContestRequest, SetContestTasksRequest, UpdateContestRequest,
+};
+use crate::schema::response::{
+    ContestAttemptResponse, ContestDetailResponse, ContestParticipantResponse, ContestResponse,
+    ScoreboardResponse, SubmissionResponse,
+};
+use crate::services::contest_service::ContestService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/contests
+///
+/// List contests, newest first
+pub async fn list_contests_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<ContestResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contests = service.list_contests(page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(contests)))
+}
+
+/// POST /api/contests
+///
+/// Create a contest over a set of tasks; the creator organizes it
+pub async fn create_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Json(payload): Json<CreateContestRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.create_contest(user_id, payload).await?;
+
+    // 3. Return response
+    Ok((StatusCode::CREATED, Json(contest)))
+}
+
+/// GET /api/contests/{id}
+///
+/// Get a contest with its tasks and the current user's registration. The
+/// tasks stay hidden from everyone but the organizer until it starts.
+pub async fn get_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ContestDetailResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.get_contest(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(contest))
+}
+
+/// PATCH /api/contests/{id}
+///
+/// Change a contest (organizer only); only the title and description once
+/// it started
+pub async fn update_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+    Json(payload): Json<UpdateContestRequest>,
+) -> Result<Json<ContestResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.update_contest(user_id, contest_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(contest.into()))
+}
+
+/// DELETE /api/contests/{id}
+///
+/// Delete a contest (organizer only); its submissions are kept
+pub async fn delete_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ContestResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.delete_contest(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(contest.into()))
+}
+
+/// PUT /api/contests/{id}/tasks
+///
+/// Replace the tasks of a contest before it starts (organizer only)
+pub async fn set_contest_tasks_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+    Json(payload): Json<SetContestTasksRequest>,
+) -> Result<Json<ContestDetailResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.set_tasks(user_id, contest_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(contest))
+}
+
+/// POST /api/contests/{id}/registration
+///
+/// Register for a contest that has not ended
+pub async fn register_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ContestParticipantResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsWrite)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let participant = service.register(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(participant.into()))
+}
+
+/// DELETE /api/contests/{id}/registration
+///
+/// Withdraw from a contest before it starts
+pub async fn unregister_contest_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ContestParticipantResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsWrite)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let participant = service.unregister(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(participant.into()))
+}
+
+/// POST /api/contests/{id}/tasks/{task_id}/submissions
+///
+/// Submit an attempt at a contest task while the contest runs. Each
+/// attempt becomes a new version of the participant's submission for the
+/// task and is judged like any other.
+pub async fn submit_contest_task_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((contest_id, task_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ContestSubmissionRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let submission = service
+        .submit(user_id, contest_id, task_id, payload)
+        .await?;
+
+    // 3. Return response
+    Ok((
+        StatusCode::CREATED,
+        Json(SubmissionResponse::from(submission)),
+    ))
+}
+
+/// GET /api/contests/{id}/attempts/me
+///
+/// List the current user's attempts at a contest with their verdicts
+pub async fn list_my_contest_attempts_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<Vec<ContestAttemptResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let attempts = service.list_my_attempts(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(attempts.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/contests/{id}/scoreboard
+///
+/// Rank the participants of a contest. Attempts made in the frozen last
+/// stretch show as pending to everyone but the organizer until the results
+/// are published.
+pub async fn contest_scoreboard_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ScoreboardResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let scoreboard = service.scoreboard(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(scoreboard))
+}
+
+/// POST /api/contests/{id}/publish
+///
+/// Publish the final results of a contest that is over (organizer only)
+pub async fn publish_contest_results_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(contest_id): Path<Uuid>,
+) -> Result<Json<ContestResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+
+    // 2. Call service
+    let service = ContestService::new(app_state);
+    let contest = service.publish_results(user_id, contest_id).await?;
+
+    // 3. Return response
+    Ok(Json(contest.into()))
+}
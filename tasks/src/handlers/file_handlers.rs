@@ -0,0 +1,101 @@
+// ============================================================================
+// handlers/file_handlers.rs - Thin HTTP Layer for file uploads
+// ============================================================================
+
+use crate::schema::request::SignedDownloadQuery;
+use crate::schema::response::FileResponse;
+use crate::services::file_service::FileService;
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+
+/// POST /api/files
+///
+/// Upload an attachment as the `file` field of a multipart form. Identical
+/// content is stored once; the response carries the `file_url` to put on a
+/// task or submission.
+pub async fn upload_file_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require_any(&[TokenScope::TasksWrite, TokenScope::SubmissionsWrite])?;
+
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("file") {
+            let content_type = field
+                .content_type()
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            let bytes = field.bytes().await?;
+            file = Some((content_type, bytes.to_vec()));
+            break;
+        }
+    }
+    let (content_type, bytes) =
+        file.ok_or_else(|| AppError::BadRequest("Missing 'file' field".to_string()))?;
+
+    // 2. Call service
+    let service = FileService::new(app_state);
+    let file = service.upload(user_id, &content_type, bytes).await?;
+
+    // 3. Return response
+    Ok((StatusCode::CREATED, Json(file)))
+}
+
+/// GET /api/files/{sha256}
+///
+/// Get an uploaded file's metadata with a fresh signed download URL
+pub async fn get_file_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(sha256): Path<String>,
+) -> Result<Json<FileResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = FileService::new(app_state);
+    let file = service.get_file(&sha256).await?;
+
+    // 3. Return response
+    Ok(Json(file))
+}
+
+/// GET /api/files/download/{key}?expires=...&signature=...
+///
+/// Serve a file from the `local` storage backend. Needs no login: the
+/// signature in the URL is the credential.
+pub async fn download_file_handler(
+    State(app_state): State<AppState>,
+    Path(key): Path<String>,
+    Query(params): Query<SignedDownloadQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Call service
+    let service = FileService::new(app_state);
+    let blob = service
+        .download(&key, params.expires, &params.signature)
+        .await?;
+
+    // 2. Return response
+    Ok((
+        [
+            (header::CONTENT_TYPE, blob.content_type),
+            (header::CACHE_CONTROL, "private, max-age=300".to_string()),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        blob.bytes,
+    ))
+}
@@ -0,0 +1,11 @@
+pub mod contest_handlers;
+pub mod file_handlers;
+pub mod rating_handlers;
+pub mod search_handlers;
+pub mod submission_handlers;
+pub mod tag_handlers;
+pub mod task_answer_handlers;
+pub mod task_event_handlers;
+pub mod task_follow_handlers;
+pub mod task_handlers;
+pub mod test_case_handlers;
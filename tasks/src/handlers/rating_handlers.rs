@@ -0,0 +1,130 @@
+// ============================================================================
+// handlers/rating_handlers.rs - Rating breakdowns of tasks and submissions
+// ============================================================================
+
+use crate::schema::request::RatingActivityQuery;
+use crate::schema::response::{
+    MyRatingResponse, RatingActivityResponse, RatingDistributionResponse,
+};
+use crate::services::rating_service::RatingService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+
+/// GET /api/tasks/{id}/ratings/distribution
+///
+/// Get the number of ratings per value (1-4) of a task, with its scores
+pub async fn task_rating_distribution_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<RatingDistributionResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = RatingService::new(app_state);
+    let distribution = service.task_distribution(task_id).await?;
+
+    // 3. Return response
+    Ok(Json(distribution))
+}
+
+/// GET /api/tasks/{id}/ratings/me
+///
+/// Get the current user's rating of a task
+pub async fn my_task_rating_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<MyRatingResponse>, AppError> {
+    scopes.require(TokenScope::TasksRead)?;
+
+    let service = RatingService::new(app_state);
+    let rating = service.my_task_rating(user_id, task_id).await?;
+
+    Ok(Json(rating))
+}
+
+/// GET /api/tasks/{id}/ratings/activity
+///
+/// Count the ratings a task received per day, week or month
+pub async fn task_rating_activity_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Query(query): Query<RatingActivityQuery>,
+) -> Result<Json<RatingActivityResponse>, AppError> {
+    scopes.require(TokenScope::TasksRead)?;
+
+    let service = RatingService::new(app_state);
+    let activity = service.task_activity(task_id, query).await?;
+
+    Ok(Json(activity))
+}
+
+/// GET /api/submissions/{id}/ratings/distribution
+///
+/// Get the number of ratings per value (1-4) of a submission, with its
+/// scores
+pub async fn submission_rating_distribution_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+) -> Result<Json<RatingDistributionResponse>, AppError> {
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    let service = RatingService::new(app_state);
+    let distribution = service
+        .submission_distribution(user_id, submission_id)
+        .await?;
+
+    Ok(Json(distribution))
+}
+
+/// GET /api/submissions/{id}/ratings/me
+///
+/// Get the current user's rating of a submission
+pub async fn my_submission_rating_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+) -> Result<Json<MyRatingResponse>, AppError> {
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    let service = RatingService::new(app_state);
+    let rating = service.my_submission_rating(user_id, submission_id).await?;
+
+    Ok(Json(rating))
+}
+
+/// GET /api/submissions/{id}/ratings/activity
+///
+/// Count the ratings a submission received per day, week or month
+pub async fn submission_rating_activity_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Query(query): Query<RatingActivityQuery>,
+) -> Result<Json<RatingActivityResponse>, AppError> {
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    let service = RatingService::new(app_state);
+    let activity = service
+        .submission_activity(user_id, submission_id, query)
+        .await?;
+
+    Ok(Json(activity))
+}
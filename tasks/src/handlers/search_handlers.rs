@@ -0,0 +1,73 @@
+// ============================================================================
+// handlers/search_handlers.rs - Thin HTTP Layer for full-text search
+// ============================================================================
+
+use crate::schema::request::SearchParams;
+use crate::schema::response::{SearchFacetsResponse, SearchHitResponse};
+use crate::services::search_service::SearchService;
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::PaginatedResponse,
+    state::AppState,
+};
+use validator::Validate;
+
+/// GET /api/search
+///
+/// Search tasks, submissions and discussions, optionally restricted with
+/// `scope` (all, tasks, submissions, discussions) and to hits whose task
+/// has the given `tags` or `difficulty`. Submissions are only found by
+/// their submitter and the task author
+pub async fn search_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<PaginatedResponse<SearchHitResponse>>, AppError> {
+    // 1. Validate request
+    let scope = params.scope.unwrap_or_default();
+    scopes.require(TokenScope::TasksRead)?;
+    if scope.includes_submissions() {
+        scopes.require(TokenScope::SubmissionsRead)?;
+    }
+    params.validate()?;
+
+    // 2. Call service
+    let service = SearchService::new(app_state);
+    let page = service.search(user_id, params).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(page)))
+}
+
+/// GET /api/search/facets
+///
+/// Difficulty and tag counts over all hits of a search, taking the same
+/// parameters as `GET /api/search`; `limit` and `cursor` are ignored
+pub async fn search_facets_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchFacetsResponse>, AppError> {
+    // 1. Validate request
+    let scope = params.scope.unwrap_or_default();
+    scopes.require(TokenScope::TasksRead)?;
+    if scope.includes_submissions() {
+        scopes.require(TokenScope::SubmissionsRead)?;
+    }
+    params.validate()?;
+
+    // 2. Call service
+    let service = SearchService::new(app_state);
+    let facets = service.facets(user_id, params).await?;
+
+    // 3. Return response
+    Ok(Json(facets.into()))
+}
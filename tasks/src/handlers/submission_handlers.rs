@@ -0,0 +1,213 @@
+// ============================================================================
+// handlers/submission_handlers.rs - Thin HTTP Layer for submissions
+// ============================================================================
+
+use crate::schema::request::{ResubmitRequest, RevisionDiffQuery};
+use crate::schema::response::{
+    AnswerAttemptResponse, GradingRunDetailResponse, GradingRunResponse, SubmissionResponse,
+    SubmissionRevisionDiffResponse, SubmissionRevisionResponse,
+};
+use crate::services::submission_service::SubmissionService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/submissions/{id}/revisions
+///
+/// List the saved versions of a submission, newest first (submitter and
+/// task author only)
+pub async fn list_submission_revisions_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<SubmissionRevisionResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let revisions = service.list_revisions(user_id, submission_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(revisions)))
+}
+
+/// GET /api/submissions/{id}/revisions/{version}
+///
+/// Get the submission as it was at a version
+pub async fn get_submission_revision_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((submission_id, version)): Path<(Uuid, i32)>,
+) -> Result<Json<SubmissionRevisionResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let revision = service
+        .get_revision(user_id, submission_id, version)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(revision.into()))
+}
+
+/// GET /api/submissions/{id}/revisions/diff?from=1&to=3
+///
+/// Show what changed between two versions of a submission
+pub async fn diff_submission_revisions_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Query(params): Query<RevisionDiffQuery>,
+) -> Result<Json<SubmissionRevisionDiffResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+    params.validate()?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let diff = service
+        .diff_revisions(user_id, submission_id, params)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(diff))
+}
+
+/// POST /api/submissions/{id}/resubmit
+///
+/// Submit new content for a submitted or rejected submission. The new
+/// content becomes a new version; the earlier submitted one is kept.
+pub async fn resubmit_submission_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Json(payload): Json<ResubmitRequest>,
+) -> Result<Json<SubmissionResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let submission = service.resubmit(user_id, submission_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(submission.into()))
+}
+
+/// GET /api/submissions/{id}/grading-runs
+///
+/// List the grading runs of a code submission, newest first (submitter and
+/// task author only)
+pub async fn list_grading_runs_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<GradingRunResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let runs = service
+        .list_grading_runs(user_id, submission_id, page)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(runs)))
+}
+
+/// GET /api/submissions/{id}/answer-attempts
+///
+/// List the verdicts of a submission's versions against the task's expected
+/// answer, newest first. The expected answer itself is never included.
+pub async fn list_answer_attempts_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<AnswerAttemptResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let attempts = service
+        .list_answer_attempts(user_id, submission_id, page)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(attempts)))
+}
+
+/// GET /api/submissions/{id}/grading-runs/{run_id}
+///
+/// Get a grading run with the verdict of every test. Test inputs and
+/// expected outputs are never included.
+pub async fn get_grading_run_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((submission_id, run_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<GradingRunDetailResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let (run, results) = service
+        .get_grading_run(user_id, submission_id, run_id)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(GradingRunDetailResponse {
+        run: run.into(),
+        results: results.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// POST /api/submissions/{id}/grading-runs
+///
+/// Queue the current version of a code submission for grading again
+pub async fn regrade_submission_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(submission_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsWrite)?;
+
+    // 2. Call service
+    let service = SubmissionService::new(app_state);
+    let run = service.regrade(user_id, submission_id).await?;
+
+    // 3. Return response
+    Ok((StatusCode::CREATED, Json(GradingRunResponse::from(run))))
+}
@@ -0,0 +1,73 @@
+// ============================================================================
+// handlers/tag_handlers.rs - Thin HTTP Layer for tags
+// ============================================================================
+
+use crate::schema::request::{PopularTagsQuery, TagAutocompleteQuery};
+use crate::schema::response::TagResponse;
+use crate::services::tag_service::TagService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::TokenScope;
+use shared::{errors::AppError, extractors::TokenScopes, state::AppState};
+use validator::Validate;
+
+/// GET /api/tags/autocomplete?q=ru
+///
+/// Suggest tags matching a prefix of their slug or one of their aliases
+pub async fn autocomplete_tags_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Query(params): Query<TagAutocompleteQuery>,
+) -> Result<Json<Vec<TagResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    params.validate()?;
+
+    // 2. Call service
+    let service = TagService::new(app_state);
+    let tags = service.autocomplete(params).await?;
+
+    // 3. Return response
+    Ok(Json(tags.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/tags/popular
+///
+/// Most used tags, by number of tasks carrying them
+pub async fn popular_tags_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Query(params): Query<PopularTagsQuery>,
+) -> Result<Json<Vec<TagResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    params.validate()?;
+
+    // 2. Call service
+    let service = TagService::new(app_state);
+    let tags = service.popular(params).await?;
+
+    // 3. Return response
+    Ok(Json(tags.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/tags/{slug}
+///
+/// Get a tag by its slug or one of its aliases
+pub async fn get_tag_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(slug): Path<String>,
+) -> Result<Json<TagResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TagService::new(app_state);
+    let tag = service.get_tag(&slug).await?;
+
+    // 3. Return response
+    Ok(Json(tag.into()))
+}
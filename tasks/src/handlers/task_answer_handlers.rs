@@ -0,0 +1,125 @@
+// ============================================================================
+// handlers/task_answer_handlers.rs - Thin HTTP Layer for expected answers
+// ============================================================================
+
+use crate::schema::request::SetTaskAnswerRequest;
+use crate::schema::response::{AnswerAttemptResponse, AnswerProgressResponse, TaskAnswerResponse};
+use crate::services::task_answer_service::TaskAnswerService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/tasks/{id}/answer
+///
+/// Get the expected answer of a task (author only)
+pub async fn get_task_answer_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskAnswerResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TaskAnswerService::new(app_state);
+    let answer = service.get_answer(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(answer.into()))
+}
+
+/// PUT /api/tasks/{id}/answer
+///
+/// Set or replace the expected answer submissions to a task are checked
+/// against (author only)
+pub async fn set_task_answer_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<SetTaskAnswerRequest>,
+) -> Result<Json<TaskAnswerResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = TaskAnswerService::new(app_state);
+    let answer = service.set_answer(user_id, task_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(answer.into()))
+}
+
+/// DELETE /api/tasks/{id}/answer
+///
+/// Stop checking submissions to a task (author only)
+pub async fn delete_task_answer_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskAnswerResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+
+    // 2. Call service
+    let service = TaskAnswerService::new(app_state);
+    let answer = service.delete_answer(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(answer.into()))
+}
+
+/// GET /api/tasks/{id}/answer/progress
+///
+/// Get the current user's attempt count and verdict at a task
+pub async fn get_answer_progress_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<AnswerProgressResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+
+    // 2. Call service
+    let service = TaskAnswerService::new(app_state);
+    let progress = service.get_progress(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(progress.into()))
+}
+
+/// GET /api/tasks/{id}/answer/attempts
+///
+/// List the current user's attempts at a task, newest first
+pub async fn list_my_answer_attempts_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<AnswerAttemptResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::SubmissionsRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = TaskAnswerService::new(app_state);
+    let attempts = service.list_my_attempts(user_id, task_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(attempts)))
+}
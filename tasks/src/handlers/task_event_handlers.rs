@@ -0,0 +1,51 @@
+// ============================================================================
+// handlers/task_event_handlers.rs - Thin HTTP Layer for live task events
+// ============================================================================
+
+use crate::services::task_event_service::TaskEventService;
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{Stream, StreamExt};
+use models::TokenScope;
+use shared::{errors::AppError, events::TaskFeedItem, extractors::TokenScopes, state::AppState};
+use std::convert::Infallible;
+use uuid::Uuid;
+
+/// GET /api/tasks/{id}/events
+///
+/// Stream comment, reply, rating and submission events of a task as
+/// Server-Sent Events. Reconnecting with `Last-Event-ID` replays what was
+/// missed; a `resync` event means the page must be reloaded instead.
+pub async fn task_events_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    // 2. Call service
+    let service = TaskEventService::new(app_state);
+    let events = service.watch_task(task_id, last_event_id).await?;
+
+    // 3. Return response
+    Ok(Sse::new(events.map(|item| Ok(sse_event(item)))).keep_alive(KeepAlive::default()))
+}
+
+fn sse_event(item: TaskFeedItem) -> Event {
+    match item {
+        TaskFeedItem::Event(event) => Event::default()
+            .id(event.id.as_str())
+            .event(event.event.as_str())
+            .data(event.data.to_string()),
+        TaskFeedItem::Resync => Event::default().event("resync").data("{}"),
+    }
+}
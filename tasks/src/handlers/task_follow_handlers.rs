@@ -0,0 +1,77 @@
+// ============================================================================
+// handlers/task_follow_handlers.rs - Thin HTTP Layer for following tasks
+// ============================================================================
+
+use crate::schema::response::TaskFollowResponse;
+use crate::services::task_follow_service::TaskFollowService;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+
+/// GET /api/tasks/{id}/follow
+///
+/// Get whether the current user follows a task
+pub async fn get_task_follow_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskFollowResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TaskFollowService::new(app_state);
+    let follow = service.get(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(follow))
+}
+
+/// PUT /api/tasks/{id}/follow
+///
+/// Follow a task; its new submissions and comments show up in the feed
+pub async fn follow_task_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskFollowResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = TaskFollowService::new(app_state);
+    let follow = service.follow(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(follow))
+}
+
+/// DELETE /api/tasks/{id}/follow
+///
+/// Stop following a task
+pub async fn unfollow_task_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<TaskFollowResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = TaskFollowService::new(app_state);
+    let follow = service.unfollow(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(follow))
+}
@@ -0,0 +1,124 @@
+// ============================================================================
+// handlers/task_handlers.rs - Thin HTTP Layer for problems/tasks
+// ============================================================================
+
+use crate::schema::request::{ListTasksQuery, RevisionDiffQuery};
+use crate::schema::response::{TaskResponse, TaskRevisionDiffResponse, TaskRevisionResponse};
+use crate::services::task_service::TaskService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/tasks
+///
+/// List tasks with optional filters (tags, difficulty, author, rating and
+/// date range) and sorting (newest, most_viewed, top_rated, highest_average,
+/// most_positive, most_submitted)
+pub async fn list_tasks_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Query(params): Query<ListTasksQuery>,
+) -> Result<Json<PaginatedResponse<TaskResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    params.validate()?;
+
+    // 2. Call service
+    let service = TaskService::new(app_state);
+    let page = service.list_tasks(params).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(page)))
+}
+
+/// GET /api/tasks/{id}/revisions
+///
+/// List the revisions of a task, newest first
+pub async fn list_task_revisions_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<TaskRevisionResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = TaskService::new(app_state);
+    let revisions = service.list_revisions(task_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(revisions)))
+}
+
+/// GET /api/tasks/{id}/revisions/{revision}
+///
+/// Get the task as it was at a revision
+pub async fn get_task_revision_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path((task_id, revision)): Path<(Uuid, i32)>,
+) -> Result<Json<TaskRevisionResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TaskService::new(app_state);
+    let revision = service.get_revision(task_id, revision).await?;
+
+    // 3. Return response
+    Ok(Json(revision.into()))
+}
+
+/// GET /api/tasks/{id}/revisions/diff?from=1&to=3
+///
+/// Show what changed between two revisions of a task
+pub async fn diff_task_revisions_handler(
+    State(app_state): State<AppState>,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Query(params): Query<RevisionDiffQuery>,
+) -> Result<Json<TaskRevisionDiffResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    params.validate()?;
+
+    // 2. Call service
+    let service = TaskService::new(app_state);
+    let diff = service.diff_revisions(task_id, params).await?;
+
+    // 3. Return response
+    Ok(Json(diff))
+}
+
+/// POST /api/tasks/{id}/revisions/{revision}/rollback
+///
+/// Restore a task to an earlier revision (author only). The restored state
+/// is recorded as a new revision.
+pub async fn rollback_task_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((task_id, revision)): Path<(Uuid, i32)>,
+) -> Result<Json<TaskResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+
+    // 2. Call service
+    let service = TaskService::new(app_state);
+    let task = service.rollback(user_id, task_id, revision).await?;
+
+    // 3. Return response
+    Ok(Json(task.into()))
+}
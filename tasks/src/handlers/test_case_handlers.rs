@@ -0,0 +1,191 @@
+// ============================================================================
+// handlers/test_case_handlers.rs - Thin HTTP Layer for task test cases
+// ============================================================================
+
+use crate::schema::request::{
+    CreateTestCaseRequest, ImportTestCasesQuery, ReorderTestCasesRequest, UpdateTestCaseRequest,
+};
+use crate::schema::response::TestCaseResponse;
+use crate::services::test_case_service::TestCaseService;
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/tasks/{id}/test-cases
+///
+/// List the test cases of a task in run order. The author gets every case,
+/// anyone else only the sample cases.
+pub async fn list_test_cases_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+) -> Result<Json<Vec<TestCaseResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let cases = service.list_test_cases(user_id, task_id).await?;
+
+    // 3. Return response
+    Ok(Json(cases.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/tasks/{id}/test-cases
+///
+/// Add a test case after the task's last one
+pub async fn create_test_case_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateTestCaseRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let case = service.create_test_case(user_id, task_id, payload).await?;
+
+    // 3. Return response
+    Ok((StatusCode::CREATED, Json(TestCaseResponse::from(case))))
+}
+
+/// GET /api/tasks/{id}/test-cases/{case_id}
+///
+/// Get a test case; hidden cases are only visible to the author
+pub async fn get_test_case_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((task_id, case_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<TestCaseResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let case = service.get_test_case(user_id, task_id, case_id).await?;
+
+    // 3. Return response
+    Ok(Json(case.into()))
+}
+
+/// POST /api/tasks/{id}/test-cases/import?replace=true&sample_count=2
+///
+/// Import test cases from a ZIP of `NN.in` / `NN.out` files, sent as the
+/// `file` field of a multipart form. Every case is imported or none is.
+pub async fn import_test_cases_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Query(params): Query<ImportTestCasesQuery>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    params.validate()?;
+
+    let mut archive = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("file") {
+            archive = Some(field.bytes().await?.to_vec());
+            break;
+        }
+    }
+    let archive =
+        archive.ok_or_else(|| AppError::BadRequest("Missing 'file' field".to_string()))?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let cases = service
+        .import_test_cases(user_id, task_id, archive, params)
+        .await?;
+
+    // 3. Return response
+    let cases: Vec<TestCaseResponse> = cases.into_iter().map(Into::into).collect();
+    Ok((StatusCode::CREATED, Json(cases)))
+}
+
+/// PUT /api/tasks/{id}/test-cases/order
+///
+/// Set the order test cases run in; the body lists every case of the task
+pub async fn reorder_test_cases_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<ReorderTestCasesRequest>,
+) -> Result<Json<Vec<TestCaseResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let cases = service
+        .reorder_test_cases(user_id, task_id, payload)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(cases.into_iter().map(Into::into).collect()))
+}
+
+/// PATCH /api/tasks/{id}/test-cases/{case_id}
+///
+/// Change the data or limits of a test case
+pub async fn update_test_case_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((task_id, case_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTestCaseRequest>,
+) -> Result<Json<TestCaseResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let case = service
+        .update_test_case(user_id, task_id, case_id, payload)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(case.into()))
+}
+
+/// DELETE /api/tasks/{id}/test-cases/{case_id}
+///
+/// Delete a test case; the ones after it move up
+pub async fn delete_test_case_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path((task_id, case_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<TestCaseResponse>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksWrite)?;
+
+    // 2. Call service
+    let service = TestCaseService::new(app_state);
+    let case = service.delete_test_case(user_id, task_id, case_id).await?;
+
+    // 3. Return response
+    Ok(Json(case.into()))
+}
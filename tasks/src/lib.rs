@@ -0,0 +1,21 @@
+pub mod handlers;
+pub mod routes;
+pub mod schema;
+pub mod services;
+pub mod utils;
+
+use crate::routes::{
+    contest_router, file_router, search_router, submission_router, tag_router, task_router,
+};
+use axum::Router;
+use shared::state::AppState;
+
+pub async fn app(state: AppState) -> Router<AppState> {
+    Router::new()
+        .nest("/tasks", task_router(state.clone()))
+        .nest("/submissions", submission_router(state.clone()))
+        .nest("/search", search_router(state.clone()))
+        .nest("/files", file_router(state.clone()))
+        .nest("/tags", tag_router(state.clone()))
+        .nest("/contests", contest_router(state))
+}
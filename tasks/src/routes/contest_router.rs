@@ -0,0 +1,35 @@
+use crate::handlers::contest_handlers::{
+    contest_scoreboard_handler, create_contest_handler, delete_contest_handler,
+    get_contest_handler, list_contests_handler, list_my_contest_attempts_handler,
+    publish_contest_results_handler, register_contest_handler, set_contest_tasks_handler,
+    submit_contest_task_handler, unregister_contest_handler, update_contest_handler,
+};
+use axum::{
+    Router, middleware,
+    routing::{get, post, put},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+pub fn contest_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_contests_handler).post(create_contest_handler))
+        .route(
+            "/{id}",
+            get(get_contest_handler)
+                .patch(update_contest_handler)
+                .delete(delete_contest_handler),
+        )
+        .route("/{id}/tasks", put(set_contest_tasks_handler))
+        .route(
+            "/{id}/tasks/{task_id}/submissions",
+            post(submit_contest_task_handler),
+        )
+        .route(
+            "/{id}/registration",
+            post(register_contest_handler).delete(unregister_contest_handler),
+        )
+        .route("/{id}/attempts/me", get(list_my_contest_attempts_handler))
+        .route("/{id}/scoreboard", get(contest_scoreboard_handler))
+        .route("/{id}/publish", post(publish_contest_results_handler))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
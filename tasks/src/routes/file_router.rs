@@ -0,0 +1,30 @@
+use crate::handlers::file_handlers::{
+    download_file_handler, get_file_handler, upload_file_handler,
+};
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+/// Room for the multipart boundaries and headers around the file itself
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+pub fn file_router(state: AppState) -> Router<AppState> {
+    let body_limit = state.config.max_upload_bytes + MULTIPART_OVERHEAD_BYTES;
+
+    let authenticated = Router::new()
+        .route(
+            "/",
+            post(upload_file_handler).layer(DefaultBodyLimit::max(body_limit)),
+        )
+        .route("/{sha256}", get(get_file_handler))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware));
+
+    // Signed download links are their own credential
+    Router::new()
+        .route("/download/{*key}", get(download_file_handler))
+        .merge(authenticated)
+}
@@ -0,0 +1,13 @@
+pub mod contest_router;
+pub mod file_router;
+pub mod search_router;
+pub mod submission_router;
+pub mod tag_router;
+pub mod task_router;
+
+pub use contest_router::contest_router;
+pub use file_router::file_router;
+pub use search_router::search_router;
+pub use submission_router::submission_router;
+pub use tag_router::tag_router;
+pub use task_router::task_router;
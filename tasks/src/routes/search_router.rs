@@ -0,0 +1,10 @@
+use crate::handlers::search_handlers::{search_facets_handler, search_handler};
+use axum::{Router, middleware, routing::get};
+use shared::{middleware::auth_middleware, state::AppState};
+
+pub fn search_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(search_handler))
+        .route("/facets", get(search_facets_handler))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
@@ -0,0 +1,44 @@
+use crate::handlers::rating_handlers::{
+    my_submission_rating_handler, submission_rating_activity_handler,
+    submission_rating_distribution_handler,
+};
+use crate::handlers::submission_handlers::{
+    diff_submission_revisions_handler, get_grading_run_handler, get_submission_revision_handler,
+    list_answer_attempts_handler, list_grading_runs_handler, list_submission_revisions_handler,
+    regrade_submission_handler, resubmit_submission_handler,
+};
+use axum::{
+    Router, middleware,
+    routing::{get, post},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+pub fn submission_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/{id}/revisions", get(list_submission_revisions_handler))
+        .route(
+            "/{id}/revisions/diff",
+            get(diff_submission_revisions_handler),
+        )
+        .route(
+            "/{id}/revisions/{version}",
+            get(get_submission_revision_handler),
+        )
+        .route("/{id}/resubmit", post(resubmit_submission_handler))
+        .route(
+            "/{id}/grading-runs",
+            get(list_grading_runs_handler).post(regrade_submission_handler),
+        )
+        .route("/{id}/grading-runs/{run_id}", get(get_grading_run_handler))
+        .route("/{id}/answer-attempts", get(list_answer_attempts_handler))
+        .route(
+            "/{id}/ratings/distribution",
+            get(submission_rating_distribution_handler),
+        )
+        .route("/{id}/ratings/me", get(my_submission_rating_handler))
+        .route(
+            "/{id}/ratings/activity",
+            get(submission_rating_activity_handler),
+        )
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
@@ -0,0 +1,13 @@
+use crate::handlers::tag_handlers::{
+    autocomplete_tags_handler, get_tag_handler, popular_tags_handler,
+};
+use axum::{Router, middleware, routing::get};
+use shared::{middleware::auth_middleware, state::AppState};
+
+pub fn tag_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/autocomplete", get(autocomplete_tags_handler))
+        .route("/popular", get(popular_tags_handler))
+        .route("/{slug}", get(get_tag_handler))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
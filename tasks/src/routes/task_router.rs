@@ -0,0 +1,85 @@
+use crate::handlers::rating_handlers::{
+    my_task_rating_handler, task_rating_activity_handler, task_rating_distribution_handler,
+};
+use crate::handlers::task_answer_handlers::{
+    delete_task_answer_handler, get_answer_progress_handler, get_task_answer_handler,
+    list_my_answer_attempts_handler, set_task_answer_handler,
+};
+use crate::handlers::task_event_handlers::task_events_handler;
+use crate::handlers::task_follow_handlers::{
+    follow_task_handler, get_task_follow_handler, unfollow_task_handler,
+};
+use crate::handlers::task_handlers::{
+    diff_task_revisions_handler, get_task_revision_handler, list_task_revisions_handler,
+    list_tasks_handler, rollback_task_handler,
+};
+use crate::handlers::test_case_handlers::{
+    create_test_case_handler, delete_test_case_handler, get_test_case_handler,
+    import_test_cases_handler, list_test_cases_handler, reorder_test_cases_handler,
+    update_test_case_handler,
+};
+use crate::utils::test_case_archive::MAX_ARCHIVE_BYTES;
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, post, put},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+/// Room for the multipart boundaries and headers around the archive itself
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+pub fn task_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_tasks_handler))
+        .route("/{id}/events", get(task_events_handler))
+        .route(
+            "/{id}/follow",
+            get(get_task_follow_handler)
+                .put(follow_task_handler)
+                .delete(unfollow_task_handler),
+        )
+        .route(
+            "/{id}/ratings/distribution",
+            get(task_rating_distribution_handler),
+        )
+        .route("/{id}/ratings/me", get(my_task_rating_handler))
+        .route("/{id}/ratings/activity", get(task_rating_activity_handler))
+        .route("/{id}/revisions", get(list_task_revisions_handler))
+        .route("/{id}/revisions/diff", get(diff_task_revisions_handler))
+        .route("/{id}/revisions/{revision}", get(get_task_revision_handler))
+        .route(
+            "/{id}/revisions/{revision}/rollback",
+            post(rollback_task_handler),
+        )
+        .route(
+            "/{id}/test-cases",
+            get(list_test_cases_handler).post(create_test_case_handler),
+        )
+        .route(
+            "/{id}/test-cases/import",
+            post(import_test_cases_handler).layer(DefaultBodyLimit::max(
+                MAX_ARCHIVE_BYTES + MULTIPART_OVERHEAD_BYTES,
+            )),
+        )
+        .route("/{id}/test-cases/order", put(reorder_test_cases_handler))
+        .route(
+            "/{id}/test-cases/{case_id}",
+            get(get_test_case_handler)
+                .patch(update_test_case_handler)
+                .delete(delete_test_case_handler),
+        )
+        .route(
+            "/{id}/answer",
+            get(get_task_answer_handler)
+                .put(set_task_answer_handler)
+                .delete(delete_task_answer_handler),
+        )
+        .route("/{id}/answer/progress", get(get_answer_progress_handler))
+        .route(
+            "/{id}/answer/attempts",
+            get(list_my_answer_attempts_handler),
+        )
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
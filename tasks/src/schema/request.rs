@@ -0,0 +1,393 @@
+use chrono::{DateTime, Utc};
+use models::{
+    AnswerMatchMode, ContestScoring, Difficulty, ProgrammingLanguage, RatingInterval, Tag,
+};
+use repositories::search_query::SearchScope;
+use repositories::task_query::{TagMatch, TaskSort};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Query string of `GET /api/tasks`
+///
+/// e.g. `?tags=rust,graphs&tag_match=all&difficulty=hard&sort=top_rated&limit=20`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ListTasksQuery {
+    /// Comma separated list of tags
+    pub tags: Option<String>,
+
+    pub tag_match: Option<TagMatch>,
+
+    pub difficulty: Option<Difficulty>,
+
+    pub author_id: Option<Uuid>,
+
+    #[validate(range(min = 0.0, max = 4.0, message = "min_rating must be between 0 and 4"))]
+    pub min_rating: Option<f64>,
+
+    #[validate(range(min = 0.0, max = 4.0, message = "max_rating must be between 0 and 4"))]
+    pub max_rating: Option<f64>,
+
+    pub created_after: Option<DateTime<Utc>>,
+
+    pub created_before: Option<DateTime<Utc>>,
+
+    pub sort: Option<TaskSort>,
+
+    #[validate(range(min = 1, max = 100, message = "limit must be between 1 and 100"))]
+    pub limit: Option<i64>,
+
+    pub cursor: Option<String>,
+}
+
+/// Slugs of a comma separated list of tags
+fn slug_list(tags: Option<&str>) -> Vec<String> {
+    tags.unwrap_or_default()
+        .split(',')
+        .map(Tag::slugify)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+impl ListTasksQuery {
+    /// Requested tags as slugs; aliases are resolved by the service
+    pub fn tag_list(&self) -> Vec<String> {
+        slug_list(self.tags.as_deref())
+    }
+}
+
+/// Query string of `GET /api/search` and `GET /api/search/facets`
+///
+/// e.g. `?q="shortest path" -dijkstra&scope=tasks&tags=graphs&difficulty=hard&limit=20`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct SearchParams {
+    #[validate(length(min = 1, max = 200, message = "q must be between 1 and 200 characters"))]
+    pub q: String,
+
+    pub scope: Option<SearchScope>,
+
+    /// Comma separated list of tags
+    pub tags: Option<String>,
+
+    pub tag_match: Option<TagMatch>,
+
+    pub difficulty: Option<Difficulty>,
+
+    #[validate(range(min = 1, max = 100, message = "limit must be between 1 and 100"))]
+    pub limit: Option<i64>,
+
+    pub cursor: Option<String>,
+}
+
+impl SearchParams {
+    /// Requested tags as slugs; aliases are resolved by the service
+    pub fn tag_list(&self) -> Vec<String> {
+        slug_list(self.tags.as_deref())
+    }
+}
+
+/// Query string of `GET /api/tags/autocomplete`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct TagAutocompleteQuery {
+    #[validate(length(max = 64, message = "q must be at most 64 characters"))]
+    pub q: String,
+
+    #[validate(range(min = 1, max = 25, message = "limit must be between 1 and 25"))]
+    pub limit: Option<i64>,
+}
+
+/// Query string of `GET /api/tags/popular`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct PopularTagsQuery {
+    #[validate(range(min = 1, max = 100, message = "limit must be between 1 and 100"))]
+    pub limit: Option<i64>,
+}
+
+/// Query string of `GET /api/tasks/{id}/revisions/diff` and
+/// `GET /api/submissions/{id}/revisions/diff`
+///
+/// `to` defaults to the current revision
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct RevisionDiffQuery {
+    #[validate(range(min = 1, message = "from must be a revision number"))]
+    pub from: i32,
+
+    #[validate(range(min = 1, message = "to must be a revision number"))]
+    pub to: Option<i32>,
+}
+
+/// Body of `POST /api/submissions/{id}/resubmit`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ResubmitRequest {
+    #[validate(length(min = 1, message = "content can not be empty"))]
+    pub content: String,
+
+    #[validate(url(message = "file_url must be a valid URL"))]
+    pub file_url: Option<String>,
+
+    /// Switch the language the code is graded as; keeps the current one
+    /// when omitted
+    pub language: Option<ProgrammingLanguage>,
+}
+
+/// Body of `POST /api/tasks/{id}/test-cases`
+///
+/// Limits fall back to 2 seconds and 256 MiB when omitted; a case is hidden
+/// and worth 1 point unless stated otherwise
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct CreateTestCaseRequest {
+    /// Fed to the program on stdin
+    #[serde(default)]
+    pub input: String,
+
+    pub expected_output: String,
+
+    #[validate(range(
+        min = 100,
+        max = 30000,
+        message = "time_limit_ms must be between 100 and 30000"
+    ))]
+    pub time_limit_ms: Option<i32>,
+
+    #[validate(range(
+        min = 8192,
+        max = 2097152,
+        message = "memory_limit_kb must be between 8192 and 2097152"
+    ))]
+    pub memory_limit_kb: Option<i32>,
+
+    /// Sample cases are shown to everyone; the others only to the author
+    pub is_sample: Option<bool>,
+
+    #[validate(range(min = 0, max = 1000, message = "points must be between 0 and 1000"))]
+    pub points: Option<i32>,
+}
+
+/// Body of `PATCH /api/tasks/{id}/test-cases/{case_id}`; omitted fields
+/// are left unchanged
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct UpdateTestCaseRequest {
+    pub input: Option<String>,
+
+    pub expected_output: Option<String>,
+
+    #[validate(range(
+        min = 100,
+        max = 30000,
+        message = "time_limit_ms must be between 100 and 30000"
+    ))]
+    pub time_limit_ms: Option<i32>,
+
+    #[validate(range(
+        min = 8192,
+        max = 2097152,
+        message = "memory_limit_kb must be between 8192 and 2097152"
+    ))]
+    pub memory_limit_kb: Option<i32>,
+
+    /// Sample cases are shown to everyone; the others only to the author
+    pub is_sample: Option<bool>,
+
+    #[validate(range(min = 0, max = 1000, message = "points must be between 0 and 1000"))]
+    pub points: Option<i32>,
+}
+
+/// Body of `PUT /api/tasks/{id}/test-cases/order`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ReorderTestCasesRequest {
+    /// Every test case of the task, in the new run order
+    #[validate(length(min = 1, message = "test_case_ids can not be empty"))]
+    pub test_case_ids: Vec<Uuid>,
+}
+
+/// Query string of `POST /api/tasks/{id}/test-cases/import`
+///
+/// The limits and points apply to every imported case
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ImportTestCasesQuery {
+    /// Delete the task's existing test cases first instead of appending
+    #[serde(default)]
+    pub replace: bool,
+
+    /// Mark the first `sample_count` imported cases as samples
+    #[validate(range(min = 0, message = "sample_count can not be negative"))]
+    pub sample_count: Option<i32>,
+
+    #[validate(range(
+        min = 100,
+        max = 30000,
+        message = "time_limit_ms must be between 100 and 30000"
+    ))]
+    pub time_limit_ms: Option<i32>,
+
+    #[validate(range(
+        min = 8192,
+        max = 2097152,
+        message = "memory_limit_kb must be between 8192 and 2097152"
+    ))]
+    pub memory_limit_kb: Option<i32>,
+
+    #[validate(range(min = 0, max = 1000, message = "points must be between 0 and 1000"))]
+    pub points: Option<i32>,
+}
+
+/// Body of `PUT /api/tasks/{id}/answer`
+#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+pub struct SetTaskAnswerRequest {
+    pub mode: AnswerMatchMode,
+
+    /// The expected answer; every acceptable answer in `answer_set` mode.
+    /// For `regex` the pattern must match the whole submitted answer.
+    #[validate(length(min = 1, max = 100, message = "answers must hold 1 to 100 answers"))]
+    pub answers: Vec<String>,
+
+    /// Ignore case; `regex` and `answer_set` only
+    #[serde(default)]
+    pub case_insensitive: bool,
+
+    /// Largest accepted absolute difference; `numeric` only, defaults to 0
+    pub tolerance: Option<f64>,
+}
+
+/// Query string of the signed download URLs handed out by the `local`
+/// storage backend
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct SignedDownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+/// Query string of the rating activity endpoints
+///
+/// e.g. `?interval=week&from=2025-01-01T00:00:00Z`; defaults to the last 30
+/// days, 12 weeks or 12 months up to now
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RatingActivityQuery {
+    pub interval: Option<RatingInterval>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// A task of a contest and what a full score on it is worth
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ContestTaskRequest {
+    pub task_id: Uuid,
+
+    /// Points contests only; defaults to 100
+    #[validate(range(min = 1, max = 10000, message = "points must be between 1 and 10000"))]
+    pub points: Option<i32>,
+}
+
+/// Body of `POST /api/contests`
+///
+/// Each rejected attempt adds 20 minutes of penalty and the public
+/// scoreboard freezes for the last hour unless stated otherwise
+#[derive(Debug, Clone, Deserialize, Serialize, Validate)]
+pub struct CreateContestRequest {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "title must be between 1 and 255 characters"
+    ))]
+    pub title: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    pub scoring: ContestScoring,
+
+    pub starts_at: DateTime<Utc>,
+
+    pub ends_at: DateTime<Utc>,
+
+    #[validate(range(
+        min = 0,
+        max = 1440,
+        message = "penalty_minutes must be between 0 and 1440"
+    ))]
+    pub penalty_minutes: Option<i32>,
+
+    #[validate(range(
+        min = 0,
+        max = 10080,
+        message = "freeze_minutes must be between 0 and 10080"
+    ))]
+    pub freeze_minutes: Option<i32>,
+
+    /// In contest order
+    #[validate(
+        length(min = 1, max = 50, message = "tasks must hold 1 to 50 tasks"),
+        nested
+    )]
+    pub tasks: Vec<ContestTaskRequest>,
+}
+
+/// Body of `PATCH /api/contests/{id}`; omitted fields are left unchanged.
+/// Only the title and description can change once the contest started.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct UpdateContestRequest {
+    #[validate(length(
+        min = 1,
+        max = 255,
+        message = "title must be between 1 and 255 characters"
+    ))]
+    pub title: Option<String>,
+
+    pub description: Option<String>,
+
+    pub scoring: Option<ContestScoring>,
+
+    pub starts_at: Option<DateTime<Utc>>,
+
+    pub ends_at: Option<DateTime<Utc>>,
+
+    #[validate(range(
+        min = 0,
+        max = 1440,
+        message = "penalty_minutes must be between 0 and 1440"
+    ))]
+    pub penalty_minutes: Option<i32>,
+
+    #[validate(range(
+        min = 0,
+        max = 10080,
+        message = "freeze_minutes must be between 0 and 10080"
+    ))]
+    pub freeze_minutes: Option<i32>,
+}
+
+impl UpdateContestRequest {
+    /// Whether anything besides the title and description changes
+    pub fn changes_schedule(&self) -> bool {
+        self.scoring.is_some()
+            || self.starts_at.is_some()
+            || self.ends_at.is_some()
+            || self.penalty_minutes.is_some()
+            || self.freeze_minutes.is_some()
+    }
+}
+
+/// Body of `PUT /api/contests/{id}/tasks`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct SetContestTasksRequest {
+    /// Every task of the contest, in contest order
+    #[validate(
+        length(min = 1, max = 50, message = "tasks must hold 1 to 50 tasks"),
+        nested
+    )]
+    pub tasks: Vec<ContestTaskRequest>,
+}
+
+/// Body of `POST /api/contests/{id}/tasks/{task_id}/submissions`
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate)]
+pub struct ContestSubmissionRequest {
+    #[validate(length(min = 1, message = "content can not be empty"))]
+    pub content: String,
+
+    #[validate(url(message = "file_url must be a valid URL"))]
+    pub file_url: Option<String>,
+
+    /// Set for code, which is graded against the task's test cases; keeps
+    /// the language of an earlier attempt when omitted
+    pub language: Option<ProgrammingLanguage>,
+}
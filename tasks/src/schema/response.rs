@@ -0,0 +1,727 @@
+use crate::utils::contest_scoreboard::ScoreboardRow;
+use crate::utils::diff::DiffLine;
+use chrono::{DateTime, Utc};
+use models::{
+    AnswerAttempt, AnswerMatchMode, AnswerVerdict, Contest, ContestAttempt, ContestParticipant,
+    ContestPhase, ContestScoring, ContestTask, Difficulty, FacetCount, GradingResult, GradingRun,
+    GradingRunStatus, GradingVerdict, ProblemOrTask, ProgrammingLanguage, RatingActivity,
+    RatingCount, RatingInterval, SearchFacets, SearchHit, SearchResultKind, Submission,
+    SubmissionRevision, SubmissionStatus, Tag, TaskAnswer, TaskAnswerProgress, TaskFollow,
+    TaskRevision, TaskTestCase,
+};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TaskResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub file_url: Option<String>,
+    pub tags: Vec<String>,
+    #[schema(value_type = String)]
+    pub difficulty: Difficulty,
+    pub average_rating: f64,
+    pub total_ratings: i32,
+    pub bayesian_rating: f64,
+    pub wilson_score: f64,
+    pub total_submissions: i32,
+    pub view_count: i32,
+    pub current_revision: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ProblemOrTask> for TaskResponse {
+    fn from(task: ProblemOrTask) -> Self {
+        Self {
+            id: task.id,
+            user_id: task.user_id,
+            title: task.title,
+            content: task.content,
+            file_url: task.file_url,
+            tags: task.tags,
+            difficulty: task.difficulty,
+            average_rating: task.average_rating,
+            total_ratings: task.total_ratings,
+            bayesian_rating: task.bayesian_rating,
+            wilson_score: task.wilson_score,
+            total_submissions: task.total_submissions,
+            view_count: task.view_count,
+            current_revision: task.current_revision,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SearchHitResponse {
+    #[schema(value_type = String)]
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    /// Matched excerpt, matching terms wrapped in `<mark>` (not HTML escaped)
+    pub snippet: String,
+    pub rank: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SearchHit> for SearchHitResponse {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            kind: hit.kind,
+            id: hit.id,
+            task_id: hit.task_id,
+            title: hit.title,
+            snippet: hit.snippet,
+            rank: hit.rank,
+            created_at: hit.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct FacetCountResponse {
+    pub value: String,
+    pub count: i64,
+}
+
+impl From<FacetCount> for FacetCountResponse {
+    fn from(count: FacetCount) -> Self {
+        Self {
+            value: count.value,
+            count: count.count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SearchFacetsResponse {
+    /// Hits per difficulty, most frequent first
+    pub difficulties: Vec<FacetCountResponse>,
+    /// Hits per tag, the most frequent tags only
+    pub tags: Vec<FacetCountResponse>,
+}
+
+impl From<SearchFacets> for SearchFacetsResponse {
+    fn from(facets: SearchFacets) -> Self {
+        Self {
+            difficulties: facets.difficulties.into_iter().map(Into::into).collect(),
+            tags: facets.tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TagResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub usage_count: i32,
+    pub aliases: Vec<String>,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            slug: tag.slug,
+            name: tag.name,
+            description: tag.description,
+            usage_count: tag.usage_count,
+            aliases: tag.aliases,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TaskRevisionResponse {
+    pub task_id: Uuid,
+    pub revision: i32,
+    pub title: String,
+    pub content: String,
+    pub file_url: Option<String>,
+    pub tags: Vec<String>,
+    #[schema(value_type = String)]
+    pub difficulty: Difficulty,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TaskRevision> for TaskRevisionResponse {
+    fn from(revision: TaskRevision) -> Self {
+        Self {
+            task_id: revision.task_id,
+            revision: revision.revision,
+            title: revision.title,
+            content: revision.content,
+            file_url: revision.file_url,
+            tags: revision.tags,
+            difficulty: revision.difficulty,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+/// Old and new value of a field that differs between two revisions
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct FieldChange<T> {
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: PartialEq> FieldChange<T> {
+    /// `None` when the value did not change
+    pub fn between(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// Changes from revision `from_revision` to `to_revision` of a task; fields
+/// that did not change are omitted
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TaskRevisionDiffResponse {
+    pub task_id: Uuid,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<FieldChange<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_url: Option<FieldChange<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<FieldChange<String>>)]
+    pub difficulty: Option<FieldChange<Difficulty>>,
+    pub tags_added: Vec<String>,
+    pub tags_removed: Vec<String>,
+    /// Line-level diff of the content, including unchanged lines
+    pub content: Vec<DiffLine>,
+    /// The content diff in `diff -u` format, empty when unchanged
+    pub unified_diff: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SubmissionResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Uuid,
+    pub content: String,
+    pub file_url: Option<String>,
+    #[schema(value_type = String)]
+    pub status: SubmissionStatus,
+    pub average_rating: f64,
+    pub total_ratings: i32,
+    pub bayesian_rating: f64,
+    pub wilson_score: f64,
+    pub is_featured: bool,
+    pub task_revision: Option<i32>,
+    pub current_version: i32,
+    #[schema(value_type = Option<String>)]
+    pub language: Option<ProgrammingLanguage>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Submission> for SubmissionResponse {
+    fn from(submission: Submission) -> Self {
+        Self {
+            id: submission.id,
+            user_id: submission.user_id,
+            task_id: submission.task_id,
+            content: submission.content,
+            file_url: submission.file_url,
+            status: submission.status,
+            average_rating: submission.average_rating.to_f64().unwrap_or_default(),
+            total_ratings: submission.total_ratings,
+            bayesian_rating: submission.bayesian_rating,
+            wilson_score: submission.wilson_score,
+            is_featured: submission.is_featured,
+            task_revision: submission.task_revision,
+            current_version: submission.current_version,
+            language: submission.language,
+            submitted_at: submission.submitted_at,
+            created_at: submission.created_at,
+            updated_at: submission.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SubmissionRevisionResponse {
+    pub submission_id: Uuid,
+    pub version: i32,
+    pub content: String,
+    pub file_url: Option<String>,
+    /// `draft` for a saved draft, `submitted` for a submitted snapshot
+    #[schema(value_type = String)]
+    pub status: SubmissionStatus,
+    pub task_revision: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SubmissionRevision> for SubmissionRevisionResponse {
+    fn from(revision: SubmissionRevision) -> Self {
+        Self {
+            submission_id: revision.submission_id,
+            version: revision.version,
+            content: revision.content,
+            file_url: revision.file_url,
+            status: revision.status,
+            task_revision: revision.task_revision,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+/// Changes from version `from_version` to `to_version` of a submission;
+/// fields that did not change are omitted
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SubmissionRevisionDiffResponse {
+    pub submission_id: Uuid,
+    pub from_version: i32,
+    pub to_version: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_url: Option<FieldChange<Option<String>>>,
+    /// Line-level diff of the content, including unchanged lines
+    pub content: Vec<DiffLine>,
+    /// The content diff in `diff -u` format, empty when unchanged
+    pub unified_diff: String,
+}
+
+/// An uploaded file. Store `file_url` on a task or submission; fetch the
+/// bytes from `download_url` before `download_url_expires_at`.
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct FileResponse {
+    pub sha256: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub file_url: String,
+    pub download_url: String,
+    pub download_url_expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A test case; hidden cases are only shown to the task author
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TestCaseResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub position: i32,
+    pub input: String,
+    pub expected_output: String,
+    pub time_limit_ms: i32,
+    pub memory_limit_kb: i32,
+    pub is_sample: bool,
+    pub points: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TaskTestCase> for TestCaseResponse {
+    fn from(case: TaskTestCase) -> Self {
+        Self {
+            id: case.id,
+            task_id: case.task_id,
+            position: case.position,
+            input: case.input,
+            expected_output: case.expected_output,
+            time_limit_ms: case.time_limit_ms,
+            memory_limit_kb: case.memory_limit_kb,
+            is_sample: case.is_sample,
+            points: case.points,
+            created_at: case.created_at,
+            updated_at: case.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct GradingRunResponse {
+    pub id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    #[schema(value_type = String)]
+    pub language: ProgrammingLanguage,
+    /// `queued`, `running`, `completed` or `failed`
+    #[schema(value_type = String)]
+    pub status: GradingRunStatus,
+    /// `AC`, `WA`, `TLE`, `MLE`, `RE` or `CE` once completed
+    #[schema(value_type = Option<String>)]
+    pub verdict: Option<GradingVerdict>,
+    pub passed_tests: i32,
+    pub total_tests: i32,
+    /// Points of the passed tests, out of `max_score`
+    pub score: i32,
+    pub max_score: i32,
+    pub compile_output: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<GradingRun> for GradingRunResponse {
+    fn from(run: GradingRun) -> Self {
+        Self {
+            id: run.id,
+            submission_id: run.submission_id,
+            submission_version: run.submission_version,
+            language: run.language,
+            status: run.status,
+            verdict: run.verdict,
+            passed_tests: run.passed_tests,
+            total_tests: run.total_tests,
+            score: run.score,
+            max_score: run.max_score,
+            compile_output: run.compile_output,
+            error: run.error,
+            created_at: run.created_at,
+            started_at: run.started_at,
+            finished_at: run.finished_at,
+        }
+    }
+}
+
+/// Outcome of one test; the test's input and expected output stay hidden
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct GradingResultResponse {
+    pub position: i32,
+    #[schema(value_type = String)]
+    pub verdict: GradingVerdict,
+    pub points: i32,
+    pub max_points: i32,
+    pub time_ms: i32,
+    pub memory_kb: i32,
+    pub exit_code: Option<i32>,
+}
+
+impl From<GradingResult> for GradingResultResponse {
+    fn from(result: GradingResult) -> Self {
+        Self {
+            position: result.position,
+            verdict: result.verdict,
+            points: result.points,
+            max_points: result.max_points,
+            time_ms: result.time_ms,
+            memory_kb: result.memory_kb,
+            exit_code: result.exit_code,
+        }
+    }
+}
+
+/// A grading run with its per-test results in test order
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct GradingRunDetailResponse {
+    #[serde(flatten)]
+    pub run: GradingRunResponse,
+    pub results: Vec<GradingResultResponse>,
+}
+
+/// The expected answer of a task, as shown to its author
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TaskAnswerResponse {
+    pub task_id: Uuid,
+    #[schema(value_type = String)]
+    pub mode: AnswerMatchMode,
+    pub answers: Vec<String>,
+    pub case_insensitive: bool,
+    pub tolerance: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TaskAnswer> for TaskAnswerResponse {
+    fn from(answer: TaskAnswer) -> Self {
+        Self {
+            task_id: answer.task_id,
+            mode: answer.mode,
+            answers: answer.answers,
+            case_insensitive: answer.case_insensitive,
+            tolerance: answer.tolerance,
+            created_at: answer.created_at,
+            updated_at: answer.updated_at,
+        }
+    }
+}
+
+/// One check of a submitted version against a task's expected answer
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct AnswerAttemptResponse {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    pub attempt_number: i32,
+    /// `correct` or `incorrect`
+    #[schema(value_type = String)]
+    pub verdict: AnswerVerdict,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AnswerAttempt> for AnswerAttemptResponse {
+    fn from(attempt: AnswerAttempt) -> Self {
+        Self {
+            id: attempt.id,
+            task_id: attempt.task_id,
+            user_id: attempt.user_id,
+            submission_id: attempt.submission_id,
+            submission_version: attempt.submission_version,
+            attempt_number: attempt.attempt_number,
+            verdict: attempt.verdict,
+            created_at: attempt.created_at,
+        }
+    }
+}
+
+/// The current user's attempts at a task with an expected answer
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct AnswerProgressResponse {
+    pub task_id: Uuid,
+    pub attempts: i32,
+    pub solved: bool,
+    pub solved_at: Option<DateTime<Utc>>,
+    #[schema(value_type = Option<String>)]
+    pub last_verdict: Option<AnswerVerdict>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl From<TaskAnswerProgress> for AnswerProgressResponse {
+    fn from(progress: TaskAnswerProgress) -> Self {
+        Self {
+            task_id: progress.task_id,
+            attempts: progress.attempts,
+            solved: progress.solved_at.is_some(),
+            solved_at: progress.solved_at,
+            last_verdict: progress.last_verdict,
+            last_attempt_at: progress.last_attempt_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct RatingCountResponse {
+    pub rating_value: i32,
+    pub count: i64,
+}
+
+impl From<RatingCount> for RatingCountResponse {
+    fn from(count: RatingCount) -> Self {
+        Self {
+            rating_value: count.rating_value,
+            count: count.count,
+        }
+    }
+}
+
+/// How the ratings of a task or submission are spread over the values 1-4
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct RatingDistributionResponse {
+    pub average_rating: f64,
+    pub total_ratings: i32,
+    pub bayesian_rating: f64,
+    pub wilson_score: f64,
+    /// One entry per value, 1 to 4
+    pub counts: Vec<RatingCountResponse>,
+}
+
+/// The current user's rating; every field is null when they have not rated
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct MyRatingResponse {
+    pub rating_value: Option<i32>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct RatingActivityBucketResponse {
+    pub bucket_start: DateTime<Utc>,
+    /// Ratings first given during the bucket
+    pub ratings: i64,
+    pub average_rating: Option<f64>,
+}
+
+impl From<RatingActivity> for RatingActivityBucketResponse {
+    fn from(activity: RatingActivity) -> Self {
+        Self {
+            bucket_start: activity.bucket_start,
+            ratings: activity.ratings,
+            average_rating: activity.average_rating,
+        }
+    }
+}
+
+/// Rating activity over time, oldest bucket first
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct RatingActivityResponse {
+    #[schema(value_type = String)]
+    pub interval: RatingInterval,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub buckets: Vec<RatingActivityBucketResponse>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ContestResponse {
+    pub id: Uuid,
+    pub created_by: Uuid,
+    pub title: String,
+    pub description: String,
+    /// `icpc` or `points`
+    #[schema(value_type = String)]
+    pub scoring: ContestScoring,
+    /// `upcoming`, `running`, `frozen`, `ended` or `published`
+    #[schema(value_type = String)]
+    pub phase: ContestPhase,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub penalty_minutes: i32,
+    pub freeze_minutes: i32,
+    /// When the public scoreboard stops changing until the results are
+    /// published
+    pub frozen_at: DateTime<Utc>,
+    pub results_published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Contest> for ContestResponse {
+    fn from(contest: Contest) -> Self {
+        Self {
+            phase: contest.phase(Utc::now()),
+            frozen_at: contest.frozen_at(),
+            id: contest.id,
+            created_by: contest.created_by,
+            title: contest.title,
+            description: contest.description,
+            scoring: contest.scoring,
+            starts_at: contest.starts_at,
+            ends_at: contest.ends_at,
+            penalty_minutes: contest.penalty_minutes,
+            freeze_minutes: contest.freeze_minutes,
+            results_published_at: contest.results_published_at,
+            created_at: contest.created_at,
+            updated_at: contest.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ContestTaskResponse {
+    pub task_id: Uuid,
+    pub position: i32,
+    pub points: i32,
+}
+
+impl From<ContestTask> for ContestTaskResponse {
+    fn from(task: ContestTask) -> Self {
+        Self {
+            task_id: task.task_id,
+            position: task.position,
+            points: task.points,
+        }
+    }
+}
+
+/// A contest with its tasks and the current user's registration
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ContestDetailResponse {
+    #[serde(flatten)]
+    pub contest: ContestResponse,
+    /// Empty for everyone but the organizer until the contest starts
+    pub tasks: Vec<ContestTaskResponse>,
+    pub participants: i64,
+    pub registered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ContestParticipantResponse {
+    pub contest_id: Uuid,
+    pub user_id: Uuid,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl From<ContestParticipant> for ContestParticipantResponse {
+    fn from(participant: ContestParticipant) -> Self {
+        Self {
+            contest_id: participant.contest_id,
+            user_id: participant.user_id,
+            registered_at: participant.registered_at,
+        }
+    }
+}
+
+/// A version submitted to a contest task, with how it was judged
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ContestAttemptResponse {
+    pub task_id: Uuid,
+    pub submission_id: Uuid,
+    pub submission_version: i32,
+    pub submitted_at: DateTime<Utc>,
+    /// `true` once graded or checked against the expected answer
+    pub judged: bool,
+    pub accepted: bool,
+    #[schema(value_type = Option<String>)]
+    pub grading_verdict: Option<GradingVerdict>,
+    pub score: Option<i32>,
+    pub max_score: Option<i32>,
+    #[schema(value_type = Option<String>)]
+    pub answer_verdict: Option<AnswerVerdict>,
+}
+
+impl From<ContestAttempt> for ContestAttemptResponse {
+    fn from(attempt: ContestAttempt) -> Self {
+        Self {
+            judged: attempt.is_judged(),
+            accepted: attempt.is_accepted(),
+            task_id: attempt.task_id,
+            submission_id: attempt.submission_id,
+            submission_version: attempt.submission_version,
+            submitted_at: attempt.submitted_at,
+            grading_verdict: attempt.grading_verdict,
+            score: attempt.score,
+            max_score: attempt.max_score,
+            answer_verdict: attempt.answer_verdict,
+        }
+    }
+}
+
+/// Standings of a contest, best first
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ScoreboardResponse {
+    pub contest_id: Uuid,
+    #[schema(value_type = String)]
+    pub scoring: ContestScoring,
+    #[schema(value_type = String)]
+    pub phase: ContestPhase,
+    /// Set when attempts from then on are shown as pending
+    pub frozen_at: Option<DateTime<Utc>>,
+    /// Contest tasks in contest order; each row lists its standings in the
+    /// same order
+    pub tasks: Vec<ContestTaskResponse>,
+    pub rows: Vec<ScoreboardRow>,
+}
+
+/// Whether the current user follows a task
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskFollowResponse {
+    pub task_id: Uuid,
+    pub following: bool,
+    /// Absent while the task is not followed
+    pub followed_at: Option<DateTime<Utc>>,
+}
+
+impl TaskFollowResponse {
+    pub fn new(task_id: Uuid, follow: Option<TaskFollow>) -> Self {
+        Self {
+            task_id,
+            following: follow.is_some(),
+            followed_at: follow.map(|follow| follow.created_at),
+        }
+    }
+}
@@ -0,0 +1,490 @@
+use crate::schema::request::{
+    ContestSubmissionRequest, ContestTaskRequest, CreateContestRequest, SetContestTasksRequest,
+    UpdateContestRequest,
+};
+use crate::schema::response::{ContestDetailResponse, ScoreboardResponse};
+use crate::utils::contest_scoreboard::compute_scoreboard;
+use chrono::{DateTime, Utc};
+use models::{
+    Contest, ContestAttempt, ContestParticipant, ContestPhase, Submission, SubmissionStatus,
+};
+use repositories::pagination::{Page, PageRequest};
+use repositories::traits::{NewContest, NewContestTask};
+use rust_decimal::Decimal;
+use shared::errors::AppError;
+use shared::state::AppState;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub struct ContestService {
+    state: AppState,
+}
+
+impl ContestService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn find_contest(&self, contest_id: Uuid) -> Result<Contest, AppError> {
+        self.state
+            .repos
+            .contest
+            .find_by_id(contest_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Contest not found".to_string()))
+    }
+
+    /// Find a contest `user_id` organizes; only the organizer may change it
+    async fn find_own_contest(&self, user_id: Uuid, contest_id: Uuid) -> Result<Contest, AppError> {
+        let contest = self.find_contest(contest_id).await?;
+        if contest.created_by != user_id {
+            return Err(AppError::Forbidden(
+                "Only the organizer can manage a contest".to_string(),
+            ));
+        }
+        Ok(contest)
+    }
+
+    async fn find_participant(
+        &self,
+        contest_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ContestParticipant, AppError> {
+        self.state
+            .repos
+            .contest
+            .find_participant(contest_id, user_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Forbidden("Only registered participants can do this".to_string())
+            })
+    }
+
+    /// Check the schedule of a contest is usable
+    fn check_schedule(
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        if ends_at <= starts_at {
+            return Err(AppError::UnprocessableEntity(
+                "ends_at must be after starts_at".to_string(),
+            ));
+        }
+        if ends_at <= now {
+            return Err(AppError::UnprocessableEntity(
+                "ends_at must be in the future".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check every task is listed once and exists
+    async fn check_tasks(
+        &self,
+        tasks: &[ContestTaskRequest],
+    ) -> Result<Vec<NewContestTask>, AppError> {
+        let mut seen = HashSet::new();
+        for task in tasks {
+            if !seen.insert(task.task_id) {
+                return Err(AppError::UnprocessableEntity(format!(
+                    "Task {} is listed more than once",
+                    task.task_id
+                )));
+            }
+            self.state
+                .repos
+                .problem_or_task
+                .find_by_id(task.task_id)
+                .await?
+                .filter(|task| task.deleted_at.is_none())
+                .ok_or_else(|| {
+                    AppError::UnprocessableEntity(format!("Task {} not found", task.task_id))
+                })?;
+        }
+
+        Ok(tasks
+            .iter()
+            .map(|task| NewContestTask {
+                task_id: task.task_id,
+                points: task.points,
+            })
+            .collect())
+    }
+
+    /// List contests, newest first
+    pub async fn list_contests(&self, page: PageRequest) -> Result<Page<Contest>, AppError> {
+        Ok(self.state.repos.contest.find_all(page).await?)
+    }
+
+    /// Get a contest with its tasks and the user's registration
+    ///
+    /// Returns:
+    /// - The tasks only to the organizer until the contest starts
+    pub async fn get_contest(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<ContestDetailResponse, AppError> {
+        let contest = self.find_contest(contest_id).await?;
+        let repo = &self.state.repos.contest;
+
+        let tasks = if contest.created_by == user_id || Utc::now() >= contest.starts_at {
+            repo.find_tasks(contest_id).await?
+        } else {
+            Vec::new()
+        };
+        let participants = repo.find_participants(contest_id).await?;
+        let registered_at = participants
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .map(|p| p.registered_at);
+
+        Ok(ContestDetailResponse {
+            contest: contest.into(),
+            tasks: tasks.into_iter().map(Into::into).collect(),
+            participants: participants.len() as i64,
+            registered_at,
+        })
+    }
+
+    /// Create a contest organized by `user_id`
+    pub async fn create_contest(
+        &self,
+        user_id: Uuid,
+        payload: CreateContestRequest,
+    ) -> Result<ContestDetailResponse, AppError> {
+        // 1. Check the schedule and tasks
+        Self::check_schedule(payload.starts_at, payload.ends_at, Utc::now())?;
+        let tasks = self.check_tasks(&payload.tasks).await?;
+
+        // 2. Create it
+        let contest = self
+            .state
+            .repos
+            .contest
+            .create(
+                NewContest {
+                    created_by: user_id,
+                    title: payload.title,
+                    description: payload.description,
+                    scoring: payload.scoring,
+                    starts_at: payload.starts_at,
+                    ends_at: payload.ends_at,
+                    penalty_minutes: payload.penalty_minutes,
+                    freeze_minutes: payload.freeze_minutes,
+                },
+                tasks,
+            )
+            .await?;
+
+        self.get_contest(user_id, contest.id).await
+    }
+
+    /// Change the details of a contest
+    ///
+    /// Only the title and description can change once it started.
+    pub async fn update_contest(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+        payload: UpdateContestRequest,
+    ) -> Result<Contest, AppError> {
+        // 1. Only the organizer may change it, and only its text once started
+        let contest = self.find_own_contest(user_id, contest_id).await?;
+        let now = Utc::now();
+        if payload.changes_schedule() {
+            if contest.phase(now) != ContestPhase::Upcoming {
+                return Err(AppError::Conflict(
+                    "The schedule and scoring of a contest can not change once it started"
+                        .to_string(),
+                ));
+            }
+            Self::check_schedule(
+                payload.starts_at.unwrap_or(contest.starts_at),
+                payload.ends_at.unwrap_or(contest.ends_at),
+                now,
+            )?;
+        }
+
+        // 2. Update it
+        Ok(self
+            .state
+            .repos
+            .contest
+            .update(
+                contest_id,
+                payload.title,
+                payload.description,
+                payload.scoring,
+                payload.starts_at,
+                payload.ends_at,
+                payload.penalty_minutes,
+                payload.freeze_minutes,
+            )
+            .await?)
+    }
+
+    /// Replace the tasks of a contest that has not started yet
+    pub async fn set_tasks(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+        payload: SetContestTasksRequest,
+    ) -> Result<ContestDetailResponse, AppError> {
+        // 1. Only the organizer may change the tasks, before the start
+        let contest = self.find_own_contest(user_id, contest_id).await?;
+        if contest.phase(Utc::now()) != ContestPhase::Upcoming {
+            return Err(AppError::Conflict(
+                "The tasks of a contest can not change once it started".to_string(),
+            ));
+        }
+
+        // 2. Replace them
+        let tasks = self.check_tasks(&payload.tasks).await?;
+        self.state
+            .repos
+            .contest
+            .set_tasks(contest_id, tasks)
+            .await?;
+
+        self.get_contest(user_id, contest_id).await
+    }
+
+    /// Delete a contest
+    ///
+    /// Side effects:
+    /// - Submissions made in it are kept as regular submissions
+    pub async fn delete_contest(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<Contest, AppError> {
+        let contest = self.find_own_contest(user_id, contest_id).await?;
+        self.state.repos.contest.delete(contest_id).await?;
+        Ok(contest)
+    }
+
+    /// Register `user_id` for a contest that has not ended
+    pub async fn register(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<ContestParticipant, AppError> {
+        let contest = self.find_contest(contest_id).await?;
+        if contest.created_by == user_id {
+            return Err(AppError::Conflict(
+                "The organizer can not take part in their own contest".to_string(),
+            ));
+        }
+        if Utc::now() >= contest.ends_at {
+            return Err(AppError::Conflict("The contest is over".to_string()));
+        }
+
+        Ok(self
+            .state
+            .repos
+            .contest
+            .register(contest_id, user_id)
+            .await?)
+    }
+
+    /// Withdraw from a contest that has not started yet
+    pub async fn unregister(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<ContestParticipant, AppError> {
+        let contest = self.find_contest(contest_id).await?;
+        let participant = self.find_participant(contest_id, user_id).await?;
+        if Utc::now() >= contest.starts_at {
+            return Err(AppError::Conflict(
+                "Registration can not be withdrawn once the contest started".to_string(),
+            ));
+        }
+
+        self.state
+            .repos
+            .contest
+            .unregister(contest_id, user_id)
+            .await?;
+        Ok(participant)
+    }
+
+    /// Submit an attempt at a contest task
+    ///
+    /// Side effects:
+    /// - The first attempt creates the participant's submission for the
+    ///   task, later ones add versions to it
+    /// - Each version is graded or checked against the expected answer like
+    ///   any other submission
+    /// - Until the results are published the submission stays out of feeds
+    ///   and only its submitter finds it in search
+    pub async fn submit(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+        task_id: Uuid,
+        payload: ContestSubmissionRequest,
+    ) -> Result<Submission, AppError> {
+        // 1. Check the contest runs and the user takes part
+        let contest = self.find_contest(contest_id).await?;
+        if !contest.is_running(Utc::now()) {
+            return Err(AppError::Conflict(
+                "Submissions are only accepted while the contest runs".to_string(),
+            ));
+        }
+        self.find_participant(contest_id, user_id).await?;
+
+        // 2. Check the task is part of the contest
+        let repo = &self.state.repos.contest;
+        if !repo
+            .find_tasks(contest_id)
+            .await?
+            .iter()
+            .any(|task| task.task_id == task_id)
+        {
+            return Err(AppError::NotFound("Contest task not found".to_string()));
+        }
+
+        // 3. Add a version to an earlier attempt
+        if let Some(submission_id) = repo
+            .find_submission_id(contest_id, user_id, task_id)
+            .await?
+        {
+            let submission = self
+                .state
+                .repos
+                .submission
+                .find_by_id(submission_id)
+                .await?
+                .filter(|submission| submission.deleted_at.is_none())
+                .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))?;
+            if !submission.status.can_resubmit() {
+                return Err(AppError::Conflict(format!(
+                    "A submission that is {} can not be resubmitted",
+                    submission.status
+                )));
+            }
+
+            return Ok(self
+                .state
+                .repos
+                .submission
+                .resubmit(
+                    submission_id,
+                    payload.content,
+                    payload.file_url,
+                    payload.language,
+                )
+                .await?);
+        }
+
+        // 4. Or start the participant's submission for the task
+        let submission = self
+            .state
+            .repos
+            .submission
+            .create(
+                user_id,
+                task_id,
+                payload.content,
+                payload.file_url,
+                Some(SubmissionStatus::Submitted),
+                Decimal::ZERO,
+                0,
+                false,
+                Some(Utc::now()),
+                payload.language,
+            )
+            .await?;
+
+        if let Err(err) = repo
+            .link_submission(contest_id, user_id, task_id, submission.id)
+            .await
+        {
+            self.state.repos.submission.delete(submission.id).await?;
+            return Err(err.into());
+        }
+
+        Ok(submission)
+    }
+
+    /// List the user's own attempts at a contest with their verdicts, which
+    /// stay visible to them while the scoreboard is frozen
+    pub async fn list_my_attempts(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<Vec<ContestAttempt>, AppError> {
+        self.find_contest(contest_id).await?;
+        self.find_participant(contest_id, user_id).await?;
+        Ok(self
+            .state
+            .repos
+            .contest
+            .find_attempts_by_user(contest_id, user_id)
+            .await?)
+    }
+
+    /// Rank the participants of a contest
+    ///
+    /// Returns:
+    /// - Live standings to the organizer
+    /// - To anyone else, attempts made since the freeze as pending until
+    ///   the results are published
+    pub async fn scoreboard(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<ScoreboardResponse, AppError> {
+        // 1. Work out what the user may see
+        let contest = self.find_contest(contest_id).await?;
+        let phase = contest.phase(Utc::now());
+        let frozen_at = match phase {
+            ContestPhase::Frozen | ContestPhase::Ended if contest.created_by != user_id => {
+                Some(contest.frozen_at())
+            }
+            _ => None,
+        };
+
+        // 2. Rank the participants
+        let repo = &self.state.repos.contest;
+        let tasks = if phase == ContestPhase::Upcoming && contest.created_by != user_id {
+            Vec::new()
+        } else {
+            repo.find_tasks(contest_id).await?
+        };
+        let participants = repo.find_participants(contest_id).await?;
+        let attempts = repo.find_attempts(contest_id).await?;
+        let rows = compute_scoreboard(&contest, &tasks, &participants, &attempts, frozen_at);
+
+        Ok(ScoreboardResponse {
+            contest_id,
+            scoring: contest.scoring,
+            phase,
+            frozen_at,
+            tasks: tasks.into_iter().map(Into::into).collect(),
+            rows,
+        })
+    }
+
+    /// Publish the final results of a contest that is over
+    ///
+    /// Side effects:
+    /// - Everyone sees the full scoreboard from then on
+    pub async fn publish_results(
+        &self,
+        user_id: Uuid,
+        contest_id: Uuid,
+    ) -> Result<Contest, AppError> {
+        let contest = self.find_own_contest(user_id, contest_id).await?;
+        if Utc::now() < contest.ends_at {
+            return Err(AppError::Conflict(
+                "Results can only be published once the contest is over".to_string(),
+            ));
+        }
+
+        Ok(self.state.repos.contest.publish_results(contest_id).await?)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::schema::response::FileResponse;
+use crate::utils::attachment::validate_content_type;
+use chrono::{Duration, Utc};
+use models::Blob;
+use sha2::{Digest, Sha256};
+use shared::errors::AppError;
+use shared::state::AppState;
+use shared::storage::{StoredBlob, UrlSigner};
+use uuid::Uuid;
+
+pub struct FileService {
+    state: AppState,
+}
+
+impl FileService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Attach a fresh signed download URL to a blob
+    fn to_response(&self, blob: Blob) -> FileResponse {
+        let config = &self.state.config;
+        let expires_at = Utc::now() + Duration::seconds(config.signed_url_ttl_seconds as i64);
+
+        FileResponse {
+            file_url: format!(
+                "{}/{}",
+                config.files_public_url.trim_end_matches('/'),
+                blob.sha256
+            ),
+            download_url: self
+                .state
+                .blob_store
+                .signed_url(&Blob::storage_key(&blob.sha256), expires_at),
+            download_url_expires_at: expires_at,
+            sha256: blob.sha256,
+            content_type: blob.content_type,
+            size_bytes: blob.size_bytes,
+            created_at: blob.created_at,
+        }
+    }
+
+    /// Store an uploaded file
+    ///
+    /// Returns:
+    /// - The blob with its `file_url` and a signed download URL
+    ///
+    /// Side effects:
+    /// - Writes the bytes to the blob store unless the same content is
+    ///   already stored
+    /// - Restarts the cleanup grace period of previously uploaded content
+    pub async fn upload(
+        &self,
+        user_id: Uuid,
+        declared_content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<FileResponse, AppError> {
+        // 1. Validate size and content
+        let max = self.state.config.max_upload_bytes;
+        if bytes.is_empty() {
+            return Err(AppError::InvalidInput("File is empty".to_string()));
+        }
+        if bytes.len() > max {
+            return Err(AppError::PayloadTooLarge(format!(
+                "File is larger than {} bytes",
+                max
+            )));
+        }
+        let content_type = validate_content_type(declared_content_type, &bytes)?;
+
+        // 2. Record it first; an earlier upload of the same content is reused
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        let key = Blob::storage_key(&sha256);
+        let blob = self
+            .state
+            .repos
+            .blob
+            .create(&sha256, &content_type, bytes.len() as i64, user_id)
+            .await?;
+
+        // 3. Store the bytes under their hash. `create` waits for a cleanup
+        // deleting the same blob to finish and the refreshed row outlives the
+        // grace period, so bytes found now stay.
+        if !self.state.blob_store.exists(&key).await? {
+            self.state
+                .blob_store
+                .put(&key, bytes, &content_type)
+                .await?;
+        }
+
+        Ok(self.to_response(blob))
+    }
+
+    /// Look up an uploaded file and sign a new download URL for it
+    pub async fn get_file(&self, sha256: &str) -> Result<FileResponse, AppError> {
+        if !Blob::is_sha256(sha256) {
+            return Err(AppError::NotFound("File not found".to_string()));
+        }
+
+        let blob = self
+            .state
+            .repos
+            .blob
+            .find_by_sha256(sha256)
+            .await?
+            .ok_or_else(|| AppError::NotFound("File not found".to_string()))?;
+
+        Ok(self.to_response(blob))
+    }
+
+    /// Read a blob for a signed download URL of the `local` backend
+    pub async fn download(
+        &self,
+        key: &str,
+        expires: i64,
+        signature: &str,
+    ) -> Result<StoredBlob, AppError> {
+        let signer = UrlSigner::new(&self.state.config.file_signing_secret);
+        if !signer.verify(key, expires, signature) {
+            return Err(AppError::Forbidden(
+                "Download link is invalid or has expired".to_string(),
+            ));
+        }
+
+        Ok(self.state.blob_store.get(key).await?)
+    }
+}
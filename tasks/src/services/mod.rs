@@ -0,0 +1,11 @@
+pub mod contest_service;
+pub mod file_service;
+pub mod rating_service;
+pub mod search_service;
+pub mod submission_service;
+pub mod tag_service;
+pub mod task_answer_service;
+pub mod task_event_service;
+pub mod task_follow_service;
+pub mod task_service;
+pub mod test_case_service;
@@ -0,0 +1,234 @@
+use crate::schema::request::RatingActivityQuery;
+use crate::schema::response::{
+    MyRatingResponse, RatingActivityResponse, RatingDistributionResponse,
+};
+use chrono::{DateTime, Duration, Months, Utc};
+use models::{ProblemOrTask, RatingInterval, Submission, SubmissionStatus};
+use rust_decimal::prelude::ToPrimitive;
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+/// Most buckets a single activity request may span
+const MAX_ACTIVITY_BUCKETS: i64 = 366;
+
+pub struct RatingService {
+    state: AppState,
+}
+
+impl RatingService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn find_task(&self, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    /// Find a submission whose ratings `user_id` may see: drafts stay private
+    /// to their owner
+    async fn find_submission(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<Submission, AppError> {
+        self.state
+            .repos
+            .submission
+            .find_by_id(submission_id)
+            .await?
+            .filter(|submission| submission.deleted_at.is_none())
+            .filter(|submission| {
+                submission.status != SubmissionStatus::Draft || submission.user_id == user_id
+            })
+            .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))
+    }
+
+    /// Resolve the interval and time range of an activity request
+    fn activity_range(
+        query: &RatingActivityQuery,
+    ) -> Result<(RatingInterval, DateTime<Utc>, DateTime<Utc>), AppError> {
+        let interval = query.interval.unwrap_or_default();
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = match query.from {
+            Some(from) => from,
+            None => match interval {
+                RatingInterval::Day => to - Duration::days(30),
+                RatingInterval::Week => to - Duration::weeks(12),
+                RatingInterval::Month => to
+                    .checked_sub_months(Months::new(12))
+                    .ok_or_else(|| AppError::InvalidInput("to is out of range".to_string()))?,
+            },
+        };
+
+        if from > to {
+            return Err(AppError::InvalidInput(
+                "from must not be after to".to_string(),
+            ));
+        }
+        let days = (to - from).num_days();
+        let buckets = match interval {
+            RatingInterval::Day => days,
+            RatingInterval::Week => days / 7,
+            RatingInterval::Month => days / 28,
+        };
+        if buckets > MAX_ACTIVITY_BUCKETS {
+            return Err(AppError::InvalidInput(format!(
+                "The range can span at most {} buckets of one {}",
+                MAX_ACTIVITY_BUCKETS,
+                interval.as_str()
+            )));
+        }
+        Ok((interval, from, to))
+    }
+
+    /// Get how the ratings of a task are spread over the values
+    pub async fn task_distribution(
+        &self,
+        task_id: Uuid,
+    ) -> Result<RatingDistributionResponse, AppError> {
+        let task = self.find_task(task_id).await?;
+        let counts = self
+            .state
+            .repos
+            .task_rating
+            .get_distribution(task_id)
+            .await?;
+
+        Ok(RatingDistributionResponse {
+            average_rating: task.average_rating,
+            total_ratings: task.total_ratings,
+            bayesian_rating: task.bayesian_rating,
+            wilson_score: task.wilson_score,
+            counts: counts.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Get how the ratings of a submission are spread over the values
+    pub async fn submission_distribution(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<RatingDistributionResponse, AppError> {
+        let submission = self.find_submission(user_id, submission_id).await?;
+        let counts = self
+            .state
+            .repos
+            .submission_rating
+            .get_distribution(submission_id)
+            .await?;
+
+        Ok(RatingDistributionResponse {
+            average_rating: submission.average_rating.to_f64().unwrap_or_default(),
+            total_ratings: submission.total_ratings,
+            bayesian_rating: submission.bayesian_rating,
+            wilson_score: submission.wilson_score,
+            counts: counts.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Get the rating `user_id` gave a task, if any
+    pub async fn my_task_rating(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<MyRatingResponse, AppError> {
+        self.find_task(task_id).await?;
+        let rating = self
+            .state
+            .repos
+            .task_rating
+            .find_by_task_and_rater(task_id, user_id)
+            .await?;
+
+        Ok(rating
+            .map(|rating| MyRatingResponse {
+                rating_value: Some(rating.rating_value),
+                created_at: Some(rating.created_at),
+                updated_at: Some(rating.updated_at),
+            })
+            .unwrap_or_default())
+    }
+
+    /// Get the rating `user_id` gave a submission, if any
+    pub async fn my_submission_rating(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<MyRatingResponse, AppError> {
+        self.find_submission(user_id, submission_id).await?;
+        let rating = self
+            .state
+            .repos
+            .submission_rating
+            .find_by_submission_and_rater(submission_id, user_id)
+            .await?;
+
+        Ok(rating
+            .map(|rating| MyRatingResponse {
+                rating_value: Some(rating.rating_value),
+                created_at: Some(rating.created_at),
+                updated_at: Some(rating.updated_at),
+            })
+            .unwrap_or_default())
+    }
+
+    /// Count the ratings a task received over time
+    pub async fn task_activity(
+        &self,
+        task_id: Uuid,
+        query: RatingActivityQuery,
+    ) -> Result<RatingActivityResponse, AppError> {
+        // 1. Check the range
+        let (interval, from, to) = Self::activity_range(&query)?;
+        self.find_task(task_id).await?;
+
+        // 2. Bucket the ratings
+        let buckets = self
+            .state
+            .repos
+            .task_rating
+            .get_activity(task_id, interval, from, to)
+            .await?;
+
+        Ok(RatingActivityResponse {
+            interval,
+            from,
+            to,
+            buckets: buckets.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Count the ratings a submission received over time
+    pub async fn submission_activity(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        query: RatingActivityQuery,
+    ) -> Result<RatingActivityResponse, AppError> {
+        // 1. Check the range
+        let (interval, from, to) = Self::activity_range(&query)?;
+        self.find_submission(user_id, submission_id).await?;
+
+        // 2. Bucket the ratings
+        let buckets = self
+            .state
+            .repos
+            .submission_rating
+            .get_activity(submission_id, interval, from, to)
+            .await?;
+
+        Ok(RatingActivityResponse {
+            interval,
+            from,
+            to,
+            buckets: buckets.into_iter().map(Into::into).collect(),
+        })
+    }
+}
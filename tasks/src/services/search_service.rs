@@ -0,0 +1,94 @@
+use crate::schema::request::SearchParams;
+use models::{SearchFacets, SearchHit};
+use repositories::pagination::Page;
+use repositories::search_query::SearchQuery;
+use shared::errors::AppError;
+use shared::pagination::PaginationQuery;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct SearchService {
+    state: AppState,
+}
+
+impl SearchService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Full-text search over tasks, submissions and discussions
+    ///
+    /// Submissions are only found by their submitter and the task author.
+    ///
+    /// Returns:
+    /// - Ranked hits, best match first, with highlighted snippets
+    pub async fn search(
+        &self,
+        user_id: Uuid,
+        params: SearchParams,
+    ) -> Result<Page<SearchHit>, AppError> {
+        // 1. Build query
+        let query = self.build_query(user_id, &params).await?;
+
+        if !query.cursor_matches() {
+            return Err(AppError::InvalidInput(
+                "Cursor does not belong to a search".to_string(),
+            ));
+        }
+
+        // 2. Run query
+        Ok(self.state.repos.search.search(&query).await?)
+    }
+
+    /// Difficulty and tag counts over every hit of a search
+    ///
+    /// Returns:
+    /// - Counts per difficulty and for the most frequent tags
+    pub async fn facets(
+        &self,
+        user_id: Uuid,
+        params: SearchParams,
+    ) -> Result<SearchFacets, AppError> {
+        // 1. Build query
+        let query = self.build_query(user_id, &params).await?;
+
+        // 2. Count facets
+        Ok(self.state.repos.search.facets(&query).await?)
+    }
+
+    async fn build_query(
+        &self,
+        user_id: Uuid,
+        params: &SearchParams,
+    ) -> Result<SearchQuery, AppError> {
+        let text = params.q.trim();
+        if text.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Search text can not be empty".to_string(),
+            ));
+        }
+
+        let page = PaginationQuery {
+            limit: params.limit,
+            cursor: params.cursor.clone(),
+        }
+        .page_request()?;
+
+        let tags = match params.tag_list() {
+            tags if tags.is_empty() => tags,
+            tags => self.state.repos.tag.resolve_slugs(&tags).await?,
+        };
+
+        let mut query = SearchQuery::new(text)
+            .scope(params.scope.unwrap_or_default())
+            .tags(tags, params.tag_match.unwrap_or_default())
+            .page(page)
+            .viewer(user_id);
+
+        if let Some(difficulty) = params.difficulty {
+            query = query.difficulty(difficulty);
+        }
+
+        Ok(query)
+    }
+}
@@ -0,0 +1,314 @@
+use crate::schema::request::{ResubmitRequest, RevisionDiffQuery};
+use crate::schema::response::{FieldChange, SubmissionRevisionDiffResponse};
+use crate::utils::diff::{diff_lines, unified_diff};
+use chrono::Utc;
+use models::{
+    AnswerAttempt, GradingResult, GradingRun, GradingRunStatus, Submission, SubmissionRevision,
+    SubmissionStatus,
+};
+use repositories::pagination::{Page, PageRequest};
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct SubmissionService {
+    state: AppState,
+}
+
+impl SubmissionService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Find a submission that has not been deleted
+    async fn find_submission(&self, submission_id: Uuid) -> Result<Submission, AppError> {
+        self.state
+            .repos
+            .submission
+            .find_by_id(submission_id)
+            .await?
+            .filter(|submission| submission.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Submission not found".to_string()))
+    }
+
+    /// Find a submission whose history `user_id` may see
+    ///
+    /// The owner sees every version. The task author sees the history once
+    /// the submission has left draft; drafts stay private to the owner.
+    async fn find_visible_submission(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<Submission, AppError> {
+        let submission = self.find_submission(submission_id).await?;
+        if submission.user_id == user_id {
+            return Ok(submission);
+        }
+
+        let task = self
+            .state
+            .repos
+            .problem_or_task
+            .find_by_id(submission.task_id)
+            .await?;
+
+        match task {
+            Some(task) if task.user_id == user_id => {
+                if submission.status == SubmissionStatus::Draft {
+                    return Err(AppError::NotFound("Submission not found".to_string()));
+                }
+                Ok(submission)
+            }
+            _ => Err(AppError::Forbidden(
+                "Only the submitter and the task author can view submission history".to_string(),
+            )),
+        }
+    }
+
+    async fn find_version(
+        &self,
+        submission_id: Uuid,
+        version: i32,
+    ) -> Result<SubmissionRevision, AppError> {
+        self.state
+            .repos
+            .submission_revision
+            .find_by_version(submission_id, version)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Version {} not found", version)))
+    }
+
+    /// List the versions of a submission, newest first
+    pub async fn list_revisions(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<SubmissionRevision>, AppError> {
+        self.find_visible_submission(user_id, submission_id).await?;
+        Ok(self
+            .state
+            .repos
+            .submission_revision
+            .find_by_submission(submission_id, page)
+            .await?)
+    }
+
+    /// Get a single version of a submission
+    pub async fn get_revision(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        version: i32,
+    ) -> Result<SubmissionRevision, AppError> {
+        self.find_visible_submission(user_id, submission_id).await?;
+        self.find_version(submission_id, version).await
+    }
+
+    /// Compare two versions of a submission
+    ///
+    /// Returns:
+    /// - A changed file URL and a line-level content diff
+    pub async fn diff_revisions(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        params: RevisionDiffQuery,
+    ) -> Result<SubmissionRevisionDiffResponse, AppError> {
+        // 1. Load both versions
+        let submission = self.find_visible_submission(user_id, submission_id).await?;
+        let from = self.find_version(submission_id, params.from).await?;
+        let to = self
+            .find_version(
+                submission_id,
+                params.to.unwrap_or(submission.current_version),
+            )
+            .await?;
+
+        // 2. Compare them
+        Ok(SubmissionRevisionDiffResponse {
+            submission_id,
+            from_version: from.version,
+            to_version: to.version,
+            content: diff_lines(&from.content, &to.content),
+            unified_diff: unified_diff(
+                &from.content,
+                &to.content,
+                &format!("version {}", from.version),
+                &format!("version {}", to.version),
+            ),
+            file_url: FieldChange::between(from.file_url, to.file_url),
+        })
+    }
+
+    /// Submit new content for a submitted or rejected submission
+    ///
+    /// Side effects:
+    /// - Records the new content as a new version; earlier versions are kept
+    /// - Moves the submission back to `submitted`
+    /// - Refused for a contest submission outside the contest
+    pub async fn resubmit(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        payload: ResubmitRequest,
+    ) -> Result<Submission, AppError> {
+        // 1. Only the submitter may resubmit
+        let submission = self.find_submission(submission_id).await?;
+        if submission.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the submitter can resubmit a submission".to_string(),
+            ));
+        }
+
+        // 2. Check the submission is waiting for review or was rejected
+        if !submission.status.can_resubmit() {
+            return Err(AppError::Conflict(format!(
+                "A submission that is {} can not be resubmitted",
+                submission.status
+            )));
+        }
+
+        // 3. Versions of a contest submission are only accepted while the
+        //    contest runs
+        let contest = self
+            .state
+            .repos
+            .contest
+            .find_by_submission(submission_id)
+            .await?;
+        if contest.is_some_and(|contest| !contest.is_running(Utc::now())) {
+            return Err(AppError::Conflict(
+                "Submissions are only accepted while the contest runs".to_string(),
+            ));
+        }
+
+        // 4. Record the new version
+        Ok(self
+            .state
+            .repos
+            .submission
+            .resubmit(
+                submission_id,
+                payload.content,
+                payload.file_url,
+                payload.language,
+            )
+            .await?)
+    }
+
+    /// List the grading runs of a submission, newest first
+    pub async fn list_grading_runs(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<GradingRun>, AppError> {
+        self.find_visible_submission(user_id, submission_id).await?;
+        Ok(self
+            .state
+            .repos
+            .grading
+            .find_runs_by_submission(submission_id, page)
+            .await?)
+    }
+
+    /// List the checks of a submission's versions against the task's
+    /// expected answer, newest first
+    pub async fn list_answer_attempts(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, AppError> {
+        self.find_visible_submission(user_id, submission_id).await?;
+        Ok(self
+            .state
+            .repos
+            .task_answer
+            .find_attempts_by_submission(submission_id, page)
+            .await?)
+    }
+
+    /// Get a grading run with its per-test results
+    pub async fn get_grading_run(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+        run_id: Uuid,
+    ) -> Result<(GradingRun, Vec<GradingResult>), AppError> {
+        // 1. Load the run
+        self.find_visible_submission(user_id, submission_id).await?;
+        let run = self
+            .state
+            .repos
+            .grading
+            .find_run(run_id)
+            .await?
+            .filter(|run| run.submission_id == submission_id)
+            .ok_or_else(|| AppError::NotFound("Grading run not found".to_string()))?;
+
+        // 2. Load its results
+        let results = self.state.repos.grading.find_results(run_id).await?;
+        Ok((run, results))
+    }
+
+    /// Grade the current version of a submission again, e.g. after the
+    /// task's test cases changed
+    ///
+    /// Side effects:
+    /// - Queues a new run; earlier runs are kept
+    pub async fn regrade(
+        &self,
+        user_id: Uuid,
+        submission_id: Uuid,
+    ) -> Result<GradingRun, AppError> {
+        // 1. Check the submission can be graded
+        let submission = self.find_visible_submission(user_id, submission_id).await?;
+        if submission.status == SubmissionStatus::Draft {
+            return Err(AppError::Conflict("A draft can not be graded".to_string()));
+        }
+        let language = submission.language.ok_or_else(|| {
+            AppError::UnprocessableEntity("The submission has no programming language".to_string())
+        })?;
+
+        let test_cases = self
+            .state
+            .repos
+            .task_test_case
+            .find_by_task(submission.task_id)
+            .await?;
+        if test_cases.is_empty() {
+            return Err(AppError::UnprocessableEntity(
+                "The task has no test cases".to_string(),
+            ));
+        }
+
+        // 2. Do not queue the same version twice
+        let latest = self
+            .state
+            .repos
+            .grading
+            .find_runs_by_submission(submission_id, PageRequest::new(Some(1), None))
+            .await?;
+        if let Some(run) = latest.items.first()
+            && run.submission_version == submission.current_version
+            && matches!(
+                run.status,
+                GradingRunStatus::Queued | GradingRunStatus::Running
+            )
+        {
+            return Err(AppError::Conflict(
+                "The submission is already being graded".to_string(),
+            ));
+        }
+
+        // 3. Queue the run
+        Ok(self
+            .state
+            .repos
+            .grading
+            .enqueue(submission_id, submission.current_version, language)
+            .await?)
+    }
+}
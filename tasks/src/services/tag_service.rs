@@ -0,0 +1,49 @@
+use crate::schema::request::{PopularTagsQuery, TagAutocompleteQuery};
+use models::Tag;
+use shared::errors::AppError;
+use shared::state::AppState;
+
+const DEFAULT_AUTOCOMPLETE_LIMIT: i64 = 10;
+const DEFAULT_POPULAR_LIMIT: i64 = 20;
+
+pub struct TagService {
+    state: AppState,
+}
+
+impl TagService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Suggest tags for a partially typed tag
+    ///
+    /// Returns:
+    /// - Tags whose slug or an alias starts with the input, or the most
+    ///   popular tags when nothing has been typed yet
+    pub async fn autocomplete(&self, params: TagAutocompleteQuery) -> Result<Vec<Tag>, AppError> {
+        let limit = params.limit.unwrap_or(DEFAULT_AUTOCOMPLETE_LIMIT);
+        let prefix = Tag::slugify(&params.q);
+
+        if prefix.is_empty() {
+            return Ok(self.state.repos.tag.find_popular(limit).await?);
+        }
+
+        Ok(self.state.repos.tag.autocomplete(&prefix, limit).await?)
+    }
+
+    /// Most used tags
+    pub async fn popular(&self, params: PopularTagsQuery) -> Result<Vec<Tag>, AppError> {
+        let limit = params.limit.unwrap_or(DEFAULT_POPULAR_LIMIT);
+        Ok(self.state.repos.tag.find_popular(limit).await?)
+    }
+
+    /// Look up a tag by slug or alias
+    pub async fn get_tag(&self, slug: &str) -> Result<Tag, AppError> {
+        self.state
+            .repos
+            .tag
+            .find_by_slug(&Tag::slugify(slug))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))
+    }
+}
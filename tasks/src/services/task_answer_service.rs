@@ -0,0 +1,206 @@
+use crate::schema::request::SetTaskAnswerRequest;
+use models::{AnswerAttempt, AnswerMatchMode, ProblemOrTask, TaskAnswer, TaskAnswerProgress};
+use repositories::pagination::{Page, PageRequest};
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+/// Longest accepted expected answer or pattern, in characters
+const MAX_ANSWER_CHARS: usize = 1000;
+
+pub struct TaskAnswerService {
+    state: AppState,
+}
+
+impl TaskAnswerService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn find_task(&self, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    /// Find a task `user_id` authored; only the author may see or change
+    /// its expected answer
+    async fn find_own_task(&self, user_id: Uuid, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        let task = self.find_task(task_id).await?;
+        if task.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author can manage the expected answer of a task".to_string(),
+            ));
+        }
+        Ok(task)
+    }
+
+    async fn find_answer(&self, task_id: Uuid) -> Result<TaskAnswer, AppError> {
+        self.state
+            .repos
+            .task_answer
+            .find_by_task(task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("The task has no expected answer".to_string()))
+    }
+
+    /// Get the expected answer of a task
+    pub async fn get_answer(&self, user_id: Uuid, task_id: Uuid) -> Result<TaskAnswer, AppError> {
+        self.find_own_task(user_id, task_id).await?;
+        self.find_answer(task_id).await
+    }
+
+    /// Set or replace the expected answer of a task
+    ///
+    /// Side effects:
+    /// - Submissions submitted from now on are checked against it; earlier
+    ///   attempts keep their verdict
+    pub async fn set_answer(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        payload: SetTaskAnswerRequest,
+    ) -> Result<TaskAnswer, AppError> {
+        // 1. Only the author may set the answer
+        self.find_own_task(user_id, task_id).await?;
+
+        // 2. Check the answers fit the mode
+        let mut answers = Vec::with_capacity(payload.answers.len());
+        for answer in &payload.answers {
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Err(AppError::InvalidInput(
+                    "Answers must not be empty".to_string(),
+                ));
+            }
+            if answer.chars().count() > MAX_ANSWER_CHARS {
+                return Err(AppError::InvalidInput(format!(
+                    "Answers must be at most {} characters",
+                    MAX_ANSWER_CHARS
+                )));
+            }
+            if !answers.iter().any(|a| a == answer) {
+                answers.push(answer.to_string());
+            }
+        }
+
+        let mode = payload.mode;
+        if mode != AnswerMatchMode::AnswerSet && payload.answers.len() != 1 {
+            return Err(AppError::InvalidInput(
+                "Only answer_set answers may list more than one answer".to_string(),
+            ));
+        }
+        if mode == AnswerMatchMode::Numeric {
+            if !answers[0].parse::<f64>().is_ok_and(f64::is_finite) {
+                return Err(AppError::InvalidInput(
+                    "A numeric answer must be a finite number".to_string(),
+                ));
+            }
+            if payload
+                .tolerance
+                .is_some_and(|tolerance| !tolerance.is_finite() || tolerance < 0.0)
+            {
+                return Err(AppError::InvalidInput(
+                    "tolerance must be a finite number of at least 0".to_string(),
+                ));
+            }
+        } else if payload.tolerance.is_some() {
+            return Err(AppError::InvalidInput(
+                "tolerance only applies to numeric answers".to_string(),
+            ));
+        }
+        if payload.case_insensitive
+            && !matches!(mode, AnswerMatchMode::Regex | AnswerMatchMode::AnswerSet)
+        {
+            return Err(AppError::InvalidInput(
+                "case_insensitive only applies to regex and answer_set answers".to_string(),
+            ));
+        }
+
+        // 3. Store it; the database rejects patterns it can not compile
+        Ok(self
+            .state
+            .repos
+            .task_answer
+            .upsert(
+                task_id,
+                mode,
+                answers,
+                payload.case_insensitive,
+                payload.tolerance,
+            )
+            .await?)
+    }
+
+    /// Remove the expected answer of a task
+    ///
+    /// Side effects:
+    /// - Submissions are no longer checked; recorded attempts and progress
+    ///   are kept
+    pub async fn delete_answer(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<TaskAnswer, AppError> {
+        // 1. Only the author may remove the answer
+        self.find_own_task(user_id, task_id).await?;
+        let answer = self.find_answer(task_id).await?;
+
+        // 2. Delete it
+        self.state.repos.task_answer.delete(task_id).await?;
+        Ok(answer)
+    }
+
+    /// Get the attempt count and verdict of `user_id` at a task
+    ///
+    /// Returns:
+    /// - Zero attempts when the user has not submitted yet
+    pub async fn get_progress(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<TaskAnswerProgress, AppError> {
+        self.find_task(task_id).await?;
+
+        let progress = self
+            .state
+            .repos
+            .task_answer
+            .find_progress(task_id, user_id)
+            .await?;
+        match progress {
+            Some(progress) => Ok(progress),
+            None => {
+                self.find_answer(task_id).await?;
+                Ok(TaskAnswerProgress {
+                    task_id,
+                    user_id,
+                    attempts: 0,
+                    solved_at: None,
+                    last_verdict: None,
+                    last_attempt_at: None,
+                })
+            }
+        }
+    }
+
+    /// List the attempts of `user_id` at a task, newest first
+    pub async fn list_my_attempts(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<AnswerAttempt>, AppError> {
+        self.find_task(task_id).await?;
+        Ok(self
+            .state
+            .repos
+            .task_answer
+            .find_attempts_by_user(task_id, user_id, page)
+            .await?)
+    }
+}
@@ -0,0 +1,38 @@
+use futures_util::Stream;
+use shared::errors::AppError;
+use shared::events::TaskFeedItem;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct TaskEventService {
+    state: AppState,
+}
+
+impl TaskEventService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Follow the live events of a task
+    ///
+    /// Returns:
+    /// - The events after `last_event_id` still buffered, then new ones as
+    ///   they happen; a `Resync` when some could not be replayed
+    pub async fn watch_task(
+        &self,
+        task_id: Uuid,
+        last_event_id: Option<String>,
+    ) -> Result<impl Stream<Item = TaskFeedItem> + Send + use<>, AppError> {
+        // 1. The task must exist
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+
+        // 2. Subscribe
+        Ok(self.state.events.watch(task_id, last_event_id).await?)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::schema::response::TaskFollowResponse;
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct TaskFollowService {
+    state: AppState,
+}
+
+impl TaskFollowService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn ensure_task_exists(&self, task_id: Uuid) -> Result<(), AppError> {
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))?;
+        Ok(())
+    }
+
+    /// Get whether the user follows a task
+    pub async fn get(&self, user_id: Uuid, task_id: Uuid) -> Result<TaskFollowResponse, AppError> {
+        self.ensure_task_exists(task_id).await?;
+        let follow = self
+            .state
+            .repos
+            .follow
+            .find_task_follow(user_id, task_id)
+            .await?;
+
+        Ok(TaskFollowResponse::new(task_id, follow))
+    }
+
+    /// Follow a task, so its new submissions and comments show up in the
+    /// user's feed; following it again changes nothing
+    pub async fn follow(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<TaskFollowResponse, AppError> {
+        self.ensure_task_exists(task_id).await?;
+        let follow = self
+            .state
+            .repos
+            .follow
+            .follow_task(user_id, task_id)
+            .await?;
+
+        Ok(TaskFollowResponse::new(task_id, Some(follow)))
+    }
+
+    /// Stop following a task; works on deleted tasks too
+    pub async fn unfollow(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<TaskFollowResponse, AppError> {
+        self.state
+            .repos
+            .follow
+            .unfollow_task(user_id, task_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("You are not following this task".to_string()))?;
+
+        Ok(TaskFollowResponse::new(task_id, None))
+    }
+}
@@ -0,0 +1,209 @@
+use crate::schema::request::{ListTasksQuery, RevisionDiffQuery};
+use crate::schema::response::{FieldChange, TaskRevisionDiffResponse};
+use crate::utils::diff::{diff_lines, unified_diff};
+use models::{ProblemOrTask, TaskRevision};
+use repositories::pagination::{Page, PageRequest};
+use repositories::task_query::TaskQuery;
+use shared::errors::AppError;
+use shared::pagination::PaginationQuery;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct TaskService {
+    state: AppState,
+}
+
+impl TaskService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// List tasks with filters, sorting and cursor pagination
+    pub async fn list_tasks(
+        &self,
+        params: ListTasksQuery,
+    ) -> Result<Page<ProblemOrTask>, AppError> {
+        // 1. Check ranges that span more than one parameter
+        if let (Some(min), Some(max)) = (params.min_rating, params.max_rating)
+            && min > max
+        {
+            return Err(AppError::InvalidInput(
+                "min_rating can not be greater than max_rating".to_string(),
+            ));
+        }
+
+        if let (Some(after), Some(before)) = (params.created_after, params.created_before)
+            && after >= before
+        {
+            return Err(AppError::InvalidInput(
+                "created_after must be earlier than created_before".to_string(),
+            ));
+        }
+
+        // 2. Build query
+        let page = PaginationQuery {
+            limit: params.limit,
+            cursor: params.cursor.clone(),
+        }
+        .page_request()?;
+
+        let tags = match params.tag_list() {
+            tags if tags.is_empty() => tags,
+            tags => self.state.repos.tag.resolve_slugs(&tags).await?,
+        };
+
+        let mut query = TaskQuery::new()
+            .tags(tags, params.tag_match.unwrap_or_default())
+            .rating_between(params.min_rating, params.max_rating)
+            .created_between(params.created_after, params.created_before)
+            .sort_by(params.sort.unwrap_or_default())
+            .page(page);
+
+        if let Some(difficulty) = params.difficulty {
+            query = query.difficulty(difficulty);
+        }
+
+        if let Some(author_id) = params.author_id {
+            query = query.author(author_id);
+        }
+
+        if !query.cursor_matches_sort() {
+            return Err(AppError::InvalidInput(
+                "Cursor does not belong to the requested sort order".to_string(),
+            ));
+        }
+
+        // 3. Run query
+        Ok(self.state.repos.problem_or_task.query(&query).await?)
+    }
+
+    /// Find a task that has not been deleted
+    async fn find_task(&self, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    async fn find_revision(&self, task_id: Uuid, revision: i32) -> Result<TaskRevision, AppError> {
+        self.state
+            .repos
+            .task_revision
+            .find_by_revision(task_id, revision)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Revision {} not found", revision)))
+    }
+
+    /// List the revisions of a task, newest first
+    pub async fn list_revisions(
+        &self,
+        task_id: Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskRevision>, AppError> {
+        self.find_task(task_id).await?;
+        Ok(self
+            .state
+            .repos
+            .task_revision
+            .find_by_task(task_id, page)
+            .await?)
+    }
+
+    /// Get a single revision of a task
+    pub async fn get_revision(
+        &self,
+        task_id: Uuid,
+        revision: i32,
+    ) -> Result<TaskRevision, AppError> {
+        self.find_task(task_id).await?;
+        self.find_revision(task_id, revision).await
+    }
+
+    /// Compare two revisions of a task
+    ///
+    /// Returns:
+    /// - Changed fields, added and removed tags and a line-level content diff
+    pub async fn diff_revisions(
+        &self,
+        task_id: Uuid,
+        params: RevisionDiffQuery,
+    ) -> Result<TaskRevisionDiffResponse, AppError> {
+        // 1. Load both revisions
+        let task = self.find_task(task_id).await?;
+        let from = self.find_revision(task_id, params.from).await?;
+        let to = self
+            .find_revision(task_id, params.to.unwrap_or(task.current_revision))
+            .await?;
+
+        // 2. Compare them
+        let tags_added = to
+            .tags
+            .iter()
+            .filter(|tag| !from.tags.contains(tag))
+            .cloned()
+            .collect();
+        let tags_removed = from
+            .tags
+            .iter()
+            .filter(|tag| !to.tags.contains(tag))
+            .cloned()
+            .collect();
+
+        Ok(TaskRevisionDiffResponse {
+            task_id,
+            from_revision: from.revision,
+            to_revision: to.revision,
+            content: diff_lines(&from.content, &to.content),
+            unified_diff: unified_diff(
+                &from.content,
+                &to.content,
+                &format!("revision {}", from.revision),
+                &format!("revision {}", to.revision),
+            ),
+            title: FieldChange::between(from.title, to.title),
+            file_url: FieldChange::between(from.file_url, to.file_url),
+            difficulty: FieldChange::between(from.difficulty, to.difficulty),
+            tags_added,
+            tags_removed,
+        })
+    }
+
+    /// Restore a task to an earlier revision
+    ///
+    /// Side effects:
+    /// - Records the restored state as a new revision; history is kept
+    pub async fn rollback(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        revision: i32,
+    ) -> Result<ProblemOrTask, AppError> {
+        // 1. Only the author may roll back
+        let task = self.find_task(task_id).await?;
+        if task.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author can roll back a task".to_string(),
+            ));
+        }
+
+        // 2. Make sure the revision exists
+        if revision == task.current_revision {
+            return Err(AppError::InvalidInput(format!(
+                "Revision {} is already the current revision",
+                revision
+            )));
+        }
+        self.find_revision(task_id, revision).await?;
+
+        // 3. Restore it
+        Ok(self
+            .state
+            .repos
+            .problem_or_task
+            .restore_revision(task_id, revision)
+            .await?)
+    }
+}
@@ -0,0 +1,254 @@
+use crate::schema::request::{
+    CreateTestCaseRequest, ImportTestCasesQuery, ReorderTestCasesRequest, UpdateTestCaseRequest,
+};
+use crate::utils::test_case_archive::read_test_case_archive;
+use models::{ProblemOrTask, TaskTestCase};
+use repositories::traits::NewTestCase;
+use shared::errors::AppError;
+use shared::state::AppState;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub struct TestCaseService {
+    state: AppState,
+}
+
+impl TestCaseService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn find_task(&self, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        self.state
+            .repos
+            .problem_or_task
+            .find_by_id(task_id)
+            .await?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Task not found".to_string()))
+    }
+
+    /// Find a task `user_id` authored; only the author may change test
+    /// cases
+    async fn find_own_task(&self, user_id: Uuid, task_id: Uuid) -> Result<ProblemOrTask, AppError> {
+        let task = self.find_task(task_id).await?;
+        if task.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author can manage the test cases of a task".to_string(),
+            ));
+        }
+        Ok(task)
+    }
+
+    async fn find_test_case(&self, task_id: Uuid, case_id: Uuid) -> Result<TaskTestCase, AppError> {
+        self.state
+            .repos
+            .task_test_case
+            .find_by_id(case_id)
+            .await?
+            .filter(|case| case.task_id == task_id)
+            .ok_or_else(|| AppError::NotFound("Test case not found".to_string()))
+    }
+
+    /// List the test cases of a task in run order
+    ///
+    /// Returns:
+    /// - Every test case for the author, the sample cases for anyone else
+    pub async fn list_test_cases(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<Vec<TaskTestCase>, AppError> {
+        let task = self.find_task(task_id).await?;
+        let cases = if task.user_id == user_id {
+            self.state
+                .repos
+                .task_test_case
+                .find_by_task(task_id)
+                .await?
+        } else {
+            self.state
+                .repos
+                .task_test_case
+                .find_samples_by_task(task_id)
+                .await?
+        };
+        Ok(cases)
+    }
+
+    /// Get a test case; hidden cases are only visible to the author
+    pub async fn get_test_case(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        case_id: Uuid,
+    ) -> Result<TaskTestCase, AppError> {
+        let task = self.find_task(task_id).await?;
+        let case = self.find_test_case(task_id, case_id).await?;
+        if !case.is_sample && task.user_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the author can view hidden test cases".to_string(),
+            ));
+        }
+        Ok(case)
+    }
+
+    /// Add a test case after the task's last one
+    ///
+    /// Side effects:
+    /// - Code submissions submitted from now on are also graded against it;
+    ///   earlier runs are not regraded
+    pub async fn create_test_case(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        payload: CreateTestCaseRequest,
+    ) -> Result<TaskTestCase, AppError> {
+        // 1. Only the author may add test cases
+        self.find_own_task(user_id, task_id).await?;
+
+        // 2. Append it
+        Ok(self
+            .state
+            .repos
+            .task_test_case
+            .create(
+                task_id,
+                NewTestCase {
+                    input: payload.input,
+                    expected_output: payload.expected_output,
+                    time_limit_ms: payload.time_limit_ms,
+                    memory_limit_kb: payload.memory_limit_kb,
+                    is_sample: payload.is_sample.unwrap_or_default(),
+                    points: payload.points,
+                },
+            )
+            .await?)
+    }
+
+    /// Add the `NN.in` / `NN.out` pairs of a ZIP archive, in number order
+    ///
+    /// Side effects:
+    /// - With `replace`, the task's existing test cases are deleted first
+    /// - Nothing is imported when any file in the archive is invalid
+    pub async fn import_test_cases(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        archive: Vec<u8>,
+        params: ImportTestCasesQuery,
+    ) -> Result<Vec<TaskTestCase>, AppError> {
+        // 1. Only the author may add test cases
+        self.find_own_task(user_id, task_id).await?;
+
+        // 2. Read the archive
+        let sample_count = params.sample_count.unwrap_or_default() as usize;
+        let cases = tokio::task::spawn_blocking(move || read_test_case_archive(&archive))
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))??
+            .into_iter()
+            .enumerate()
+            .map(|(index, case)| NewTestCase {
+                input: case.input,
+                expected_output: case.expected_output,
+                time_limit_ms: params.time_limit_ms,
+                memory_limit_kb: params.memory_limit_kb,
+                is_sample: index < sample_count,
+                points: params.points,
+            })
+            .collect();
+
+        // 3. Store every case or none
+        Ok(self
+            .state
+            .repos
+            .task_test_case
+            .import(task_id, cases, params.replace)
+            .await?)
+    }
+
+    /// Change the data, limits, visibility or points of a test case
+    pub async fn update_test_case(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        case_id: Uuid,
+        payload: UpdateTestCaseRequest,
+    ) -> Result<TaskTestCase, AppError> {
+        // 1. Only the author may change test cases
+        self.find_own_task(user_id, task_id).await?;
+        self.find_test_case(task_id, case_id).await?;
+
+        // 2. Update it
+        Ok(self
+            .state
+            .repos
+            .task_test_case
+            .update(
+                case_id,
+                payload.input,
+                payload.expected_output,
+                payload.time_limit_ms,
+                payload.memory_limit_kb,
+                payload.is_sample,
+                payload.points,
+            )
+            .await?)
+    }
+
+    /// Change the order test cases run in
+    pub async fn reorder_test_cases(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        payload: ReorderTestCasesRequest,
+    ) -> Result<Vec<TaskTestCase>, AppError> {
+        // 1. Only the author may reorder test cases
+        self.find_own_task(user_id, task_id).await?;
+
+        // 2. Check the new order is a permutation of the current one
+        let current: HashSet<Uuid> = self
+            .state
+            .repos
+            .task_test_case
+            .find_by_task(task_id)
+            .await?
+            .into_iter()
+            .map(|case| case.id)
+            .collect();
+        let requested: HashSet<Uuid> = payload.test_case_ids.iter().copied().collect();
+        if requested.len() != payload.test_case_ids.len() || requested != current {
+            return Err(AppError::InvalidInput(
+                "test_case_ids must list every test case of the task exactly once".to_string(),
+            ));
+        }
+
+        // 3. Apply it
+        Ok(self
+            .state
+            .repos
+            .task_test_case
+            .reorder(task_id, &payload.test_case_ids)
+            .await?)
+    }
+
+    /// Delete a test case
+    ///
+    /// Side effects:
+    /// - The test cases after it move up one position
+    /// - Results of earlier runs against it are kept without the test case
+    pub async fn delete_test_case(
+        &self,
+        user_id: Uuid,
+        task_id: Uuid,
+        case_id: Uuid,
+    ) -> Result<TaskTestCase, AppError> {
+        // 1. Only the author may delete test cases
+        self.find_own_task(user_id, task_id).await?;
+        let case = self.find_test_case(task_id, case_id).await?;
+
+        // 2. Delete it
+        self.state.repos.task_test_case.delete(case_id).await?;
+        Ok(case)
+    }
+}
@@ -0,0 +1,56 @@
+use shared::errors::AppError;
+
+/// Content types accepted for task and submission attachments
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/json",
+    "application/pdf",
+    "application/zip",
+    "image/gif",
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "text/csv",
+    "text/markdown",
+    "text/plain",
+];
+
+/// Check the declared content type of an upload against the allow list and
+/// the file's leading bytes, so a renamed executable can not pass as a PDF
+///
+/// Returns the normalized content type, without parameters such as charset
+pub fn validate_content_type(declared: &str, bytes: &[u8]) -> Result<String, AppError> {
+    let content_type = declared
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    if !ALLOWED_CONTENT_TYPES.contains(&content_type.as_str()) {
+        return Err(AppError::UnsupportedMediaType(format!(
+            "Files of type '{}' are not allowed, expected one of: {}",
+            content_type,
+            ALLOWED_CONTENT_TYPES.join(", ")
+        )));
+    }
+
+    let matches = match content_type.as_str() {
+        "application/pdf" => bytes.starts_with(b"%PDF-"),
+        "application/zip" => bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06"),
+        "image/gif" => bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a"),
+        "image/jpeg" => bytes.starts_with(&[0xFF, 0xD8, 0xFF]),
+        "image/png" => bytes.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "image/webp" => bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP",
+        // Text formats: UTF-8 without NUL bytes
+        _ => std::str::from_utf8(bytes).is_ok_and(|text| !text.contains('\0')),
+    };
+
+    if !matches {
+        return Err(AppError::UnsupportedMediaType(format!(
+            "File content does not match its declared type '{}'",
+            content_type
+        )));
+    }
+
+    Ok(content_type)
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use models::{
+    Contest, ContestAttempt, ContestParticipant, ContestScoring, ContestTask, GradingVerdict,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How a participant did on one contest task
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct TaskStanding {
+    pub task_id: Uuid,
+    /// Accepted in ICPC contests, full points in points contests
+    pub solved: bool,
+    /// Points of the best attempt; always 0 in ICPC contests
+    pub points: i32,
+    /// Judged attempts counted towards the result; compilation errors and
+    /// attempts after an ICPC solve are not
+    pub attempts: i32,
+    /// Attempts waiting to be judged, or made after the scoreboard froze
+    pub pending: i32,
+    /// Minutes from the start to the solve (ICPC) or best attempt (points),
+    /// plus the penalty for the rejected attempts before it
+    pub penalty_minutes: i64,
+    /// When the counted solve or best attempt was submitted
+    pub scored_at: Option<DateTime<Utc>>,
+}
+
+/// One participant's line on a contest scoreboard
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ScoreboardRow {
+    /// Starts at 1; participants with the same result share a rank
+    pub rank: u32,
+    pub user_id: Uuid,
+    pub solved: i32,
+    pub points: i32,
+    pub penalty_minutes: i64,
+    /// In contest task order
+    pub tasks: Vec<TaskStanding>,
+}
+
+/// Rank the participants of a contest
+///
+/// Attempts submitted at or after `cutoff` count as pending whatever their
+/// verdict, which is how a frozen scoreboard hides the last hour.
+pub fn compute_scoreboard(
+    contest: &Contest,
+    tasks: &[ContestTask],
+    participants: &[ContestParticipant],
+    attempts: &[ContestAttempt],
+    cutoff: Option<DateTime<Utc>>,
+) -> Vec<ScoreboardRow> {
+    let mut by_participant_task: HashMap<(Uuid, Uuid), Vec<&ContestAttempt>> = HashMap::new();
+    for attempt in attempts {
+        by_participant_task
+            .entry((attempt.user_id, attempt.task_id))
+            .or_default()
+            .push(attempt);
+    }
+
+    let mut rows: Vec<ScoreboardRow> = participants
+        .iter()
+        .map(|participant| {
+            let standings: Vec<TaskStanding> = tasks
+                .iter()
+                .map(|task| {
+                    let mut attempts = by_participant_task
+                        .remove(&(participant.user_id, task.task_id))
+                        .unwrap_or_default();
+                    attempts.sort_by_key(|a| (a.submitted_at, a.submission_version));
+                    score_task(contest, task, &attempts, cutoff)
+                })
+                .collect();
+
+            ScoreboardRow {
+                rank: 0,
+                user_id: participant.user_id,
+                solved: standings.iter().filter(|s| s.solved).count() as i32,
+                points: standings.iter().map(|s| s.points).sum(),
+                penalty_minutes: standings
+                    .iter()
+                    .filter(|s| s.scored_at.is_some())
+                    .map(|s| s.penalty_minutes)
+                    .sum(),
+                tasks: standings,
+            }
+        })
+        .collect();
+
+    // Stable, so ties stay in registration order
+    let key = |row: &ScoreboardRow| match contest.scoring {
+        ContestScoring::Icpc => (-row.solved, row.penalty_minutes),
+        ContestScoring::Points => (-row.points, row.penalty_minutes),
+    };
+    rows.sort_by_key(key);
+
+    for index in 0..rows.len() {
+        rows[index].rank = if index > 0 && key(&rows[index]) == key(&rows[index - 1]) {
+            rows[index - 1].rank
+        } else {
+            index as u32 + 1
+        };
+    }
+
+    rows
+}
+
+/// Score one participant's attempts at one task, oldest first
+fn score_task(
+    contest: &Contest,
+    task: &ContestTask,
+    attempts: &[&ContestAttempt],
+    cutoff: Option<DateTime<Utc>>,
+) -> TaskStanding {
+    let mut standing = TaskStanding {
+        task_id: task.task_id,
+        solved: false,
+        points: 0,
+        attempts: 0,
+        pending: 0,
+        penalty_minutes: 0,
+        scored_at: None,
+    };
+    // Counted attempts before the current best one
+    let mut rejected_before_best = 0;
+
+    for attempt in attempts {
+        if contest.scoring == ContestScoring::Icpc && standing.solved {
+            break;
+        }
+        let hidden = cutoff.is_some_and(|cutoff| attempt.submitted_at >= cutoff);
+        let Some(fraction) = attempt.score_fraction().filter(|_| !hidden) else {
+            standing.pending += 1;
+            continue;
+        };
+        if attempt.grading_verdict == Some(GradingVerdict::CE) {
+            continue;
+        }
+
+        let improved = match contest.scoring {
+            ContestScoring::Icpc => {
+                standing.solved = attempt.is_accepted();
+                standing.solved
+            }
+            ContestScoring::Points => {
+                let points = (fraction * task.points as f64).round() as i32;
+                let improved = points > standing.points;
+                if improved {
+                    standing.points = points;
+                    standing.solved = points >= task.points;
+                }
+                improved
+            }
+        };
+        if improved {
+            standing.scored_at = Some(attempt.submitted_at);
+            rejected_before_best = standing.attempts;
+        }
+        standing.attempts += 1;
+    }
+
+    if let Some(scored_at) = standing.scored_at {
+        standing.penalty_minutes = (scored_at - contest.starts_at).num_minutes()
+            + contest.penalty_minutes as i64 * rejected_before_best as i64;
+    }
+    standing
+}
@@ -0,0 +1,4 @@
+pub mod attachment;
+pub mod contest_scoreboard;
+pub mod diff;
+pub mod test_case_archive;
@@ -0,0 +1,145 @@
+use shared::errors::AppError;
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// Largest archive accepted for upload
+pub const MAX_ARCHIVE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Most bytes the files of an archive may unpack to, so a small archive can
+/// not expand without bound
+pub const MAX_ARCHIVE_UNPACKED_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Most test cases one archive may hold
+pub const MAX_ARCHIVE_TEST_CASES: usize = 500;
+
+/// An input / expected output pair read from an archive
+#[derive(Debug, Clone)]
+pub struct ArchiveTestCase {
+    /// The `NN` of `NN.in` / `NN.out`
+    pub number: u32,
+    pub input: String,
+    pub expected_output: String,
+}
+
+/// Read the `NN.in` / `NN.out` pairs of a ZIP archive, ordered by number
+///
+/// Files may sit at the root of the archive or in folders; only their names
+/// count. Hidden files and macOS metadata are skipped, any other file is an
+/// error, as is a test without both its `.in` and its `.out`.
+pub fn read_test_case_archive(bytes: &[u8]) -> Result<Vec<ArchiveTestCase>, AppError> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|_| {
+        AppError::UnprocessableEntity("The file is not a valid ZIP archive".to_string())
+    })?;
+
+    let mut inputs = BTreeMap::new();
+    let mut outputs = BTreeMap::new();
+    let mut unpacked = 0;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(invalid_archive)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        // 1. Work out which test the file belongs to
+        let path = file.name().map_err(invalid_archive)?.to_string();
+        let name = path.rsplit('/').next().unwrap_or_default();
+        if path.starts_with("__MACOSX/") || name.starts_with('.') {
+            continue;
+        }
+
+        let (number, files) = match name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && stem.bytes().all(|b| b.is_ascii_digit()) => {
+                let files = match ext {
+                    "in" => &mut inputs,
+                    "out" => &mut outputs,
+                    _ => return Err(unexpected_file(&path)),
+                };
+                let number: u32 = stem.parse().map_err(|_| unexpected_file(&path))?;
+                (number, files)
+            }
+            _ => return Err(unexpected_file(&path)),
+        };
+
+        if let Some((other, _)) = files.get(&number) {
+            return Err(AppError::UnprocessableEntity(format!(
+                "'{}' and '{}' are both test {}",
+                other, path, number
+            )));
+        }
+        if files.len() == MAX_ARCHIVE_TEST_CASES {
+            return Err(AppError::UnprocessableEntity(format!(
+                "An archive can hold at most {} test cases",
+                MAX_ARCHIVE_TEST_CASES
+            )));
+        }
+
+        // 2. Read it, without trusting the sizes the archive declares
+        let mut data = Vec::new();
+        file.by_ref()
+            .take(MAX_ARCHIVE_UNPACKED_BYTES - unpacked + 1)
+            .read_to_end(&mut data)
+            .map_err(|err| {
+                AppError::UnprocessableEntity(format!("Can not read '{}': {}", path, err))
+            })?;
+        unpacked += data.len() as u64;
+        if unpacked > MAX_ARCHIVE_UNPACKED_BYTES {
+            return Err(AppError::PayloadTooLarge(format!(
+                "The archive unpacks to more than {} MiB",
+                MAX_ARCHIVE_UNPACKED_BYTES / (1024 * 1024)
+            )));
+        }
+
+        let text = String::from_utf8(data)
+            .ok()
+            .filter(|text| !text.contains('\0'))
+            .ok_or_else(|| {
+                AppError::UnprocessableEntity(format!("'{}' is not a UTF-8 text file", path))
+            })?;
+        files.insert(number, (path, text));
+    }
+
+    // 3. Pair inputs with outputs
+    if let Some(number) = outputs.keys().find(|number| !inputs.contains_key(*number)) {
+        return Err(AppError::UnprocessableEntity(format!(
+            "Test {} has an .out file but no .in file",
+            number
+        )));
+    }
+
+    let mut test_cases = Vec::with_capacity(inputs.len());
+    for (number, (_, input)) in inputs {
+        let (_, expected_output) = outputs.remove(&number).ok_or_else(|| {
+            AppError::UnprocessableEntity(format!(
+                "Test {} has an .in file but no .out file",
+                number
+            ))
+        })?;
+        test_cases.push(ArchiveTestCase {
+            number,
+            input,
+            expected_output,
+        });
+    }
+
+    if test_cases.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "The archive contains no NN.in / NN.out test cases".to_string(),
+        ));
+    }
+
+    Ok(test_cases)
+}
+
+fn unexpected_file(path: &str) -> AppError {
+    AppError::UnprocessableEntity(format!(
+        "Unexpected file '{}', expected only NN.in and NN.out files",
+        path
+    ))
+}
+
+fn invalid_archive(err: ZipError) -> AppError {
+    AppError::UnprocessableEntity(format!("Invalid ZIP archive: {}", err))
+}
@@ -0,0 +1,108 @@
+// ============================================================================
+// handlers/activity_handlers.rs - Contribution calendar and streaks
+//
+// Activity is read from when the user's tasks, submissions, comments and
+// ratings were created, and counted in days of the user's time zone.
+// ============================================================================
+
+use crate::schema::request::{ActivityCalendarQuery, UpdateTimezoneRequest};
+use crate::schema::response::{ActivityCalendarResponse, ActivityStreakResponse, TimezoneResponse};
+use crate::services::activity_service::ActivityService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/users/{id}/activity/calendar
+///
+/// Get a user's activity per day over a range, the last year by default
+pub async fn get_activity_calendar_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<ActivityCalendarQuery>,
+) -> Result<Json<ActivityCalendarResponse>, AppError> {
+    let service = ActivityService::new(app_state);
+    let calendar = service.calendar(&user_id, query).await?;
+
+    Ok(Json(calendar))
+}
+
+/// GET /api/users/{id}/activity/streak
+///
+/// Get a user's current and longest streaks of active days
+pub async fn get_activity_streak_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<ActivityStreakResponse>, AppError> {
+    let service = ActivityService::new(app_state);
+    let streak = service.streak(&user_id).await?;
+
+    Ok(Json(streak.into()))
+}
+
+/// GET /api/users/me/activity/calendar
+///
+/// Get the current user's activity per day over a range
+pub async fn get_my_activity_calendar_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(query): Query<ActivityCalendarQuery>,
+) -> Result<Json<ActivityCalendarResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = ActivityService::new(app_state);
+    let calendar = service.calendar(&user_id, query).await?;
+
+    // 3. Return response
+    Ok(Json(calendar))
+}
+
+/// GET /api/users/me/timezone
+///
+/// Get the time zone the current user's days are counted in
+pub async fn get_my_timezone_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+) -> Result<Json<TimezoneResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = ActivityService::new(app_state);
+    let timezone = service.get_timezone(&user_id).await?;
+
+    // 3. Return response
+    Ok(Json(timezone.into()))
+}
+
+/// PUT /api/users/me/timezone
+///
+/// Change the time zone the current user's days are counted in
+pub async fn update_my_timezone_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Json(payload): Json<UpdateTimezoneRequest>,
+) -> Result<Json<TimezoneResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = ActivityService::new(app_state);
+    let timezone = service.set_timezone(&user_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(timezone.into()))
+}
@@ -18,12 +18,18 @@ use crate::schema::response::{
     LoginResponse, RefreshTokenResponse, ResponeOnlyMessage, UserResponse,
 };
 use crate::services::auth_service::AuthService;
+use crate::utils::csrf::generate_csrf_token;
 use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
 use axum_extra::extract::{
     CookieJar,
     cookie::{Cookie, SameSite},
 };
-use shared::{errors::AppError, state::AppState};
+use shared::{
+    auth_utils::{ACCESS_TOKEN_COOKIE, CSRF_TOKEN_COOKIE},
+    config::Config,
+    errors::AppError,
+    state::AppState,
+};
 use time::Duration;
 use validator::Validate;
 
@@ -46,9 +52,36 @@ fn create_refresh_cookie(
         .build()
 }
 
+/// Create HTTP-only access token cookie (cookie auth mode)
+fn create_access_cookie(
+    token: String,
+    duration_minutes: u64,
+    is_production: bool,
+) -> Cookie<'static> {
+    Cookie::build((ACCESS_TOKEN_COOKIE, token))
+        .path("/")
+        .max_age(Duration::minutes(duration_minutes as i64))
+        .same_site(SameSite::Strict)
+        .http_only(true)
+        .secure(is_production)
+        .build()
+}
+
+/// Create CSRF cookie - deliberately readable by JavaScript so the
+/// frontend can echo it back in the `X-CSRF-Token` header
+fn create_csrf_cookie(token: String, duration_days: u64, is_production: bool) -> Cookie<'static> {
+    Cookie::build((CSRF_TOKEN_COOKIE, token))
+        .path("/")
+        .max_age(Duration::days(duration_days as i64))
+        .same_site(SameSite::Strict)
+        .http_only(false)
+        .secure(is_production)
+        .build()
+}
+
 /// Create expired cookie for logout
-fn create_expired_cookie() -> Cookie<'static> {
-    Cookie::build(("refresh_token", ""))
+fn create_expired_cookie(name: &'static str) -> Cookie<'static> {
+    Cookie::build((name, ""))
         .path("/")
         .max_age(Duration::seconds(0))
         .same_site(SameSite::Strict)
@@ -56,6 +89,36 @@ fn create_expired_cookie() -> Cookie<'static> {
         .build()
 }
 
+/// Add access token and CSRF cookies when cookie auth is enabled
+///
+/// Returns the CSRF token so it can also be handed back in the body
+fn add_cookie_auth(
+    jar: CookieJar,
+    access_token: &str,
+    config: &Config,
+) -> (CookieJar, Option<String>) {
+    if !config.cookie_auth_enabled {
+        return (jar, None);
+    }
+
+    let is_production = config.environment == "production";
+    let csrf_token = generate_csrf_token();
+
+    let jar = jar
+        .add(create_access_cookie(
+            access_token.to_string(),
+            config.access_token_duration,
+            is_production,
+        ))
+        .add(create_csrf_cookie(
+            csrf_token.clone(),
+            config.refresh_token_duration,
+            is_production,
+        ));
+
+    (jar, Some(csrf_token))
+}
+
 // ============================================================================
 // Handler Functions
 // ============================================================================
@@ -96,7 +159,7 @@ pub async fn verify_email_handler(
     let user = service.verify_email(payload).await?;
 
     // 4. Return response
-    Ok(Json(user.into()))
+    Ok(Json(user))
 }
 
 /// POST /api/auth/resend-verification
@@ -144,15 +207,23 @@ pub async fn login_handler(
         app_state.config.environment == "production",
     );
 
-    // 5. Build response
+    // 5. Issue access token + CSRF cookies in cookie auth mode
+    let (jar, csrf_token) = add_cookie_auth(
+        jar.add(refresh_cookie),
+        &result.access_token,
+        &app_state.config,
+    );
+
+    // 6. Build response
     let response = LoginResponse {
         status: "Success".to_string(),
         message: "Login successful".to_string(),
         access_token: result.access_token,
-        user: result.user.into(),
+        user: result.user,
+        csrf_token,
     };
 
-    Ok((jar.add(refresh_cookie), Json(response)))
+    Ok((jar, Json(response)))
 }
 
 /// POST /api/auth/refresh
@@ -182,13 +253,21 @@ pub async fn refresh_token_handler(
         app_state.config.environment == "production",
     );
 
-    // 5. Build response
+    // 5. Rotate access token + CSRF cookies in cookie auth mode
+    let (jar, csrf_token) = add_cookie_auth(
+        jar.add(refresh_cookie),
+        &result.access_token,
+        &app_state.config,
+    );
+
+    // 6. Build response
     Ok((
         StatusCode::OK,
-        jar.add(refresh_cookie),
+        jar,
         Json(RefreshTokenResponse {
             status: "Success".to_string(),
             access_token: result.access_token,
+            csrf_token,
         }),
     ))
 }
@@ -249,21 +328,23 @@ pub async fn logout_handler(
         // Try to validate token to get user_id
         if let Ok(token_data) =
             shared::auth_utils::validate_token(refresh_token, &app_state.config.refresh_secret)
+            && let Ok(user_id) = uuid::Uuid::parse_str(&token_data.sub)
         {
-            if let Ok(user_id) = uuid::Uuid::parse_str(&token_data.sub) {
-                // 2. Call service
-                let service = AuthService::new(app_state.clone());
-                let _ = service.logout(&user_id).await; // Ignore errors
-            }
+            // 2. Call service
+            let service = AuthService::new(app_state.clone());
+            let _ = service.logout(&user_id).await; // Ignore errors
         }
     }
 
-    // 3. Always clear the cookie (even if token validation fails)
-    let expired_cookie = create_expired_cookie();
+    // 3. Always clear the cookies (even if token validation fails)
+    let jar = jar
+        .add(create_expired_cookie("refresh_token"))
+        .add(create_expired_cookie(ACCESS_TOKEN_COOKIE))
+        .add(create_expired_cookie(CSRF_TOKEN_COOKIE));
 
     // 4. Return response
     Ok((
-        jar.add(expired_cookie),
+        jar,
         Json(ResponeOnlyMessage {
             status: "Success".to_string(),
             message: "Logged out successfully".to_string(),
@@ -0,0 +1,63 @@
+// ============================================================================
+// handlers/badge_handlers.rs - Badge catalog and earned badges
+//
+// Badges are awarded by the badges worker as users reach the thresholds of
+// the catalog; these endpoints only expose the catalog and who earned what.
+// ============================================================================
+
+use crate::schema::response::{BadgeResponse, UserBadgeResponse};
+use crate::services::badge_service::BadgeService;
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+
+/// GET /api/users/badges
+///
+/// List every badge with how to earn it and how many users did
+pub async fn list_badges_handler(
+    State(app_state): State<AppState>,
+) -> Result<Json<Vec<BadgeResponse>>, AppError> {
+    let service = BadgeService::new(app_state);
+    let badges = service.catalog().await?;
+
+    Ok(Json(badges.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/users/{id}/badges
+///
+/// List the badges a user earned, most recent first
+pub async fn list_user_badges_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<Vec<UserBadgeResponse>>, AppError> {
+    let service = BadgeService::new(app_state);
+    let badges = service.list_user_badges(&user_id).await?;
+
+    Ok(Json(badges.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/users/me/badges
+///
+/// List the badges the current user earned, most recent first
+pub async fn list_my_badges_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+) -> Result<Json<Vec<UserBadgeResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = BadgeService::new(app_state);
+    let badges = service.list_user_badges(&user_id).await?;
+
+    // 3. Return response
+    Ok(Json(badges.into_iter().map(Into::into).collect()))
+}
@@ -0,0 +1,187 @@
+// ============================================================================
+// handlers/follow_handlers.rs - Following users and the activity feed
+//
+// The feed is assembled when read from the users and tasks the current user
+// follows: new tasks by followed users, new submissions and comments on
+// followed tasks, and featured submissions of either.
+// ============================================================================
+
+use crate::schema::request::MarkFeedReadRequest;
+use crate::schema::response::{
+    FeedItemResponse, FeedReadStateResponse, FollowResponse, FollowedTaskResponse,
+};
+use crate::services::feed_service::FeedService;
+use crate::services::follow_service::FollowService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::TokenScope;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+use uuid::Uuid;
+
+/// PUT /api/users/{id}/follow
+///
+/// Follow a user; following them again changes nothing
+pub async fn follow_user_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(followee_id): Path<Uuid>,
+) -> Result<Json<FollowResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = FollowService::new(app_state);
+    let followee = service.follow(&user_id, &followee_id).await?;
+
+    // 3. Return response
+    Ok(Json(followee))
+}
+
+/// DELETE /api/users/{id}/follow
+///
+/// Stop following a user
+pub async fn unfollow_user_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(followee_id): Path<Uuid>,
+) -> Result<Json<FollowResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = FollowService::new(app_state);
+    let followee = service.unfollow(&user_id, &followee_id).await?;
+
+    // 3. Return response
+    Ok(Json(followee))
+}
+
+/// GET /api/users/{id}/followers
+///
+/// List the users following a user, most recent first
+pub async fn list_followers_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    // 1. Validate request
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = FollowService::new(app_state);
+    let followers = service.followers(&user_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(followers)))
+}
+
+/// GET /api/users/{id}/following
+///
+/// List the users a user follows, most recent first
+pub async fn list_following_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FollowResponse>>, AppError> {
+    // 1. Validate request
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = FollowService::new(app_state);
+    let following = service.following(&user_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(following)))
+}
+
+/// GET /api/users/me/following/tasks
+///
+/// List the tasks the current user follows, most recent first
+pub async fn list_followed_tasks_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FollowedTaskResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require(TokenScope::TasksRead)?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = FollowService::new(app_state);
+    let tasks = service.followed_tasks(&user_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(tasks)))
+}
+
+/// GET /api/users/me/feed
+///
+/// List the current user's activity feed, newest first, with the entries
+/// newer than their read marker flagged unread
+pub async fn list_feed_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<FeedItemResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = FeedService::new(app_state);
+    let feed = service.feed(&user_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(feed)))
+}
+
+/// GET /api/users/me/feed/read
+///
+/// Get the current user's read marker and the number of unread entries
+pub async fn get_feed_read_state_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+) -> Result<Json<FeedReadStateResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = FeedService::new(app_state);
+    let state = service.read_state(&user_id).await?;
+
+    // 3. Return response
+    Ok(Json(state))
+}
+
+/// PUT /api/users/me/feed/read
+///
+/// Mark the current user's feed read up to a point, now by default
+pub async fn mark_feed_read_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    payload: Option<Json<MarkFeedReadRequest>>,
+) -> Result<Json<FeedReadStateResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    // 2. Call service
+    let service = FeedService::new(app_state);
+    let state = service.mark_read(&user_id, payload).await?;
+
+    // 3. Return response
+    Ok(Json(state))
+}
@@ -0,0 +1,58 @@
+// ============================================================================
+// handlers/leaderboard_handlers.rs - Leaderboards
+//
+// Users ranked by reputation, solved tasks and positive ratings on their
+// tasks, globally or per tag, for all time or the current week or month.
+// The rankings are read from Redis, which the leaderboards worker keeps up
+// to date, so they may lag writes by a moment.
+// ============================================================================
+
+use crate::schema::request::LeaderboardQuery;
+use crate::schema::response::{LeaderboardEntryResponse, LeaderboardPositionResponse};
+use crate::services::leaderboard_service::LeaderboardService;
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+};
+use models::LeaderboardMetric;
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::PaginatedResponse,
+    state::AppState,
+};
+
+/// GET /api/users/leaderboards/{metric}
+///
+/// List a leaderboard, highest score first
+pub async fn list_leaderboard_handler(
+    State(app_state): State<AppState>,
+    Path(metric): Path<LeaderboardMetric>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<PaginatedResponse<LeaderboardEntryResponse>>, AppError> {
+    let service = LeaderboardService::new(app_state);
+    let entries = service.list(metric, query).await?;
+
+    Ok(Json(PaginatedResponse::from_page(entries)))
+}
+
+/// GET /api/users/leaderboards/{metric}/me
+///
+/// Get the current user's rank and score on a leaderboard
+pub async fn my_leaderboard_position_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(metric): Path<LeaderboardMetric>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<Json<LeaderboardPositionResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    // 2. Call service
+    let service = LeaderboardService::new(app_state);
+    let position = service.position(user_id, metric, query).await?;
+
+    // 3. Return response
+    Ok(Json(position))
+}
@@ -1 +1,8 @@
-pub mod auth_handlers;
\ No newline at end of file
+pub mod activity_handlers;
+pub mod auth_handlers;
+pub mod badge_handlers;
+pub mod follow_handlers;
+pub mod leaderboard_handlers;
+pub mod reputation_handlers;
+pub mod token_handlers;
+pub mod user_handlers;
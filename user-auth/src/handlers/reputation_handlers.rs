@@ -0,0 +1,56 @@
+// ============================================================================
+// handlers/reputation_handlers.rs - Reputation ledger and rules
+//
+// Reputation is awarded by the database as ratings, featured submissions and
+// moderator removals happen; these endpoints only expose the ledger and how
+// points are earned.
+// ============================================================================
+
+use crate::schema::response::{ReputationEventResponse, ReputationRulesResponse};
+use crate::services::reputation_service::ReputationService;
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    pagination::{PaginatedResponse, PaginationQuery},
+    state::AppState,
+};
+
+/// GET /api/users/me/reputation/events
+///
+/// List the current user's reputation ledger, newest first
+pub async fn list_my_reputation_events_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Query(pagination): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<ReputationEventResponse>>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    let page = pagination.page_request()?;
+
+    // 2. Call service
+    let service = ReputationService::new(app_state);
+    let events = service.list_events(&user_id, page).await?;
+
+    // 3. Return response
+    Ok(Json(PaginatedResponse::from_page(events)))
+}
+
+/// GET /api/users/reputation/rules
+///
+/// Get the points awarded per event and the daily cap on gains
+pub async fn get_reputation_rules_handler(
+    State(app_state): State<AppState>,
+) -> Result<Json<ReputationRulesResponse>, AppError> {
+    let service = ReputationService::new(app_state);
+    let (daily_cap, weights) = service.rules().await?;
+
+    Ok(Json(ReputationRulesResponse {
+        daily_cap,
+        weights: weights.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,89 @@
+// ============================================================================
+// handlers/token_handlers.rs - Personal access token management
+//
+// All routes here sit behind `auth_middleware`. Managing tokens requires a
+// session login so that a leaked token cannot be used to mint new ones.
+// ============================================================================
+
+use crate::schema::request::CreatePersonalAccessTokenRequest;
+use crate::schema::response::{CreatedPersonalAccessTokenResponse, PersonalAccessTokenResponse};
+use crate::services::user_service::UserService;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// GET /api/auth/tokens
+///
+/// List the current user's personal access tokens
+pub async fn list_tokens_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+) -> Result<Json<Vec<PersonalAccessTokenResponse>>, AppError> {
+    scopes.require_session()?;
+
+    let service = UserService::new(app_state);
+    let tokens = service.list_personal_access_tokens(&user_id).await?;
+
+    Ok(Json(tokens.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/auth/tokens
+///
+/// Create a personal access token. The plaintext token is only returned here.
+pub async fn create_token_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Json(payload): Json<CreatePersonalAccessTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = UserService::new(app_state);
+    let result = service
+        .create_personal_access_token(&user_id, payload)
+        .await?;
+
+    // 3. Return response
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedPersonalAccessTokenResponse {
+            status: "Success".to_string(),
+            message: "Token created. Copy it now - it will not be shown again".to_string(),
+            token: result.token,
+            personal_access_token: result.personal_access_token.into(),
+        }),
+    ))
+}
+
+/// DELETE /api/auth/tokens/{id}
+///
+/// Revoke a personal access token
+pub async fn revoke_token_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Path(token_id): Path<Uuid>,
+) -> Result<Json<PersonalAccessTokenResponse>, AppError> {
+    scopes.require_session()?;
+
+    let service = UserService::new(app_state);
+    let token = service
+        .revoke_personal_access_token(&user_id, &token_id)
+        .await?;
+
+    Ok(Json(token.into()))
+}
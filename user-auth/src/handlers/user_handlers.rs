@@ -0,0 +1,117 @@
+// ============================================================================
+// handlers/user_handlers.rs - User profile avatars
+//
+// Uploading and removing an avatar requires a session login. Avatar images
+// are public so they can be embedded anywhere.
+// ============================================================================
+
+use crate::schema::dto::AvatarImageDto;
+use crate::schema::request::{AvatarQuery, SetAvatarUrlRequest};
+use crate::schema::response::UserResponse;
+use crate::services::avatar_service::AvatarService;
+use axum::{
+    Json,
+    extract::{Multipart, Path, Query, State},
+    http::header,
+    response::{IntoResponse, Redirect, Response},
+};
+use shared::{
+    errors::AppError,
+    extractors::{CurrentUser, TokenScopes},
+    state::AppState,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+/// PUT /api/users/me/avatar
+///
+/// Upload a PNG, JPEG, GIF or WebP image as the `avatar` field of a
+/// multipart form
+pub async fn upload_avatar_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    mut multipart: Multipart,
+) -> Result<Json<UserResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+
+    let mut avatar = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("avatar") {
+            avatar = Some(field.bytes().await?.to_vec());
+            break;
+        }
+    }
+    let bytes = avatar.ok_or_else(|| AppError::BadRequest("Missing 'avatar' field".to_string()))?;
+
+    // 2. Call service
+    let service = AvatarService::new(app_state);
+    let user = service.upload_avatar(&user_id, bytes).await?;
+
+    // 3. Return response
+    Ok(Json(user.into()))
+}
+
+/// PUT /api/users/me/avatar/url
+///
+/// Use an external http(s) image as avatar instead of an uploaded one
+pub async fn set_avatar_url_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+    Json(payload): Json<SetAvatarUrlRequest>,
+) -> Result<Json<UserResponse>, AppError> {
+    // 1. Validate request
+    scopes.require_session()?;
+    payload.validate()?;
+
+    // 2. Call service
+    let service = AvatarService::new(app_state);
+    let user = service
+        .set_avatar_url(&user_id, &payload.avatar_url)
+        .await?;
+
+    // 3. Return response
+    Ok(Json(user.into()))
+}
+
+/// DELETE /api/users/me/avatar
+///
+/// Remove the current user's avatar; the identicon is served instead
+pub async fn delete_avatar_handler(
+    State(app_state): State<AppState>,
+    CurrentUser(user_id): CurrentUser,
+    scopes: TokenScopes,
+) -> Result<Json<UserResponse>, AppError> {
+    scopes.require_session()?;
+
+    let service = AvatarService::new(app_state);
+    let user = service.remove_avatar(&user_id).await?;
+
+    Ok(Json(user.into()))
+}
+
+/// GET /api/users/{id}/avatar
+///
+/// Get a user's avatar as PNG in `size` pixels, falling back to an identicon
+pub async fn get_avatar_handler(
+    State(app_state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<AvatarQuery>,
+) -> Result<Response, AppError> {
+    let service = AvatarService::new(app_state);
+    let image = service.avatar_image(&user_id, query.size).await?;
+
+    Ok(match image {
+        AvatarImageDto::Png(png) => (
+            [
+                (header::CONTENT_TYPE, "image/png"),
+                (header::CACHE_CONTROL, "public, max-age=3600"),
+            ],
+            png,
+        )
+            .into_response(),
+        AvatarImageDto::Redirect(url) => Redirect::temporary(&url).into_response(),
+    })
+}
@@ -5,9 +5,12 @@ pub mod services;
 pub mod utils;
 
 use crate::routes::auth_router::auth_router;
+use crate::routes::user_router::user_router;
 use axum::Router;
 use shared::state::AppState;
 
-pub async fn app() -> Router<AppState> {
-    Router::new().nest("/auth", auth_router())
+pub async fn app(state: AppState) -> Router<AppState> {
+    Router::new()
+        .nest("/auth", auth_router(state.clone()))
+        .nest("/users", user_router(state))
 }
@@ -2,10 +2,11 @@ use crate::handlers::auth_handlers::{
     forgot_password_handler, login_handler, logout_handler, refresh_token_handler,
     register_handler, resend_verification_handler, reset_password_handler, verify_email_handler,
 };
+use crate::routes::token_router::token_router;
 use axum::{Router, routing::post};
 use shared::state::AppState;
 
-pub fn auth_router() -> Router<AppState> {
+pub fn auth_router(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/register", post(register_handler))
         .route("/verify-email", post(verify_email_handler))
@@ -15,4 +16,5 @@ pub fn auth_router() -> Router<AppState> {
         .route("/forgot-password", post(forgot_password_handler))
         .route("/reset-password", post(reset_password_handler))
         .route("/logout", post(logout_handler))
+        .nest("/tokens", token_router(state))
 }
@@ -1,4 +1,8 @@
 pub mod auth_router;
+pub mod token_router;
+pub mod user_router;
 
 
-pub use auth_router::auth_router;
\ No newline at end of file
+pub use auth_router::auth_router;
+pub use token_router::token_router;
+pub use user_router::user_router;
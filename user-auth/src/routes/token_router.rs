@@ -0,0 +1,15 @@
+use crate::handlers::token_handlers::{
+    create_token_handler, list_tokens_handler, revoke_token_handler,
+};
+use axum::{
+    Router, middleware,
+    routing::{delete, get},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+pub fn token_router(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_tokens_handler).post(create_token_handler))
+        .route("/{id}", delete(revoke_token_handler))
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware))
+}
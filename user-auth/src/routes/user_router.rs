@@ -0,0 +1,87 @@
+use crate::handlers::activity_handlers::{
+    get_activity_calendar_handler, get_activity_streak_handler, get_my_activity_calendar_handler,
+    get_my_timezone_handler, update_my_timezone_handler,
+};
+use crate::handlers::badge_handlers::{
+    list_badges_handler, list_my_badges_handler, list_user_badges_handler,
+};
+use crate::handlers::follow_handlers::{
+    follow_user_handler, get_feed_read_state_handler, list_feed_handler,
+    list_followed_tasks_handler, list_followers_handler, list_following_handler,
+    mark_feed_read_handler, unfollow_user_handler,
+};
+use crate::handlers::leaderboard_handlers::{
+    list_leaderboard_handler, my_leaderboard_position_handler,
+};
+use crate::handlers::reputation_handlers::{
+    get_reputation_rules_handler, list_my_reputation_events_handler,
+};
+use crate::handlers::user_handlers::{
+    delete_avatar_handler, get_avatar_handler, set_avatar_url_handler, upload_avatar_handler,
+};
+use crate::utils::avatar::MAX_AVATAR_BYTES;
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{get, put},
+};
+use shared::{middleware::auth_middleware, state::AppState};
+
+/// Room for the multipart framing around the image
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+pub fn user_router(state: AppState) -> Router<AppState> {
+    let authenticated = Router::new()
+        .route(
+            "/me/avatar",
+            put(upload_avatar_handler).delete(delete_avatar_handler),
+        )
+        .layer(DefaultBodyLimit::max(
+            MAX_AVATAR_BYTES + MULTIPART_OVERHEAD_BYTES,
+        ))
+        .route("/me/avatar/url", put(set_avatar_url_handler))
+        .route(
+            "/me/reputation/events",
+            get(list_my_reputation_events_handler),
+        )
+        .route("/me/badges", get(list_my_badges_handler))
+        .route(
+            "/me/activity/calendar",
+            get(get_my_activity_calendar_handler),
+        )
+        .route(
+            "/me/timezone",
+            get(get_my_timezone_handler).put(update_my_timezone_handler),
+        )
+        .route("/me/feed", get(list_feed_handler))
+        .route(
+            "/me/feed/read",
+            get(get_feed_read_state_handler).put(mark_feed_read_handler),
+        )
+        .route("/me/following/tasks", get(list_followed_tasks_handler))
+        .route(
+            "/{id}/follow",
+            put(follow_user_handler).delete(unfollow_user_handler),
+        )
+        .route(
+            "/leaderboards/{metric}/me",
+            get(my_leaderboard_position_handler),
+        )
+        .route_layer(middleware::from_fn_with_state(state, auth_middleware));
+
+    Router::new()
+        .route("/{id}/avatar", get(get_avatar_handler))
+        .route("/{id}/badges", get(list_user_badges_handler))
+        .route(
+            "/{id}/activity/calendar",
+            get(get_activity_calendar_handler),
+        )
+        .route("/{id}/activity/streak", get(get_activity_streak_handler))
+        .route("/{id}/followers", get(list_followers_handler))
+        .route("/{id}/following", get(list_following_handler))
+        .route("/reputation/rules", get(get_reputation_rules_handler))
+        .route("/badges", get(list_badges_handler))
+        .route("/leaderboards/{metric}", get(list_leaderboard_handler))
+        .merge(authenticated)
+}
@@ -1,4 +1,5 @@
 use crate::schema::response::UserResponse;
+use models::PersonalAccessToken;
 
 #[derive(Debug, Clone)]
 pub struct LoginResultDto {
@@ -16,4 +17,18 @@ pub struct RefreshResultDto {
 #[derive(Debug, Clone)]
 pub struct RefreshTokenDto {
     pub refresh_token: String,
-}
\ No newline at end of file
+}
+#[derive(Debug, Clone)]
+pub struct CreatedTokenDto {
+    pub token: String,
+    pub personal_access_token: PersonalAccessToken,
+}
+
+/// What `GET /api/users/{id}/avatar` serves
+#[derive(Debug, Clone)]
+pub enum AvatarImageDto {
+    /// A stored or generated PNG
+    Png(Vec<u8>),
+    /// An external `avatar_url` set through the profile
+    Redirect(String),
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use models::{LeaderboardWindow, TokenScope};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -79,3 +81,71 @@ pub struct LoginRequest {
 pub struct RefreshToken {
     pub refresh_token: String,
 }
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct CreatePersonalAccessTokenRequest {
+    #[validate(length(min = 1, max = 100, message = "name must be between 1 and 100 characters"))]
+    pub name: String,
+
+    #[validate(length(min = 1, message = "At least one scope is required"))]
+    pub scopes: Vec<TokenScope>,
+
+    #[validate(range(min = 1, max = 365, message = "expiry must be between 1 and 365 days"))]
+    pub expires_in_days: Option<u32>,
+}
+
+/// Body of `PUT /api/users/me/avatar/url`
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetAvatarUrlRequest {
+    #[validate(url(message = "avatar_url must be a valid URL"))]
+    #[validate(length(max = 2048, message = "avatar_url must be at most 2048 characters"))]
+    pub avatar_url: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AvatarQuery {
+    /// One of 64, 128 or 256; defaults to 128
+    pub size: Option<u32>,
+}
+
+/// Query string of `GET /api/users/leaderboards/{metric}`
+///
+/// e.g. `?window=week&tag=graphs&limit=20&cursor=<next_cursor>`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LeaderboardQuery {
+    /// Defaults to all time
+    pub window: Option<LeaderboardWindow>,
+    /// Tag slug or alias; the global leaderboard when absent
+    pub tag: Option<String>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// Query string of the activity calendar endpoints
+///
+/// e.g. `?from=2026-01-01&to=2026-06-30`; dates are days of the user's time
+/// zone
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ActivityCalendarQuery {
+    /// Defaults to 364 days before `to`
+    pub from: Option<NaiveDate>,
+    /// Defaults to today
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, Validate, Serialize)]
+pub struct UpdateTimezoneRequest {
+    /// IANA time zone name, e.g. `Europe/Berlin`
+    #[validate(length(
+        min = 1,
+        max = 64,
+        message = "timezone must be between 1 and 64 characters"
+    ))]
+    pub timezone: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct MarkFeedReadRequest {
+    /// Mark the entries up to this point read; defaults to now
+    pub until: Option<DateTime<Utc>>,
+}
@@ -1,5 +1,9 @@
-use chrono::{DateTime, Utc};
-use models::User;
+use chrono::{DateTime, NaiveDate, Utc};
+use models::{
+    ActivityStreak, Badge, BadgeRule, FeedEventKind, FeedItem, LeaderboardMetric,
+    LeaderboardWindow, PersonalAccessToken, ReputationEvent, ReputationEventType, ReputationWeight,
+    TaskFollow, User, UserBadge, UserTimezone,
+};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -67,10 +71,329 @@ pub struct LoginResponse {
     pub status: String,
     pub access_token: String,
     pub user: UserResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csrf_token: Option<String>,
 }
 
 #[derive(Serialize, ToSchema, Deserialize, Debug)]
 pub struct RefreshTokenResponse {
     pub status: String,
     pub access_token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csrf_token: Option<String>,
+}
+
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct PersonalAccessTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PersonalAccessToken> for PersonalAccessTokenResponse {
+    fn from(token: PersonalAccessToken) -> Self {
+        Self {
+            id: token.id,
+            name: token.name,
+            token_prefix: token.token_prefix,
+            scopes: token.scopes,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked_at: token.revoked_at,
+            created_at: token.created_at,
+        }
+    }
+}
+
+/// Returned only once, when the token is created - the plaintext is never stored
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct CreatedPersonalAccessTokenResponse {
+    pub status: String,
+    pub message: String,
+    pub token: String,
+    pub personal_access_token: PersonalAccessTokenResponse,
+}
+
+/// One entry of a user's reputation ledger
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ReputationEventResponse {
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub event_type: ReputationEventType,
+    pub source_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    /// Points applied to the score, after the daily cap
+    pub points: Decimal,
+    /// Points the event was worth before the daily cap
+    pub base_points: Decimal,
+    /// Set on reversals: the event this one undoes
+    pub reverses_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ReputationEvent> for ReputationEventResponse {
+    fn from(event: ReputationEvent) -> Self {
+        Self {
+            id: event.id,
+            event_type: event.event_type,
+            source_id: event.source_id,
+            actor_id: event.actor_id,
+            points: event.points,
+            base_points: event.base_points,
+            reverses_id: event.reverses_id,
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ReputationWeightResponse {
+    #[schema(value_type = String)]
+    pub event_type: ReputationEventType,
+    /// The rating the points apply to; absent for events that are not ratings
+    pub rating_value: Option<i16>,
+    pub points: Decimal,
+}
+
+impl From<ReputationWeight> for ReputationWeightResponse {
+    fn from(weight: ReputationWeight) -> Self {
+        Self {
+            rating_value: weight.event_type.is_rated().then_some(weight.rating_value),
+            event_type: weight.event_type,
+            points: weight.points,
+        }
+    }
+}
+
+/// How reputation is earned
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ReputationRulesResponse {
+    /// Most points a user can gain per UTC day
+    pub daily_cap: Decimal,
+    pub weights: Vec<ReputationWeightResponse>,
+}
+
+/// A user's place on a leaderboard
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct LeaderboardEntryResponse {
+    /// Starts at 1; users with the same score get consecutive ranks
+    pub rank: u64,
+    pub user_id: Uuid,
+    /// Absent when the account no longer exists
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub score: f64,
+}
+
+/// The current user's place on a leaderboard
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct LeaderboardPositionResponse {
+    #[schema(value_type = String)]
+    pub metric: LeaderboardMetric,
+    #[schema(value_type = String)]
+    pub window: LeaderboardWindow,
+    pub tag: Option<String>,
+    /// Absent while the user has no score on the leaderboard
+    pub rank: Option<u64>,
+    pub score: Option<f64>,
+    /// Number of users on the leaderboard
+    pub total: u64,
+}
+
+/// A badge of the catalog
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct BadgeResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    /// What the threshold counts
+    #[schema(value_type = String)]
+    pub rule: BadgeRule,
+    pub threshold: i32,
+    /// Users who earned it
+    pub holders: i64,
+}
+
+impl From<Badge> for BadgeResponse {
+    fn from(badge: Badge) -> Self {
+        Self {
+            id: badge.id,
+            slug: badge.slug,
+            name: badge.name,
+            description: badge.description,
+            rule: badge.rule,
+            threshold: badge.threshold,
+            holders: badge.holders,
+        }
+    }
+}
+
+/// A badge a user earned
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct UserBadgeResponse {
+    pub badge_id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    #[schema(value_type = String)]
+    pub rule: BadgeRule,
+    pub threshold: i32,
+    pub awarded_at: DateTime<Utc>,
+}
+
+impl From<UserBadge> for UserBadgeResponse {
+    fn from(badge: UserBadge) -> Self {
+        Self {
+            badge_id: badge.badge_id,
+            slug: badge.slug,
+            name: badge.name,
+            description: badge.description,
+            rule: badge.rule,
+            threshold: badge.threshold,
+            awarded_at: badge.awarded_at,
+        }
+    }
+}
+
+/// What a user did on one day
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ActivityDayResponse {
+    pub date: NaiveDate,
+    pub tasks: i64,
+    pub submissions: i64,
+    pub comments: i64,
+    pub ratings: i64,
+    pub total: i64,
+    /// Shade of the day on the calendar, from 0 (nothing) to 4 (the
+    /// busiest days of the range)
+    pub level: u8,
+}
+
+/// Runs of consecutive active days
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ActivityStreakResponse {
+    /// Days in the run that ends today, or yesterday while today is still
+    /// open
+    pub current: i32,
+    pub current_since: Option<NaiveDate>,
+    pub longest: i32,
+    pub last_active_on: Option<NaiveDate>,
+}
+
+impl From<ActivityStreak> for ActivityStreakResponse {
+    fn from(streak: ActivityStreak) -> Self {
+        Self {
+            current: streak.current,
+            current_since: streak.current_since,
+            longest: streak.longest,
+            last_active_on: streak.last_active_on,
+        }
+    }
+}
+
+/// A user's activity per day over a range, with every day of the range
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct ActivityCalendarResponse {
+    pub user_id: Uuid,
+    /// Time zone the days are in
+    pub timezone: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Everything the user did over the range
+    pub total: i64,
+    /// Days with any activity
+    pub active_days: i64,
+    /// Oldest first
+    pub days: Vec<ActivityDayResponse>,
+    pub streak: ActivityStreakResponse,
+}
+
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct TimezoneResponse {
+    pub timezone: String,
+    /// The date it is in that time zone now
+    pub today: NaiveDate,
+}
+
+impl From<UserTimezone> for TimezoneResponse {
+    fn from(timezone: UserTimezone) -> Self {
+        Self {
+            timezone: timezone.timezone,
+            today: timezone.today,
+        }
+    }
+}
+
+/// A user on one side of a follow
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct FollowResponse {
+    pub user_id: Uuid,
+    /// Absent when the account no longer exists
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct FollowedTaskResponse {
+    pub task_id: Uuid,
+    pub followed_at: DateTime<Utc>,
+}
+
+impl From<TaskFollow> for FollowedTaskResponse {
+    fn from(follow: TaskFollow) -> Self {
+        Self {
+            task_id: follow.task_id,
+            followed_at: follow.created_at,
+        }
+    }
+}
+
+/// One entry of the activity feed
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct FeedItemResponse {
+    /// The task, submission, comment or reply the entry is about
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub kind: FeedEventKind,
+    pub actor_id: Uuid,
+    pub actor_name: Option<String>,
+    pub task_id: Uuid,
+    pub task_title: Option<String>,
+    pub submission_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Newer than the read marker
+    pub unread: bool,
+}
+
+impl From<FeedItem> for FeedItemResponse {
+    fn from(item: FeedItem) -> Self {
+        Self {
+            id: item.id,
+            kind: item.kind,
+            actor_id: item.actor_id,
+            actor_name: item.actor_name,
+            task_id: item.task_id,
+            task_title: item.task_title,
+            submission_id: item.submission_id,
+            created_at: item.created_at,
+            unread: item.unread,
+        }
+    }
+}
+
+/// Where the feed was read up to, and what came in since
+#[derive(Serialize, ToSchema, Deserialize, Debug)]
+pub struct FeedReadStateResponse {
+    /// Absent until the feed is first marked read
+    pub read_until: Option<DateTime<Utc>>,
+    /// Unread entries, counted up to `unread_cap`
+    pub unread: i64,
+    pub unread_cap: i64,
 }
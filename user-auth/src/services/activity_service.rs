@@ -0,0 +1,151 @@
+use crate::schema::request::{ActivityCalendarQuery, UpdateTimezoneRequest};
+use crate::schema::response::{ActivityCalendarResponse, ActivityDayResponse};
+use chrono::Duration;
+use models::{ActivityDay, ActivityStreak, UserTimezone};
+use shared::errors::AppError;
+use shared::state::AppState;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Longest range a calendar covers, in days; a leap year
+const MAX_CALENDAR_DAYS: i64 = 366;
+
+/// Range a calendar covers when none is given, in days
+const DEFAULT_CALENDAR_DAYS: i64 = 365;
+
+/// Shade of a day from 0 to 4, relative to the busiest day of the range
+fn level(total: i64, busiest: i64) -> u8 {
+    if total <= 0 || busiest <= 0 {
+        return 0;
+    }
+    ((total * 4 + busiest - 1) / busiest).clamp(1, 4) as u8
+}
+
+pub struct ActivityService {
+    state: AppState,
+}
+
+impl ActivityService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn timezone(&self, user_id: &Uuid) -> Result<UserTimezone, AppError> {
+        self.state
+            .repos
+            .activity
+            .find_timezone(*user_id)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))
+    }
+
+    /// Build a user's contribution calendar: every day of the range with
+    /// what they did on it, in their time zone
+    ///
+    /// Returns: The days oldest first, with the totals and streaks
+    pub async fn calendar(
+        &self,
+        user_id: &Uuid,
+        query: ActivityCalendarQuery,
+    ) -> Result<ActivityCalendarResponse, AppError> {
+        // 1. Work out the range in the user's time zone
+        let timezone = self.timezone(user_id).await?;
+        let to = query.to.unwrap_or(timezone.today);
+        let from = query
+            .from
+            .unwrap_or(to - Duration::days(DEFAULT_CALENDAR_DAYS - 1));
+        if from > to {
+            return Err(AppError::BadRequest(
+                "'from' must not be after 'to'".to_string(),
+            ));
+        }
+        if (to - from).num_days() + 1 > MAX_CALENDAR_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "A calendar covers at most {} days",
+                MAX_CALENDAR_DAYS
+            )));
+        }
+
+        // 2. Read the active days and the streaks
+        let activity = &self.state.repos.activity;
+        let mut active: HashMap<_, _> = activity
+            .find_days(*user_id, &timezone.timezone, from, to)
+            .await?
+            .into_iter()
+            .map(|day| (day.day, day))
+            .collect();
+        let streak = activity.find_streak(*user_id, &timezone.timezone).await?;
+
+        // 3. Fill in the quiet days and shade each one
+        let busiest = active.values().map(ActivityDay::total).max().unwrap_or(0);
+        let days: Vec<ActivityDayResponse> = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|date| {
+                let day = active
+                    .remove(&date)
+                    .unwrap_or_else(|| ActivityDay::empty(date));
+                let total = day.total();
+                ActivityDayResponse {
+                    date,
+                    tasks: day.tasks,
+                    submissions: day.submissions,
+                    comments: day.comments,
+                    ratings: day.ratings,
+                    total,
+                    level: level(total, busiest),
+                }
+            })
+            .collect();
+
+        Ok(ActivityCalendarResponse {
+            user_id: *user_id,
+            timezone: timezone.timezone,
+            from,
+            to,
+            total: days.iter().map(|day| day.total).sum(),
+            active_days: days.iter().filter(|day| day.total > 0).count() as i64,
+            days,
+            streak: streak.into(),
+        })
+    }
+
+    /// Get a user's current and longest streaks of active days
+    pub async fn streak(&self, user_id: &Uuid) -> Result<ActivityStreak, AppError> {
+        let timezone = self.timezone(user_id).await?;
+        Ok(self
+            .state
+            .repos
+            .activity
+            .find_streak(*user_id, &timezone.timezone)
+            .await?)
+    }
+
+    /// Get the time zone the user's days are counted in
+    pub async fn get_timezone(&self, user_id: &Uuid) -> Result<UserTimezone, AppError> {
+        self.timezone(user_id).await
+    }
+
+    /// Change the time zone the user's days are counted in; past activity
+    /// moves to the days of the new time zone
+    pub async fn set_timezone(
+        &self,
+        user_id: &Uuid,
+        request: UpdateTimezoneRequest,
+    ) -> Result<UserTimezone, AppError> {
+        // 1. Only accept time zones Postgres can convert to
+        let activity = &self.state.repos.activity;
+        if !activity.is_known_timezone(&request.timezone).await? {
+            return Err(AppError::UnprocessableEntity(format!(
+                "Unknown time zone '{}'",
+                request.timezone
+            )));
+        }
+
+        // 2. Store it
+        activity
+            .set_timezone(*user_id, &request.timezone)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))
+    }
+}
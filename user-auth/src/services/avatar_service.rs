@@ -0,0 +1,189 @@
+use crate::schema::dto::AvatarImageDto;
+use crate::utils::avatar::{
+    AVATAR_SIZES, DEFAULT_AVATAR_SIZE, MAX_AVATAR_BYTES, avatar_hash, avatar_key, identicon,
+    process_avatar,
+};
+use models::User;
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct AvatarService {
+    state: AppState,
+}
+
+impl AvatarService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Replace a user's avatar with an uploaded image
+    ///
+    /// Returns: The updated user
+    ///
+    /// Side effects:
+    /// - Stores a PNG per `AVATAR_SIZES` size in the blob store
+    /// - Points `avatar_url` at `GET /api/users/{id}/avatar`
+    /// - Deletes the files of the previously uploaded avatar
+    pub async fn upload_avatar(&self, user_id: &Uuid, bytes: Vec<u8>) -> Result<User, AppError> {
+        // 1. Validate size, then decode and render off the async runtime
+        if bytes.len() > MAX_AVATAR_BYTES {
+            return Err(AppError::PayloadTooLarge(format!(
+                "Avatar exceeds the {} byte limit",
+                MAX_AVATAR_BYTES
+            )));
+        }
+
+        let previous = self.find_user(user_id).await?;
+
+        let hash = avatar_hash(&bytes);
+        let rendered = tokio::task::spawn_blocking(move || process_avatar(&bytes))
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))??;
+
+        // 2. Store every size before the user row points at them
+        for (size, png) in rendered {
+            self.state
+                .blob_store
+                .put(&avatar_key(user_id, &hash, size), png, "image/png")
+                .await?;
+        }
+
+        // 3. Update the user; the hash in the URL busts caches on change
+        let avatar_url = format!(
+            "{}/{}/avatar?v={}",
+            self.state.config.users_public_url.trim_end_matches('/'),
+            user_id,
+            hash
+        );
+        let user = self
+            .state
+            .repos
+            .user
+            .set_avatar(user_id, Some(&avatar_url), Some(&hash))
+            .await?;
+
+        // 4. Clean up the replaced avatar
+        if let Some(old_hash) = previous.avatar_hash.filter(|old| *old != hash) {
+            self.delete_files(user_id, &old_hash).await;
+        }
+
+        Ok(user)
+    }
+
+    /// Point a user's avatar at an external image
+    ///
+    /// Returns: The updated user
+    ///
+    /// Side effects:
+    /// - `GET /api/users/{id}/avatar` redirects to `url`
+    /// - Deletes the files of the previously uploaded avatar
+    pub async fn set_avatar_url(&self, user_id: &Uuid, url: &str) -> Result<User, AppError> {
+        // 1. Only web URLs are safe to redirect to
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(AppError::InvalidInput(
+                "avatar_url must be an http or https URL".to_string(),
+            ));
+        }
+
+        let previous = self.find_user(user_id).await?;
+
+        // 2. Update the user
+        let user = self
+            .state
+            .repos
+            .user
+            .set_avatar(user_id, Some(url), None)
+            .await?;
+
+        // 3. Clean up the replaced avatar
+        if let Some(old_hash) = previous.avatar_hash {
+            self.delete_files(user_id, &old_hash).await;
+        }
+
+        Ok(user)
+    }
+
+    /// Remove a user's avatar, falling back to the identicon
+    ///
+    /// Returns: The updated user
+    ///
+    /// Side effects:
+    /// - Clears `avatar_url` and deletes uploaded avatar files
+    pub async fn remove_avatar(&self, user_id: &Uuid) -> Result<User, AppError> {
+        let previous = self.find_user(user_id).await?;
+
+        let user = self
+            .state
+            .repos
+            .user
+            .set_avatar(user_id, None, None)
+            .await?;
+
+        if let Some(old_hash) = previous.avatar_hash {
+            self.delete_files(user_id, &old_hash).await;
+        }
+
+        Ok(user)
+    }
+
+    /// Resolve the avatar image of a user in one of the standard sizes
+    ///
+    /// Returns: The uploaded PNG, a redirect to an external `avatar_url`,
+    /// or a generated identicon when no avatar is set
+    pub async fn avatar_image(
+        &self,
+        user_id: &Uuid,
+        size: Option<u32>,
+    ) -> Result<AvatarImageDto, AppError> {
+        // 1. Validate size
+        let size = size.unwrap_or(DEFAULT_AVATAR_SIZE);
+        if !AVATAR_SIZES.contains(&size) {
+            return Err(AppError::BadRequest(format!(
+                "size must be one of {:?}",
+                AVATAR_SIZES
+            )));
+        }
+
+        // 2. Serve uploaded avatar, external URL or identicon
+        let user = self.find_user(user_id).await?;
+
+        if let Some(hash) = &user.avatar_hash {
+            let blob = self
+                .state
+                .blob_store
+                .get(&avatar_key(user_id, hash, size))
+                .await?;
+            return Ok(AvatarImageDto::Png(blob.bytes));
+        }
+
+        if let Some(url) = user.avatar_url {
+            return Ok(AvatarImageDto::Redirect(url));
+        }
+
+        let user_id = *user_id;
+        let png = tokio::task::spawn_blocking(move || identicon(&user_id, size))
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))??;
+        Ok(AvatarImageDto::Png(png))
+    }
+
+    async fn find_user(&self, user_id: &Uuid) -> Result<User, AppError> {
+        self.state
+            .repos
+            .user
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    /// Best effort: a leftover file only wastes storage
+    async fn delete_files(&self, user_id: &Uuid, hash: &str) {
+        for size in AVATAR_SIZES {
+            let key = avatar_key(user_id, hash, size);
+            if let Err(e) = self.state.blob_store.delete(&key).await {
+                tracing::warn!("Failed to delete avatar file {}: {}", key, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use models::{Badge, UserBadge};
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct BadgeService {
+    state: AppState,
+}
+
+impl BadgeService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Get every badge and how many users earned it
+    pub async fn catalog(&self) -> Result<Vec<Badge>, AppError> {
+        Ok(self.state.repos.badge.find_catalog().await?)
+    }
+
+    /// List the badges a user earned, most recent first
+    pub async fn list_user_badges(&self, user_id: &Uuid) -> Result<Vec<UserBadge>, AppError> {
+        self.state
+            .repos
+            .user
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+        Ok(self.state.repos.badge.find_by_user(*user_id).await?)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::schema::request::MarkFeedReadRequest;
+use crate::schema::response::FeedReadStateResponse;
+use chrono::Utc;
+use models::FeedItem;
+use repositories::pagination::{Page, PageRequest};
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+/// Unread entries are counted no further than this
+const UNREAD_CAP: i64 = 100;
+
+pub struct FeedService {
+    state: AppState,
+}
+
+impl FeedService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// List a page of the user's feed, newest first, each entry marked
+    /// read or unread
+    pub async fn feed(
+        &self,
+        user_id: &Uuid,
+        page: PageRequest,
+    ) -> Result<Page<FeedItem>, AppError> {
+        Ok(self.state.repos.feed.find_feed(*user_id, page).await?)
+    }
+
+    /// Get the user's read marker and how many entries are newer
+    pub async fn read_state(&self, user_id: &Uuid) -> Result<FeedReadStateResponse, AppError> {
+        let feed = &self.state.repos.feed;
+        let read_until = feed.find_read_until(*user_id).await?;
+        let unread = feed.count_unread(*user_id, UNREAD_CAP).await?;
+
+        Ok(FeedReadStateResponse {
+            read_until,
+            unread,
+            unread_cap: UNREAD_CAP,
+        })
+    }
+
+    /// Mark the user's feed read up to a point, now by default; the
+    /// marker never moves back
+    ///
+    /// Returns: The read marker and what is still unread
+    pub async fn mark_read(
+        &self,
+        user_id: &Uuid,
+        request: MarkFeedReadRequest,
+    ) -> Result<FeedReadStateResponse, AppError> {
+        // 1. Entries cannot be read before they exist
+        let now = Utc::now();
+        let until = request.until.map_or(now, |until| until.min(now));
+
+        // 2. Move the marker and count what is left
+        let feed = &self.state.repos.feed;
+        let read_until = feed.mark_read(*user_id, until).await?;
+        let unread = feed.count_unread(*user_id, UNREAD_CAP).await?;
+
+        Ok(FeedReadStateResponse {
+            read_until: Some(read_until),
+            unread,
+            unread_cap: UNREAD_CAP,
+        })
+    }
+}
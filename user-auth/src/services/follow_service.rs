@@ -0,0 +1,169 @@
+use crate::schema::response::FollowResponse;
+use models::{TaskFollow, UserFollow};
+use repositories::pagination::{Page, PageRequest};
+use shared::errors::AppError;
+use shared::state::AppState;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct FollowService {
+    state: AppState,
+}
+
+impl FollowService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn ensure_user_exists(&self, user_id: &Uuid) -> Result<(), AppError> {
+        self.state
+            .repos
+            .user
+            .get_user_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))?;
+        Ok(())
+    }
+
+    /// Add who the users on the other side of each follow are
+    async fn with_users(
+        &self,
+        follows: Page<UserFollow>,
+        other: impl Fn(&UserFollow) -> Uuid,
+    ) -> Result<Page<FollowResponse>, AppError> {
+        let user_ids: Vec<Uuid> = follows.items.iter().map(&other).collect();
+        let mut users: HashMap<Uuid, _> = self
+            .state
+            .repos
+            .user
+            .get_users_by_ids(&user_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+
+        Ok(follows.map(|follow| {
+            let user_id = other(&follow);
+            let user = users.remove(&user_id);
+            FollowResponse {
+                user_id,
+                display_name: user.as_ref().map(|u| u.display_name.clone()),
+                avatar_url: user.and_then(|u| u.avatar_url),
+                followed_at: follow.created_at,
+            }
+        }))
+    }
+
+    /// Follow a user; following someone again changes nothing
+    ///
+    /// Returns: The followed user
+    pub async fn follow(
+        &self,
+        follower_id: &Uuid,
+        followee_id: &Uuid,
+    ) -> Result<FollowResponse, AppError> {
+        // 1. Users cannot follow themselves
+        if follower_id == followee_id {
+            return Err(AppError::UnprocessableEntity(
+                "You cannot follow yourself".to_string(),
+            ));
+        }
+
+        // 2. Check the user exists
+        let followee = self
+            .state
+            .repos
+            .user
+            .get_user_by_id(followee_id)
+            .await?
+            .ok_or(AppError::NotFound("User not found".to_string()))?;
+
+        // 3. Follow them
+        let follow = self
+            .state
+            .repos
+            .follow
+            .follow_user(*follower_id, *followee_id)
+            .await?;
+
+        Ok(FollowResponse {
+            user_id: followee.id,
+            display_name: Some(followee.display_name),
+            avatar_url: followee.avatar_url,
+            followed_at: follow.created_at,
+        })
+    }
+
+    /// Stop following a user
+    ///
+    /// Returns: The user that is no longer followed
+    pub async fn unfollow(
+        &self,
+        follower_id: &Uuid,
+        followee_id: &Uuid,
+    ) -> Result<FollowResponse, AppError> {
+        let follow = self
+            .state
+            .repos
+            .follow
+            .unfollow_user(*follower_id, *followee_id)
+            .await?
+            .ok_or(AppError::NotFound(
+                "You are not following this user".to_string(),
+            ))?;
+
+        let user = self.state.repos.user.get_user_by_id(followee_id).await?;
+        Ok(FollowResponse {
+            user_id: follow.followee_id,
+            display_name: user.as_ref().map(|u| u.display_name.clone()),
+            avatar_url: user.and_then(|u| u.avatar_url),
+            followed_at: follow.created_at,
+        })
+    }
+
+    /// List the users following a user, most recent first
+    pub async fn followers(
+        &self,
+        user_id: &Uuid,
+        page: PageRequest,
+    ) -> Result<Page<FollowResponse>, AppError> {
+        self.ensure_user_exists(user_id).await?;
+        let follows = self
+            .state
+            .repos
+            .follow
+            .find_followers(*user_id, page)
+            .await?;
+        self.with_users(follows, |f| f.follower_id).await
+    }
+
+    /// List the users a user follows, most recent first
+    pub async fn following(
+        &self,
+        user_id: &Uuid,
+        page: PageRequest,
+    ) -> Result<Page<FollowResponse>, AppError> {
+        self.ensure_user_exists(user_id).await?;
+        let follows = self
+            .state
+            .repos
+            .follow
+            .find_following(*user_id, page)
+            .await?;
+        self.with_users(follows, |f| f.followee_id).await
+    }
+
+    /// List the tasks a user follows, most recent first
+    pub async fn followed_tasks(
+        &self,
+        user_id: &Uuid,
+        page: PageRequest,
+    ) -> Result<Page<TaskFollow>, AppError> {
+        Ok(self
+            .state
+            .repos
+            .follow
+            .find_followed_tasks(*user_id, page)
+            .await?)
+    }
+}
@@ -0,0 +1,124 @@
+use crate::schema::request::LeaderboardQuery;
+use crate::schema::response::{LeaderboardEntryResponse, LeaderboardPositionResponse};
+use models::{LeaderboardMetric, Tag};
+use repositories::pagination::{Page, PageRequest};
+use shared::errors::AppError;
+use shared::leaderboards::{Leaderboard, decode_offset, encode_offset};
+use shared::state::AppState;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct LeaderboardService {
+    state: AppState,
+}
+
+impl LeaderboardService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// List a page of a leaderboard, highest score first
+    pub async fn list(
+        &self,
+        metric: LeaderboardMetric,
+        query: LeaderboardQuery,
+    ) -> Result<Page<LeaderboardEntryResponse>, AppError> {
+        // 1. Work out the leaderboard and the page
+        let offset = match query.cursor.as_deref().filter(|c| !c.is_empty()) {
+            Some(cursor) => decode_offset(cursor)
+                .ok_or_else(|| AppError::InvalidInput("Invalid pagination cursor".to_string()))?,
+            None => 0,
+        };
+        let limit = PageRequest::new(query.limit, None).limit as u64;
+        let leaderboard = self.leaderboard(metric, &query).await?;
+
+        // 2. Read the page, with one extra entry to know if there is more
+        let (mut entries, _) = self
+            .state
+            .leaderboards
+            .page(&leaderboard, offset, limit + 1)
+            .await?;
+        let next_cursor = (entries.len() as u64 > limit).then(|| encode_offset(offset + limit));
+        entries.truncate(limit as usize);
+
+        // 3. Add who the users are
+        let user_ids: Vec<Uuid> = entries.iter().map(|(user_id, _)| *user_id).collect();
+        let mut users: HashMap<Uuid, _> = self
+            .state
+            .repos
+            .user
+            .get_users_by_ids(&user_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user))
+            .collect();
+
+        let items = (offset + 1..)
+            .zip(entries)
+            .map(|(rank, (user_id, score))| {
+                let user = users.remove(&user_id);
+                LeaderboardEntryResponse {
+                    rank,
+                    user_id,
+                    display_name: user.as_ref().map(|u| u.display_name.clone()),
+                    avatar_url: user.and_then(|u| u.avatar_url),
+                    score,
+                }
+            })
+            .collect();
+
+        Ok(Page { items, next_cursor })
+    }
+
+    /// Get a user's rank and score on a leaderboard
+    pub async fn position(
+        &self,
+        user_id: Uuid,
+        metric: LeaderboardMetric,
+        query: LeaderboardQuery,
+    ) -> Result<LeaderboardPositionResponse, AppError> {
+        let leaderboard = self.leaderboard(metric, &query).await?;
+        let position = self
+            .state
+            .leaderboards
+            .position(&leaderboard, user_id)
+            .await?;
+        let total = self.state.leaderboards.size(&leaderboard).await?;
+
+        Ok(LeaderboardPositionResponse {
+            metric,
+            window: leaderboard.window,
+            tag: leaderboard.tag,
+            rank: position.map(|p| p.rank),
+            score: position.map(|p| p.score),
+            total,
+        })
+    }
+
+    /// The leaderboard a query is about; aliases resolve to their tag
+    async fn leaderboard(
+        &self,
+        metric: LeaderboardMetric,
+        query: &LeaderboardQuery,
+    ) -> Result<Leaderboard, AppError> {
+        let tag = match query.tag.as_deref().map(Tag::slugify) {
+            Some(slug) if !slug.is_empty() => {
+                let tag = self
+                    .state
+                    .repos
+                    .tag
+                    .find_by_slug(&slug)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound(format!("Tag '{}' not found", slug)))?;
+                Some(tag.slug)
+            }
+            _ => None,
+        };
+
+        Ok(Leaderboard {
+            metric,
+            window: query.window.unwrap_or_default(),
+            tag,
+        })
+    }
+}
@@ -1,2 +1,9 @@
+pub mod activity_service;
 pub mod auth_service;
+pub mod avatar_service;
+pub mod badge_service;
+pub mod feed_service;
+pub mod follow_service;
+pub mod leaderboard_service;
+pub mod reputation_service;
 pub mod user_service;
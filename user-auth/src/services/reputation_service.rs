@@ -0,0 +1,37 @@
+use models::{ReputationEvent, ReputationWeight};
+use repositories::pagination::{Page, PageRequest};
+use rust_decimal::Decimal;
+use shared::errors::AppError;
+use shared::state::AppState;
+use uuid::Uuid;
+
+pub struct ReputationService {
+    state: AppState,
+}
+
+impl ReputationService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// List the entries of a user's reputation ledger, newest first
+    pub async fn list_events(
+        &self,
+        user_id: &Uuid,
+        page: PageRequest,
+    ) -> Result<Page<ReputationEvent>, AppError> {
+        Ok(self
+            .state
+            .repos
+            .reputation
+            .find_events_by_user(*user_id, page)
+            .await?)
+    }
+
+    /// Get the points per event and the daily cap
+    pub async fn rules(&self) -> Result<(Decimal, Vec<ReputationWeight>), AppError> {
+        let daily_cap = self.state.repos.reputation.find_daily_cap().await?;
+        let weights = self.state.repos.reputation.find_weights().await?;
+        Ok((daily_cap, weights))
+    }
+}
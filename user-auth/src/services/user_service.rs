@@ -1,4 +1,15 @@
+use crate::schema::dto::CreatedTokenDto;
+use crate::schema::request::CreatePersonalAccessTokenRequest;
+use crate::utils::api_token::{generate_personal_access_token, visible_token_prefix};
+use chrono::{Duration, Utc};
+use models::PersonalAccessToken;
+use shared::auth_utils::hash_api_token;
+use shared::errors::AppError;
 use shared::state::AppState;
+use uuid::Uuid;
+
+// Constants
+const MAX_ACTIVE_TOKENS_PER_USER: usize = 20;
 
 pub struct UserService {
     state: AppState,
@@ -8,4 +19,93 @@ impl UserService {
     pub fn new(state: AppState) -> Self {
         Self { state }
     }
+
+    /// List all personal access tokens of a user, including revoked ones
+    pub async fn list_personal_access_tokens(
+        &self,
+        user_id: &Uuid,
+    ) -> Result<Vec<PersonalAccessToken>, AppError> {
+        Ok(self
+            .state
+            .repos
+            .personal_access_token
+            .find_by_user(*user_id)
+            .await?)
+    }
+
+    /// Create a personal access token
+    ///
+    /// Returns: The plaintext token (shown once) and the stored record
+    ///
+    /// Side effects:
+    /// - Stores the SHA-256 hash of the token in the database
+    pub async fn create_personal_access_token(
+        &self,
+        user_id: &Uuid,
+        dto: CreatePersonalAccessTokenRequest,
+    ) -> Result<CreatedTokenDto, AppError> {
+        // 1. Enforce a sane upper bound on live tokens
+        let active_tokens = self
+            .state
+            .repos
+            .personal_access_token
+            .find_by_user(*user_id)
+            .await?
+            .into_iter()
+            .filter(|t| t.revoked_at.is_none() && t.expires_at.is_none_or(|e| e > Utc::now()))
+            .count();
+
+        if active_tokens >= MAX_ACTIVE_TOKENS_PER_USER {
+            return Err(AppError::UnprocessableEntity(format!(
+                "A user can have at most {} active tokens",
+                MAX_ACTIVE_TOKENS_PER_USER
+            )));
+        }
+
+        // 2. Generate the token and keep only its hash
+        let token = generate_personal_access_token();
+        let token_hash = hash_api_token(&token);
+
+        let mut scopes: Vec<String> = dto.scopes.iter().map(|s| s.as_str().to_string()).collect();
+        scopes.sort();
+        scopes.dedup();
+
+        let expires_at = dto
+            .expires_in_days
+            .map(|days| Utc::now() + Duration::days(days.into()));
+
+        // 3. Store token
+        let personal_access_token = self
+            .state
+            .repos
+            .personal_access_token
+            .create(
+                *user_id,
+                dto.name,
+                visible_token_prefix(&token),
+                token_hash,
+                scopes,
+                expires_at,
+            )
+            .await?;
+
+        Ok(CreatedTokenDto {
+            token,
+            personal_access_token,
+        })
+    }
+
+    /// Revoke a personal access token owned by the user
+    pub async fn revoke_personal_access_token(
+        &self,
+        user_id: &Uuid,
+        token_id: &Uuid,
+    ) -> Result<PersonalAccessToken, AppError> {
+        Ok(self
+            .state
+            .repos
+            .personal_access_token
+            .revoke(*token_id, *user_id)
+            .await?)
+    }
 }
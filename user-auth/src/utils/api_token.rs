@@ -0,0 +1,24 @@
+use rand::{Rng, distr::Alphanumeric};
+use shared::auth_utils::PERSONAL_ACCESS_TOKEN_PREFIX;
+
+const TOKEN_SECRET_LENGTH: usize = 40;
+const VISIBLE_SECRET_CHARS: usize = 4;
+
+/// Generate a new personal access token, e.g. `cfz_pat_Xk3...`
+pub fn generate_personal_access_token() -> String {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+
+    format!("{}{}", PERSONAL_ACCESS_TOKEN_PREFIX, secret)
+}
+
+/// The part of a token that is safe to show back to the user for identification
+pub fn visible_token_prefix(token: &str) -> String {
+    token
+        .chars()
+        .take(PERSONAL_ACCESS_TOKEN_PREFIX.len() + VISIBLE_SECRET_CHARS)
+        .collect()
+}
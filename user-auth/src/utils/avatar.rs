@@ -0,0 +1,138 @@
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, Rgba, RgbaImage};
+use sha2::{Digest, Sha256};
+use shared::errors::AppError;
+use std::io::Cursor;
+use uuid::Uuid;
+
+/// Square sizes every avatar is rendered in, in pixels
+pub const AVATAR_SIZES: [u32; 3] = [64, 128, 256];
+pub const DEFAULT_AVATAR_SIZE: u32 = 128;
+pub const MAX_AVATAR_BYTES: usize = 5 * 1024 * 1024;
+
+/// Larger images are rejected before decoding (decompression bombs)
+const MAX_SOURCE_DIMENSION: u32 = 8192;
+const MAX_DECODE_ALLOC_BYTES: u64 = 256 * 1024 * 1024;
+
+const ACCEPTED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+
+/// Object name of one rendered size of an uploaded avatar
+pub fn avatar_key(user_id: &Uuid, avatar_hash: &str, size: u32) -> String {
+    format!("avatars/{}/{}/{}.png", user_id, avatar_hash, size)
+}
+
+/// Short content hash identifying an uploaded avatar
+pub fn avatar_hash(bytes: &[u8]) -> String {
+    hex::encode(&Sha256::digest(bytes)[..8])
+}
+
+/// Decode an uploaded image and render it in every `AVATAR_SIZES` size
+///
+/// The image is rotated according to its EXIF orientation, center-cropped
+/// to a square and re-encoded as PNG, which drops EXIF and all other
+/// metadata.
+pub fn process_avatar(bytes: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, AppError> {
+    // 1. Accept only known raster formats, sniffed from the content
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    match reader.format() {
+        Some(format) if ACCEPTED_FORMATS.contains(&format) => {}
+        _ => {
+            return Err(AppError::UnsupportedMediaType(
+                "Avatar must be a PNG, JPEG, GIF or WebP image".to_string(),
+            ));
+        }
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODE_ALLOC_BYTES);
+    reader.limits(limits);
+
+    // 2. Decode, applying the EXIF orientation before it is discarded
+    let invalid = |e: image::ImageError| {
+        AppError::UnprocessableEntity(format!("Could not read the image: {}", e))
+    };
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().map_err(invalid)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+
+    // 3. Crop to a square and render every size
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let resized = image.resize_to_fill(size, size, FilterType::Lanczos3);
+            Ok((
+                size,
+                encode_png(&DynamicImage::ImageRgba8(resized.to_rgba8()))?,
+            ))
+        })
+        .collect()
+}
+
+/// A 5x5 mirrored identicon derived from the user id
+pub fn identicon(user_id: &Uuid, size: u32) -> Result<Vec<u8>, AppError> {
+    let hash = Sha256::digest(user_id.as_bytes());
+
+    // Columns 3 and 4 mirror columns 1 and 0
+    let filled = |row: usize, col: usize| {
+        let col = col.min(4 - col);
+        hash[row * 3 + col] % 2 == 0
+    };
+
+    let hue = u16::from_be_bytes([hash[15], hash[16]]) as f32 / u16::MAX as f32;
+    let foreground = hsl_to_rgba(hue, 0.55, 0.5);
+    let background = Rgba([240, 240, 240, 255]);
+
+    // Five cells plus half a cell of margin on each side
+    let cell = size as f32 / 6.0;
+    let margin = cell / 2.0;
+    let image = RgbaImage::from_fn(size, size, |x, y| {
+        let col = ((x as f32 - margin) / cell).floor();
+        let row = ((y as f32 - margin) / cell).floor();
+        let inside = (0.0..5.0).contains(&col) && (0.0..5.0).contains(&row);
+
+        if inside && filled(row as usize, col as usize) {
+            foreground
+        } else {
+            background
+        }
+    });
+
+    encode_png(&DynamicImage::ImageRgba8(image))
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, AppError> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    Ok(png)
+}
+
+fn hsl_to_rgba(hue: f32, saturation: f32, lightness: f32) -> Rgba<u8> {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue * 6.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round() as u8;
+
+    Rgba([channel(r), channel(g), channel(b), 255])
+}
@@ -0,0 +1,12 @@
+use rand::{Rng, distr::Alphanumeric};
+
+const CSRF_TOKEN_LENGTH: usize = 32;
+
+/// Random value for the double-submit CSRF cookie
+pub fn generate_csrf_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(CSRF_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
@@ -1,4 +1,7 @@
+pub mod api_token;
+pub mod avatar;
+pub mod csrf;
 pub mod email_templates;
 pub mod email_utils;
 pub mod otp;
-pub mod constant;
\ No newline at end of file
+pub mod constant;